@@ -23,6 +23,21 @@ fn bench_index_full(c: &mut Criterion) {
         db_path,
         data_dir: data_dir.clone(),
         progress: None,
+        only: None,
+        skip: Default::default(),
+        on_progress: None,
+        watch_debounce_ms: coding_agent_search::indexer::DEFAULT_WATCH_DEBOUNCE_MS,
+        watch_max_wait_ms: coding_agent_search::indexer::DEFAULT_WATCH_MAX_WAIT_MS,
+        watch_timeout: None,
+        batch_max_conversations: None,
+        ignore_parse_cache: false,
+        jobs: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        strict: false,
+        optimize_segments: None,
+        on_span: None,
     };
 
     // create empty index dir so Tantivy opens cleanly
@@ -122,6 +122,13 @@ fn make_bench_hit(id: &str, score: f32) -> SearchHit {
         source_id: "local".to_string(),
         origin_kind: "local".to_string(),
         origin_host: None,
+        explanation: None,
+        rerank_score: None,
+        duplicate_count: None,
+        message_matches: None,
+        relative_path: None,
+        message_id: None,
+        conversation_external_id: None,
     }
 }
 
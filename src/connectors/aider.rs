@@ -1,4 +1,7 @@
-use super::{Connector, DetectionResult, NormalizedConversation, NormalizedMessage, ScanContext};
+use super::{
+    Connector, DetectionResult, NormalizedConversation, NormalizedMessage, NormalizedSnippet,
+    ScanContext,
+};
 use anyhow::Result;
 use serde_json::json;
 use std::fs;
@@ -37,66 +40,173 @@ impl AiderConnector {
         files
     }
 
+    /// Parse a `# aider chat started at YYYY-MM-DD HH:MM:SS` session header (local time).
+    fn parse_session_header(line: &str) -> Option<i64> {
+        let stamp = line.strip_prefix("# aider chat started at ")?.trim();
+        let naive = chrono::NaiveDateTime::parse_from_str(stamp, "%Y-%m-%d %H:%M:%S").ok()?;
+        naive
+            .and_local_timezone(chrono::Local)
+            .earliest()
+            .map(|dt| dt.timestamp_millis())
+    }
+
+    /// Pull file edits out of fenced blocks: ```diff blocks and aider's
+    /// SEARCH/REPLACE blocks (whose file name sits on the line before the fence).
+    fn extract_edit_snippets(content: &str) -> Vec<NormalizedSnippet> {
+        let mut snippets = Vec::new();
+        let mut prev_line: Option<&str> = None;
+        let mut lines = content.lines();
+
+        while let Some(line) = lines.next() {
+            let trimmed = line.trim();
+            let Some(lang) = trimmed.strip_prefix("```") else {
+                if !trimmed.is_empty() {
+                    prev_line = Some(trimmed);
+                }
+                continue;
+            };
+
+            let mut body = Vec::new();
+            for inner in lines.by_ref() {
+                if inner.trim_start().starts_with("```") {
+                    break;
+                }
+                body.push(inner);
+            }
+            let body = body.join("\n");
+            let lang = lang.trim();
+            let is_diff = lang == "diff";
+            let is_search_replace = body.contains("<<<<<<< SEARCH");
+
+            if is_diff || is_search_replace {
+                let diff_path = body.lines().find_map(|l| {
+                    l.strip_prefix("+++ ")
+                        .or_else(|| l.strip_prefix("--- "))
+                        .map(|p| p.trim().trim_start_matches("a/").trim_start_matches("b/"))
+                        .filter(|p| *p != "/dev/null")
+                });
+                let file_path = diff_path
+                    .or(prev_line
+                        .filter(|p| !p.contains(' ') && (p.contains('.') || p.contains('/'))))
+                    .map(PathBuf::from);
+                snippets.push(NormalizedSnippet {
+                    file_path,
+                    start_line: None,
+                    end_line: None,
+                    language: (!lang.is_empty()).then(|| lang.to_string()),
+                    snippet_text: Some(body),
+                });
+            }
+            prev_line = None;
+        }
+        snippets
+    }
+
+    fn push_message(
+        messages: &mut Vec<NormalizedMessage>,
+        role: &str,
+        content: &mut String,
+        created_at: Option<i64>,
+    ) {
+        if content.trim().is_empty() {
+            content.clear();
+            return;
+        }
+        let text = content.trim().to_string();
+        let snippets = if role == "user" {
+            Vec::new()
+        } else {
+            Self::extract_edit_snippets(&text)
+        };
+        messages.push(NormalizedMessage {
+            idx: messages.len() as i64,
+            role: role.to_string(),
+            author: Some(role.to_string()),
+            created_at,
+            content: text,
+            extra: json!({}),
+            snippets,
+        });
+        content.clear();
+    }
+
+    /// Parse a markdown chat history.
+    ///
+    /// User input is `> ` quoted or `#### ` prefixed; everything else is the assistant.
+    /// Session headers split messages and supply their timestamps, and fenced diff /
+    /// SEARCH-REPLACE blocks become snippets.
     fn parse_chat_history(&self, path: &Path) -> Result<NormalizedConversation> {
         let content = fs::read_to_string(path)?;
         let mut messages = Vec::new();
         let mut current_role = "system";
         let mut current_content = String::new();
-        let mut msg_idx = 0;
+        let mut session_ts: Option<i64> = None;
+        let mut first_session_ts: Option<i64> = None;
+        let mut in_fence = false;
 
         for line in content.lines() {
-            if line.trim().starts_with("> ") {
+            let trimmed = line.trim();
+
+            if !in_fence && let Some(ts) = Self::parse_session_header(trimmed) {
+                Self::push_message(
+                    &mut messages,
+                    current_role,
+                    &mut current_content,
+                    session_ts,
+                );
+                current_role = "system";
+                session_ts = Some(ts);
+                first_session_ts.get_or_insert(ts);
+                continue;
+            }
+
+            let user_text = if in_fence {
+                None
+            } else {
+                trimmed
+                    .strip_prefix("> ")
+                    .or_else(|| line.strip_prefix("#### "))
+            };
+
+            if let Some(text) = user_text {
                 // Only push previous content if switching from non-user role
-                if current_role != "user" && !current_content.trim().is_empty() {
-                    messages.push(NormalizedMessage {
-                        idx: msg_idx,
-                        role: current_role.to_string(),
-                        author: Some(current_role.to_string()),
-                        created_at: None,
-                        content: current_content.trim().to_string(),
-                        extra: json!({}),
-                        snippets: Vec::new(),
-                    });
-                    msg_idx += 1;
-                    current_content.clear();
+                if current_role != "user" {
+                    Self::push_message(
+                        &mut messages,
+                        current_role,
+                        &mut current_content,
+                        session_ts,
+                    );
                 }
                 current_role = "user";
-                current_content.push_str(line.trim_start_matches("> ").trim());
-                current_content.push('\n');
-            } else {
-                if current_role == "user" && !line.trim().is_empty() && !line.starts_with('>') {
-                    if !current_content.trim().is_empty() {
-                        messages.push(NormalizedMessage {
-                            idx: msg_idx,
-                            role: "user".to_string(),
-                            author: Some("user".to_string()),
-                            created_at: None,
-                            content: current_content.trim().to_string(),
-                            extra: json!({}),
-                            snippets: Vec::new(),
-                        });
-                        msg_idx += 1;
-                        current_content.clear();
-                    }
-                    current_role = "assistant";
-                }
-                current_content.push_str(line);
+                current_content.push_str(text.trim());
                 current_content.push('\n');
+                continue;
             }
-        }
 
-        if !current_content.trim().is_empty() {
-            messages.push(NormalizedMessage {
-                idx: msg_idx,
-                role: current_role.to_string(),
-                author: Some(current_role.to_string()),
-                created_at: None,
-                content: current_content.trim().to_string(),
-                extra: json!({}),
-                snippets: Vec::new(),
-            });
+            if current_role == "user" && !trimmed.is_empty() && !line.starts_with('>') {
+                Self::push_message(
+                    &mut messages,
+                    current_role,
+                    &mut current_content,
+                    session_ts,
+                );
+                current_role = "assistant";
+            }
+            if trimmed.starts_with("```") {
+                in_fence = !in_fence;
+            }
+            current_content.push_str(line);
+            current_content.push('\n');
         }
 
+        Self::push_message(
+            &mut messages,
+            current_role,
+            &mut current_content,
+            session_ts,
+        );
+
         let mtime = fs::metadata(path)?.modified()?;
         let ts = mtime
             .duration_since(std::time::UNIX_EPOCH)
@@ -105,11 +215,13 @@ impl AiderConnector {
 
         Ok(NormalizedConversation {
             agent_slug: "aider".to_string(),
+            // Keyed by the history file (one per repo) so appends extend the same
+            // conversation; unchanged files are skipped via their mtime in scan().
             external_id: Some(path.to_string_lossy().to_string()),
             title: Some(format!("Aider Chat: {}", path.display())),
             workspace: path.parent().map(std::path::Path::to_path_buf),
             source_path: path.to_path_buf(),
-            started_at: Some(ts),
+            started_at: first_session_ts.map(|first| first.min(ts)).or(Some(ts)),
             ended_at: Some(ts),
             metadata: json!({}),
             messages,
@@ -154,7 +266,10 @@ impl Connector for AiderConnector {
             };
         }
 
-        DetectionResult::not_found()
+        DetectionResult::not_found_because(vec![
+            format!("no {}", cwd_history.display()),
+            "CASS_AIDER_DATA_ROOT not set".to_string(),
+        ])
     }
 
     fn scan(&self, ctx: &ScanContext) -> Result<Vec<NormalizedConversation>> {
@@ -213,10 +328,13 @@ impl Connector for AiderConnector {
 
         let mut conversations = Vec::new();
         for path in files {
+            if !ctx.path_allowed(&path) {
+                continue;
+            }
             if !super::file_modified_since(&path, ctx.since_ts) {
                 continue;
             }
-            if let Ok(conv) = self.parse_chat_history(&path) {
+            if let Some(conv) = ctx.tolerate_file_error(&path, self.parse_chat_history(&path))? {
                 conversations.push(conv);
             }
         }
@@ -644,4 +762,71 @@ Done!"#;
         assert!(conv.messages[0].content.contains("`foo()`"));
         assert!(conv.messages[1].content.contains("\"bar\""));
     }
+
+    #[test]
+    fn parse_chat_history_uses_session_headers_and_hash_prompts() {
+        let dir = TempDir::new().unwrap();
+        let history_file = dir.path().join(".aider.chat.history.md");
+        let content = "# aider chat started at 2024-05-01 10:00:00\n\n#### fix the bug\n\nFixed it.\n\n# aider chat started at 2024-05-02 09:30:00\n\n#### add tests\n\nAdded tests.\n";
+        fs::write(&history_file, content).unwrap();
+
+        let connector = AiderConnector::new();
+        let conv = connector.parse_chat_history(&history_file).unwrap();
+
+        let roles: Vec<&str> = conv.messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["user", "assistant", "user", "assistant"]);
+        assert_eq!(conv.messages[0].content, "fix the bug");
+        assert!(!conv.messages[1].content.contains("aider chat started"));
+
+        let first =
+            AiderConnector::parse_session_header("# aider chat started at 2024-05-01 10:00:00");
+        let second =
+            AiderConnector::parse_session_header("# aider chat started at 2024-05-02 09:30:00");
+        assert!(first.is_some() && first < second);
+        assert_eq!(conv.messages[0].created_at, first);
+        assert_eq!(conv.messages[1].created_at, first);
+        assert_eq!(conv.messages[3].created_at, second);
+        assert_eq!(conv.started_at, first);
+    }
+
+    #[test]
+    fn parse_chat_history_extracts_diff_snippets() {
+        let dir = TempDir::new().unwrap();
+        let history_file = dir.path().join(".aider.chat.history.md");
+        let content = "#### rename foo\n\nHere is the change:\n```diff\n--- a/src/lib.rs\n+++ b/src/lib.rs\n-fn foo() {}\n+fn bar() {}\n```\n";
+        fs::write(&history_file, content).unwrap();
+
+        let connector = AiderConnector::new();
+        let conv = connector.parse_chat_history(&history_file).unwrap();
+
+        assert_eq!(conv.messages.len(), 2);
+        let snippets = &conv.messages[1].snippets;
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].file_path, Some(PathBuf::from("src/lib.rs")));
+        assert_eq!(snippets[0].language.as_deref(), Some("diff"));
+        assert!(
+            snippets[0]
+                .snippet_text
+                .as_ref()
+                .unwrap()
+                .contains("+fn bar() {}")
+        );
+    }
+
+    #[test]
+    fn parse_chat_history_extracts_search_replace_snippets() {
+        let dir = TempDir::new().unwrap();
+        let history_file = dir.path().join(".aider.chat.history.md");
+        let content = "#### greet louder\n\nsrc/app.py\n```python\n<<<<<<< SEARCH\nprint(\"hi\")\n=======\nprint(\"HI\")\n>>>>>>> REPLACE\n```\n\n```python\nprint(\"unrelated\")\n```\n";
+        fs::write(&history_file, content).unwrap();
+
+        let connector = AiderConnector::new();
+        let conv = connector.parse_chat_history(&history_file).unwrap();
+
+        assert_eq!(conv.messages.len(), 2);
+        let snippets = &conv.messages[1].snippets;
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].file_path, Some(PathBuf::from("src/app.py")));
+        assert_eq!(snippets[0].language.as_deref(), Some("python"));
+    }
 }
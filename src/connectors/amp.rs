@@ -63,7 +63,7 @@ impl Connector for AmpConnector {
             .collect();
 
         if evidence.is_empty() {
-            DetectionResult::not_found()
+            DetectionResult::missing(Self::candidate_roots())
         } else {
             DetectionResult {
                 detected: true,
@@ -109,20 +109,20 @@ impl Connector for AmpConnector {
                     continue;
                 }
                 let path = entry.path();
-                if !is_amp_log_file(path) {
+                if !is_amp_log_file(path) || !ctx.path_allowed(path) {
                     continue;
                 }
                 // NOTE: We intentionally skip the file_modified_since() check for Amp.
                 // Amp does not update file mtime when new messages are added to a thread,
                 // so mtime-based incremental indexing would miss new messages.
                 // This means Amp files are always re-read, but correctness is preserved.
-                let text = match std::fs::read_to_string(path) {
-                    Ok(t) => t,
-                    Err(_) => continue,
+                let Some(text) = ctx.tolerate_file_error(path, std::fs::read_to_string(path))?
+                else {
+                    continue;
                 };
-                let val: Value = match serde_json::from_str(&text) {
-                    Ok(v) => v,
-                    Err(_) => continue,
+                let parsed = serde_json::from_str::<Value>(&text);
+                let Some(val) = ctx.tolerate_file_error(path, parsed)? else {
+                    continue;
                 };
 
                 if let Some(messages) = extract_messages(&val, ctx.since_ts) {
@@ -170,8 +170,11 @@ impl Connector for AmpConnector {
                             title,
                             workspace,
                             source_path: path.to_path_buf(),
-                            started_at: messages.first().and_then(|m| m.created_at),
-                            ended_at: messages.last().and_then(|m| m.created_at),
+                            started_at: messages
+                                .iter()
+                                .find_map(|m| m.created_at)
+                                .or_else(|| thread_created_at(&val)),
+                            ended_at: messages.iter().rev().find_map(|m| m.created_at),
                             metadata: val.clone(),
                             messages,
                         });
@@ -221,15 +224,7 @@ fn extract_messages(val: &Value, _since_ts: Option<i64>) -> Option<Vec<Normalize
             continue;
         }
 
-        // Use parse_timestamp to handle both i64 milliseconds and ISO-8601 strings
-        // Also check sentAt which Amp uses
-        let created_at = m
-            .get("created_at")
-            .or_else(|| m.get("createdAt"))
-            .or_else(|| m.get("sentAt"))
-            .or_else(|| m.get("timestamp"))
-            .or_else(|| m.get("ts"))
-            .and_then(crate::connectors::parse_timestamp);
+        let created_at = message_created_at(&m);
         let author = m
             .get("author")
             .or_else(|| m.get("sender"))
@@ -258,6 +253,25 @@ fn extract_messages(val: &Value, _since_ts: Option<i64>) -> Option<Vec<Normalize
     if out.is_empty() { None } else { Some(out) }
 }
 
+/// Timestamp of one Amp message in epoch millis. Every field goes through
+/// `parse_timestamp`, so integer millis/seconds and ISO-8601 strings agree.
+/// Current Amp threads keep `sentAt` under the message's `meta` object.
+fn message_created_at(m: &Value) -> Option<i64> {
+    ["created_at", "createdAt", "sentAt", "timestamp", "ts"]
+        .iter()
+        .find_map(|k| m.get(*k))
+        .or_else(|| m.get("meta").and_then(|meta| meta.get("sentAt")))
+        .and_then(crate::connectors::parse_timestamp)
+}
+
+/// Thread-level creation time, used when no message carries a timestamp.
+fn thread_created_at(val: &Value) -> Option<i64> {
+    val.get("created")
+        .or_else(|| val.get("createdAt"))
+        .or_else(|| val.get("thread").and_then(|t| t.get("created")))
+        .and_then(crate::connectors::parse_timestamp)
+}
+
 /// Extract text content from a value that may be a string or an array of content blocks.
 /// Uses the shared flatten_content helper for consistent handling across all connectors.
 fn extract_content_value(val: Option<&Value>) -> Option<String> {
@@ -729,6 +743,15 @@ mod tests {
         assert_eq!(msgs[0].created_at, Some(1733000005000));
     }
 
+    #[test]
+    fn extract_messages_parses_meta_sent_at() {
+        let val = json!({
+            "messages": [{"role": "user", "content": "Test", "meta": {"sentAt": 1700000000100_i64}}]
+        });
+        let msgs = extract_messages(&val, None).unwrap();
+        assert_eq!(msgs[0].created_at, Some(1700000000100));
+    }
+
     // =====================================================
     // scan() Tests
     // =====================================================
@@ -889,6 +912,69 @@ mod tests {
         assert_eq!(convs[0].ended_at, Some(1733000100000));
     }
 
+    #[test]
+    fn scan_parses_real_thread_timestamps_exactly() {
+        let dir = TempDir::new().unwrap();
+        let amp_dir = create_amp_dir(&dir);
+
+        // Shape of a thread file written by Amp: integer-millis `created`, millis
+        // and ISO `createdAt`, and `meta.sentAt` on newer messages.
+        let content = r#"{
+  "v": 12,
+  "id": "T-01872a67-152b-46af-a1af-4de6fce3d2b3",
+  "created": 1700000000000,
+  "title": "Fix flaky test",
+  "messages": [
+    {"role": "user", "content": [{"type": "text", "text": "why is this flaky?"}], "createdAt": 1700000000100},
+    {"role": "assistant", "content": [{"type": "text", "text": "mtime race"}], "createdAt": "2023-11-14T22:13:21.250Z"},
+    {"role": "assistant", "content": [{"type": "text", "text": "status: done"}]},
+    {"role": "user", "content": [{"type": "text", "text": "thanks"}], "meta": {"sentAt": 1700000002999}}
+  ]
+}"#;
+        fs::write(
+            amp_dir.join("T-01872a67-152b-46af-a1af-4de6fce3d2b3.json"),
+            content,
+        )
+        .unwrap();
+
+        let connector = AmpConnector::new();
+        let ctx = ScanContext::local_default(amp_dir.clone(), None);
+        let convs = connector.scan(&ctx).unwrap();
+
+        assert_eq!(convs.len(), 1);
+        let times: Vec<_> = convs[0].messages.iter().map(|m| m.created_at).collect();
+        assert_eq!(
+            times,
+            vec![
+                Some(1700000000100),
+                Some(1700000001250),
+                None,
+                Some(1700000002999)
+            ]
+        );
+        assert_eq!(convs[0].started_at, Some(1700000000100));
+        assert_eq!(convs[0].ended_at, Some(1700000002999));
+    }
+
+    #[test]
+    fn scan_falls_back_to_thread_created_for_started_at() {
+        let dir = TempDir::new().unwrap();
+        let amp_dir = create_amp_dir(&dir);
+
+        let content = json!({
+            "created": 1700000000000_i64,
+            "messages": [{"role": "user", "content": "no timestamps here"}]
+        });
+        fs::write(amp_dir.join("thread.json"), content.to_string()).unwrap();
+
+        let connector = AmpConnector::new();
+        let ctx = ScanContext::local_default(amp_dir.clone(), None);
+        let convs = connector.scan(&ctx).unwrap();
+
+        assert_eq!(convs[0].started_at, Some(1700000000000));
+        assert_eq!(convs[0].ended_at, None);
+    }
+
     #[test]
     fn scan_skips_invalid_json() {
         let dir = TempDir::new().unwrap();
@@ -452,8 +452,17 @@ impl Connector for ChatGptConnector {
                     root_paths: vec![base],
                 };
             }
+            return DetectionResult::not_found_because(vec![format!(
+                "found {} but no conversation directories",
+                base.display()
+            )]);
+        }
+        match Self::app_support_dir() {
+            Some(base) => DetectionResult::missing([base]),
+            None => DetectionResult::not_found_because(vec![
+                "ChatGPT desktop is only available on macOS".to_string(),
+            ]),
         }
-        DetectionResult::not_found()
     }
 
     fn scan(&self, ctx: &ScanContext) -> Result<Vec<NormalizedConversation>> {
@@ -522,6 +531,9 @@ impl Connector for ChatGptConnector {
                     continue;
                 }
 
+                if !ctx.path_allowed(path) {
+                    continue;
+                }
                 // Skip files not modified since last scan
                 if !crate::connectors::file_modified_since(path, ctx.since_ts) {
                     continue;
@@ -544,6 +556,9 @@ impl Connector for ChatGptConnector {
                             "chatgpt no messages in conversation"
                         );
                     }
+                    Err(e) if ctx.strict => {
+                        return Err(e.context(format!("session file {}", path.display())));
+                    }
                     Err(e) => {
                         if is_encrypted {
                             tracing::warn!(
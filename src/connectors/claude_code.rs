@@ -38,7 +38,7 @@ impl Connector for ClaudeCodeConnector {
                 root_paths: vec![root],
             }
         } else {
-            DetectionResult::not_found()
+            DetectionResult::missing([root])
         }
     }
 
@@ -80,6 +80,9 @@ impl Connector for ClaudeCodeConnector {
             if ext != Some("jsonl") && ext != Some("json") && ext != Some("claude") {
                 continue;
             }
+            if !ctx.path_allowed(entry.path()) {
+                continue;
+            }
             // Skip files not modified since last scan (incremental indexing)
             if !crate::connectors::file_modified_since(entry.path(), ctx.since_ts) {
                 continue;
@@ -99,8 +102,11 @@ impl Connector for ClaudeCodeConnector {
             let mut content_string = String::new();
 
             if ext == Some("jsonl") {
-                let file = std::fs::File::open(entry.path())
-                    .with_context(|| format!("open {}", entry.path().display()))?;
+                let opened = std::fs::File::open(entry.path())
+                    .with_context(|| format!("open {}", entry.path().display()));
+                let Some(file) = ctx.tolerate_file_error(entry.path(), opened)? else {
+                    continue;
+                };
                 let reader = std::io::BufReader::new(file);
 
                 for line_res in std::io::BufRead::lines(reader) {
@@ -191,15 +197,16 @@ impl Connector for ClaudeCodeConnector {
                 // Re-assign sequential indices after filtering
                 super::reindex_messages(&mut messages);
             } else {
-                content_string = fs::read_to_string(entry.path())
-                    .with_context(|| format!("read {}", entry.path().display()))?;
+                let read = fs::read_to_string(entry.path())
+                    .with_context(|| format!("read {}", entry.path().display()));
+                let Some(data) = ctx.tolerate_file_error(entry.path(), read)? else {
+                    continue;
+                };
+                content_string = data;
                 // JSON or Claude format files
-                let val: Value = match serde_json::from_str(&content_string) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        tracing::debug!(path = %entry.path().display(), error = %e, "claude_code skipping malformed JSON");
-                        continue;
-                    }
+                let parsed = serde_json::from_str::<Value>(&content_string);
+                let Some(val) = ctx.tolerate_file_error(entry.path(), parsed)? else {
+                    continue;
                 };
                 if let Some(arr) = val.get("messages").and_then(|m| m.as_array()) {
                     for item in arr {
@@ -50,16 +50,16 @@ impl ClawdbotConnector {
 
 impl Connector for ClawdbotConnector {
     fn detect(&self) -> DetectionResult {
-        if let Some(root) = Self::agents_root() {
-            if root.exists() {
-                return DetectionResult {
-                    detected: true,
-                    evidence: vec![format!("found {}", root.display())],
-                    root_paths: vec![root],
-                };
-            }
+        if let Some(root) = Self::agents_root()
+            && root.exists()
+        {
+            return DetectionResult {
+                detected: true,
+                evidence: vec![format!("found {}", root.display())],
+                root_paths: vec![root],
+            };
         }
-        DetectionResult::not_found()
+        DetectionResult::missing(Self::agents_root())
     }
 
     fn scan(&self, ctx: &ScanContext) -> Result<Vec<NormalizedConversation>> {
@@ -110,17 +110,16 @@ impl Connector for ClawdbotConnector {
                 continue;
             }
 
+            if !ctx.path_allowed(path) {
+                continue;
+            }
             // Skip files not modified since last scan (incremental indexing)
             if !file_modified_since(path, ctx.since_ts) {
                 continue;
             }
 
-            match parse_clawdbot_session(path) {
-                Ok(Some(conv)) => convs.push(conv),
-                Ok(None) => {}
-                Err(e) => {
-                    tracing::debug!(path = %path.display(), error = %e, "clawdbot parse error");
-                }
+            if let Some(Some(conv)) = ctx.tolerate_file_error(path, parse_clawdbot_session(path))? {
+                convs.push(conv);
             }
         }
 
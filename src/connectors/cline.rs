@@ -106,7 +106,7 @@ impl Connector for ClineConnector {
                 root_paths: roots,
             }
         } else {
-            DetectionResult::not_found()
+            DetectionResult::missing(Self::candidate_roots())
         }
     }
 
@@ -165,19 +165,22 @@ impl Connector for ClineConnector {
                     continue;
                 };
 
+                if !ctx.path_allowed(&file) {
+                    continue;
+                }
                 // Skip files not modified since last scan (incremental indexing)
                 if !crate::connectors::file_modified_since(&file, ctx.since_ts) {
                     continue;
                 }
 
-                let data = fs::read_to_string(&file)
-                    .with_context(|| format!("read {}", file.display()))?;
-                let val: Value = match serde_json::from_str(&data) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        tracing::debug!(path = %file.display(), error = %e, "cline skipping malformed JSON");
-                        continue;
-                    }
+                let read =
+                    fs::read_to_string(&file).with_context(|| format!("read {}", file.display()));
+                let Some(data) = ctx.tolerate_file_error(&file, read)? else {
+                    continue;
+                };
+                let parsed = serde_json::from_str::<Value>(&data);
+                let Some(val) = ctx.tolerate_file_error(&file, parsed)? else {
+                    continue;
                 };
 
                 let mut messages = Vec::new();
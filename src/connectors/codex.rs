@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -5,6 +6,7 @@ use anyhow::{Context, Result};
 use serde_json::Value;
 use walkdir::WalkDir;
 
+use crate::connectors::parse_cache::FileStamp;
 use crate::connectors::{
     Connector, DetectionResult, NormalizedConversation, NormalizedMessage, ScanContext,
 };
@@ -56,6 +58,90 @@ impl CodexConnector {
         }
         out
     }
+
+    /// Whether the global `history.jsonl` prompt log is indexed
+    /// (`CASS_CODEX_HISTORY=1`). Off by default: the log can be large and mostly
+    /// repeats prompts already in session files.
+    fn history_enabled() -> bool {
+        dotenvy::var("CASS_CODEX_HISTORY").is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+    }
+
+    /// Prompts from `history.jsonl` (`{"session_id", "ts", "text"}` per line) as
+    /// one conversation per UTC day. A prompt is skipped when its session has a
+    /// rollout file in `rollouts`, or when its text is already a user message in
+    /// `sessions`, so it isn't indexed twice.
+    fn history_conversations(
+        path: &Path,
+        rollouts: &[PathBuf],
+        sessions: &[NormalizedConversation],
+    ) -> Result<Vec<NormalizedConversation>> {
+        // Rollout names end in the session UUID: rollout-<timestamp>-<uuid>.jsonl
+        let rollout_sessions: HashSet<&str> = rollouts
+            .iter()
+            .filter_map(|f| f.file_stem()?.to_str())
+            .filter_map(|stem| stem.get(stem.len().checked_sub(36)?..))
+            .collect();
+        let session_prompts: HashSet<&str> = sessions
+            .iter()
+            .flat_map(|c| &c.messages)
+            .filter(|m| m.role == "user")
+            .map(|m| m.content.trim())
+            .collect();
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("read codex history {}", path.display()))?;
+        let mut days: BTreeMap<String, Vec<NormalizedMessage>> = BTreeMap::new();
+        for line in content.lines() {
+            let Ok(val) = serde_json::from_str::<Value>(line) else {
+                continue;
+            };
+            let text = val.get("text").and_then(|v| v.as_str()).unwrap_or("");
+            if text.trim().is_empty() || session_prompts.contains(text.trim()) {
+                continue;
+            }
+            if val
+                .get("session_id")
+                .and_then(|v| v.as_str())
+                .is_some_and(|id| rollout_sessions.contains(id))
+            {
+                continue;
+            }
+            let created = val.get("ts").and_then(crate::connectors::parse_timestamp);
+            let day = created
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map_or_else(
+                    || "undated".to_string(),
+                    |dt| dt.format("%Y-%m-%d").to_string(),
+                );
+            days.entry(day).or_default().push(NormalizedMessage {
+                idx: 0,
+                role: "user".to_string(),
+                author: None,
+                created_at: created,
+                content: text.to_string(),
+                extra: val,
+                snippets: Vec::new(),
+            });
+        }
+
+        Ok(days
+            .into_iter()
+            .map(|(day, mut messages)| {
+                super::reindex_messages(&mut messages);
+                NormalizedConversation {
+                    agent_slug: "codex".to_string(),
+                    external_id: Some(format!("history/{day}")),
+                    title: Some(format!("Codex prompts {day}")),
+                    workspace: None,
+                    source_path: path.to_path_buf(),
+                    started_at: messages.iter().filter_map(|m| m.created_at).min(),
+                    ended_at: messages.iter().filter_map(|m| m.created_at).max(),
+                    metadata: serde_json::json!({"source": "history"}),
+                    messages,
+                }
+            })
+            .collect())
+    }
 }
 
 impl Connector for CodexConnector {
@@ -70,7 +156,7 @@ impl Connector for CodexConnector {
                 root_paths: vec![sessions],
             }
         } else {
-            DetectionResult::not_found()
+            DetectionResult::missing([sessions])
         }
     }
 
@@ -107,12 +193,20 @@ impl Connector for CodexConnector {
         let files = Self::rollout_files(&home);
         let mut convs = Vec::new();
 
-        for file in files {
+        for file in &files {
             let source_path = file.clone();
+            if !ctx.path_allowed(file) {
+                continue;
+            }
             // Skip files not modified since last scan (incremental indexing)
-            if !crate::connectors::file_modified_since(&file, ctx.since_ts) {
+            if !crate::connectors::file_modified_since(file, ctx.since_ts) {
+                continue;
+            }
+            if let Some(cached) = ctx.parse_cache.as_ref().and_then(|c| c.get(file)) {
+                convs.extend(cached);
                 continue;
             }
+            let stamp = ctx.parse_cache.as_ref().and_then(|_| FileStamp::of(file));
             // Use relative path from sessions dir as external_id for uniqueness
             // e.g., "2025/11/20/rollout-1" instead of just "rollout-1"
             let sessions_dir = Self::sessions_dir(&home);
@@ -137,8 +231,11 @@ impl Connector for CodexConnector {
             let mut session_cwd: Option<PathBuf> = None;
 
             if ext == Some("jsonl") {
-                let f = std::fs::File::open(&file)
-                    .with_context(|| format!("open rollout {}", file.display()))?;
+                let opened = std::fs::File::open(file)
+                    .with_context(|| format!("open rollout {}", file.display()));
+                let Some(f) = ctx.tolerate_file_error(file, opened)? else {
+                    continue;
+                };
                 let reader = std::io::BufReader::new(f);
 
                 // Modern envelope format: each line has {type, timestamp, payload}
@@ -260,12 +357,15 @@ impl Connector for CodexConnector {
                 // Re-assign sequential indices after filtering
                 super::reindex_messages(&mut messages);
             } else if ext == Some("json") {
-                let content = fs::read_to_string(&file)
-                    .with_context(|| format!("read rollout {}", file.display()))?;
+                let read = fs::read_to_string(file)
+                    .with_context(|| format!("read rollout {}", file.display()));
+                let Some(content) = ctx.tolerate_file_error(file, read)? else {
+                    continue;
+                };
                 // Legacy format: single JSON object with {session, items}
-                let val: Value = match serde_json::from_str(&content) {
-                    Ok(v) => v,
-                    Err(_) => continue,
+                let parsed = serde_json::from_str::<Value>(&content);
+                let Some(val) = ctx.tolerate_file_error(file, parsed)? else {
+                    continue;
                 };
 
                 // Extract workspace from session.cwd
@@ -338,7 +438,7 @@ impl Connector for CodexConnector {
                         .map(|s| s.chars().take(100).collect())
                 });
 
-            convs.push(NormalizedConversation {
+            let conv = NormalizedConversation {
                 agent_slug: "codex".to_string(),
                 external_id,
                 title,
@@ -348,7 +448,23 @@ impl Connector for CodexConnector {
                 ended_at,
                 metadata: serde_json::json!({"source": if ext == Some("json") { "rollout_json" } else { "rollout" }}),
                 messages,
-            });
+            };
+            if let (Some(cache), Some(stamp)) = (&ctx.parse_cache, &stamp) {
+                cache.put(file, stamp, std::slice::from_ref(&conv));
+            }
+            convs.push(conv);
+        }
+
+        let history = home.join("history.jsonl");
+        if Self::history_enabled()
+            && history.is_file()
+            && ctx.path_allowed(&history)
+            && crate::connectors::file_modified_since(&history, ctx.since_ts)
+        {
+            let parsed = Self::history_conversations(&history, &files, &convs);
+            if let Some(days) = ctx.tolerate_file_error(&history, parsed)? {
+                convs.extend(days);
+            }
         }
 
         Ok(convs)
@@ -509,6 +625,10 @@ mod tests {
         unsafe { std::env::remove_var("CODEX_HOME") };
 
         assert!(!result.detected);
+        assert_eq!(
+            result.evidence,
+            vec![format!("no {}", dir.path().join("sessions").display())]
+        );
     }
 
     // =====================================================
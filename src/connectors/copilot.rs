@@ -0,0 +1,429 @@
+//! Connector for GitHub Copilot Chat sessions in VS Code.
+//!
+//! Copilot Chat writes one JSON file per chat session under the VS Code user dir:
+//!   - `User/workspaceStorage/{id}/chatSessions/{session-id}.json` for chats opened
+//!     in a folder; the sibling `User/workspaceStorage/{id}/workspace.json` names it
+//!   - `User/globalStorage/emptyWindowChatSessions/{session-id}.json` for chats
+//!     opened without one
+//!
+//! A session holds `requests`, each a user prompt (`message`) paired with
+//! Copilot's reply (`response`). Newer sessions store the prompt as
+//! `{"text": ..., "parts": [...]}` and the reply as a list of streamed parts
+//! (`{"value": "..."}` fragments, `markdownContent`, tool invocations and
+//! references), with per-request `timestamp`s. Older ones store the prompt as
+//! a plain string and the reply as a single `{"value": ...}` object, with only
+//! session-level `creationDate`/`lastMessageDate`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::connectors::{
+    Connector, DetectionResult, NormalizedConversation, NormalizedMessage, ScanContext,
+    cursor::CursorConnector, file_modified_since, parse_timestamp, reindex_messages,
+};
+
+/// VS Code user dirs relative to the home directory, stable and Insiders builds.
+const USER_DIRS: &[&str] = &[
+    ".config/Code/User",
+    ".config/Code - Insiders/User",
+    "Library/Application Support/Code/User",
+    "Library/Application Support/Code - Insiders/User",
+    "AppData/Roaming/Code/User",
+    "AppData/Roaming/Code - Insiders/User",
+];
+
+pub struct CopilotConnector;
+
+impl Default for CopilotConnector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CopilotConnector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn candidate_roots() -> Vec<PathBuf> {
+        let Some(home) = dirs::home_dir() else {
+            return Vec::new();
+        };
+        USER_DIRS.iter().map(|dir| home.join(dir)).collect()
+    }
+
+    fn storage_roots() -> Vec<PathBuf> {
+        Self::candidate_roots()
+            .into_iter()
+            .filter(|root| looks_like_copilot_storage(root))
+            .collect()
+    }
+
+    /// Session files under a VS Code user dir, each with the workspace folder
+    /// its `workspaceStorage` entry maps to.
+    fn session_files(root: &Path) -> Vec<(PathBuf, Option<PathBuf>)> {
+        let mut files = Vec::new();
+        if let Ok(entries) = fs::read_dir(root.join("workspaceStorage")) {
+            for entry in entries.flatten() {
+                let dir = entry.path();
+                let workspace = workspace_folder(&dir);
+                for file in json_files(&dir.join("chatSessions")) {
+                    files.push((file, workspace.clone()));
+                }
+            }
+        }
+        for file in json_files(&root.join("globalStorage/emptyWindowChatSessions")) {
+            files.push((file, None));
+        }
+        files
+    }
+}
+
+impl Connector for CopilotConnector {
+    fn detect(&self) -> DetectionResult {
+        let roots = Self::storage_roots();
+        if roots.is_empty() {
+            return DetectionResult::missing(Self::candidate_roots());
+        }
+        DetectionResult {
+            detected: true,
+            evidence: roots
+                .iter()
+                .map(|r| format!("found {}", r.display()))
+                .collect(),
+            root_paths: roots,
+        }
+    }
+
+    fn scan(&self, ctx: &ScanContext) -> Result<Vec<NormalizedConversation>> {
+        let roots = if ctx.use_default_detection() {
+            // A data_dir that is itself a VS Code user dir (tests, explicit paths)
+            // takes precedence over the default locations.
+            if looks_like_copilot_storage(&ctx.data_dir) {
+                vec![ctx.data_dir.clone()]
+            } else {
+                Self::storage_roots()
+            }
+        } else {
+            ctx.scan_roots
+                .iter()
+                .flat_map(|sr| {
+                    std::iter::once(sr.path.clone())
+                        .chain(USER_DIRS.iter().map(|dir| sr.path.join(dir)))
+                })
+                .filter(|root| looks_like_copilot_storage(root))
+                .collect()
+        };
+
+        let mut convs = Vec::new();
+        for root in roots {
+            for (path, workspace) in Self::session_files(&root) {
+                if !ctx.path_allowed(&path) {
+                    continue;
+                }
+                // Skip files not modified since last scan (incremental indexing)
+                if !file_modified_since(&path, ctx.since_ts) {
+                    continue;
+                }
+                let parsed = parse_chat_session(&path, workspace);
+                if let Some(Some(conv)) = ctx.tolerate_file_error(&path, parsed)? {
+                    convs.push(conv);
+                }
+            }
+        }
+        Ok(convs)
+    }
+}
+
+/// Whether `path` is a VS Code user dir holding Copilot chat sessions.
+fn looks_like_copilot_storage(path: &Path) -> bool {
+    if path.join("globalStorage/emptyWindowChatSessions").is_dir() {
+        return true;
+    }
+    fs::read_dir(path.join("workspaceStorage"))
+        .map(|mut entries| entries.any(|e| e.is_ok_and(|e| e.path().join("chatSessions").is_dir())))
+        .unwrap_or(false)
+}
+
+fn json_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Folder a `workspaceStorage/{id}` entry belongs to, from its `workspace.json`.
+fn workspace_folder(storage_dir: &Path) -> Option<PathBuf> {
+    let text = fs::read_to_string(storage_dir.join("workspace.json")).ok()?;
+    let val: Value = serde_json::from_str(&text).ok()?;
+    let uri = val
+        .get("folder")
+        .or_else(|| val.get("workspace"))
+        .and_then(Value::as_str)?;
+    CursorConnector::parse_workspace_uri(uri)
+}
+
+/// Prompt text: a plain string (older sessions) or `{"text": ...}`, falling
+/// back to the concatenated text of its `parts`.
+fn prompt_text(message: &Value) -> String {
+    if let Some(text) = message.as_str() {
+        return text.to_string();
+    }
+    if let Some(text) = message.get("text").and_then(Value::as_str) {
+        return text.to_string();
+    }
+    message
+        .get("parts")
+        .and_then(Value::as_array)
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|p| p.get("text").and_then(Value::as_str))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reply text: streamed markdown parts (newer sessions) or a single
+/// `{"value": ...}` object (older ones). References, progress messages and
+/// tool invocations carry no reply text and are skipped.
+fn response_text(response: &Value) -> String {
+    match response {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts.iter().filter_map(part_text).collect(),
+        Value::Object(_) => part_text(response).unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+fn part_text(part: &Value) -> Option<String> {
+    let value = match part.get("kind").and_then(Value::as_str) {
+        None | Some("markdownContent") => part.get("value").or_else(|| part.get("content"))?,
+        Some(_) => return None,
+    };
+    // Either a bare string or a VS Code MarkdownString (`{"value": "..."}`).
+    value
+        .as_str()
+        .or_else(|| value.get("value").and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+/// Parse a Copilot chat session file into a NormalizedConversation.
+fn parse_chat_session(
+    path: &Path,
+    workspace: Option<PathBuf>,
+) -> Result<Option<NormalizedConversation>> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("read session {}", path.display()))?;
+    let val: Value =
+        serde_json::from_str(&text).with_context(|| format!("parse session {}", path.display()))?;
+    let Some(requests) = val.get("requests").and_then(Value::as_array) else {
+        return Ok(None);
+    };
+
+    let mut messages = Vec::new();
+    for request in requests {
+        let created = request.get("timestamp").and_then(parse_timestamp);
+        let prompt = request.get("message").map(prompt_text).unwrap_or_default();
+        if !prompt.trim().is_empty() {
+            messages.push(NormalizedMessage {
+                idx: 0, // Reassigned after collection
+                role: "user".into(),
+                author: val
+                    .get("requesterUsername")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+                created_at: created,
+                content: prompt,
+                extra: serde_json::json!({ "requestId": request.get("requestId") }),
+                snippets: Vec::new(),
+            });
+        }
+
+        let reply = request
+            .get("response")
+            .map(response_text)
+            .unwrap_or_default();
+        if !reply.trim().is_empty() {
+            // Replies finish `totalElapsed` ms after the prompt when timings are recorded.
+            let elapsed = request
+                .pointer("/result/timings/totalElapsed")
+                .and_then(Value::as_i64)
+                .unwrap_or(0);
+            messages.push(NormalizedMessage {
+                idx: 0,
+                role: "assistant".into(),
+                author: request
+                    .get("modelId")
+                    .or_else(|| val.get("responderUsername"))
+                    .and_then(Value::as_str)
+                    .map(String::from),
+                created_at: created.map(|ts| ts + elapsed),
+                content: reply,
+                extra: serde_json::json!({
+                    "requestId": request.get("requestId"),
+                    "isCanceled": request.get("isCanceled"),
+                }),
+                snippets: Vec::new(),
+            });
+        }
+    }
+
+    reindex_messages(&mut messages);
+    if messages.is_empty() {
+        return Ok(None);
+    }
+
+    let session_id = val
+        .get("sessionId")
+        .and_then(Value::as_str)
+        .map(String::from)
+        .or_else(|| path.file_stem().and_then(|s| s.to_str()).map(String::from));
+    let title = val
+        .get("customTitle")
+        .and_then(Value::as_str)
+        .filter(|t| !t.trim().is_empty())
+        .map(String::from)
+        .or_else(|| {
+            messages.iter().find(|m| m.role == "user").map(|m| {
+                m.content
+                    .lines()
+                    .next()
+                    .unwrap_or(&m.content)
+                    .chars()
+                    .take(100)
+                    .collect()
+            })
+        });
+    let started_at = val
+        .get("creationDate")
+        .and_then(parse_timestamp)
+        .or_else(|| messages.iter().find_map(|m| m.created_at));
+    let ended_at = val
+        .get("lastMessageDate")
+        .and_then(parse_timestamp)
+        .or_else(|| messages.iter().rev().find_map(|m| m.created_at));
+
+    Ok(Some(NormalizedConversation {
+        agent_slug: "copilot".into(),
+        external_id: session_id.clone(),
+        title,
+        workspace,
+        source_path: path.to_path_buf(),
+        started_at,
+        ended_at,
+        metadata: serde_json::json!({
+            "source": "copilot",
+            "sessionId": session_id,
+            "version": val.get("version"),
+            "location": val.get("initialLocation"),
+        }),
+        messages,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_session(dir: &Path, name: &str, body: &Value) -> PathBuf {
+        fs::create_dir_all(dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, serde_json::to_vec(body).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn parses_newer_schema_with_streamed_parts() {
+        let tmp = TempDir::new().unwrap();
+        let path = write_session(
+            tmp.path(),
+            "new.json",
+            &serde_json::json!({
+                "version": 3,
+                "sessionId": "sess-new",
+                "creationDate": 1_700_000_000_000i64,
+                "requests": [{
+                    "requestId": "request_1",
+                    "timestamp": 1_700_000_001_000i64,
+                    "modelId": "copilot/gpt-4o",
+                    "message": {"text": "Explain lifetimes", "parts": []},
+                    "response": [
+                        {"value": "Lifetimes tie ", "supportThemeIcons": false},
+                        {"kind": "inlineReference", "inlineReference": {"path": "/x.rs"}},
+                        {"value": "borrows to scopes."},
+                        {"kind": "toolInvocationSerialized", "toolId": "read_file"}
+                    ],
+                    "result": {"timings": {"totalElapsed": 2500}}
+                }]
+            }),
+        );
+
+        let conv = parse_chat_session(&path, Some(PathBuf::from("/work/app")))
+            .unwrap()
+            .expect("conversation");
+        assert_eq!(conv.agent_slug, "copilot");
+        assert_eq!(conv.external_id.as_deref(), Some("sess-new"));
+        assert_eq!(conv.title.as_deref(), Some("Explain lifetimes"));
+        assert_eq!(conv.workspace, Some(PathBuf::from("/work/app")));
+        assert_eq!(conv.messages.len(), 2);
+        assert_eq!(conv.messages[1].role, "assistant");
+        assert_eq!(conv.messages[1].content, "Lifetimes tie borrows to scopes.");
+        assert_eq!(conv.messages[1].author.as_deref(), Some("copilot/gpt-4o"));
+        assert_eq!(conv.messages[1].created_at, Some(1_700_000_003_500));
+        assert_eq!(conv.started_at, Some(1_700_000_000_000));
+        assert_eq!(conv.ended_at, Some(1_700_000_003_500));
+    }
+
+    #[test]
+    fn parses_older_schema_with_string_prompts() {
+        let tmp = TempDir::new().unwrap();
+        let path = write_session(
+            tmp.path(),
+            "old.json",
+            &serde_json::json!({
+                "version": 1,
+                "requesterUsername": "octocat",
+                "responderUsername": "GitHub Copilot",
+                "requests": [
+                    {"message": "What is a trait?", "response": {"value": "A shared interface."}},
+                    {"message": "Thanks", "response": [], "isCanceled": true}
+                ]
+            }),
+        );
+
+        let conv = parse_chat_session(&path, None)
+            .unwrap()
+            .expect("conversation");
+        assert_eq!(conv.external_id.as_deref(), Some("old"));
+        let roles: Vec<_> = conv.messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant", "user"]);
+        assert_eq!(conv.messages[0].author.as_deref(), Some("octocat"));
+        assert_eq!(conv.messages[1].content, "A shared interface.");
+        assert_eq!(conv.messages[1].author.as_deref(), Some("GitHub Copilot"));
+        assert_eq!(conv.messages[2].idx, 2);
+    }
+
+    #[test]
+    fn sessions_without_requests_are_skipped() {
+        let tmp = TempDir::new().unwrap();
+        let empty = write_session(
+            tmp.path(),
+            "empty.json",
+            &serde_json::json!({"requests": []}),
+        );
+        let other = write_session(tmp.path(), "other.json", &serde_json::json!({"foo": 1}));
+        assert!(parse_chat_session(&empty, None).unwrap().is_none());
+        assert!(parse_chat_session(&other, None).unwrap().is_none());
+    }
+}
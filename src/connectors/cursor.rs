@@ -11,6 +11,10 @@
 //!
 //! And in the `ItemTable` with keys like:
 //! - `workbench.panel.aichat.view.aichat.chatdata` - Legacy chat data
+//! - `aiService.prompts` / `aiService.generations` - Prompt history per workspace
+//!
+//! Workspace databases sit next to a `workspace.json` whose `folder` URI names
+//! the project the chats belong to.
 
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -32,6 +36,13 @@ mod bubble_type {
     pub const ASSISTANT: i64 = 2;
 }
 
+/// Read-only connection to a Cursor database, possibly backed by a temp copy.
+struct CursorDb {
+    conn: Connection,
+    // Declared after `conn` so the copy is removed only once the connection closes.
+    _tmp: Option<tempfile::TempDir>,
+}
+
 /// Type alias for the bubble data lookup map.
 /// Keys are bubble IDs for O(1) lookup within a composer.
 type BubbleDataMap = HashMap<String, Value>;
@@ -211,7 +222,7 @@ impl CursorConnector {
 
     /// Parse a workspace URI to extract the path.
     /// Handles file:// and vscode-remote:// URIs.
-    fn parse_workspace_uri(uri: &str) -> Option<PathBuf> {
+    pub(crate) fn parse_workspace_uri(uri: &str) -> Option<PathBuf> {
         if let Some(path) = uri.strip_prefix("file://") {
             // URL decode and return
             let decoded = urlencoding::decode(path).ok()?;
@@ -232,20 +243,104 @@ impl CursorConnector {
         None
     }
 
+    /// Workspace folder for a `workspaceStorage/{id}/state.vscdb`, taken from the
+    /// sibling `workspace.json` (`{"folder": "file:///..."}`).
+    fn workspace_for_db(db_path: &Path) -> Option<PathBuf> {
+        let json_path = db_path.parent()?.join("workspace.json");
+        let text = std::fs::read_to_string(json_path).ok()?;
+        let val: Value = serde_json::from_str(&text).ok()?;
+        let uri = val
+            .get("folder")
+            .or_else(|| val.get("workspace"))
+            .and_then(|v| v.as_str())?;
+        Self::parse_workspace_uri(uri)
+    }
+
+    /// Open a Cursor database read-only.
+    ///
+    /// A running Cursor can hold the database locked; in that case the file (plus any
+    /// WAL/SHM sidecars) is copied to a temp dir and the copy is opened instead.
+    fn open_db(db_path: &Path) -> Result<CursorDb> {
+        let open = |path: &Path| -> rusqlite::Result<Connection> {
+            let conn = Connection::open_with_flags(
+                path,
+                rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY
+                    | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )?;
+            // Don't wait out rusqlite's default 5s busy timeout on a live lock.
+            conn.busy_timeout(std::time::Duration::from_millis(250))?;
+            // Opening is lazy; touch the schema so lock errors surface here.
+            conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))?;
+            Ok(conn)
+        };
+
+        match open(db_path) {
+            Ok(conn) => Ok(CursorDb { conn, _tmp: None }),
+            Err(e) => {
+                tracing::debug!(
+                    path = %db_path.display(),
+                    error = %e,
+                    "cursor db unreadable in place; retrying from temp copy"
+                );
+                let tmp = tempfile::TempDir::new()?;
+                let copy = tmp.path().join("state.vscdb");
+                std::fs::copy(db_path, &copy)
+                    .with_context(|| format!("failed to open Cursor db: {}", db_path.display()))?;
+                for suffix in ["-wal", "-shm"] {
+                    let sidecar = PathBuf::from(format!("{}{suffix}", db_path.display()));
+                    if sidecar.exists() {
+                        let _ = std::fs::copy(
+                            &sidecar,
+                            tmp.path().join(format!("state.vscdb{suffix}")),
+                        );
+                    }
+                }
+                let conn = open(&copy).with_context(|| {
+                    format!("failed to open copy of Cursor db: {}", db_path.display())
+                })?;
+                Ok(CursorDb {
+                    conn,
+                    _tmp: Some(tmp),
+                })
+            }
+        }
+    }
+
+    fn has_table(conn: &Connection, name: &str) -> bool {
+        conn.query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [name],
+            |_| Ok(()),
+        )
+        .is_ok()
+    }
+
+    fn item_value(conn: &Connection, key: &str) -> Option<String> {
+        conn.query_row("SELECT value FROM ItemTable WHERE key = ?1", [key], |row| {
+            row.get(0)
+        })
+        .ok()
+    }
+
     /// Extract chat sessions from a SQLite database
     fn extract_from_db(
         db_path: &Path,
         since_ts: Option<i64>,
     ) -> Result<Vec<NormalizedConversation>> {
-        let conn = Connection::open_with_flags(
-            db_path,
-            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
-        )
-        .with_context(|| format!("failed to open Cursor db: {}", db_path.display()))?;
+        let db = Self::open_db(db_path)?;
+        let conn = &db.conn;
 
         let mut convs = Vec::new();
         let mut seen_ids = HashSet::new();
 
+        if !Self::has_table(conn, "cursorDiskKV") && !Self::has_table(conn, "ItemTable") {
+            tracing::warn!(
+                path = %db_path.display(),
+                "cursor db has neither cursorDiskKV nor ItemTable; skipping"
+            );
+            return Ok(convs);
+        }
+
         // Try cursorDiskKV table for composerData entries
         if let Ok(mut stmt) =
             conn.prepare("SELECT key, value FROM cursorDiskKV WHERE key LIKE 'composerData:%'")
@@ -265,7 +360,7 @@ impl CursorConnector {
                         db_path,
                         since_ts,
                         &mut seen_ids,
-                        Some(&conn),
+                        Some(conn),
                     ) {
                         convs.push(conv);
                     }
@@ -295,9 +390,118 @@ impl CursorConnector {
             }
         }
 
+        // Prompt history kept by the AI service (per workspace)
+        if let Some(prompts) = Self::item_value(conn, "aiService.prompts") {
+            let generations = Self::item_value(conn, "aiService.generations");
+            if let Some(conv) = Self::parse_ai_service_prompts(
+                &prompts,
+                generations.as_deref(),
+                db_path,
+                &mut seen_ids,
+            ) {
+                convs.push(conv);
+            }
+        }
+
+        // Fill in the workspace from workspace.json for chats that didn't carry one
+        if let Some(workspace) = Self::workspace_for_db(db_path) {
+            for conv in convs.iter_mut().filter(|c| c.workspace.is_none()) {
+                conv.workspace = Some(workspace.clone());
+            }
+        }
+
         Ok(convs)
     }
 
+    /// Parse `aiService.prompts` (user prompts) into a conversation.
+    ///
+    /// Prompts carry no timestamps; they are matched to `aiService.generations`
+    /// entries by text to recover `unixMs` where possible. Every workspace DB has
+    /// its own prompt list, so the id carries the `workspaceStorage/{id}` folder name.
+    fn parse_ai_service_prompts(
+        prompts: &str,
+        generations: Option<&str>,
+        db_path: &Path,
+        seen_ids: &mut HashSet<String>,
+    ) -> Option<NormalizedConversation> {
+        let prompts: Value = serde_json::from_str(prompts).ok()?;
+        let prompts = prompts.as_array()?;
+
+        let id = match db_path.parent().and_then(|dir| dir.file_name()) {
+            Some(workspace) => format!("aiService.prompts:{}", workspace.to_string_lossy()),
+            None => "aiService.prompts".to_string(),
+        };
+        if !seen_ids.insert(id.clone()) {
+            return None;
+        }
+
+        let mut generations: Vec<(String, Option<i64>)> = generations
+            .and_then(|g| serde_json::from_str::<Value>(g).ok())
+            .and_then(|g| g.as_array().cloned())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|g| {
+                let text = g.get("textDescription").and_then(|v| v.as_str())?;
+                Some((text.to_string(), g.get("unixMs").and_then(|v| v.as_i64())))
+            })
+            .collect();
+
+        let mut messages = Vec::new();
+        for prompt in prompts {
+            let Some(text) = prompt
+                .get("text")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+            else {
+                continue;
+            };
+            let created_at = generations
+                .iter()
+                .position(|(desc, _)| desc.trim() == text)
+                .and_then(|pos| generations.remove(pos).1);
+            messages.push(NormalizedMessage {
+                idx: 0,
+                role: "user".to_string(),
+                author: None,
+                created_at,
+                content: text.to_string(),
+                extra: prompt.clone(),
+                snippets: Vec::new(),
+            });
+        }
+
+        if messages.is_empty() {
+            return None;
+        }
+        super::reindex_messages(&mut messages);
+
+        let title = messages.first().map(|m| {
+            m.content
+                .lines()
+                .next()
+                .unwrap_or("")
+                .chars()
+                .take(100)
+                .collect()
+        });
+        let started_at = messages.iter().filter_map(|m| m.created_at).min();
+        let ended_at = messages.iter().filter_map(|m| m.created_at).max();
+        let unique_source_path = db_path.join(urlencoding::encode(&id).as_ref());
+
+        Some(NormalizedConversation {
+            agent_slug: "cursor".to_string(),
+            external_id: Some(id),
+            title,
+            workspace: None,
+            source_path: unique_source_path,
+            started_at,
+            ended_at,
+            metadata: serde_json::json!({"source": "cursor_ai_service"}),
+            messages,
+        })
+    }
+
     /// Parse composerData JSON into a conversation.
     ///
     /// Supports multiple Cursor formats:
@@ -644,8 +848,12 @@ impl Connector for CursorConnector {
                     root_paths: vec![base],
                 };
             }
+            return DetectionResult::not_found_because(vec![format!(
+                "found {} but no database files",
+                base.display()
+            )]);
         }
-        DetectionResult::not_found()
+        DetectionResult::missing(Self::app_support_dir())
     }
 
     fn scan(&self, ctx: &ScanContext) -> Result<Vec<NormalizedConversation>> {
@@ -681,27 +889,22 @@ impl Connector for CursorConnector {
         let mut all_convs = Vec::new();
 
         for db_path in db_files {
+            if !ctx.path_allowed(&db_path) {
+                continue;
+            }
             // Skip files not modified since last scan
             if !crate::connectors::file_modified_since(&db_path, ctx.since_ts) {
                 continue;
             }
 
-            match Self::extract_from_db(&db_path, ctx.since_ts) {
-                Ok(convs) => {
-                    tracing::debug!(
-                        path = %db_path.display(),
-                        count = convs.len(),
-                        "cursor extracted conversations"
-                    );
-                    all_convs.extend(convs);
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        path = %db_path.display(),
-                        error = %e,
-                        "cursor failed to extract from db"
-                    );
-                }
+            let extracted = Self::extract_from_db(&db_path, ctx.since_ts);
+            if let Some(convs) = ctx.tolerate_file_error(&db_path, extracted)? {
+                tracing::debug!(
+                    path = %db_path.display(),
+                    count = convs.len(),
+                    "cursor extracted conversations"
+                );
+                all_convs.extend(convs);
             }
         }
 
@@ -1271,6 +1474,90 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn extract_from_db_parses_ai_service_prompts_with_workspace() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("state.vscdb");
+        fs::write(
+            dir.path().join("workspace.json"),
+            r#"{"folder": "file:///home/me/my%20project"}"#,
+        )
+        .unwrap();
+
+        let conn = create_test_db(&db_path);
+        let prompts = json!([
+            {"text": "explain this function", "commandType": 4},
+            {"text": "   ", "commandType": 4},
+            {"text": "now add tests", "commandType": 4}
+        ])
+        .to_string();
+        let generations = json!([
+            {"unixMs": 1700000000000i64, "type": "composer", "textDescription": "explain this function"},
+            {"unixMs": 1700000005000i64, "type": "composer", "textDescription": "now add tests"}
+        ])
+        .to_string();
+        conn.execute(
+            "INSERT INTO ItemTable (key, value) VALUES (?, ?)",
+            ["aiService.prompts", &prompts],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO ItemTable (key, value) VALUES (?, ?)",
+            ["aiService.generations", &generations],
+        )
+        .unwrap();
+        drop(conn);
+
+        let convs = CursorConnector::extract_from_db(&db_path, None).unwrap();
+        assert_eq!(convs.len(), 1);
+        let conv = &convs[0];
+        let expected_id = format!(
+            "aiService.prompts:{}",
+            dir.path().file_name().unwrap().to_string_lossy()
+        );
+        assert_eq!(conv.external_id.as_deref(), Some(expected_id.as_str()));
+        assert_eq!(conv.workspace, Some(PathBuf::from("/home/me/my project")));
+        assert_eq!(conv.messages.len(), 2);
+        assert_eq!(conv.messages[0].role, "user");
+        assert_eq!(conv.messages[1].content, "now add tests");
+        assert_eq!(conv.started_at, Some(1700000000000));
+        assert_eq!(conv.ended_at, Some(1700000005000));
+    }
+
+    #[test]
+    fn extract_from_db_returns_empty_for_unexpected_schema() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("state.vscdb");
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute("CREATE TABLE other (id INTEGER)", []).unwrap();
+        drop(conn);
+
+        let convs = CursorConnector::extract_from_db(&db_path, None).unwrap();
+        assert!(convs.is_empty());
+    }
+
+    #[test]
+    fn extract_from_db_reads_locked_db_via_copy() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("state.vscdb");
+
+        let conn = create_test_db(&db_path);
+        let value = json!({"text": "Locked db test"}).to_string();
+        conn.execute(
+            "INSERT INTO cursorDiskKV (key, value) VALUES (?, ?)",
+            ["composerData:locked-1", &value],
+        )
+        .unwrap();
+        // Hold an exclusive lock as a running Cursor would
+        conn.execute_batch("BEGIN EXCLUSIVE").unwrap();
+
+        let convs = CursorConnector::extract_from_db(&db_path, None).unwrap();
+        assert_eq!(convs.len(), 1);
+        assert!(convs[0].messages[0].content.contains("Locked db test"));
+
+        conn.execute_batch("COMMIT").unwrap();
+    }
+
     // =========================================================================
     // Detection tests
     // =========================================================================
@@ -1335,6 +1622,34 @@ mod tests {
         assert_eq!(convs.len(), 1);
     }
 
+    #[test]
+    fn scan_keeps_ai_service_prompts_per_workspace() {
+        let dir = TempDir::new().unwrap();
+        let cursor_dir = dir.path().join("Cursor");
+        for (ws, prompt) in [("ws1", "first workspace"), ("ws2", "second workspace")] {
+            let ws_dir = cursor_dir.join("workspaceStorage").join(ws);
+            fs::create_dir_all(&ws_dir).unwrap();
+            let conn = create_test_db(&ws_dir.join("state.vscdb"));
+            let prompts = json!([{"text": prompt, "commandType": 4}]).to_string();
+            conn.execute(
+                "INSERT INTO ItemTable (key, value) VALUES (?, ?)",
+                ["aiService.prompts", &prompts],
+            )
+            .unwrap();
+        }
+
+        let ctx = ScanContext::local_default(cursor_dir, None);
+        let mut convs = CursorConnector::new().scan(&ctx).unwrap();
+        convs.sort_by(|a, b| a.external_id.cmp(&b.external_id));
+
+        let ids: Vec<_> = convs.iter().map(|c| c.external_id.as_deref()).collect();
+        assert_eq!(
+            ids,
+            vec![Some("aiService.prompts:ws1"), Some("aiService.prompts:ws2")]
+        );
+        assert_eq!(convs[1].messages[0].content, "second workspace");
+    }
+
     #[test]
     fn scan_recognizes_cursor_in_path() {
         let dir = TempDir::new().unwrap();
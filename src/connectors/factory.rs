@@ -66,7 +66,7 @@ impl Connector for FactoryConnector {
                 root_paths: vec![root],
             };
         }
-        DetectionResult::not_found()
+        DetectionResult::missing(Self::sessions_root())
     }
 
     fn scan(&self, ctx: &ScanContext) -> Result<Vec<NormalizedConversation>> {
@@ -117,17 +117,16 @@ impl Connector for FactoryConnector {
                 continue;
             }
 
+            if !ctx.path_allowed(path) {
+                continue;
+            }
             // Skip files not modified since last scan (incremental indexing)
             if !file_modified_since(path, ctx.since_ts) {
                 continue;
             }
 
-            match parse_factory_session(path) {
-                Ok(Some(conv)) => convs.push(conv),
-                Ok(None) => {}
-                Err(e) => {
-                    tracing::debug!(path = %path.display(), error = %e, "factory parse error");
-                }
+            if let Some(Some(conv)) = ctx.tolerate_file_error(path, parse_factory_session(path))? {
+                convs.push(conv);
             }
         }
 
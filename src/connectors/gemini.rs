@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -141,8 +142,23 @@ impl GeminiConnector {
         )
     }
 
+    /// Project directories keyed by project hash, read from `<root>/projects.json`
+    /// (`{"<hash>": "/path/to/project"}`). Empty when the file is missing or invalid.
+    fn project_paths(root: &Path) -> HashMap<String, PathBuf> {
+        fs::read_to_string(root.join("projects.json"))
+            .ok()
+            .and_then(|s| serde_json::from_str::<HashMap<String, String>>(&s).ok())
+            .map(|map| {
+                map.into_iter()
+                    .filter(|(_, path)| !path.trim().is_empty())
+                    .map(|(hash, path)| (hash, PathBuf::from(path)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Find all session JSON files in the Gemini structure.
-    /// Structure: ~/.gemini/tmp/<hash>/chats/session-*.json
+    /// Structure: ~/.gemini/tmp/<hash>/chats/{session,checkpoint}-*.json
     fn session_files(root: &Path) -> Vec<PathBuf> {
         let mut files = Vec::new();
         for entry in WalkDir::new(root).into_iter().flatten() {
@@ -150,9 +166,11 @@ impl GeminiConnector {
                 continue;
             }
             let path = entry.path();
-            // Only process session-*.json files in chats/ directories
+            // Only process session/checkpoint JSON files in chats/ directories
             let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
-            if name.starts_with("session-") && name.ends_with(".json") {
+            if (name.starts_with("session-") || name.starts_with("checkpoint-"))
+                && name.ends_with(".json")
+            {
                 // Verify it's in a chats/ directory
                 if path
                     .parent()
@@ -178,7 +196,7 @@ impl Connector for GeminiConnector {
                 root_paths: vec![root],
             }
         } else {
-            DetectionResult::not_found()
+            DetectionResult::missing([root])
         }
     }
 
@@ -212,19 +230,26 @@ impl Connector for GeminiConnector {
         }
 
         let files = Self::session_files(&root);
+        let project_paths = Self::project_paths(&root);
         let mut convs = Vec::new();
 
         for file in files {
+            if !ctx.path_allowed(&file) {
+                continue;
+            }
             // Skip files not modified since last scan (incremental indexing)
             if !crate::connectors::file_modified_since(&file, ctx.since_ts) {
                 continue;
             }
-            let content = fs::read_to_string(&file)
-                .with_context(|| format!("read session {}", file.display()))?;
+            let read = fs::read_to_string(&file)
+                .with_context(|| format!("read session {}", file.display()));
+            let Some(content) = ctx.tolerate_file_error(&file, read)? else {
+                continue;
+            };
 
-            let val: Value = match serde_json::from_str(&content) {
-                Ok(v) => v,
-                Err(_) => continue,
+            let parsed = serde_json::from_str::<Value>(&content);
+            let Some(val) = ctx.tolerate_file_error(&file, parsed)? else {
+                continue;
             };
 
             // Extract session metadata
@@ -232,10 +257,18 @@ impl Connector for GeminiConnector {
                 .get("sessionId")
                 .and_then(|v| v.as_str())
                 .map(String::from);
+            // Checkpoints carry no hash of their own; take it from <hash>/chats/.
             let project_hash = val
                 .get("projectHash")
                 .and_then(|v| v.as_str())
-                .map(String::from);
+                .map(String::from)
+                .or_else(|| {
+                    file.parent()
+                        .and_then(|p| p.parent())
+                        .and_then(|p| p.file_name())
+                        .and_then(|n| n.to_str())
+                        .map(String::from)
+                });
 
             // Parse session-level timestamps
             let start_time = val
@@ -245,8 +278,16 @@ impl Connector for GeminiConnector {
                 .get("lastUpdated")
                 .and_then(crate::connectors::parse_timestamp);
 
-            // Parse messages array
-            let Some(messages_arr) = val.get("messages").and_then(|m| m.as_array()) else {
+            // Legacy sessions wrap turns as `{ sessionId, messages: [...] }`; checkpoints
+            // are a bare array of `{ role, parts: [{ text }] }` turns (or `{ history: [...] }`).
+            let (format, turns) = if let Some(arr) = val.as_array() {
+                ("checkpoint", Some(arr))
+            } else if let Some(arr) = val.get("history").and_then(|h| h.as_array()) {
+                ("checkpoint", Some(arr))
+            } else {
+                ("session", val.get("messages").and_then(|m| m.as_array()))
+            };
+            let Some(messages_arr) = turns else {
                 continue;
             };
 
@@ -255,8 +296,12 @@ impl Connector for GeminiConnector {
             let mut ended_at = last_updated;
 
             for item in messages_arr {
-                // Role from "type" field - Gemini uses "user" and "model"
-                let msg_type = item.get("type").and_then(|v| v.as_str()).unwrap_or("model");
+                // Role from "type" (sessions) or "role" (checkpoints) - Gemini uses "user" and "model"
+                let msg_type = item
+                    .get("type")
+                    .or_else(|| item.get("role"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("model");
                 let role = if msg_type == "model" {
                     "assistant"
                 } else {
@@ -279,6 +324,7 @@ impl Connector for GeminiConnector {
                 // Extract content using flatten_content for consistency
                 let content_str = item
                     .get("content")
+                    .or_else(|| item.get("parts"))
                     .map(crate::connectors::flatten_content)
                     .unwrap_or_default();
 
@@ -325,15 +371,18 @@ impl Connector for GeminiConnector {
                         .map(|s| s.chars().take(100).collect())
                 });
 
-            // Try to extract actual workspace from message content first
-            // Gemini stores by hash, but messages often contain the real project path
-            let workspace = extract_workspace_from_content(&messages).or_else(|| {
-                // Fallback to parent directory structure
-                // Structure: ~/.gemini/tmp/<hash>/chats/session-*.json
-                file.parent() // chats/
-                    .and_then(|p| p.parent()) // <hash>/
-                    .map(std::path::Path::to_path_buf)
-            });
+            // Gemini stores sessions by hash: prefer the recorded hash→path mapping,
+            // then a project path mentioned in the messages, then the hash directory.
+            let workspace = project_hash
+                .as_ref()
+                .and_then(|hash| project_paths.get(hash).cloned())
+                .or_else(|| extract_workspace_from_content(&messages))
+                .or_else(|| {
+                    // Structure: ~/.gemini/tmp/<hash>/chats/session-*.json
+                    file.parent() // chats/
+                        .and_then(|p| p.parent()) // <hash>/
+                        .map(std::path::Path::to_path_buf)
+                });
 
             convs.push(NormalizedConversation {
                 agent_slug: "gemini".into(),
@@ -346,7 +395,8 @@ impl Connector for GeminiConnector {
                 ended_at,
                 metadata: serde_json::json!({
                     "source": "gemini",
-                    "project_hash": project_hash
+                    "project_hash": project_hash,
+                    "format": format
                 }),
                 messages,
             });
@@ -3,7 +3,9 @@
 use crate::sources::config::{PathMapping, Platform};
 use crate::sources::provenance::Origin;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub mod aider;
 pub mod amp;
@@ -12,10 +14,12 @@ pub mod claude_code;
 pub mod clawdbot;
 pub mod cline;
 pub mod codex;
+pub mod copilot;
 pub mod cursor;
 pub mod factory;
 pub mod gemini;
 pub mod opencode;
+pub mod parse_cache;
 pub mod pi_agent;
 
 /// High-level detection status for a connector.
@@ -34,6 +38,24 @@ impl DetectionResult {
             root_paths: Vec::new(),
         }
     }
+
+    /// Not detected, with evidence explaining what was checked.
+    pub fn not_found_because(evidence: Vec<String>) -> Self {
+        Self {
+            evidence,
+            ..Self::not_found()
+        }
+    }
+
+    /// Not detected because none of `paths` exist; each is reported as `no <path>`.
+    pub fn missing<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> Self {
+        Self::not_found_because(
+            paths
+                .into_iter()
+                .map(|p| format!("no {}", p.as_ref().display()))
+                .collect(),
+        )
+    }
 }
 
 /// A root directory to scan with associated provenance.
@@ -100,7 +122,7 @@ impl ScanRoot {
             .iter()
             .filter(|m| m.applies_to_agent(agent))
             .collect();
-        mappings.sort_by(|a, b| b.from.len().cmp(&a.from.len()));
+        mappings.sort_by_key(|m| std::cmp::Reverse(m.from.len()));
 
         for mapping in mappings {
             if let Some(rewritten) = mapping.apply(path) {
@@ -124,6 +146,29 @@ pub struct ScanContext {
 
     /// High-water mark for incremental indexing (milliseconds since epoch).
     pub since_ts: Option<i64>,
+
+    /// Optional per-file cache of parsed conversations. Connectors that support
+    /// it skip parsing files that are unchanged since they were last cached.
+    pub parse_cache: Option<Arc<parse_cache::ParseCache>>,
+
+    /// Glob patterns a source file's absolute path must match to be scanned.
+    /// Empty means every file is eligible.
+    pub include_globs: Vec<glob::Pattern>,
+
+    /// Glob patterns whose matching source files are skipped. Applied after
+    /// `include_globs`.
+    pub exclude_globs: Vec<glob::Pattern>,
+
+    /// Source files larger than this many bytes are skipped. `None` is unlimited.
+    pub max_file_bytes: Option<u64>,
+
+    /// Count of files skipped for exceeding `max_file_bytes`. Shared so a
+    /// caller can total skips across every connector it scans.
+    pub oversized_files: Arc<AtomicUsize>,
+
+    /// Fail the scan on the first source file that cannot be read or parsed
+    /// instead of skipping it with a warning.
+    pub strict: bool,
 }
 
 impl ScanContext {
@@ -135,6 +180,12 @@ impl ScanContext {
             data_dir,
             scan_roots: Vec::new(),
             since_ts,
+            parse_cache: None,
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            max_file_bytes: None,
+            oversized_files: Arc::default(),
+            strict: false,
         }
     }
 
@@ -144,6 +195,12 @@ impl ScanContext {
             data_dir,
             scan_roots,
             since_ts,
+            parse_cache: None,
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            max_file_bytes: None,
+            oversized_files: Arc::default(),
+            strict: false,
         }
     }
 
@@ -154,6 +211,100 @@ impl ScanContext {
         &self.data_dir
     }
 
+    /// Attach a parse cache shared by every connector scanned with this context.
+    pub fn with_parse_cache(mut self, cache: Option<Arc<parse_cache::ParseCache>>) -> Self {
+        self.parse_cache = cache;
+        self
+    }
+
+    /// Restrict scanning to source files matching `include` and not `exclude`.
+    pub fn with_path_filters(
+        mut self,
+        include: Vec<glob::Pattern>,
+        exclude: Vec<glob::Pattern>,
+    ) -> Self {
+        self.include_globs = include;
+        self.exclude_globs = exclude;
+        self
+    }
+
+    /// Skip source files larger than `limit` bytes, counting each skip in `skipped`.
+    pub fn with_max_file_bytes(mut self, limit: Option<u64>, skipped: Arc<AtomicUsize>) -> Self {
+        self.max_file_bytes = limit;
+        self.oversized_files = skipped;
+        self
+    }
+
+    /// Fail fast on unreadable or malformed source files (`index --strict`).
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Per-file error policy for connector scan loops. A failure to read or
+    /// parse `path` is logged with the path and error and yields `Ok(None)`,
+    /// so the caller skips that file and keeps scanning the rest. In strict
+    /// mode the error is returned instead, annotated with the path.
+    pub fn tolerate_file_error<T, E>(
+        &self,
+        path: &Path,
+        result: Result<T, E>,
+    ) -> anyhow::Result<Option<T>>
+    where
+        E: Into<anyhow::Error>,
+    {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => {
+                let e = e.into();
+                if self.strict {
+                    return Err(e.context(format!("session file {}", path.display())));
+                }
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %format!("{e:#}"),
+                    "skipping session file that failed to parse"
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Whether `path` should be scanned: it passes the include/exclude globs
+    /// and is no larger than `max_file_bytes`. Patterns are matched against the
+    /// absolute path.
+    pub fn path_allowed(&self, path: &std::path::Path) -> bool {
+        if !self.include_globs.is_empty() || !self.exclude_globs.is_empty() {
+            let abs = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+            let matches = |pattern: &glob::Pattern| pattern.matches_path(&abs);
+            if !(self.include_globs.is_empty() || self.include_globs.iter().any(matches))
+                || self.exclude_globs.iter().any(matches)
+            {
+                return false;
+            }
+        }
+        self.within_size_limit(path)
+    }
+
+    fn within_size_limit(&self, path: &std::path::Path) -> bool {
+        let Some(limit) = self.max_file_bytes else {
+            return true;
+        };
+        match std::fs::metadata(path) {
+            Ok(meta) if meta.len() > limit => {
+                tracing::warn!(
+                    path = %path.display(),
+                    size = meta.len(),
+                    limit,
+                    "skipping session file larger than --max-file-size"
+                );
+                self.oversized_files.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+            _ => true,
+        }
+    }
+
     /// Check if we should use default detection logic (no explicit roots).
     pub fn use_default_detection(&self) -> bool {
         self.scan_roots.is_empty()
@@ -232,42 +383,51 @@ pub fn file_modified_since(path: &std::path::Path, since_ts: Option<i64>) -> boo
     }
 }
 
-/// Parse a timestamp from either i64 milliseconds or ISO-8601 string.
+/// Bare integers in `[1e9, 1e10)` are epoch seconds: 2001-09-09 through 2286.
+const EPOCH_SECONDS_RANGE: std::ops::Range<i64> = 1_000_000_000..10_000_000_000;
+
+/// Bare integers at or above `1e15` are epoch microseconds. Read as millis they
+/// would land after year 33000; read as micros, `1e15` is 2001-09-09.
+const EPOCH_MICROS_MIN: i64 = 1_000_000_000_000_000;
+
+/// Normalize a bare epoch integer to milliseconds by magnitude: 10 digits are
+/// seconds, 16+ digits are microseconds, anything else (including negative
+/// values) is already milliseconds.
+fn epoch_to_millis(ts: i64) -> i64 {
+    if EPOCH_SECONDS_RANGE.contains(&ts) {
+        ts.saturating_mul(1000)
+    } else if ts >= EPOCH_MICROS_MIN {
+        ts / 1000
+    } else {
+        ts
+    }
+}
+
+/// Parse a timestamp from either an epoch integer or ISO-8601 string.
 /// Returns milliseconds since Unix epoch, or None if unparseable.
 ///
 /// Handles both legacy integer timestamps and modern ISO-8601 strings like:
-/// - `1700000000000` (i64 milliseconds)
+/// - `1700000000` (epoch seconds)
+/// - `1700000000000` (epoch milliseconds)
+/// - `1700000000000000` (epoch microseconds, e.g. Cursor)
 /// - `"2025-11-12T18:31:32.217Z"` (ISO-8601 string)
 pub fn parse_timestamp(val: &serde_json::Value) -> Option<i64> {
     // Try direct i64 first (legacy format)
     if let Some(ts) = val.as_i64() {
-        // Heuristic:
-        // - Values in the typical Unix-seconds range (>= 1e9 and < 1e10) are treated as seconds
-        //   and converted to millis.
-        // - Negative values are treated as millis (ambiguous, but preserves pre-1970 ms inputs).
-        let ts = if (1_000_000_000..10_000_000_000).contains(&ts) {
-            ts.saturating_mul(1000)
-        } else {
-            ts
-        };
-        return Some(ts);
+        return Some(epoch_to_millis(ts));
     }
     // Try ISO-8601 string (modern format)
     if let Some(s) = val.as_str() {
-        // Numeric strings (seconds or milliseconds)
+        // Numeric strings (seconds, milliseconds or microseconds)
         if let Ok(num) = s.parse::<i64>() {
-            let ts = if (1_000_000_000..10_000_000_000).contains(&num) {
-                num.saturating_mul(1000)
-            } else {
-                num
-            };
-            return Some(ts);
+            return Some(epoch_to_millis(num));
         }
         if let Ok(num) = s.parse::<f64>() {
+            // Keep sub-second precision for fractional seconds.
             let ts = if (1_000_000_000.0..10_000_000_000.0).contains(&num) {
                 (num * 1000.0).round() as i64
             } else {
-                num.round() as i64
+                epoch_to_millis(num.round() as i64)
             };
             return Some(ts);
         }
@@ -294,7 +454,21 @@ pub fn parse_timestamp(val: &serde_json::Value) -> Option<i64> {
 /// - Array of content blocks with `{"type": "text", "text": "..."}`
 /// - Tool use blocks: `{"type": "tool_use", "name": "Read", "input": {...}}`
 /// - Codex `input_text` blocks: `{"type": "input_text", "text": "..."}`
+/// - Thinking blocks: `{"type": "thinking", "thinking": "..."}` (unless `CASS_INDEX_THINKING=0`)
+/// - Tool results: `{"type": "tool_result", "content": ...}`, flattened recursively
 pub fn flatten_content(val: &serde_json::Value) -> String {
+    flatten_content_with(val, *INDEX_THINKING)
+}
+
+/// Whether thinking blocks are indexed (checked once at first use).
+/// Set CASS_INDEX_THINKING=0 to leave them out of flattened content.
+static INDEX_THINKING: once_cell::sync::Lazy<bool> = once_cell::sync::Lazy::new(|| {
+    dotenvy::var("CASS_INDEX_THINKING")
+        .map(|v| v != "0" && v.to_lowercase() != "false")
+        .unwrap_or(true)
+});
+
+fn flatten_content_with(val: &serde_json::Value, include_thinking: bool) -> String {
     // Direct string content (user messages in Claude Code)
     if let Some(s) = val.as_str() {
         return s.to_string();
@@ -305,7 +479,7 @@ pub fn flatten_content(val: &serde_json::Value) -> String {
     if let Some(arr) = val.as_array() {
         let mut result = String::new();
         for item in arr {
-            let part = extract_content_part(item);
+            let part = extract_content_part(item, include_thinking);
             if let Some(text) = part {
                 if !result.is_empty() {
                     result.push('\n');
@@ -321,7 +495,7 @@ pub fn flatten_content(val: &serde_json::Value) -> String {
 
 /// Extract text content from a single content block item.
 /// Returns None if the item doesn't contain extractable text.
-fn extract_content_part(item: &serde_json::Value) -> Option<String> {
+fn extract_content_part(item: &serde_json::Value, include_thinking: bool) -> Option<String> {
     // Handle plain strings in array (e.g., ["Hello", "World"])
     if let Some(text) = item.as_str() {
         return Some(text.to_string());
@@ -359,6 +533,27 @@ fn extract_content_part(item: &serde_json::Value) -> Option<String> {
         return Some(format!("[Tool: {name} - {desc}]"));
     }
 
+    // Thinking block: {"type": "thinking", "thinking": "..."}
+    if item_type == Some("thinking") {
+        if !include_thinking {
+            return None;
+        }
+        return item
+            .get("thinking")
+            .and_then(|v| v.as_str())
+            .filter(|t| !t.is_empty())
+            .map(str::to_string);
+    }
+
+    // Tool result block - content may be a string or nested content blocks
+    if item_type == Some("tool_result") {
+        let text = item
+            .get("content")
+            .map(|c| flatten_content_with(c, include_thinking))
+            .unwrap_or_default();
+        return (!text.is_empty()).then_some(text);
+    }
+
     None
 }
 
@@ -479,6 +674,39 @@ mod tests {
         assert_eq!(super::parse_timestamp(&val), Some(1_700_000_000_000));
     }
 
+    #[test]
+    fn parse_timestamp_i64_microseconds() {
+        let val = serde_json::json!(1_700_000_000_123_456_i64);
+        assert_eq!(super::parse_timestamp(&val), Some(1_700_000_000_123));
+    }
+
+    #[test]
+    fn parse_timestamp_numeric_string_microseconds() {
+        let val = serde_json::json!("1700000000123456");
+        assert_eq!(super::parse_timestamp(&val), Some(1_700_000_000_123));
+    }
+
+    #[test]
+    fn parse_timestamp_seconds_boundary() {
+        // Just below 1e9 is read as millis (1970-01-12), 1e9 as seconds (2001-09-09).
+        let below = serde_json::json!(999_999_999_i64);
+        assert_eq!(super::parse_timestamp(&below), Some(999_999_999));
+        let at = serde_json::json!(1_000_000_000_i64);
+        assert_eq!(super::parse_timestamp(&at), Some(1_000_000_000_000));
+        // 1e10 is past the seconds range (year 2286) and stays millis.
+        let above = serde_json::json!(10_000_000_000_i64);
+        assert_eq!(super::parse_timestamp(&above), Some(10_000_000_000));
+    }
+
+    #[test]
+    fn parse_timestamp_micros_boundary() {
+        // Just below 1e15 stays millis (year ~33658); 1e15 is micros for 2001-09-09.
+        let below = serde_json::json!(999_999_999_999_999_i64);
+        assert_eq!(super::parse_timestamp(&below), Some(999_999_999_999_999));
+        let at = serde_json::json!(1_000_000_000_000_000_i64);
+        assert_eq!(super::parse_timestamp(&at), Some(1_000_000_000_000));
+    }
+
     #[test]
     fn parse_timestamp_numeric_string_seconds() {
         let val = serde_json::json!("1700000000");
@@ -595,6 +823,32 @@ mod tests {
         assert!(result.contains("Read"));
     }
 
+    #[test]
+    fn flatten_content_thinking_block_is_gated() {
+        let val = serde_json::json!([
+            {"type": "thinking", "thinking": "weighing the options"},
+            {"type": "text", "text": "Answer"}
+        ]);
+        let with = super::flatten_content_with(&val, true);
+        assert!(with.contains("weighing the options"));
+        assert!(with.contains("Answer"));
+        let without = super::flatten_content_with(&val, false);
+        assert_eq!(without, "Answer");
+    }
+
+    #[test]
+    fn flatten_content_tool_result_nested() {
+        let val = serde_json::json!([
+            {"type": "tool_result", "tool_use_id": "t1", "content": "plain output"},
+            {"type": "tool_result", "tool_use_id": "t2", "content": [
+                {"type": "text", "text": "nested output"}
+            ]}
+        ]);
+        let result = super::flatten_content(&val);
+        assert!(result.contains("plain output"));
+        assert!(result.contains("nested output"));
+    }
+
     #[test]
     fn flatten_content_input_text_block() {
         let val = serde_json::json!([
@@ -762,4 +1016,15 @@ mod tests {
         assert!(result.detected);
         assert_eq!(result.evidence.len(), 2);
     }
+
+    #[test]
+    fn detection_result_missing_lists_checked_paths() {
+        let result = DetectionResult::missing(["/home/u/.codex/sessions", "/home/u/.claude"]);
+        assert!(!result.detected);
+        assert_eq!(
+            result.evidence,
+            vec!["no /home/u/.codex/sessions", "no /home/u/.claude"]
+        );
+        assert!(result.root_paths.is_empty());
+    }
 }
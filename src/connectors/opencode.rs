@@ -34,31 +34,32 @@ impl OpenCodeConnector {
     /// Get the OpenCode storage directory.
     /// OpenCode stores sessions in ~/.local/share/opencode/storage/
     fn storage_root() -> Option<PathBuf> {
+        Self::storage_candidates().into_iter().find(|p| p.exists())
+    }
+
+    /// Storage locations to check, in priority order.
+    fn storage_candidates() -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+
         // Check for env override first (useful for testing)
         if let Ok(path) = dotenvy::var("OPENCODE_STORAGE_ROOT") {
-            let p = PathBuf::from(path);
-            if p.exists() {
-                return Some(p);
-            }
+            candidates.push(PathBuf::from(path));
         }
 
         // Primary location: XDG data directory (Linux/macOS)
         if let Some(data) = dirs::data_local_dir() {
-            let storage_dir = data.join("opencode/storage");
-            if storage_dir.exists() {
-                return Some(storage_dir);
-            }
+            candidates.push(data.join("opencode/storage"));
         }
 
         // Fallback: ~/.local/share/opencode/storage
         if let Some(home) = dirs::home_dir() {
             let storage_dir = home.join(".local/share/opencode/storage");
-            if storage_dir.exists() {
-                return Some(storage_dir);
+            if !candidates.contains(&storage_dir) {
+                candidates.push(storage_dir);
             }
         }
 
-        None
+        candidates
     }
 }
 
@@ -143,7 +144,7 @@ impl Connector for OpenCodeConnector {
                 root_paths: vec![storage],
             }
         } else {
-            DetectionResult::not_found()
+            DetectionResult::missing(Self::storage_candidates())
         }
     }
 
@@ -190,20 +191,17 @@ impl Connector for OpenCodeConnector {
         let mut seen_ids = std::collections::HashSet::new();
 
         for session_file in session_files {
+            if !ctx.path_allowed(&session_file) {
+                continue;
+            }
             if !session_has_updates(&session_file, &message_dir, &part_dir, ctx.since_ts) {
                 continue;
             }
 
             // Parse session
-            let session = match parse_session_file(&session_file) {
-                Ok(s) => s,
-                Err(e) => {
-                    tracing::debug!(
-                        "opencode: failed to parse session {}: {e}",
-                        session_file.display()
-                    );
-                    continue;
-                }
+            let parsed = parse_session_file(&session_file);
+            let Some(session) = ctx.tolerate_file_error(&session_file, parsed)? else {
+                continue;
             };
 
             // Deduplicate by session ID
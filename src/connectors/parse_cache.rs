@@ -0,0 +1,354 @@
+//! Per-file parse cache for connector scans.
+//!
+//! A full scan (`cass index --full`) re-reads and re-parses every session file
+//! even though most of them have not changed since the last run. Connectors
+//! that opt in look each file up here first: a row in the
+//! `<data_dir>/parse_cache.db` sidecar records the file's path, mtime, size and
+//! content hash together with the conversations parsed from it. Connectors take
+//! that [`FileStamp`] before parsing, so a session file that is still being
+//! appended to is never recorded against conversations parsed from less of it.
+//! An entry is
+//! reused when mtime and size still match, or when only the mtime moved but the
+//! content hash is unchanged (e.g. a `touch` or a copy that preserved bytes).
+//! Entries written by a different cass version are ignored so parser changes
+//! always take effect.
+//!
+//! After a full scan the indexer calls [`ParseCache::prune_unseen`], which
+//! drops rows for files the scan no longer visited (deleted or rotated
+//! sessions). The cache is disposable: deleting `parse_cache.db` clears it.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rusqlite::{Connection, OptionalExtension, params};
+use sha2::{Digest, Sha256};
+
+use super::NormalizedConversation;
+
+const CACHE_DB_NAME: &str = "parse_cache.db";
+
+/// A file's mtime, size and content hash, as recorded in a cache entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStamp {
+    mtime_ms: i64,
+    size: u64,
+    content_hash: String,
+}
+
+impl FileStamp {
+    /// Stamp `path` as it is now. Take this before parsing the file.
+    pub fn of(path: &Path) -> Option<Self> {
+        let (mtime_ms, size) = stat(path)?;
+        Some(Self {
+            mtime_ms,
+            size,
+            content_hash: content_hash(path)?,
+        })
+    }
+}
+
+/// SQLite-backed cache of parsed conversations keyed by source file.
+#[derive(Debug)]
+pub struct ParseCache {
+    conn: Option<Mutex<Connection>>,
+    reuse: bool,
+    seen: Mutex<HashSet<String>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl ParseCache {
+    /// Cache stored under `data_dir`. With `reuse` false every lookup misses,
+    /// but fresh parses are still written so the next scan can use them.
+    /// If the sidecar cannot be opened the cache is disabled for this run.
+    pub fn new(data_dir: &Path, reuse: bool) -> Self {
+        let conn = match open_db(&data_dir.join(CACHE_DB_NAME)) {
+            Ok(conn) => Some(Mutex::new(conn)),
+            Err(e) => {
+                tracing::debug!(error = %e, "parse_cache_open_failed");
+                None
+            }
+        };
+        Self {
+            conn,
+            reuse,
+            seen: Mutex::new(HashSet::new()),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Conversations previously parsed from `path`, if the file is unchanged.
+    pub fn get(&self, path: &Path) -> Option<Vec<NormalizedConversation>> {
+        self.mark_seen(path);
+        let found = self.lookup(path);
+        let counter = if found.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    fn lookup(&self, path: &Path) -> Option<Vec<NormalizedConversation>> {
+        if !self.reuse {
+            return None;
+        }
+        let (mtime_ms, size) = stat(path)?;
+        let conn = self.conn.as_ref()?.lock().ok()?;
+        let (version, entry_mtime, entry_size, entry_hash, conversations): (
+            String,
+            i64,
+            i64,
+            String,
+            String,
+        ) = conn
+            .query_row(
+                "SELECT version, mtime_ms, size, content_hash, conversations
+                 FROM entries WHERE path = ?1",
+                params![path.to_string_lossy()],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                    ))
+                },
+            )
+            .optional()
+            .ok()??;
+        drop(conn);
+        if version != env!("CARGO_PKG_VERSION") || entry_size != size as i64 {
+            return None;
+        }
+        if entry_mtime == mtime_ms || content_hash(path)? == entry_hash {
+            return serde_json::from_str(&conversations).ok();
+        }
+        None
+    }
+
+    /// Record the conversations parsed from `path`, which was in the state
+    /// `stamp` describes when parsing started. Failures are logged and
+    /// otherwise ignored: the cache must never fail a scan.
+    pub fn put(&self, path: &Path, stamp: &FileStamp, conversations: &[NormalizedConversation]) {
+        self.mark_seen(path);
+        if let Err(e) = self.write_entry(path, stamp, conversations) {
+            tracing::debug!(error = %e, path = %path.display(), "parse_cache_write_failed");
+        }
+    }
+
+    /// Drop entries for every file this cache instance has not been asked
+    /// about. Only meaningful after a full scan, which visits every file;
+    /// returns the number of entries removed.
+    pub fn prune_unseen(&self) -> usize {
+        match self.try_prune_unseen() {
+            Ok(removed) => removed,
+            Err(e) => {
+                tracing::debug!(error = %e, "parse_cache_prune_failed");
+                0
+            }
+        }
+    }
+
+    /// Lookups served from the cache so far.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Lookups that required a fresh parse so far.
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn mark_seen(&self, path: &Path) {
+        if let Ok(mut seen) = self.seen.lock() {
+            seen.insert(path.to_string_lossy().into_owned());
+        }
+    }
+
+    fn write_entry(
+        &self,
+        path: &Path,
+        stamp: &FileStamp,
+        conversations: &[NormalizedConversation],
+    ) -> anyhow::Result<()> {
+        let Some(conn) = &self.conn else {
+            return Ok(());
+        };
+        let json = serde_json::to_string(conversations)?;
+        let conn = conn
+            .lock()
+            .map_err(|_| anyhow::anyhow!("parse cache lock poisoned"))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO entries
+                 (path, version, mtime_ms, size, content_hash, conversations)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                path.to_string_lossy(),
+                env!("CARGO_PKG_VERSION"),
+                stamp.mtime_ms,
+                stamp.size as i64,
+                stamp.content_hash,
+                json
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn try_prune_unseen(&self) -> anyhow::Result<usize> {
+        let Some(conn) = &self.conn else {
+            return Ok(0);
+        };
+        let seen = self
+            .seen
+            .lock()
+            .map_err(|_| anyhow::anyhow!("parse cache lock poisoned"))?;
+        let mut conn = conn
+            .lock()
+            .map_err(|_| anyhow::anyhow!("parse cache lock poisoned"))?;
+        let stale: Vec<String> = conn
+            .prepare("SELECT path FROM entries")?
+            .query_map([], |row| row.get::<_, String>(0))?
+            .filter_map(Result::ok)
+            .filter(|path| !seen.contains(path))
+            .collect();
+        if stale.is_empty() {
+            return Ok(0);
+        }
+        let tx = conn.transaction()?;
+        for path in &stale {
+            tx.execute("DELETE FROM entries WHERE path = ?1", params![path])?;
+        }
+        tx.commit()?;
+        // Hand the freed pages back to the filesystem.
+        conn.execute_batch("PRAGMA incremental_vacuum;")?;
+        Ok(stale.len())
+    }
+}
+
+fn open_db(path: &Path) -> anyhow::Result<Connection> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let conn = Connection::open(path)?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))?;
+    // auto_vacuum only takes effect before the first table is created.
+    conn.execute_batch(
+        "PRAGMA auto_vacuum = INCREMENTAL;
+         CREATE TABLE IF NOT EXISTS entries (
+             path TEXT PRIMARY KEY,
+             version TEXT NOT NULL,
+             mtime_ms INTEGER NOT NULL,
+             size INTEGER NOT NULL,
+             content_hash TEXT NOT NULL,
+             conversations TEXT NOT NULL
+         );",
+    )?;
+    Ok(conn)
+}
+
+fn stat(path: &Path) -> Option<(i64, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    let mtime_ms = meta
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_millis() as i64;
+    Some((mtime_ms, meta.len()))
+}
+
+fn content_hash(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    Some(hex::encode(Sha256::digest(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conv(path: &Path) -> NormalizedConversation {
+        NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: Some("rollout-1".into()),
+            title: Some("hello".into()),
+            workspace: None,
+            source_path: path.to_path_buf(),
+            started_at: Some(1),
+            ended_at: Some(2),
+            metadata: serde_json::json!({}),
+            messages: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn hit_until_content_changes() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let file = tmp.path().join("rollout-1.jsonl");
+        fs::write(&file, "{}\n").unwrap();
+        let cache = ParseCache::new(tmp.path(), true);
+
+        assert!(cache.get(&file).is_none());
+        cache.put(&file, &FileStamp::of(&file).unwrap(), &[conv(&file)]);
+        let cached = cache.get(&file).expect("cache hit");
+        assert_eq!(cached[0].title.as_deref(), Some("hello"));
+
+        fs::write(&file, "{}\n{}\n").unwrap();
+        assert!(cache.get(&file).is_none());
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+    }
+
+    #[test]
+    fn entry_keeps_the_stamp_taken_before_parsing() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let file = tmp.path().join("rollout-1.jsonl");
+        fs::write(&file, "{}\n").unwrap();
+        let cache = ParseCache::new(tmp.path(), true);
+
+        // The session grows while it is being parsed.
+        let stamp = FileStamp::of(&file).unwrap();
+        fs::write(&file, "{}\n{}\n").unwrap();
+        cache.put(&file, &stamp, &[conv(&file)]);
+
+        assert!(cache.get(&file).is_none());
+    }
+
+    #[test]
+    fn reuse_disabled_still_refreshes_entries() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let file = tmp.path().join("rollout-1.jsonl");
+        fs::write(&file, "{}\n").unwrap();
+
+        let refresh = ParseCache::new(tmp.path(), false);
+        refresh.put(&file, &FileStamp::of(&file).unwrap(), &[conv(&file)]);
+        assert!(refresh.get(&file).is_none());
+
+        assert!(ParseCache::new(tmp.path(), true).get(&file).is_some());
+    }
+
+    #[test]
+    fn prune_drops_files_not_seen_by_the_scan() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let kept = tmp.path().join("rollout-1.jsonl");
+        let gone = tmp.path().join("rollout-2.jsonl");
+        fs::write(&kept, "{}\n").unwrap();
+        fs::write(&gone, "{}\n").unwrap();
+        let first = ParseCache::new(tmp.path(), true);
+        first.put(&kept, &FileStamp::of(&kept).unwrap(), &[conv(&kept)]);
+        first.put(&gone, &FileStamp::of(&gone).unwrap(), &[conv(&gone)]);
+
+        let second = ParseCache::new(tmp.path(), true);
+        assert!(second.get(&kept).is_some());
+        assert_eq!(second.prune_unseen(), 1);
+
+        let third = ParseCache::new(tmp.path(), true);
+        assert!(third.get(&kept).is_some());
+        assert!(third.get(&gone).is_none());
+    }
+}
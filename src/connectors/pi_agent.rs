@@ -143,14 +143,15 @@ impl PiAgentConnector {
 impl Connector for PiAgentConnector {
     fn detect(&self) -> DetectionResult {
         let home = Self::home();
-        if home.join("sessions").exists() {
+        let sessions = home.join("sessions");
+        if sessions.exists() {
             DetectionResult {
                 detected: true,
                 evidence: vec![format!("found {}", home.display())],
                 root_paths: vec![home],
             }
         } else {
-            DetectionResult::not_found()
+            DetectionResult::missing([sessions])
         }
     }
 
@@ -190,6 +191,9 @@ impl Connector for PiAgentConnector {
         let mut convs = Vec::new();
 
         for file in files {
+            if !ctx.path_allowed(&file) {
+                continue;
+            }
             // Skip files not modified since last scan
             if !file_modified_since(&file, ctx.since_ts) {
                 continue;
@@ -211,8 +215,11 @@ impl Connector for PiAgentConnector {
                         .map(String::from)
                 });
 
-            let content = fs::read_to_string(&file)
-                .with_context(|| format!("read pi-agent session {}", file.display()))?;
+            let read = fs::read_to_string(&file)
+                .with_context(|| format!("read pi-agent session {}", file.display()));
+            let Some(content) = ctx.tolerate_file_error(&file, read)? else {
+                continue;
+            };
 
             let mut messages = Vec::new();
             let mut started_at: Option<i64> = None;
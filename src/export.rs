@@ -4,9 +4,18 @@
 //! - Markdown - formatted with headers, code blocks, and metadata
 //! - JSON - structured data for programmatic use
 //! - Plain Text - simple, copy-paste friendly format
-
-use crate::search::query::SearchHit;
+//!
+//! It also defines the color themes, JSON sidecar and passphrase envelope
+//! used by conversation HTML exports.
+
+use crate::encryption::{Argon2Params, aes_gcm_decrypt, aes_gcm_encrypt, argon2id_hash};
+use crate::search::query::{SearchHit, permalink};
+use aes_gcm::aead::OsRng;
+use aes_gcm::aead::rand_core::RngCore;
+use anyhow::{Context, anyhow, bail};
+use base64::prelude::*;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 /// Supported export formats
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -228,6 +237,7 @@ fn export_json(hits: &[SearchHit], options: &ExportOptions) -> String {
                 "agent": hit.agent,
                 "workspace": hit.workspace,
                 "snippet": truncate_text(&hit.snippet, options.max_snippet_len),
+                "permalink": permalink(hit),
             });
 
             if options.include_score {
@@ -350,6 +360,231 @@ fn truncate_text(text: &str, max_len: usize) -> String {
     truncated
 }
 
+/// Color theme for HTML conversation exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ExportTheme {
+    /// Light background; best for printing
+    #[default]
+    Light,
+    /// Dark background
+    Dark,
+    /// Follow the reader's `prefers-color-scheme`
+    Auto,
+}
+
+/// Colors for one HTML export theme, by role.
+#[derive(Debug, Clone, Copy)]
+pub struct HtmlPalette {
+    pub background: &'static str,
+    pub surface: &'static str,
+    pub text: &'static str,
+    pub heading: &'static str,
+    pub muted: &'static str,
+    pub role: &'static str,
+    pub user_accent: &'static str,
+    pub assistant_accent: &'static str,
+    pub tool_background: &'static str,
+}
+
+pub const LIGHT_PALETTE: HtmlPalette = HtmlPalette {
+    background: "#f5f5f5",
+    surface: "#ffffff",
+    text: "#111827",
+    heading: "#1f2937",
+    muted: "#6b7280",
+    role: "#374151",
+    user_accent: "#2563eb",
+    assistant_accent: "#16a34a",
+    tool_background: "#f3f4f6",
+};
+
+pub const DARK_PALETTE: HtmlPalette = HtmlPalette {
+    background: "#1a1b26",
+    surface: "#24283b",
+    text: "#a9b1d6",
+    heading: "#c0caf5",
+    muted: "#565f89",
+    role: "#c0caf5",
+    user_accent: "#7aa2f7",
+    assistant_accent: "#9ece6a",
+    tool_background: "#1f2335",
+};
+
+/// CSS custom property for each entry of [`HtmlPalette::colors`], in order.
+const PALETTE_CSS_VARS: [&str; 9] = [
+    "--bg",
+    "--surface",
+    "--text",
+    "--heading",
+    "--muted",
+    "--role",
+    "--user",
+    "--assistant",
+    "--tool-bg",
+];
+
+impl HtmlPalette {
+    fn css_vars(&self) -> String {
+        PALETTE_CSS_VARS
+            .iter()
+            .zip(self.colors())
+            .map(|(var, color)| format!("{var}: {color};"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn colors(&self) -> [&'static str; 9] {
+        [
+            self.background,
+            self.surface,
+            self.text,
+            self.heading,
+            self.muted,
+            self.role,
+            self.user_accent,
+            self.assistant_accent,
+            self.tool_background,
+        ]
+    }
+}
+
+/// CSS custom properties for `theme`, to be placed at the top of an export's
+/// `<style>` block. `Auto` emits the light palette with a dark override under
+/// `@media (prefers-color-scheme: dark)`.
+pub fn theme_css(theme: ExportTheme) -> String {
+    match theme {
+        ExportTheme::Light => format!(":root {{ {} }}", LIGHT_PALETTE.css_vars()),
+        ExportTheme::Dark => format!(":root {{ {} }}", DARK_PALETTE.css_vars()),
+        ExportTheme::Auto => format!(
+            ":root {{ {} }}\n        @media (prefers-color-scheme: dark) {{ :root {{ {} }} }}",
+            LIGHT_PALETTE.css_vars(),
+            DARK_PALETTE.css_vars()
+        ),
+    }
+}
+
+/// `id` of the `<script type="application/json">` block holding an embedded conversation.
+pub const CONVERSATION_SCRIPT_ID: &str = "cass-conversation";
+
+/// Machine-readable copy of a conversation embedded in HTML exports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedConversation {
+    pub title: Option<String>,
+    pub started_at: Option<i64>,
+    pub messages: Vec<ExportedMessage>,
+}
+
+/// One message of an [`ExportedConversation`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedMessage {
+    pub idx: usize,
+    pub role: String,
+    pub content: String,
+    pub created_at: Option<i64>,
+}
+
+/// Render `conv` as a `<script type="application/json">` block for an HTML page.
+///
+/// `</` is written as `<\/` (an equivalent JSON escape) so message text can
+/// never close the script element early.
+pub fn conversation_script_block(conv: &ExportedConversation) -> String {
+    let json = serde_json::to_string(conv)
+        .unwrap_or_default()
+        .replace("</", "<\\/");
+    format!(r#"<script type="application/json" id="{CONVERSATION_SCRIPT_ID}">{json}</script>"#)
+}
+
+/// Parse the conversation embedded by [`conversation_script_block`] out of an
+/// exported HTML page.
+pub fn extract_conversation(html: &str) -> anyhow::Result<ExportedConversation> {
+    let open = format!(r#"<script type="application/json" id="{CONVERSATION_SCRIPT_ID}">"#);
+    let start = html
+        .find(&open)
+        .ok_or_else(|| anyhow!("no embedded conversation (export with --embed-json)"))?
+        + open.len();
+    let end = html[start..]
+        .find("</script>")
+        .ok_or_else(|| anyhow!("unterminated conversation script block"))?;
+    serde_json::from_str(&html[start..start + end]).context("invalid embedded conversation JSON")
+}
+
+/// Argon2id cost for passphrase-encrypted exports (19 MiB, 2 passes, 1 lane).
+const EXPORT_KDF_COST: (u32, u32, u32) = (19_456, 2, 1);
+
+/// Passphrase-encrypted export file: Argon2id-derived key, AES-256-GCM payload.
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedExport {
+    version: u8,
+    kdf: String,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+    tag: String,
+}
+
+fn export_key(passphrase: &str, salt: &[u8], m: u32, t: u32, p: u32) -> anyhow::Result<Vec<u8>> {
+    let params = Argon2Params::new(m, t, p, Some(32)).map_err(|e| anyhow!("argon2 params: {e}"))?;
+    Ok(argon2id_hash(passphrase.as_bytes(), salt, &params))
+}
+
+/// Encrypt an export under `passphrase`, returning a JSON envelope.
+pub fn encrypt_export(plaintext: &[u8], passphrase: &str) -> anyhow::Result<Vec<u8>> {
+    if passphrase.is_empty() {
+        bail!("passphrase must not be empty");
+    }
+    let (m_cost, t_cost, p_cost) = EXPORT_KDF_COST;
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+    let key = export_key(passphrase, &salt, m_cost, t_cost, p_cost)?;
+    let (ciphertext, tag) = aes_gcm_encrypt(&key, &nonce, plaintext, b"cass-export-v1");
+    let envelope = EncryptedExport {
+        version: 1,
+        kdf: "argon2id".to_string(),
+        m_cost,
+        t_cost,
+        p_cost,
+        salt: BASE64_STANDARD.encode(salt),
+        nonce: BASE64_STANDARD.encode(nonce),
+        ciphertext: BASE64_STANDARD.encode(ciphertext),
+        tag: BASE64_STANDARD.encode(tag),
+    };
+    Ok(serde_json::to_vec_pretty(&envelope)?)
+}
+
+/// Decrypt an envelope produced by [`encrypt_export`].
+pub fn decrypt_export(envelope: &[u8], passphrase: &str) -> anyhow::Result<Vec<u8>> {
+    let env: EncryptedExport =
+        serde_json::from_slice(envelope).context("not an encrypted cass export")?;
+    if env.version != 1 || env.kdf != "argon2id" {
+        bail!("unsupported export envelope (v{} {})", env.version, env.kdf);
+    }
+    let decode = |field: &str| {
+        BASE64_STANDARD
+            .decode(field)
+            .context("corrupt export envelope")
+    };
+    let key = export_key(
+        passphrase,
+        &decode(&env.salt)?,
+        env.m_cost,
+        env.t_cost,
+        env.p_cost,
+    )?;
+    aes_gcm_decrypt(
+        &key,
+        &decode(&env.nonce)?,
+        &decode(&env.ciphertext)?,
+        b"cass-export-v1",
+        &decode(&env.tag)?,
+    )
+    .map_err(|_| anyhow!("wrong passphrase or corrupt export"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,6 +605,13 @@ mod tests {
             source_id: "local".to_string(),
             origin_kind: "local".to_string(),
             origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
         }
     }
 
@@ -422,6 +664,7 @@ mod tests {
 
         assert!(output.contains("\"count\": 1"));
         assert!(output.contains("\"agent\": \"claude_code\""));
+        assert!(output.contains(&format!("\"permalink\": \"{}\"", permalink(&hits[0]))));
     }
 
     #[test]
@@ -453,4 +696,70 @@ mod tests {
         // Should use 4 backticks because content has 3
         assert!(output.contains("````\nContains ``` backticks"));
     }
+
+    #[test]
+    fn test_colors_are_valid_hex() {
+        for palette in [LIGHT_PALETTE, DARK_PALETTE] {
+            for color in palette.colors() {
+                assert_eq!(color.len(), 7, "{color}");
+                assert!(color.starts_with('#'), "{color}");
+                assert!(color[1..].chars().all(|c| c.is_ascii_hexdigit()), "{color}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_theme_css_selects_palettes() {
+        let light = theme_css(ExportTheme::Light);
+        assert!(light.contains(LIGHT_PALETTE.background));
+        assert!(!light.contains("prefers-color-scheme"));
+        assert!(theme_css(ExportTheme::Dark).contains(DARK_PALETTE.background));
+        let auto = theme_css(ExportTheme::Auto);
+        assert!(auto.contains(LIGHT_PALETTE.background));
+        assert!(auto.contains("@media (prefers-color-scheme: dark)"));
+        assert!(auto.contains(DARK_PALETTE.background));
+    }
+
+    #[test]
+    fn embedded_conversation_round_trips() {
+        let conv = ExportedConversation {
+            title: Some("Fix <script> handling".to_string()),
+            started_at: Some(1_700_000_000_000),
+            messages: vec![
+                ExportedMessage {
+                    idx: 0,
+                    role: "user".to_string(),
+                    content: "why does </script> break the page?".to_string(),
+                    created_at: Some(1_700_000_000_000),
+                },
+                ExportedMessage {
+                    idx: 1,
+                    role: "assistant".to_string(),
+                    content: "Escape it: \"<\\/script>\" & friends\nline two".to_string(),
+                    created_at: None,
+                },
+            ],
+        };
+        let html = format!(
+            "<html><body><h1>x</h1>{}</body></html>",
+            conversation_script_block(&conv)
+        );
+        assert_eq!(html.matches("</script>").count(), 1);
+        assert_eq!(extract_conversation(&html).unwrap(), conv);
+    }
+
+    #[test]
+    fn extract_conversation_requires_block() {
+        assert!(extract_conversation("<html></html>").is_err());
+    }
+
+    #[test]
+    fn encrypted_export_round_trips() {
+        let html = b"<html><body>secret</body></html>";
+        let envelope = encrypt_export(html, "correct horse").unwrap();
+        assert!(!String::from_utf8_lossy(&envelope).contains("secret"));
+        assert_eq!(decrypt_export(&envelope, "correct horse").unwrap(), html);
+        assert!(decrypt_export(&envelope, "wrong").is_err());
+        assert!(encrypt_export(html, "").is_err());
+    }
 }
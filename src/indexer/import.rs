@@ -0,0 +1,245 @@
+//! Ad-hoc transcript import (`cass import`).
+//!
+//! Transcripts exported from tools cass has no connector for can be made
+//! searchable by converting them to one of two generic formats:
+//!
+//! * **JSONL** (`.jsonl`, `.ndjson`): one message object per line with a
+//!   `role` string, a `content` string (or array of content blocks) and an
+//!   optional `created_at` (epoch seconds/milliseconds or ISO-8601). Blank
+//!   lines are ignored.
+//! * **Markdown** (`.md`, `.markdown`): an optional `# Title` line, then one
+//!   `## <Role>` heading per message (`User`, `Assistant`, `System` or `Tool`)
+//!   followed by the message text. Headings inside fenced code blocks are
+//!   treated as text.
+//!
+//! The file's canonical path is used as both source path and external id, so
+//! importing the same file again only appends messages that are new.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+
+use crate::connectors::{
+    NormalizedConversation, NormalizedMessage, flatten_content, parse_timestamp, reindex_messages,
+};
+
+/// Roles accepted as `## <Role>` headings in markdown transcripts.
+const MARKDOWN_ROLES: &[&str] = &["user", "assistant", "system", "tool"];
+
+/// Read and validate the transcript at `path` as a conversation for `agent`.
+pub fn parse_transcript(
+    path: &Path,
+    agent: &str,
+    workspace: Option<PathBuf>,
+) -> Result<NormalizedConversation> {
+    validate_agent_slug(agent)?;
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("cannot read {}", path.display()))?;
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    let format = match ext.as_deref() {
+        Some("jsonl" | "ndjson") => "jsonl",
+        Some("md" | "markdown") => "markdown",
+        _ => bail!(
+            "{}: unsupported file type; expected a .jsonl or .md transcript",
+            path.display()
+        ),
+    };
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("cannot read {}", path.display()))?;
+    let (title, mut messages) = match format {
+        "jsonl" => (None, parse_jsonl(&text)?),
+        _ => parse_markdown(&text)?,
+    };
+    if messages.is_empty() {
+        bail!("{}: transcript contains no messages", path.display());
+    }
+    reindex_messages(&mut messages);
+
+    let title = title.or_else(|| {
+        messages
+            .iter()
+            .find(|m| m.role == "user")
+            .or(messages.first())
+            .and_then(|m| m.content.lines().next())
+            .map(|line| line.chars().take(100).collect())
+    });
+    let mut started_at = messages.iter().filter_map(|m| m.created_at).min();
+    let mut ended_at = messages.iter().filter_map(|m| m.created_at).max();
+    if started_at.is_none() {
+        // Without per-message times, date the conversation by the file itself.
+        let mtime = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64);
+        started_at = mtime;
+        ended_at = mtime;
+    }
+
+    Ok(NormalizedConversation {
+        agent_slug: agent.to_string(),
+        external_id: Some(path.to_string_lossy().into_owned()),
+        title,
+        workspace,
+        source_path: path,
+        started_at,
+        ended_at,
+        metadata: serde_json::json!({ "source": "import", "format": format }),
+        messages,
+    })
+}
+
+fn validate_agent_slug(agent: &str) -> Result<()> {
+    let valid = !agent.is_empty()
+        && agent
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    if !valid {
+        bail!(
+            "invalid agent slug '{agent}': use lowercase letters, digits, '_' or '-' (e.g. my_tool)"
+        );
+    }
+    Ok(())
+}
+
+fn parse_jsonl(text: &str) -> Result<Vec<NormalizedMessage>> {
+    let mut messages = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let val: Value = serde_json::from_str(line)
+            .with_context(|| format!("line {line_no}: not a valid JSON object"))?;
+        if !val.is_object() {
+            bail!("line {line_no}: expected a JSON object, found {val}");
+        }
+        let role = match val.get("role") {
+            Some(Value::String(role)) if !role.trim().is_empty() => role.trim().to_lowercase(),
+            Some(_) => bail!("line {line_no}: `role` must be a non-empty string"),
+            None => bail!("line {line_no}: missing `role`"),
+        };
+        let content = match val.get("content") {
+            Some(content @ (Value::String(_) | Value::Array(_))) => flatten_content(content),
+            Some(_) => bail!("line {line_no}: `content` must be a string or an array"),
+            None => bail!("line {line_no}: missing `content`"),
+        };
+        let created_at = match val.get("created_at") {
+            None | Some(Value::Null) => None,
+            Some(ts) => Some(parse_timestamp(ts).with_context(|| {
+                format!(
+                    "line {line_no}: `created_at` must be epoch seconds/milliseconds or an ISO-8601 string, found {ts}"
+                )
+            })?),
+        };
+        if content.trim().is_empty() {
+            continue;
+        }
+        messages.push(NormalizedMessage {
+            idx: 0,
+            role,
+            author: None,
+            created_at,
+            content,
+            extra: val,
+            snippets: Vec::new(),
+        });
+    }
+    Ok(messages)
+}
+
+fn parse_markdown(text: &str) -> Result<(Option<String>, Vec<NormalizedMessage>)> {
+    let mut title = None;
+    let mut messages = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+    let mut in_fence = false;
+
+    let mut flush = |current: Option<(String, Vec<&str>)>| {
+        if let Some((role, lines)) = current {
+            let content = lines.join("\n").trim().to_string();
+            if !content.is_empty() {
+                messages.push(NormalizedMessage {
+                    idx: 0,
+                    role,
+                    author: None,
+                    created_at: None,
+                    content,
+                    extra: Value::Null,
+                    snippets: Vec::new(),
+                });
+            }
+        }
+    };
+
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if !in_fence && let Some(heading) = line.strip_prefix("## ") {
+            let role = heading.trim().to_lowercase();
+            if MARKDOWN_ROLES.contains(&role.as_str()) {
+                flush(current.take());
+                current = Some((role, Vec::new()));
+                continue;
+            }
+        }
+        let Some((_, lines)) = current.as_mut() else {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if title.is_none()
+                && let Some(text) = line.strip_prefix("# ")
+            {
+                title = Some(text.trim().to_string());
+                continue;
+            }
+            bail!(
+                "line {line_no}: text before the first message; start each message with a `## User` or `## Assistant` heading"
+            );
+        };
+        lines.push(line);
+    }
+    if current.is_none() {
+        bail!(
+            "no `## <Role>` headings found; markdown transcripts need `## User` / `## Assistant` sections"
+        );
+    }
+    flush(current);
+    Ok((title, messages))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jsonl_reports_the_offending_line() {
+        let err = parse_jsonl("{\"role\":\"user\",\"content\":\"hi\"}\n{\"content\":\"x\"}\n")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 2: missing `role`");
+
+        let err = parse_jsonl("{\"role\":\"user\",\"content\":\"hi\",\"created_at\":true}\n")
+            .unwrap_err();
+        assert!(err.to_string().starts_with("line 1: `created_at`"));
+    }
+
+    #[test]
+    fn markdown_splits_on_role_headings_outside_fences() {
+        let text = "# Debugging notes\n\n## User\nWhy does this fail?\n\n## Assistant\nTry this:\n```md\n## User\n```\n";
+        let (title, messages) = parse_markdown(text).unwrap();
+        assert_eq!(title.as_deref(), Some("Debugging notes"));
+        let roles: Vec<_> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant"]);
+        assert!(messages[1].content.ends_with("## User\n```"));
+
+        let err = parse_markdown("stray text\n## User\nhi\n").unwrap_err();
+        assert!(err.to_string().starts_with("line 1: text before"));
+    }
+}
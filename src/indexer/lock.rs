@@ -0,0 +1,212 @@
+//! Advisory lock that keeps indexers from writing the same data dir concurrently.
+//!
+//! The TUI starts a background watch indexer while a user may also run
+//! `cass index`; Tantivy and SQLite both misbehave with two writers. The lock is
+//! an `O_EXCL` sentinel file (`<data_dir>/index.lock`) holding the owner's PID and
+//! a heartbeat timestamp. Holders refresh the heartbeat while they run, so a lock
+//! whose heartbeat is older than [`STALE_LOCK_SECS`] belongs to a crashed process
+//! and may be reclaimed.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Lock file name inside the data dir.
+pub const LOCK_FILE_NAME: &str = "index.lock";
+
+/// Locks whose heartbeat is older than this are considered abandoned.
+pub const STALE_LOCK_SECS: u64 = 600;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LockInfo {
+    pid: u32,
+    acquired_at: i64,
+    heartbeat_at: i64,
+}
+
+#[derive(Debug, Error)]
+pub enum IndexLockError {
+    #[error("another indexer (pid {pid}) holds {}", path.display())]
+    Busy {
+        pid: u32,
+        acquired_at: i64,
+        path: PathBuf,
+    },
+    #[error("failed to create index lock: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Held index lock; released (file removed) on drop.
+pub struct IndexLock {
+    path: PathBuf,
+    info: LockInfo,
+    stop: Arc<AtomicBool>,
+    heartbeat: Option<JoinHandle<()>>,
+}
+
+impl IndexLock {
+    /// Acquire the lock for `data_dir`, reclaiming it if the current holder's
+    /// heartbeat is stale.
+    pub fn acquire(data_dir: &Path) -> Result<Self, IndexLockError> {
+        fs::create_dir_all(data_dir)?;
+        let path = data_dir.join(LOCK_FILE_NAME);
+        let now = now_millis();
+        let info = LockInfo {
+            pid: std::process::id(),
+            acquired_at: now,
+            heartbeat_at: now,
+        };
+
+        // Two attempts: the second follows removal of a stale lock.
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(&serde_json::to_vec(&info).unwrap_or_default())?;
+                    return Ok(Self::start(path, info));
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let Some(holder) = read_info(&path) else {
+                        // Unreadable or half-written; treat as stale only if the file is old.
+                        if file_age_secs(&path).is_some_and(|age| age >= STALE_LOCK_SECS) {
+                            let _ = fs::remove_file(&path);
+                            continue;
+                        }
+                        return Err(IndexLockError::Busy {
+                            pid: 0,
+                            acquired_at: 0,
+                            path,
+                        });
+                    };
+                    let age_secs = (now - holder.heartbeat_at).max(0) as u64 / 1000;
+                    if age_secs >= STALE_LOCK_SECS {
+                        tracing::warn!(pid = holder.pid, age_secs, "reclaiming stale index lock");
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    return Err(IndexLockError::Busy {
+                        pid: holder.pid,
+                        acquired_at: holder.acquired_at,
+                        path,
+                    });
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        let holder = read_info(&path);
+        Err(IndexLockError::Busy {
+            pid: holder.as_ref().map_or(0, |h| h.pid),
+            acquired_at: holder.map_or(0, |h| h.acquired_at),
+            path,
+        })
+    }
+
+    fn start(path: PathBuf, info: LockInfo) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let heartbeat = {
+            let stop = stop.clone();
+            let path = path.clone();
+            let mut info = info.clone();
+            std::thread::Builder::new()
+                .name("cass-index-lock".into())
+                .spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        std::thread::park_timeout(HEARTBEAT_INTERVAL);
+                        if stop.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        // Only refresh a lock we still own.
+                        if read_info(&path).is_some_and(|cur| is_same_owner(&cur, &info)) {
+                            info.heartbeat_at = now_millis();
+                            let _ = fs::write(&path, serde_json::to_vec(&info).unwrap_or_default());
+                        }
+                    }
+                })
+                .ok()
+        };
+        Self {
+            path,
+            info,
+            stop,
+            heartbeat,
+        }
+    }
+}
+
+impl Drop for IndexLock {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.heartbeat.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+        if read_info(&self.path).is_some_and(|cur| is_same_owner(&cur, &self.info)) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+fn is_same_owner(a: &LockInfo, b: &LockInfo) -> bool {
+    a.pid == b.pid && a.acquired_at == b.acquired_at
+}
+
+fn read_info(path: &Path) -> Option<LockInfo> {
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+fn file_age_secs(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.elapsed().ok()?.as_secs())
+}
+
+fn now_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_acquire_is_busy_until_release() {
+        let tmp = tempfile::tempdir().unwrap();
+        let lock = IndexLock::acquire(tmp.path()).expect("first acquire");
+        assert!(matches!(
+            IndexLock::acquire(tmp.path()),
+            Err(IndexLockError::Busy { .. })
+        ));
+        drop(lock);
+        assert!(!tmp.path().join(LOCK_FILE_NAME).exists());
+        IndexLock::acquire(tmp.path()).expect("acquire after release");
+    }
+
+    #[test]
+    fn stale_lock_is_reclaimed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let old = now_millis() - (STALE_LOCK_SECS as i64 + 5) * 1000;
+        let stale = LockInfo {
+            pid: u32::MAX,
+            acquired_at: old,
+            heartbeat_at: old,
+        };
+        fs::write(
+            tmp.path().join(LOCK_FILE_NAME),
+            serde_json::to_vec(&stale).unwrap(),
+        )
+        .unwrap();
+
+        let lock = IndexLock::acquire(tmp.path()).expect("stale lock reclaimed");
+        assert_eq!(lock.info.pid, std::process::id());
+    }
+}
@@ -1,26 +1,31 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossbeam_channel::{Receiver, Sender};
 use notify::{RecursiveMode, Watcher, recommended_watcher};
 
 use crate::connectors::NormalizedConversation;
+use crate::connectors::parse_cache::ParseCache;
 use crate::connectors::{
     Connector, ScanRoot, aider::AiderConnector, amp::AmpConnector, chatgpt::ChatGptConnector,
     claude_code::ClaudeCodeConnector, clawdbot::ClawdbotConnector, cline::ClineConnector,
-    codex::CodexConnector, cursor::CursorConnector, factory::FactoryConnector,
-    gemini::GeminiConnector, opencode::OpenCodeConnector, pi_agent::PiAgentConnector,
+    codex::CodexConnector, copilot::CopilotConnector, cursor::CursorConnector,
+    factory::FactoryConnector, gemini::GeminiConnector, opencode::OpenCodeConnector,
+    pi_agent::PiAgentConnector,
 };
 use crate::search::tantivy::{TantivyIndex, index_dir};
 use crate::sources::config::{Platform, SourcesConfig};
 use crate::sources::provenance::{Origin, Source};
 use crate::sources::sync::path_to_safe_dirname;
-use crate::storage::sqlite::SqliteStorage;
+use crate::storage::sqlite::{IMPORTED_CONVERSATION_SQL, SqlitePragmas, SqliteStorage};
+
+pub mod import;
+pub mod lock;
 
 #[derive(Debug, Clone)]
 pub enum ReindexCommand {
@@ -46,6 +51,83 @@ pub struct IndexingProgress {
     pub discovered_agent_names: Mutex<Vec<String>>,
     /// Last error message from background indexer, if any
     pub last_error: Mutex<Option<String>>,
+    /// Session files skipped for exceeding `IndexOptions::max_file_bytes`
+    pub skipped_oversized: AtomicUsize,
+}
+
+/// Default quiet period before a burst of watch events triggers a reindex.
+pub const DEFAULT_WATCH_DEBOUNCE_MS: u64 = 2_000;
+/// Default upper bound on how long a continuous burst can delay a reindex.
+pub const DEFAULT_WATCH_MAX_WAIT_MS: u64 = 5_000;
+
+/// Push-based progress notification emitted by `run_index`.
+///
+/// Mirrors the `IndexingProgress` counters for embedders that prefer callbacks over polling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressEvent {
+    /// Connector discovery is starting.
+    ScanStarted,
+    /// A connector was detected (or has remote roots) and is being scanned.
+    AgentDiscovered(String),
+    /// A connector's conversations were persisted.
+    BatchIngested { connector: String, count: usize },
+    /// Phase changed (same encoding as `IndexingProgress::phase`: 0=Idle, 1=Scanning, 2=Indexing).
+    PhaseChanged(usize),
+    /// The scan/ingest pass finished.
+    Done,
+}
+
+/// Callback invoked for each [`ProgressEvent`]; shared so `IndexOptions` stays `Clone`.
+pub type ProgressCallback = Arc<dyn Fn(ProgressEvent) + Send + Sync>;
+
+/// Timing of one indexing phase, reported through [`IndexOptions::on_span`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseSpan {
+    /// `scan`, `ingest` or `commit`.
+    pub phase: &'static str,
+    /// Connector the span covers; `None` for run-wide phases (`commit`).
+    pub connector: Option<String>,
+    pub duration_ms: u64,
+    /// Conversations scanned, ingested or committed during the span.
+    pub conversations: usize,
+}
+
+/// Callback invoked for each [`PhaseSpan`] (e.g. to append them to `--trace-file`).
+pub type SpanCallback = Arc<dyn Fn(PhaseSpan) + Send + Sync>;
+
+/// Index runs refused before any data is touched.
+#[derive(Debug, thiserror::Error)]
+pub enum IndexError {
+    /// `--full`, `--force-rebuild` or a pending schema rebuild clears every
+    /// connector's data, but a connector- or path-filtered run would only
+    /// rescan some of it.
+    #[error("{reason} would discard conversations that this filtered run does not rescan")]
+    FilteredRebuild { reason: &'static str },
+    /// A single-file write (`reindex-one`, `import`, `forget`) found an index
+    /// that only a full `cass index` run can rebuild.
+    #[error("the search index {reason}; run `cass index` first")]
+    StaleIndex { reason: RebuildReason },
+}
+
+/// Why the Tantivy index has to be rebuilt from a full scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebuildReason {
+    /// No index has been built yet.
+    Missing,
+    /// The index was written with a different schema.
+    SchemaChanged,
+    /// The index exists but Tantivy cannot open it.
+    Unreadable,
+}
+
+impl std::fmt::Display for RebuildReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Missing => "has not been built",
+            Self::SchemaChanged => "was built with an older schema",
+            Self::Unreadable => "cannot be opened",
+        })
+    }
 }
 
 #[derive(Clone)]
@@ -58,43 +140,166 @@ pub struct IndexOptions {
     pub db_path: PathBuf,
     pub data_dir: PathBuf,
     pub progress: Option<Arc<IndexingProgress>>,
+    /// Restrict the run to these connector slugs (`--connector`). `None` runs all connectors.
+    pub only: Option<HashSet<String>>,
+    /// Connector slugs to leave out of the run (`--skip-connector`).
+    pub skip: HashSet<String>,
+    /// Optional push-based progress callback, invoked alongside the `progress` counters.
+    pub on_progress: Option<ProgressCallback>,
+    /// Watch mode: quiet period (ms) before pending changes are reindexed.
+    pub watch_debounce_ms: u64,
+    /// Watch mode: maximum delay (ms) for a continuous burst; must be >= `watch_debounce_ms`.
+    pub watch_max_wait_ms: u64,
+    /// Watch mode: return once no events have arrived for this long and nothing is
+    /// pending (`--watch-timeout`). `None` watches until the process is stopped.
+    pub watch_timeout: Option<Duration>,
+    /// Ingest at most this many conversations per SQLite transaction (`--batch-size`).
+    /// `None` ingests each connector's whole scan in one batch. Smaller batches keep
+    /// transactions and WAL growth short at the cost of more commits.
+    pub batch_max_conversations: Option<usize>,
+    /// Re-parse every session file instead of reusing cached parses of unchanged
+    /// files (`--ignore-cache`). Fresh parses still refresh the cache.
+    pub ignore_parse_cache: bool,
+    /// Scan at most this many connectors at once (`--jobs`). `None` uses one
+    /// thread per CPU.
+    pub jobs: Option<usize>,
+    /// Only scan source files whose absolute path matches one of these globs
+    /// (`--include`). Empty scans every file.
+    pub include_globs: Vec<String>,
+    /// Skip source files whose absolute path matches one of these globs (`--exclude`).
+    pub exclude_globs: Vec<String>,
+    /// Skip session files larger than this many bytes (`--max-file-size`). `None` is unlimited.
+    pub max_file_bytes: Option<u64>,
+    /// Fail the run on the first session file that cannot be read or parsed
+    /// (`--strict`) instead of skipping it with a warning.
+    pub strict: bool,
+    /// Merge the Tantivy index down to this many segments once indexing has
+    /// committed (`--optimize`). `None` leaves merging to the background policy.
+    pub optimize_segments: Option<usize>,
+    /// Optional per-phase timing sink (scan and ingest per connector, then commit).
+    pub on_span: Option<SpanCallback>,
+}
+
+impl IndexOptions {
+    /// Options for a one-shot incremental run of every connector with default
+    /// settings; callers override fields with struct update syntax.
+    pub fn new(db_path: PathBuf, data_dir: PathBuf) -> Self {
+        Self {
+            full: false,
+            force_rebuild: false,
+            watch: false,
+            watch_once_paths: None,
+            db_path,
+            data_dir,
+            progress: None,
+            only: None,
+            skip: HashSet::new(),
+            on_progress: None,
+            watch_debounce_ms: DEFAULT_WATCH_DEBOUNCE_MS,
+            watch_max_wait_ms: DEFAULT_WATCH_MAX_WAIT_MS,
+            watch_timeout: None,
+            batch_max_conversations: None,
+            ignore_parse_cache: false,
+            jobs: None,
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            max_file_bytes: None,
+            strict: false,
+            optimize_segments: None,
+            on_span: None,
+        }
+    }
+
+    /// Whether the connector `slug` passes the `only`/`skip` filters.
+    pub fn connector_enabled(&self, slug: &str) -> bool {
+        if self.skip.contains(slug) {
+            return false;
+        }
+        self.only.as_ref().is_none_or(|only| only.contains(slug))
+    }
+
+    /// True when this run leaves out some connectors or, through the
+    /// include/exclude globs, some of their files.
+    fn scan_filtered(&self) -> bool {
+        self.only.is_some()
+            || !self.skip.is_empty()
+            || !self.include_globs.is_empty()
+            || !self.exclude_globs.is_empty()
+    }
+
+    /// The include and exclude globs, compiled once for every scan of this run.
+    fn path_filters(&self) -> Result<(Vec<glob::Pattern>, Vec<glob::Pattern>)> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| {
+                    glob::Pattern::new(p).map_err(|e| anyhow::anyhow!("invalid glob '{p}': {e}"))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        Ok((compile(&self.include_globs)?, compile(&self.exclude_globs)?))
+    }
+
+    /// Debounce and max-wait durations for watch mode, validated so max-wait >= debounce.
+    pub fn watch_timings(&self) -> Result<(Duration, Duration)> {
+        if self.watch_max_wait_ms < self.watch_debounce_ms {
+            anyhow::bail!(
+                "watch max wait ({}ms) must be >= debounce ({}ms)",
+                self.watch_max_wait_ms,
+                self.watch_debounce_ms
+            );
+        }
+        Ok((
+            Duration::from_millis(self.watch_debounce_ms),
+            Duration::from_millis(self.watch_max_wait_ms),
+        ))
+    }
+
+    fn emit(&self, event: ProgressEvent) {
+        if let Some(cb) = &self.on_progress {
+            cb(event);
+        }
+    }
+
+    fn span(
+        &self,
+        phase: &'static str,
+        connector: Option<&str>,
+        started: Instant,
+        conversations: usize,
+    ) {
+        if let Some(cb) = &self.on_span {
+            cb(PhaseSpan {
+                phase,
+                connector: connector.map(str::to_string),
+                duration_ms: started.elapsed().as_millis() as u64,
+                conversations,
+            });
+        }
+    }
 }
 
 pub fn run_index(
     opts: IndexOptions,
     event_channel: Option<(Sender<IndexerEvent>, Receiver<IndexerEvent>)>,
 ) -> Result<()> {
-    let mut storage = SqliteStorage::open(&opts.db_path)?;
+    // Held for the whole run, including watch mode.
+    let _lock = lock::IndexLock::acquire(&opts.data_dir)?;
+    let (include_globs, exclude_globs) = opts.path_filters()?;
+    let mut storage = SqliteStorage::open_with_pragmas(&opts.db_path, &SqlitePragmas::from_env())?;
     let index_path = index_dir(&opts.data_dir)?;
 
-    // Detect if we are rebuilding due to missing meta/schema mismatch/index corruption.
-    // IMPORTANT: This must stay aligned with TantivyIndex::open_or_create() rebuild triggers.
-    let schema_hash_path = index_path.join("schema_hash.json");
-    let schema_matches = schema_hash_path.exists()
-        && std::fs::read_to_string(&schema_hash_path)
-            .ok()
-            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
-            .and_then(|json| {
-                json.get("schema_hash")
-                    .and_then(|v| v.as_str())
-                    .map(String::from)
-            })
-            .as_deref()
-            == Some(crate::search::tantivy::SCHEMA_HASH);
+    let needs_rebuild = opts.force_rebuild || index_rebuild_reason(&index_path).is_some();
 
-    // Treat missing schema hash as rebuild (open_or_create will wipe/recreate).
-    let mut needs_rebuild =
-        opts.force_rebuild || !index_path.join("meta.json").exists() || !schema_matches;
-
-    // Preflight open: if Tantivy can't open, force a rebuild so we do a full scan and
-    // reindex messages into the new Tantivy index (SQLite is incremental-only by default).
-    if !needs_rebuild && let Err(e) = tantivy::Index::open_in_dir(&index_path) {
-        tracing::warn!(
-            error = %e,
-            path = %index_path.display(),
-            "tantivy open preflight failed; forcing rebuild"
-        );
-        needs_rebuild = true;
+    if (opts.full || needs_rebuild) && opts.scan_filtered() && has_conversations(&storage)? {
+        let reason = if opts.full {
+            "--full"
+        } else if opts.force_rebuild {
+            "--force-rebuild"
+        } else {
+            "the pending index rebuild"
+        };
+        return Err(IndexError::FilteredRebuild { reason }.into());
     }
 
     if needs_rebuild && let Some(p) = &opts.progress {
@@ -111,6 +316,12 @@ pub fn run_index(
         reset_storage(&mut storage)?;
         t_index.delete_all()?;
     }
+    if opts.full || needs_rebuild {
+        let restored = reindex_imports(&storage, &mut t_index)?;
+        if restored > 0 {
+            tracing::info!(conversations = restored, "reindexed_imported_conversations");
+        }
+    }
 
     // Get last scan timestamp for incremental indexing.
     // If full rebuild or force_rebuild, scan everything (since_ts = None).
@@ -133,7 +344,13 @@ pub fn run_index(
     // Record scan start time before scanning
     let scan_start_ts = SqliteStorage::now_millis();
 
-    let connector_factories = get_connector_factories();
+    let connector_factories: Vec<_> = get_connector_factories()
+        .into_iter()
+        .filter(|(name, _)| opts.connector_enabled(name))
+        .collect();
+
+    opts.emit(ProgressEvent::ScanStarted);
+    opts.emit(ProgressEvent::PhaseChanged(1));
 
     // First pass: Scan all to get counts if we have progress tracker
     // Use parallel iteration for faster agent discovery
@@ -166,99 +383,165 @@ pub fn run_index(
 
     let progress_ref = opts.progress.as_ref();
     let data_dir = opts.data_dir.clone();
+    let parse_cache = Arc::new(ParseCache::new(&opts.data_dir, !opts.ignore_parse_cache));
+    let oversized_files = Arc::new(AtomicUsize::new(0));
+    let strict_error: Mutex<Option<anyhow::Error>> = Mutex::new(None);
+    let record_strict_error = |e: anyhow::Error| {
+        if let Ok(mut first) = strict_error.lock()
+            && first.is_none()
+        {
+            *first = Some(e);
+        }
+    };
 
-    let pending_batches: Vec<(&'static str, Vec<NormalizedConversation>)> = connector_factories
-        .into_par_iter()
-        .filter_map(|(name, factory)| {
-            let conn = factory();
-            let detect = conn.detect();
-            let was_detected = detect.detected;
-            let mut convs = Vec::new();
-
-            if detect.detected {
-                // Update discovered agents count immediately when detected
-                // This gives fast UI feedback during the discovery phase
-                if let Some(p) = progress_ref {
-                    p.discovered_agents.fetch_add(1, Ordering::Relaxed);
-                    if let Ok(mut names) = p.discovered_agent_names.lock() {
-                        names.push(name.to_string());
-                    }
-                }
-
-                let ctx = crate::connectors::ScanContext::local_default(data_dir.clone(), since_ts);
-                match conn.scan(&ctx) {
-                    Ok(mut local_convs) => {
-                        let local_origin = Origin::local();
-                        for conv in &mut local_convs {
-                            inject_provenance(conv, &local_origin);
+    let pool = scan_pool(opts.jobs)?;
+    let pending_batches: Vec<(&'static str, Vec<NormalizedConversation>)> = pool.install(|| {
+        connector_factories
+            .into_par_iter()
+            .filter_map(|(name, factory)| {
+                let scan_started = Instant::now();
+                let conn = factory();
+                let detect = conn.detect();
+                let was_detected = detect.detected;
+                let mut convs = Vec::new();
+
+                if detect.detected {
+                    // Update discovered agents count immediately when detected
+                    // This gives fast UI feedback during the discovery phase
+                    opts.emit(ProgressEvent::AgentDiscovered(name.to_string()));
+                    if let Some(p) = progress_ref {
+                        p.discovered_agents.fetch_add(1, Ordering::Relaxed);
+                        if let Ok(mut names) = p.discovered_agent_names.lock() {
+                            names.push(name.to_string());
                         }
-                        convs.extend(local_convs);
                     }
-                    Err(e) => {
-                        // Note: agent was counted as discovered but scan failed
-                        // This is acceptable as detection succeeded (agent exists)
-                        tracing::warn!("scan failed for {}: {}", name, e);
-                    }
-                }
-            }
 
-            if !remote_roots.is_empty() {
-                for root in &remote_roots {
-                    let ctx = crate::connectors::ScanContext::with_roots(
-                        root.path.clone(),
-                        vec![root.clone()],
-                        since_ts,
-                    );
+                    let ctx =
+                        crate::connectors::ScanContext::local_default(data_dir.clone(), since_ts)
+                            .with_parse_cache(Some(parse_cache.clone()))
+                            .with_path_filters(include_globs.clone(), exclude_globs.clone())
+                            .with_max_file_bytes(opts.max_file_bytes, oversized_files.clone())
+                            .with_strict(opts.strict);
                     match conn.scan(&ctx) {
-                        Ok(mut remote_convs) => {
-                            tracing::info!(
-                                connector = name,
-                                source_id = %root.origin.source_id,
-                                count = remote_convs.len(),
-                                "scanned remote conversations"
-                            );
-                            for conv in &mut remote_convs {
-                                inject_provenance(conv, &root.origin);
-                                apply_workspace_rewrite(conv, &root.workspace_rewrites);
+                        Ok(mut local_convs) => {
+                            let local_origin = Origin::local();
+                            for conv in &mut local_convs {
+                                inject_provenance(conv, &local_origin);
                             }
-                            convs.extend(remote_convs);
+                            convs.extend(local_convs);
                         }
                         Err(e) => {
-                            tracing::warn!(
-                                connector = name,
-                                root = %root.path.display(),
-                                "remote scan failed: {e}"
-                            );
+                            // Note: agent was counted as discovered but scan failed
+                            // This is acceptable as detection succeeded (agent exists)
+                            tracing::warn!("scan failed for {}: {:#}", name, e);
+                            if opts.strict {
+                                record_strict_error(e.context(format!("scan failed for {name}")));
+                            }
+                        }
+                    }
+                }
+
+                if !remote_roots.is_empty() {
+                    for root in &remote_roots {
+                        let ctx = crate::connectors::ScanContext::with_roots(
+                            root.path.clone(),
+                            vec![root.clone()],
+                            since_ts,
+                        )
+                        .with_parse_cache(Some(parse_cache.clone()))
+                        .with_path_filters(include_globs.clone(), exclude_globs.clone())
+                        .with_max_file_bytes(opts.max_file_bytes, oversized_files.clone())
+                        .with_strict(opts.strict);
+                        match conn.scan(&ctx) {
+                            Ok(mut remote_convs) => {
+                                tracing::info!(
+                                    connector = name,
+                                    source_id = %root.origin.source_id,
+                                    count = remote_convs.len(),
+                                    "scanned remote conversations"
+                                );
+                                for conv in &mut remote_convs {
+                                    inject_provenance(conv, &root.origin);
+                                    apply_workspace_rewrite(conv, &root.workspace_rewrites);
+                                }
+                                convs.extend(remote_convs);
+                            }
+                            Err(e) => {
+                                tracing::warn!(
+                                    connector = name,
+                                    root = %root.path.display(),
+                                    "remote scan failed: {e:#}"
+                                );
+                                if opts.strict {
+                                    record_strict_error(e.context(format!(
+                                        "remote scan failed for {name} ({})",
+                                        root.origin.source_id
+                                    )));
+                                }
+                            }
                         }
                     }
                 }
-            }
 
-            if !was_detected && let Some(p) = progress_ref {
-                p.discovered_agents.fetch_add(1, Ordering::Relaxed);
-                if let Ok(mut names) = p.discovered_agent_names.lock() {
-                    names.push(name.to_string());
+                if !was_detected && !remote_roots.is_empty() {
+                    opts.emit(ProgressEvent::AgentDiscovered(name.to_string()));
+                }
+                if !was_detected && let Some(p) = progress_ref {
+                    p.discovered_agents.fetch_add(1, Ordering::Relaxed);
+                    if let Ok(mut names) = p.discovered_agent_names.lock() {
+                        names.push(name.to_string());
+                    }
                 }
-            }
 
-            // Mark this connector as scanned for discovery progress.
-            if let Some(p) = progress_ref {
-                p.current.fetch_add(1, Ordering::Relaxed);
-            }
+                // Mark this connector as scanned for discovery progress.
+                if let Some(p) = progress_ref {
+                    p.current.fetch_add(1, Ordering::Relaxed);
+                }
 
-            if convs.is_empty() {
-                return None;
-            }
+                if was_detected || !remote_roots.is_empty() {
+                    opts.span("scan", Some(name), scan_started, convs.len());
+                }
 
-            tracing::info!(
-                connector = name,
-                conversations = convs.len(),
-                "parallel_scan_complete"
-            );
-            Some((name, convs))
-        })
-        .collect();
+                if convs.is_empty() {
+                    return None;
+                }
+
+                tracing::info!(
+                    connector = name,
+                    conversations = convs.len(),
+                    "parallel_scan_complete"
+                );
+                Some((name, convs))
+            })
+            .collect()
+    });
+    if let Some(e) = strict_error.into_inner().ok().flatten() {
+        return Err(e);
+    }
+
+    tracing::info!(
+        hits = parse_cache.hits(),
+        misses = parse_cache.misses(),
+        "parse_cache"
+    );
+    // Only a full, unfiltered scan visits every file, so only then can unvisited
+    // entries be treated as belonging to deleted or rotated sessions.
+    if since_ts.is_none() && !opts.scan_filtered() {
+        let pruned = parse_cache.prune_unseen();
+        if pruned > 0 {
+            tracing::info!(entries = pruned, "parse_cache_pruned");
+        }
+    }
+    let skipped_oversized = oversized_files.load(Ordering::Relaxed);
+    if skipped_oversized > 0 {
+        tracing::warn!(files = skipped_oversized, "skipped_oversized_files");
+    }
+    if let Some(p) = &opts.progress {
+        p.skipped_oversized
+            .store(skipped_oversized, Ordering::Relaxed);
+    }
 
+    opts.emit(ProgressEvent::PhaseChanged(2));
     if let Some(p) = &opts.progress {
         let total_conversations: usize = pending_batches.iter().map(|(_, convs)| convs.len()).sum();
         p.phase.store(2, Ordering::Relaxed); // Indexing
@@ -266,14 +549,25 @@ pub fn run_index(
         p.current.store(0, Ordering::Relaxed);
     }
 
+    let batch_size = opts.batch_max_conversations.unwrap_or(usize::MAX).max(1);
+    let mut ingested = 0;
     for (name, convs) in pending_batches {
-        ingest_batch(
-            &mut storage,
-            &mut t_index,
-            &convs,
-            &opts.progress,
-            needs_rebuild,
-        )?;
+        let ingest_started = Instant::now();
+        for batch in convs.chunks(batch_size) {
+            ingest_batch(
+                &mut storage,
+                &mut t_index,
+                batch,
+                &opts.progress,
+                needs_rebuild,
+            )?;
+            opts.emit(ProgressEvent::BatchIngested {
+                connector: name.to_string(),
+                count: batch.len(),
+            });
+        }
+        opts.span("ingest", Some(name), ingest_started, convs.len());
+        ingested += convs.len();
         tracing::info!(
             connector = name,
             conversations = convs.len(),
@@ -281,33 +575,54 @@ pub fn run_index(
         );
     }
 
+    let commit_started = Instant::now();
     t_index.commit()?;
+    opts.span("commit", None, commit_started, ingested);
+    if let Some(target) = opts.optimize_segments {
+        let optimize_started = Instant::now();
+        let segments = t_index.merge_segments(target)?;
+        opts.span("optimize", None, optimize_started, segments);
+        tracing::info!(segments, target, "index_optimized");
+    }
+    let generation = storage.bump_index_generation()?;
+    tracing::info!(generation, "index_generation");
 
-    // Update last_scan_ts after successful scan and commit
-    storage.set_last_scan_ts(scan_start_ts)?;
-    tracing::info!(
-        scan_start_ts,
-        "updated last_scan_ts for incremental indexing"
-    );
+    // Update last_scan_ts after successful scan and commit. A filtered run leaves
+    // it alone so skipped connectors and files still pick up older changes next time.
+    if opts.scan_filtered() {
+        tracing::info!("scan filter active; keeping previous last_scan_ts");
+    } else {
+        storage.set_last_scan_ts(scan_start_ts)?;
+        tracing::info!(
+            scan_start_ts,
+            "updated last_scan_ts for incremental indexing"
+        );
+    }
 
     if let Some(p) = &opts.progress {
         p.phase.store(0, Ordering::Relaxed); // Idle
         p.is_rebuilding.store(false, Ordering::Relaxed);
     }
+    opts.emit(ProgressEvent::PhaseChanged(0));
+    opts.emit(ProgressEvent::Done);
 
     if opts.watch || opts.watch_once_paths.is_some() {
+        let (debounce, max_wait) = opts.watch_timings()?;
         let opts_clone = opts.clone();
         let state = Arc::new(Mutex::new(load_watch_state(&opts.data_dir)));
         let storage = Arc::new(Mutex::new(storage));
         let t_index = Arc::new(Mutex::new(t_index));
 
         // Detect roots once for the watcher setup
-        let watch_roots = detect_watch_roots();
+        let watch_roots = detect_watch_roots(&opts);
 
         watch_sources(
             opts.watch_once_paths.clone(),
             watch_roots.clone(),
             event_channel,
+            debounce,
+            max_wait,
+            opts.watch_timeout,
             move |paths, roots, is_rebuild| {
                 if is_rebuild {
                     if let Ok(mut g) = state.lock() {
@@ -377,15 +692,27 @@ pub fn get_connector_factories() -> Vec<(&'static str, fn() -> Box<dyn Connector
         ("chatgpt", || Box::new(ChatGptConnector::new())),
         ("pi_agent", || Box::new(PiAgentConnector::new())),
         ("factory", || Box::new(FactoryConnector::new())),
+        ("copilot", || Box::new(CopilotConnector::new())),
     ]
 }
 
+/// Slugs of all known connectors, in scan order.
+pub fn connector_slugs() -> Vec<&'static str> {
+    get_connector_factories()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
 /// Detect all active roots for watching/scanning.
-fn detect_watch_roots() -> Vec<(ConnectorKind, PathBuf)> {
+fn detect_watch_roots(opts: &IndexOptions) -> Vec<(ConnectorKind, PathBuf)> {
     let factories = get_connector_factories();
     let mut roots = Vec::new();
 
     for (name, factory) in factories {
+        if !opts.connector_enabled(name) {
+            continue;
+        }
         if let Some(kind) = ConnectorKind::from_slug(name) {
             let conn = factory();
             let detection = conn.detect();
@@ -400,6 +727,15 @@ fn detect_watch_roots() -> Vec<(ConnectorKind, PathBuf)> {
 }
 
 impl ConnectorKind {
+    /// Connector slug for this kind: the factory name that [`Self::from_slug`] maps back to it.
+    fn slug(self) -> &'static str {
+        get_connector_factories()
+            .into_iter()
+            .map(|(name, _)| name)
+            .find(|name| Self::from_slug(name) == Some(self))
+            .unwrap_or("unknown")
+    }
+
     fn from_slug(slug: &str) -> Option<Self> {
         match slug {
             "codex" => Some(Self::Codex),
@@ -414,6 +750,7 @@ impl ConnectorKind {
             "chatgpt" => Some(Self::ChatGpt),
             "pi_agent" => Some(Self::PiAgent),
             "factory" => Some(Self::Factory),
+            "copilot" => Some(Self::Copilot),
             _ => None,
         }
     }
@@ -434,6 +771,7 @@ impl ConnectorKind {
             Self::ChatGpt => Box::new(ChatGptConnector::new()),
             Self::PiAgent => Box::new(PiAgentConnector::new()),
             Self::Factory => Box::new(FactoryConnector::new()),
+            Self::Copilot => Box::new(CopilotConnector::new()),
         }
     }
 }
@@ -442,6 +780,9 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, PathBuf)], bool) + Send +
     watch_once_paths: Option<Vec<PathBuf>>,
     roots: Vec<(ConnectorKind, PathBuf)>,
     event_channel: Option<(Sender<IndexerEvent>, Receiver<IndexerEvent>)>,
+    debounce: Duration,
+    max_wait: Duration,
+    idle_timeout: Option<Duration>,
     callback: F,
 ) -> Result<()> {
     if let Some(paths) = watch_once_paths {
@@ -469,14 +810,20 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, PathBuf)], bool) + Send +
         }
     }
 
-    let debounce = Duration::from_secs(2);
-    let max_wait = Duration::from_secs(5);
     let mut pending: Vec<PathBuf> = Vec::new();
     let mut first_event: Option<std::time::Instant> = None;
 
     loop {
         if pending.is_empty() {
-            match rx.recv() {
+            let received = match idle_timeout {
+                Some(timeout) => rx.recv_timeout(timeout).map_err(|e| {
+                    if e.is_timeout() {
+                        tracing::info!(?timeout, "watch idle timeout reached");
+                    }
+                }),
+                None => rx.recv().map_err(|_| ()),
+            };
+            match received {
                 Ok(event) => match event {
                     IndexerEvent::Notify(paths) => {
                         pending.extend(paths);
@@ -488,7 +835,7 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, PathBuf)], bool) + Send +
                         }
                     },
                 },
-                Err(_) => break, // Channel closed
+                Err(()) => break, // Idle timeout or channel closed
             }
         } else {
             let now = std::time::Instant::now();
@@ -521,31 +868,120 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, PathBuf)], bool) + Send +
                     callback(std::mem::take(&mut pending), &roots, false);
                     first_event = None;
                 }
-                Err(crossbeam_channel::RecvTimeoutError::Disconnected) => break,
+                Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                    callback(std::mem::take(&mut pending), &roots, false);
+                    break;
+                }
             }
         }
     }
     Ok(())
 }
 
+/// Thread pool for connector scans, capped at `jobs` threads when given.
+/// Connectors that parallelize internally share the same cap.
+fn scan_pool(jobs: Option<usize>) -> Result<rayon::ThreadPool> {
+    let mut builder = rayon::ThreadPoolBuilder::new().thread_name(|i| format!("cass-scan-{i}"));
+    if let Some(jobs) = jobs {
+        builder = builder.num_threads(jobs.max(1));
+    }
+    Ok(builder.build()?)
+}
+
+/// Whether the Tantivy index at `index_path` needs a rebuild (missing meta,
+/// schema mismatch or corruption), in which case `run_index` does a full scan.
+/// IMPORTANT: This must stay aligned with TantivyIndex::open_or_create() rebuild triggers.
+fn index_rebuild_reason(index_path: &Path) -> Option<RebuildReason> {
+    if !index_path.join("meta.json").exists() {
+        return Some(RebuildReason::Missing);
+    }
+    // Treat missing schema hash as rebuild (open_or_create will wipe/recreate).
+    let schema_matches = std::fs::read_to_string(index_path.join("schema_hash.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|json| {
+            json.get("schema_hash")
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        })
+        .as_deref()
+        == Some(crate::search::tantivy::SCHEMA_HASH);
+    if !schema_matches {
+        return Some(RebuildReason::SchemaChanged);
+    }
+    // Preflight open: if Tantivy can't open, force a rebuild so we do a full scan and
+    // reindex messages into the new Tantivy index (SQLite is incremental-only by default).
+    if let Err(e) = tantivy::Index::open_in_dir(index_path) {
+        tracing::warn!(
+            error = %e,
+            path = %index_path.display(),
+            "tantivy open preflight failed; forcing rebuild"
+        );
+        return Some(RebuildReason::Unreadable);
+    }
+    None
+}
+
+/// Refuse to write into an index that `run_index` would rebuild. Opening it
+/// with `open_or_create` would recreate it with only this write in it and
+/// stamp the current schema, so the next `cass index` would skip the rebuild
+/// and every other stored conversation would drop out of search. A missing
+/// index is fine while the database is still empty.
+fn ensure_index_current(storage: &SqliteStorage, index_path: &Path) -> Result<()> {
+    match index_rebuild_reason(index_path) {
+        None => Ok(()),
+        Some(RebuildReason::Missing) if !has_conversations(storage)? => Ok(()),
+        Some(reason) => Err(IndexError::StaleIndex { reason }.into()),
+    }
+}
+
+fn has_conversations(storage: &SqliteStorage) -> Result<bool> {
+    Ok(storage
+        .raw()
+        .query_row("SELECT EXISTS(SELECT 1 FROM conversations)", [], |r| {
+            r.get(0)
+        })?)
+}
+
+/// Clear the database for a `--full` run. Imported transcripts are kept, with
+/// the agents and workspaces they use, since no connector scan would bring them back.
 fn reset_storage(storage: &mut SqliteStorage) -> Result<()> {
     // Wrap in transaction to ensure atomic reset - if any DELETE fails,
     // all changes are rolled back to prevent inconsistent state
-    storage.raw().execute_batch(
+    storage.raw().execute_batch(&format!(
         "BEGIN TRANSACTION;
-         DELETE FROM fts_messages;
-         DELETE FROM snippets;
-         DELETE FROM messages;
-         DELETE FROM conversations;
-         DELETE FROM agents;
-         DELETE FROM workspaces;
-         DELETE FROM tags;
-         DELETE FROM conversation_tags;
-         COMMIT;",
-    )?;
+         CREATE TEMP TABLE reset_kept AS
+             SELECT id FROM conversations WHERE {IMPORTED_CONVERSATION_SQL};
+         DELETE FROM fts_messages WHERE message_id NOT IN
+             (SELECT id FROM messages WHERE conversation_id IN reset_kept);
+         DELETE FROM snippets WHERE message_id NOT IN
+             (SELECT id FROM messages WHERE conversation_id IN reset_kept);
+         DELETE FROM messages WHERE conversation_id NOT IN reset_kept;
+         DELETE FROM conversation_tags WHERE conversation_id NOT IN reset_kept;
+         DELETE FROM conversations WHERE id NOT IN reset_kept;
+         DELETE FROM agents WHERE id NOT IN (SELECT agent_id FROM conversations);
+         DELETE FROM workspaces WHERE id NOT IN
+             (SELECT workspace_id FROM conversations WHERE workspace_id IS NOT NULL);
+         DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM conversation_tags);
+         DROP TABLE reset_kept;
+         COMMIT;"
+    ))?;
     Ok(())
 }
 
+/// Add imported transcripts back to a freshly cleared or rebuilt Tantivy index
+/// from SQLite; connector scans only restore what they find on disk.
+fn reindex_imports(storage: &SqliteStorage, t_index: &mut TantivyIndex) -> Result<usize> {
+    let imported = storage.imported_conversations()?;
+    let count = imported.len();
+    for conv in imported {
+        let Some(id) = conv.id else { continue };
+        let messages = storage.fetch_messages(id)?;
+        t_index.add_conversation(&normalized_from_stored(conv, messages))?;
+    }
+    Ok(count)
+}
+
 fn reindex_paths(
     opts: &IndexOptions,
     paths: Vec<PathBuf>,
@@ -562,6 +998,7 @@ fn reindex_paths(
     if triggers.is_empty() {
         return Ok(());
     }
+    let (include_globs, exclude_globs) = opts.path_filters()?;
 
     for (kind, ts) in triggers {
         let conn = kind.create_connector();
@@ -587,7 +1024,10 @@ fn reindex_paths(
                 .or_else(|| ts.map(|v| v.saturating_sub(1)))
                 .map(|v| v.saturating_sub(1))
         };
-        let ctx = crate::connectors::ScanContext::local_default(opts.data_dir.clone(), since_ts);
+        let ctx = crate::connectors::ScanContext::local_default(opts.data_dir.clone(), since_ts)
+            .with_path_filters(include_globs.clone(), exclude_globs.clone())
+            .with_max_file_bytes(opts.max_file_bytes, Arc::default())
+            .with_strict(opts.strict);
 
         // SCAN PHASE: IO-heavy, no locks held
         let mut convs = conn.scan(&ctx)?;
@@ -619,6 +1059,7 @@ fn reindex_paths(
 
             // Commit to Tantivy immediately to ensure index consistency before advancing watch state.
             t_index.commit()?;
+            storage.bump_index_generation()?;
         }
 
         if let Some(ts_val) = ts {
@@ -639,6 +1080,358 @@ fn reindex_paths(
     Ok(())
 }
 
+/// One connector's entry in a [`DryRunReport`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DryRunConnector {
+    pub name: &'static str,
+    pub detected: bool,
+    /// Local roots reported by the connector's `detect`.
+    pub roots: Vec<PathBuf>,
+    /// Files under `roots` modified since the incremental cutoff.
+    pub candidate_files: usize,
+}
+
+/// A synced remote mirror that a real run would scan.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DryRunRemoteRoot {
+    pub source_id: String,
+    pub path: PathBuf,
+    pub candidate_files: usize,
+}
+
+/// What `cass index --dry-run` found, without touching storage.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DryRunReport {
+    /// Incremental cutoff (ms); `None` means every file would be scanned.
+    pub since_ts: Option<i64>,
+    pub connectors: Vec<DryRunConnector>,
+    pub remote_roots: Vec<DryRunRemoteRoot>,
+    pub total_candidates: usize,
+}
+
+/// Run connector detection and scan-root enumeration and count the files an
+/// index run would consider. Opens the database read-only (if it exists) and
+/// never creates or writes SQLite or Tantivy state.
+pub fn dry_run(opts: &IndexOptions) -> Result<DryRunReport> {
+    let storage = if opts.db_path.exists() {
+        Some(SqliteStorage::open_readonly(&opts.db_path)?)
+    } else {
+        None
+    };
+    // Mirror run_index: a missing index forces a full scan.
+    let index_missing = !crate::search::tantivy::index_dir_path(&opts.data_dir)
+        .join("meta.json")
+        .exists();
+    let since_ts = if opts.full || opts.force_rebuild || index_missing {
+        None
+    } else {
+        storage
+            .as_ref()
+            .and_then(|s| s.get_last_scan_ts().ok().flatten())
+            .map(|ts| ts.saturating_sub(1))
+    };
+
+    let count_candidates = |root: &Path| {
+        walkdir::WalkDir::new(root)
+            .into_iter()
+            .flatten()
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| crate::connectors::file_modified_since(e.path(), since_ts))
+            .count()
+    };
+
+    let connectors: Vec<DryRunConnector> = get_connector_factories()
+        .into_iter()
+        .filter(|(name, _)| opts.connector_enabled(name))
+        .map(|(name, factory)| {
+            let detect = factory().detect();
+            let candidate_files = if detect.detected {
+                detect.root_paths.iter().map(|r| count_candidates(r)).sum()
+            } else {
+                0
+            };
+            DryRunConnector {
+                name,
+                detected: detect.detected,
+                roots: detect.root_paths,
+                candidate_files,
+            }
+        })
+        .collect();
+
+    let remote_roots: Vec<DryRunRemoteRoot> = collect_scan_roots(storage.as_ref(), &opts.data_dir)
+        .into_iter()
+        .filter(|r| r.origin.is_remote())
+        .map(|r| DryRunRemoteRoot {
+            candidate_files: count_candidates(&r.path),
+            source_id: r.origin.source_id,
+            path: r.path,
+        })
+        .collect();
+
+    let total_candidates = connectors.iter().map(|c| c.candidate_files).sum::<usize>()
+        + remote_roots
+            .iter()
+            .map(|r| r.candidate_files)
+            .sum::<usize>();
+    Ok(DryRunReport {
+        since_ts,
+        connectors,
+        remote_roots,
+        total_candidates,
+    })
+}
+
+/// Counts reported by [`reindex_one`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ReindexOneReport {
+    /// Connector slug the path was classified to.
+    pub connector: String,
+    /// Conversations parsed from the file.
+    pub conversations: usize,
+    /// Conversations that received at least one new message (includes newly created ones).
+    pub conversations_updated: usize,
+    /// Messages newly written to the database and Tantivy index.
+    pub messages_added: usize,
+}
+
+/// Re-ingest a single session file, as if the watcher had reported a change to it.
+///
+/// The path is classified to its connector by watch root. That root is the scan's
+/// only root and an exact-path include filter limits parsing to the one file; only
+/// conversations whose `source_path` is that file (or lies under it, for stores that
+/// hold several conversations) are ingested, the Tantivy index is committed, and the
+/// connector's entry in `watch_state.json` advances to the file's mtime. A file that
+/// yields no conversations is an error and leaves the watch state alone.
+pub fn reindex_one(opts: &IndexOptions, path: &Path) -> Result<ReindexOneReport> {
+    let _lock = lock::IndexLock::acquire(&opts.data_dir)?;
+    let path = path
+        .canonicalize()
+        .map_err(|e| anyhow::anyhow!("cannot read {}: {e}", path.display()))?;
+    let mtime = fs::metadata(&path)?
+        .modified()?
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_millis() as i64;
+
+    // Connectors report paths under the root as they walk it, which may not be
+    // canonical, so the include filter is built from the root as detected.
+    let (kind, root, target) = detect_watch_roots(opts)
+        .into_iter()
+        .find_map(|(kind, root)| {
+            if path.starts_with(&root) {
+                return Some((kind, root, path.clone()));
+            }
+            let rel = path.strip_prefix(root.canonicalize().ok()?).ok()?;
+            let target = root.join(rel);
+            Some((kind, root, target))
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} is not under any detected connector root",
+                path.display()
+            )
+        })?;
+
+    let include = glob::Pattern::new(&glob::Pattern::escape(
+        &std::path::absolute(&target)?.to_string_lossy(),
+    ))?;
+    let ctx =
+        crate::connectors::ScanContext::with_roots(root.clone(), vec![ScanRoot::local(root)], None)
+            .with_path_filters(vec![include], Vec::new())
+            .with_strict(opts.strict);
+    let mut convs = kind.create_connector().scan(&ctx)?;
+    // Multi-conversation stores (e.g. Cursor's state.vscdb) report `<file>/<id>`.
+    convs.retain(|conv| {
+        conv.source_path.starts_with(&path)
+            || conv.source_path.starts_with(&target)
+            || conv.source_path.canonicalize().is_ok_and(|p| p == path)
+    });
+    if convs.is_empty() {
+        anyhow::bail!(
+            "no {} conversations found in {}",
+            kind.slug(),
+            path.display()
+        );
+    }
+    let local_origin = Origin::local();
+    for conv in &mut convs {
+        inject_provenance(conv, &local_origin);
+    }
+
+    let mut storage = SqliteStorage::open_with_pragmas(&opts.db_path, &SqlitePragmas::from_env())?;
+    let index_path = index_dir(&opts.data_dir)?;
+    ensure_index_current(&storage, &index_path)?;
+    let mut t_index = TantivyIndex::open_or_create(&index_path)?;
+    let outcomes =
+        persist::persist_conversations_batched(&mut storage, &mut t_index, &convs, false)?;
+    t_index.commit()?;
+    storage.bump_index_generation()?;
+
+    let mut state = load_watch_state(&opts.data_dir);
+    let entry = state.entry(kind).or_insert(mtime);
+    *entry = (*entry).max(mtime);
+    save_watch_state(&opts.data_dir, &state)?;
+
+    tracing::info!(?kind, path = %path.display(), conversations = convs.len(), "reindex_one");
+
+    Ok(ReindexOneReport {
+        connector: kind.slug().to_string(),
+        conversations: convs.len(),
+        conversations_updated: outcomes
+            .iter()
+            .filter(|o| !o.inserted_indices.is_empty())
+            .count(),
+        messages_added: outcomes.iter().map(|o| o.inserted_indices.len()).sum(),
+    })
+}
+
+/// Outcome of [`import_transcript`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ImportReport {
+    /// Canonical path of the imported file (also its external id).
+    pub source_path: PathBuf,
+    /// Messages parsed from the transcript.
+    pub messages: usize,
+    /// Messages newly written to the database and Tantivy index. Lower than
+    /// `messages` when the file was imported before.
+    pub messages_added: usize,
+}
+
+/// Import an exported transcript (generic JSONL or markdown, see [`import`]) as
+/// one conversation for `agent`, then commit the Tantivy index.
+pub fn import_transcript(
+    opts: &IndexOptions,
+    path: &Path,
+    agent: &str,
+    workspace: Option<PathBuf>,
+) -> Result<ImportReport> {
+    let mut conv = import::parse_transcript(path, agent, workspace)?;
+    inject_provenance(&mut conv, &Origin::local());
+
+    let _lock = lock::IndexLock::acquire(&opts.data_dir)?;
+    let mut storage = SqliteStorage::open(&opts.db_path)?;
+    let index_path = index_dir(&opts.data_dir)?;
+    ensure_index_current(&storage, &index_path)?;
+    let mut t_index = TantivyIndex::open_or_create(&index_path)?;
+    let outcomes = persist::persist_conversations_batched(
+        &mut storage,
+        &mut t_index,
+        std::slice::from_ref(&conv),
+        false,
+    )?;
+    t_index.commit()?;
+    storage.bump_index_generation()?;
+
+    tracing::info!(agent, path = %conv.source_path.display(), "import_transcript");
+
+    Ok(ImportReport {
+        messages: conv.messages.len(),
+        messages_added: outcomes.iter().map(|o| o.inserted_indices.len()).sum(),
+        source_path: conv.source_path,
+    })
+}
+
+/// Counts reported by [`forget`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ForgetReport {
+    /// Conversations removed from the database.
+    pub conversations: usize,
+    /// Messages removed from the database (including their FTS rows).
+    pub messages: usize,
+    /// Source paths whose documents were purged from the Tantivy index.
+    pub source_paths: Vec<String>,
+}
+
+/// Remove every conversation whose source path or external id is `target` from
+/// SQLite and the Tantivy index, then commit.
+///
+/// Tantivy documents are keyed by source path alone, so conversations that share
+/// a path with a forgotten one (single-file stores such as Cursor's) are re-added
+/// from the database after the purge.
+pub fn forget(opts: &IndexOptions, target: &str) -> Result<ForgetReport> {
+    let _lock = lock::IndexLock::acquire(&opts.data_dir)?;
+    let mut storage = SqliteStorage::open(&opts.db_path)?;
+    // A stale index would be recreated by `open_or_create` below; leave that to `cass index`.
+    let index_path = crate::search::tantivy::index_dir_path(&opts.data_dir);
+    match index_rebuild_reason(&index_path) {
+        None | Some(RebuildReason::Missing) => {}
+        Some(reason) => return Err(IndexError::StaleIndex { reason }.into()),
+    }
+    let mut report = ForgetReport::default();
+    for conv in storage.find_conversations(target)? {
+        let Some(id) = conv.id else { continue };
+        report.messages += storage.delete_conversation(id)?.len();
+        report.conversations += 1;
+        let path = conv.source_path.to_string_lossy().into_owned();
+        if !report.source_paths.contains(&path) {
+            report.source_paths.push(path);
+        }
+    }
+
+    // No index yet: the next index run builds it from the (already purged) database.
+    if report.source_paths.is_empty() || !index_path.join("meta.json").exists() {
+        return Ok(report);
+    }
+    let mut t_index = TantivyIndex::open_or_create(&index_path)?;
+    for path in &report.source_paths {
+        t_index.delete_source_path(path);
+        for sibling in storage.find_conversations(path)? {
+            if sibling.source_path.to_string_lossy() != path.as_str() {
+                continue;
+            }
+            let Some(id) = sibling.id else { continue };
+            let messages = storage.fetch_messages(id)?;
+            t_index.add_conversation(&normalized_from_stored(sibling, messages))?;
+        }
+    }
+    t_index.commit()?;
+    storage.bump_index_generation()?;
+
+    tracing::info!(
+        key = target,
+        conversations = report.conversations,
+        messages = report.messages,
+        "forget"
+    );
+    Ok(report)
+}
+
+/// Rebuild the connector-side view of a stored conversation for re-indexing.
+fn normalized_from_stored(
+    conv: crate::model::types::Conversation,
+    messages: Vec<crate::model::types::Message>,
+) -> NormalizedConversation {
+    use crate::model::types::MessageRole;
+    NormalizedConversation {
+        agent_slug: conv.agent_slug,
+        external_id: conv.external_id,
+        title: conv.title,
+        workspace: conv.workspace,
+        source_path: conv.source_path,
+        started_at: conv.started_at,
+        ended_at: conv.ended_at,
+        metadata: conv.metadata_json,
+        messages: messages
+            .into_iter()
+            .map(|m| crate::connectors::NormalizedMessage {
+                idx: m.idx,
+                role: match m.role {
+                    MessageRole::User => "user".to_string(),
+                    MessageRole::Agent => "assistant".to_string(),
+                    MessageRole::Tool => "tool".to_string(),
+                    MessageRole::System => "system".to_string(),
+                    MessageRole::Other(r) => r,
+                },
+                author: m.author,
+                created_at: m.created_at,
+                content: m.content,
+                extra: m.extra_json,
+                snippets: Vec::new(),
+            })
+            .collect(),
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum ConnectorKind {
     Codex,
@@ -653,6 +1446,7 @@ enum ConnectorKind {
     ChatGpt,
     PiAgent,
     Factory,
+    Copilot,
 }
 
 fn state_path(data_dir: &Path) -> PathBuf {
@@ -669,6 +1463,20 @@ fn load_watch_state(data_dir: &Path) -> HashMap<ConnectorKind, i64> {
     HashMap::new()
 }
 
+/// Per-connector watermarks (mtime millis) from `watch_state.json`, keyed by
+/// connector slug. `None` when no watch or targeted reindex has run yet.
+pub fn watch_state_watermarks(data_dir: &Path) -> Option<BTreeMap<&'static str, i64>> {
+    if !state_path(data_dir).exists() {
+        return None;
+    }
+    Some(
+        load_watch_state(data_dir)
+            .into_iter()
+            .map(|(kind, ts)| (kind.slug(), ts))
+            .collect(),
+    )
+}
+
 fn save_watch_state(data_dir: &Path, state: &HashMap<ConnectorKind, i64>) -> Result<()> {
     let path = state_path(data_dir);
     if let Some(parent) = path.parent() {
@@ -764,6 +1572,12 @@ fn sync_sources_config_to_db(storage: &SqliteStorage) {
 ///
 /// Part of P2.2 - Indexer multi-root orchestration.
 pub fn build_scan_roots(storage: &SqliteStorage, data_dir: &Path) -> Vec<ScanRoot> {
+    collect_scan_roots(Some(storage), data_dir)
+}
+
+/// [`build_scan_roots`] without requiring a database; registered-source
+/// mirrors are skipped when `storage` is `None`.
+fn collect_scan_roots(storage: Option<&SqliteStorage>, data_dir: &Path) -> Vec<ScanRoot> {
     let mut roots = Vec::new();
 
     // Add local default root with local provenance
@@ -815,7 +1629,7 @@ pub fn build_scan_roots(storage: &SqliteStorage, data_dir: &Path) -> Vec<ScanRoo
 
     // Fallback: remote mirror roots from registered sources
     // This scans entire mirror directories without trying to match individual paths
-    if let Ok(sources) = storage.list_sources() {
+    if let Some(Ok(sources)) = storage.map(SqliteStorage::list_sources) {
         for source in sources {
             // Skip local source - already handled above
             if !source.kind.is_remote() {
@@ -1000,7 +1814,7 @@ pub fn apply_workspace_rewrite(
 
     // Sort by prefix length descending for longest-prefix match
     let mut mappings: Vec<_> = workspace_rewrites.iter().collect();
-    mappings.sort_by(|a, b| b.from.len().cmp(&a.from.len()));
+    mappings.sort_by_key(|m| std::cmp::Reverse(m.from.len()));
 
     // Try to apply a mapping
     for mapping in mappings {
@@ -1148,34 +1962,50 @@ pub mod persist {
 
         let internal_conv = map_to_internal(conv);
 
-        let InsertOutcome {
-            conversation_id: _,
-            inserted_indices,
-        } = storage.insert_conversation_tree(agent_id, workspace_id, &internal_conv)?;
+        let outcome = storage.insert_conversation_tree(agent_id, workspace_id, &internal_conv)?;
 
         // Only add newly inserted messages to the Tantivy index (incremental)
-        if !inserted_indices.is_empty() {
-            let new_msgs: Vec<_> = conv
-                .messages
-                .iter()
-                .filter(|m| inserted_indices.contains(&m.idx))
-                .cloned()
-                .collect();
-            t_index.add_messages(conv, &new_msgs)?;
+        index_inserted_messages(t_index, conv, &outcome)
+    }
+
+    /// Mirror an incremental SQLite insert into Tantivy: drop documents of rows
+    /// rewritten in place, then add the written messages under the index SQLite
+    /// stored them at, so `msg_idx` and `line_number` agree with the database.
+    fn index_inserted_messages(
+        t_index: &mut TantivyIndex,
+        conv: &NormalizedConversation,
+        outcome: &InsertOutcome,
+    ) -> Result<()> {
+        if outcome.inserted_indices.is_empty() {
+            return Ok(());
+        }
+        for &idx in &outcome.replaced_indices {
+            t_index.delete_message(conv, idx)?;
         }
-        Ok(())
+        let new_msgs: Vec<_> = outcome
+            .source_indices
+            .iter()
+            .zip(&outcome.inserted_indices)
+            .filter_map(|(&source_idx, &idx)| {
+                let mut msg = conv.messages.iter().find(|m| m.idx == source_idx)?.clone();
+                msg.idx = idx;
+                Some(msg)
+            })
+            .collect();
+        t_index.add_messages(conv, &new_msgs)
     }
 
     /// Persist multiple conversations in a single database transaction for better performance.
     /// This reduces SQLite transaction overhead when indexing many conversations at once.
+    /// Returns one `InsertOutcome` per conversation, in input order.
     pub fn persist_conversations_batched(
         storage: &mut SqliteStorage,
         t_index: &mut TantivyIndex,
         convs: &[NormalizedConversation],
         force_tantivy_reindex: bool,
-    ) -> Result<()> {
+    ) -> Result<Vec<InsertOutcome>> {
         if convs.is_empty() {
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         // Prepare data for batched insert: (agent_id, workspace_id, Conversation)
@@ -1213,18 +2043,12 @@ pub mod persist {
             if force_tantivy_reindex {
                 // Rebuild path: the Tantivy index is known-empty, so index all messages.
                 t_index.add_messages(conv, &conv.messages)?;
-            } else if !outcome.inserted_indices.is_empty() {
-                let new_msgs: Vec<_> = conv
-                    .messages
-                    .iter()
-                    .filter(|m| outcome.inserted_indices.contains(&m.idx))
-                    .cloned()
-                    .collect();
-                t_index.add_messages(conv, &new_msgs)?;
+            } else {
+                index_inserted_messages(t_index, conv, outcome)?;
             }
         }
 
-        Ok(())
+        Ok(outcomes)
     }
 
     fn map_role(role: &str) -> MessageRole {
@@ -1307,6 +2131,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scan_pool_caps_concurrent_connector_scans() {
+        use rayon::prelude::*;
+
+        let max_concurrent = |jobs: Option<usize>| {
+            let active = AtomicUsize::new(0);
+            let peak = AtomicUsize::new(0);
+            scan_pool(jobs).unwrap().install(|| {
+                (0..8).into_par_iter().for_each(|_| {
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(20));
+                    active.fetch_sub(1, Ordering::SeqCst);
+                });
+            });
+            peak.load(Ordering::SeqCst)
+        };
+        assert_eq!(max_concurrent(Some(1)), 1);
+        assert!(max_concurrent(Some(2)) <= 2);
+    }
+
     #[test]
     fn reset_storage_clears_data_but_leaves_meta() {
         let tmp = TempDir::new().unwrap();
@@ -1371,7 +2216,7 @@ mod tests {
             .query_row("SELECT COUNT(*) FROM messages", [], |r| r.get(0))
             .unwrap();
         assert_eq!(msg_count, 0);
-        assert_eq!(storage.schema_version().unwrap(), 5);
+        assert_eq!(storage.schema_version().unwrap(), 6);
     }
 
     #[test]
@@ -1405,6 +2250,171 @@ mod tests {
         assert_eq!(reader.searcher().num_docs(), 3);
     }
 
+    #[test]
+    fn persist_rescan_of_reordered_conversation_keeps_doc_count() {
+        let tmp = TempDir::new().unwrap();
+        let data_dir = tmp.path().join("data");
+        std::fs::create_dir_all(&data_dir).unwrap();
+
+        let db_path = data_dir.join("db.sqlite");
+        let mut storage = SqliteStorage::open(&db_path).unwrap();
+        ensure_fts_schema(storage.raw());
+        let mut index = TantivyIndex::open_or_create(&index_dir(&data_dir).unwrap()).unwrap();
+
+        let msgs = vec![norm_msg(0, 100), norm_msg(1, 200), norm_msg(2, 300)];
+        let conv1 = norm_conv(Some("ext"), msgs.clone());
+        persist::persist_conversation(&mut storage, &mut index, &conv1).unwrap();
+        index.commit().unwrap();
+
+        // Same messages, renumbered in a different order (e.g. a resumed session)
+        let mut reordered: Vec<NormalizedMessage> = msgs.into_iter().rev().collect();
+        for (i, m) in reordered.iter_mut().enumerate() {
+            m.idx = i as i64;
+        }
+        let conv2 = norm_conv(Some("ext"), reordered);
+        persist::persist_conversation(&mut storage, &mut index, &conv2).unwrap();
+        index.commit().unwrap();
+
+        let reader = index.reader().unwrap();
+        reader.reload().unwrap();
+        assert_eq!(reader.searcher().num_docs(), 3);
+        let msg_count: i64 = storage
+            .raw()
+            .query_row("SELECT COUNT(*) FROM messages", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(msg_count, 3);
+    }
+
+    #[test]
+    fn persist_rescan_of_grown_message_replaces_it_in_place() {
+        let tmp = TempDir::new().unwrap();
+        let data_dir = tmp.path().join("data");
+        std::fs::create_dir_all(&data_dir).unwrap();
+
+        let db_path = data_dir.join("db.sqlite");
+        let mut storage = SqliteStorage::open(&db_path).unwrap();
+        ensure_fts_schema(storage.raw());
+        let mut index = TantivyIndex::open_or_create(&index_dir(&data_dir).unwrap()).unwrap();
+
+        let conv1 = norm_conv(Some("ext"), vec![norm_msg(0, 100), norm_msg(1, 200)]);
+        persist::persist_conversation(&mut storage, &mut index, &conv1).unwrap();
+        index.commit().unwrap();
+
+        // The final reply kept streaming after the first scan
+        let mut grown = norm_msg(1, 200);
+        grown.content = "msg-1 and the rest of the reply".into();
+        let conv2 = norm_conv(Some("ext"), vec![norm_msg(0, 100), grown]);
+        persist::persist_conversation(&mut storage, &mut index, &conv2).unwrap();
+        index.commit().unwrap();
+
+        let reader = index.reader().unwrap();
+        reader.reload().unwrap();
+        assert_eq!(reader.searcher().num_docs(), 2);
+        let rows: Vec<(i64, String)> = storage
+            .raw()
+            .prepare("SELECT idx, content FROM messages ORDER BY idx")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (0, "msg-0".to_string()),
+                (1, "msg-1 and the rest of the reply".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn connector_filters_apply_only_and_skip() {
+        let mut opts = IndexOptions::new(PathBuf::from("db"), PathBuf::from("data"));
+        assert!(opts.connector_enabled("codex"));
+        assert!(!opts.scan_filtered());
+
+        opts.only = Some(["claude".to_string(), "codex".to_string()].into());
+        assert!(opts.connector_enabled("codex"));
+        assert!(!opts.connector_enabled("cursor"));
+
+        opts.skip.insert("codex".to_string());
+        assert!(!opts.connector_enabled("codex"));
+        assert!(opts.connector_enabled("claude"));
+        assert!(opts.scan_filtered());
+    }
+
+    #[test]
+    fn path_globs_filter_the_scan_and_must_compile() {
+        let mut opts = IndexOptions::new(PathBuf::from("db"), PathBuf::from("data"));
+        opts.exclude_globs = vec!["**/archive/**".to_string()];
+        assert!(opts.scan_filtered());
+        assert_eq!(opts.path_filters().unwrap().1.len(), 1);
+
+        opts.include_globs = vec!["[".to_string()];
+        let err = opts.path_filters().unwrap_err().to_string();
+        assert!(err.contains("invalid glob '['"), "{err}");
+    }
+
+    #[test]
+    fn watch_timings_require_max_wait_at_least_debounce() {
+        let mut opts = IndexOptions {
+            watch: true,
+            ..IndexOptions::new(PathBuf::from("db"), PathBuf::from("data"))
+        };
+        let (debounce, max_wait) = opts.watch_timings().unwrap();
+        assert_eq!(debounce, Duration::from_secs(2));
+        assert_eq!(max_wait, Duration::from_secs(5));
+
+        opts.watch_debounce_ms = 8_000;
+        assert!(opts.watch_timings().is_err());
+
+        opts.watch_max_wait_ms = 8_000;
+        assert!(opts.watch_timings().is_ok());
+    }
+
+    #[test]
+    fn watch_sources_flushes_and_returns_after_idle_timeout() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().to_path_buf();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+
+        let touched = root.join("rollout-1.jsonl");
+        let writer = {
+            let touched = touched.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(200));
+                std::fs::write(&touched, "{}\n").unwrap();
+            })
+        };
+
+        let started = Instant::now();
+        watch_sources(
+            None,
+            vec![(ConnectorKind::Codex, root)],
+            None,
+            Duration::from_millis(50),
+            Duration::from_millis(200),
+            Some(Duration::from_millis(800)),
+            move |paths, _, _| sink.lock().unwrap().extend(paths),
+        )
+        .unwrap();
+        writer.join().unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(10));
+        let seen = seen.lock().unwrap();
+        assert!(seen.iter().any(|p| p.ends_with("rollout-1.jsonl")));
+    }
+
+    #[test]
+    fn connector_slugs_match_factories() {
+        let slugs = connector_slugs();
+        assert_eq!(slugs.len(), get_connector_factories().len());
+        for slug in slugs {
+            assert!(ConnectorKind::from_slug(slug).is_some(), "{slug}");
+        }
+    }
+
     #[test]
     fn classify_paths_uses_latest_mtime_per_connector() {
         let tmp = TempDir::new().unwrap();
@@ -1506,15 +2516,7 @@ mod tests {
         )
         .unwrap();
 
-        let opts = super::IndexOptions {
-            full: false,
-            watch: false,
-            force_rebuild: false,
-            db_path: data_dir.join("agent_search.db"),
-            data_dir: data_dir.clone(),
-            progress: None,
-            watch_once_paths: None,
-        };
+        let opts = super::IndexOptions::new(data_dir.join("agent_search.db"), data_dir.clone());
 
         // Manually set up dependencies for reindex_paths
         let storage = SqliteStorage::open(&opts.db_path).unwrap();
@@ -1555,6 +2557,79 @@ mod tests {
         }
     }
 
+    #[test]
+    #[serial]
+    fn reindex_one_ingests_single_file_and_updates_watch_state() {
+        let tmp = TempDir::new().unwrap();
+        let xdg = tmp.path().join("xdg_reindex_one");
+        std::fs::create_dir_all(&xdg).unwrap();
+        let prev = dotenvy::var("XDG_DATA_HOME").ok();
+        unsafe { std::env::set_var("XDG_DATA_HOME", &xdg) };
+
+        let data_dir = xdg.join("amp");
+        let amp_dir = data_dir.join("amp");
+        std::fs::create_dir_all(&amp_dir).unwrap();
+        let amp_file = amp_dir.join("thread-003.json");
+        std::fs::write(
+            &amp_file,
+            r#"{
+  "id": "thread-003",
+  "title": "Amp reindex-one",
+  "messages": [
+    {"role":"user","text":"hi","createdAt":1700000000100},
+    {"role":"assistant","text":"hello","createdAt":1700000000200}
+  ]
+}"#,
+        )
+        .unwrap();
+
+        let opts = super::IndexOptions {
+            only: Some(HashSet::from(["amp".to_string()])),
+            ..super::IndexOptions::new(data_dir.join("agent_search.db"), data_dir.clone())
+        };
+
+        let report = reindex_one(&opts, &amp_file).unwrap();
+        assert_eq!(report.connector, "amp");
+        assert_eq!(report.conversations, 1);
+        assert_eq!(report.conversations_updated, 1);
+        assert_eq!(report.messages_added, 2);
+        assert!(load_watch_state(&data_dir).contains_key(&ConnectorKind::Amp));
+
+        // Re-running on an unchanged file finds nothing new.
+        let again = reindex_one(&opts, &amp_file).unwrap();
+        assert_eq!(again.conversations, 1);
+        assert_eq!(again.messages_added, 0);
+
+        // A file that parses to nothing is reported instead of advancing the watch state.
+        let state_before = load_watch_state(&data_dir);
+        let empty = amp_dir.join("thread-004.json");
+        std::fs::write(&empty, "{}").unwrap();
+        assert!(reindex_one(&opts, &empty).is_err());
+        assert_eq!(load_watch_state(&data_dir), state_before);
+
+        let outside = tmp.path().join("elsewhere.json");
+        std::fs::write(&outside, "{}").unwrap();
+        assert!(reindex_one(&opts, &outside).is_err());
+
+        // A stale index is left for `cass index` to rebuild instead of being recreated.
+        let hash_path = index_dir(&data_dir).unwrap().join("schema_hash.json");
+        std::fs::write(&hash_path, r#"{"schema_hash":"old"}"#).unwrap();
+        let err = reindex_one(&opts, &amp_file).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<IndexError>(),
+            Some(IndexError::StaleIndex {
+                reason: RebuildReason::SchemaChanged
+            })
+        ));
+        assert!(std::fs::read_to_string(&hash_path).unwrap().contains("old"));
+
+        if let Some(prev) = prev {
+            unsafe { std::env::set_var("XDG_DATA_HOME", prev) };
+        } else {
+            unsafe { std::env::remove_var("XDG_DATA_HOME") };
+        }
+    }
+
     fn ensure_fts_schema(conn: &Connection) {
         let mut stmt = conn
             .prepare("PRAGMA table_info(fts_messages)")
@@ -1584,6 +2659,131 @@ CREATE VIRTUAL TABLE fts_messages USING fts5(
         }
     }
 
+    #[test]
+    #[serial]
+    fn run_index_emits_progress_events_in_order() {
+        let tmp = TempDir::new().unwrap();
+        let xdg = tmp.path().join("xdg_events");
+        std::fs::create_dir_all(&xdg).unwrap();
+        let prev = dotenvy::var("XDG_DATA_HOME").ok();
+        unsafe { std::env::set_var("XDG_DATA_HOME", &xdg) };
+
+        let data_dir = xdg.join("amp");
+        let amp_dir = data_dir.join("amp");
+        std::fs::create_dir_all(&amp_dir).unwrap();
+        std::fs::write(
+            amp_dir.join("thread-events.json"),
+            r#"{"id":"te","messages":[{"role":"user","text":"hi","createdAt":1700000000100}]}"#,
+        )
+        .unwrap();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let spans = Arc::new(Mutex::new(Vec::new()));
+        let span_sink = spans.clone();
+        let opts = IndexOptions {
+            full: true,
+            progress: Some(Arc::new(IndexingProgress::default())),
+            only: Some(["amp".to_string()].into()),
+            on_progress: Some(Arc::new(move |ev| sink.lock().unwrap().push(ev))),
+            on_span: Some(Arc::new(move |span| span_sink.lock().unwrap().push(span))),
+            ..IndexOptions::new(data_dir.join("db.sqlite"), data_dir.clone())
+        };
+        run_index(opts, None).unwrap();
+
+        let spans: Vec<_> = spans
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|s| (s.phase, s.connector.clone(), s.conversations))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("scan", Some("amp".to_string()), 1),
+                ("ingest", Some("amp".to_string()), 1),
+                ("commit", None, 1),
+            ]
+        );
+
+        let events = events.lock().unwrap().clone();
+        assert_eq!(
+            events,
+            vec![
+                ProgressEvent::ScanStarted,
+                ProgressEvent::PhaseChanged(1),
+                ProgressEvent::AgentDiscovered("amp".to_string()),
+                ProgressEvent::PhaseChanged(2),
+                ProgressEvent::BatchIngested {
+                    connector: "amp".to_string(),
+                    count: 1,
+                },
+                ProgressEvent::PhaseChanged(0),
+                ProgressEvent::Done,
+            ]
+        );
+
+        if let Some(prev) = prev {
+            unsafe { std::env::set_var("XDG_DATA_HOME", prev) };
+        } else {
+            unsafe { std::env::remove_var("XDG_DATA_HOME") };
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn run_index_splits_scans_into_configured_batches() {
+        let tmp = TempDir::new().unwrap();
+        let xdg = tmp.path().join("xdg_batches");
+        std::fs::create_dir_all(&xdg).unwrap();
+        let prev = dotenvy::var("XDG_DATA_HOME").ok();
+        unsafe { std::env::set_var("XDG_DATA_HOME", &xdg) };
+
+        let data_dir = xdg.join("amp");
+        let amp_dir = data_dir.join("amp");
+        std::fs::create_dir_all(&amp_dir).unwrap();
+        for id in ["tb1", "tb2", "tb3"] {
+            std::fs::write(
+                amp_dir.join(format!("thread-{id}.json")),
+                format!(
+                    r#"{{"id":"{id}","messages":[{{"role":"user","text":"hi","createdAt":1700000000100}}]}}"#
+                ),
+            )
+            .unwrap();
+        }
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let opts = IndexOptions {
+            full: true,
+            only: Some(["amp".to_string()].into()),
+            on_progress: Some(Arc::new(move |ev| sink.lock().unwrap().push(ev))),
+            batch_max_conversations: Some(2),
+            ..IndexOptions::new(data_dir.join("db.sqlite"), data_dir.clone())
+        };
+        run_index(opts, None).unwrap();
+
+        let batches: Vec<usize> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|ev| match ev {
+                ProgressEvent::BatchIngested { count, .. } => Some(*count),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(batches, vec![2, 1]);
+
+        let storage = SqliteStorage::open(&data_dir.join("db.sqlite")).unwrap();
+        assert_eq!(storage.list_conversations(10, 0).unwrap().len(), 3);
+
+        if let Some(prev) = prev {
+            unsafe { std::env::set_var("XDG_DATA_HOME", prev) };
+        } else {
+            unsafe { std::env::remove_var("XDG_DATA_HOME") };
+        }
+    }
+
     #[test]
     #[serial]
     fn reindex_paths_updates_progress() {
@@ -1601,30 +2801,17 @@ CREATE VIRTUAL TABLE fts_messages USING fts5(
         let amp_dir = data_dir.join("amp");
         std::fs::create_dir_all(&amp_dir).unwrap();
         let amp_file = amp_dir.join("thread-progress.json");
-        // Use a timestamp well in the future to avoid race with file mtime.
-        // The since_ts filter compares message.createdAt > file_mtime - 1, so if
-        // there's any delay between capturing 'now' and writing the file, the message
-        // could be filtered out. Adding 10s buffer ensures the message is always included.
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as i64
-            + 10_000;
+        // Amp never filters messages by since_ts, so a fixed timestamp is safe here.
         std::fs::write(
             &amp_file,
-            format!(r#"{{"id":"tp","messages":[{{"role":"user","text":"p","createdAt":{now}}}]}}"#),
+            r#"{"id":"tp","messages":[{"role":"user","text":"p","createdAt":1700000000100}]}"#,
         )
         .unwrap();
 
         let progress = Arc::new(super::IndexingProgress::default());
         let opts = super::IndexOptions {
-            full: false,
-            watch: false,
-            force_rebuild: false,
-            watch_once_paths: None,
-            db_path: data_dir.join("db.sqlite"),
-            data_dir: data_dir.clone(),
             progress: Some(progress.clone()),
+            ..super::IndexOptions::new(data_dir.join("db.sqlite"), data_dir.clone())
         };
 
         let storage = SqliteStorage::open(&opts.db_path).unwrap();
@@ -50,7 +50,7 @@ fn read_watch_once_paths_env() -> Option<Vec<std::path::PathBuf>> {
 )]
 pub struct Cli {
     /// Path to the `SQLite` database (defaults to platform data dir)
-    #[arg(long)]
+    #[arg(long, env = "CASS_DB_PATH")]
     pub db: Option<PathBuf>,
 
     /// Deterministic machine-first help (wide, no TUI)
@@ -61,6 +61,10 @@ pub struct Cli {
     #[arg(long)]
     pub trace_file: Option<PathBuf>,
 
+    /// Path to the remote sources config (defaults to `$CASS_SOURCES_CONFIG`, then ~/.config/cass/sources.toml)
+    #[arg(long, value_name = "PATH")]
+    pub sources: Option<PathBuf>,
+
     /// Reduce log noise (warnings and errors only)
     #[arg(long, short = 'q', default_value_t = false)]
     pub quiet: bool,
@@ -85,6 +89,10 @@ pub struct Cli {
     #[arg(long, default_value_t = false)]
     pub nowrap: bool,
 
+    /// Skip the startup update check and prompt (no network access)
+    #[arg(long, default_value_t = false)]
+    pub no_update_check: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -105,6 +113,18 @@ pub enum Commands {
         /// Override data dir (matches index --data-dir)
         #[arg(long)]
         data_dir: Option<PathBuf>,
+
+        /// Pre-fill the search bar and run this query on launch
+        #[arg(long)]
+        query: Option<String>,
+
+        /// Start with this agent filter applied (can be specified multiple times)
+        #[arg(long)]
+        agent: Vec<String>,
+
+        /// Start with this workspace filter applied (can be specified multiple times)
+        #[arg(long)]
+        workspace: Vec<String>,
     },
     /// Run indexer
     Index {
@@ -124,6 +144,71 @@ pub enum Commands {
         #[arg(long, value_delimiter = ',', num_args = 1..)]
         watch_once: Option<Vec<PathBuf>>,
 
+        /// Only run these connectors (repeatable, e.g. `--connector claude --connector codex`)
+        #[arg(long = "connector", value_name = "SLUG")]
+        connector: Vec<String>,
+
+        /// Skip these connectors (repeatable)
+        #[arg(long = "skip-connector", value_name = "SLUG")]
+        skip_connector: Vec<String>,
+
+        /// Quiet period in milliseconds before watch mode reindexes a burst of changes
+        #[arg(long, value_name = "MS", default_value_t = indexer::DEFAULT_WATCH_DEBOUNCE_MS)]
+        watch_debounce: u64,
+
+        /// Longest watch-mode delay in milliseconds while changes keep arriving (default: the larger of 5000 and --watch-debounce)
+        #[arg(long, value_name = "MS")]
+        watch_max_wait: Option<u64>,
+
+        /// Exit watch mode after SECS with no file changes (pending changes are indexed first)
+        #[arg(long, value_name = "SECS", requires = "watch")]
+        watch_timeout: Option<u64>,
+
+        /// Ingest at most N conversations per database transaction (default: a connector's whole scan)
+        #[arg(long, value_name = "N")]
+        batch_size: Option<usize>,
+
+        /// Re-parse every session file instead of reusing cached parses of unchanged files
+        #[arg(long)]
+        ignore_cache: bool,
+
+        /// Scan at most N connectors in parallel (default: one per CPU)
+        #[arg(long, value_name = "N")]
+        jobs: Option<usize>,
+
+        /// Only index session files whose absolute path matches this glob (repeatable)
+        #[arg(long = "include", value_name = "GLOB")]
+        include: Vec<String>,
+
+        /// Skip session files whose absolute path matches this glob (repeatable)
+        #[arg(long = "exclude", value_name = "GLOB")]
+        exclude: Vec<String>,
+
+        /// Skip session files larger than MB megabytes (default: no limit)
+        #[arg(long, value_name = "MB")]
+        max_file_size: Option<u64>,
+
+        /// Fail on the first session file that cannot be read or parsed instead of skipping it
+        #[arg(long)]
+        strict: bool,
+
+        /// After indexing, merge the search index down to --optimize-segments segments.
+        /// Rewrites the index on disk, so it is I/O-heavy on large indexes
+        #[arg(long)]
+        optimize: bool,
+
+        /// Segment count --optimize merges down to
+        #[arg(long, value_name = "N", default_value_t = 1, requires = "optimize")]
+        optimize_segments: usize,
+
+        /// Report detected connectors and candidate files without indexing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Print the index generation (bumped on every index commit) and exit
+        #[arg(long)]
+        print_generation: bool,
+
         /// Override data dir (index + db). Defaults to platform data dir.
         #[arg(long)]
         data_dir: Option<PathBuf>,
@@ -137,10 +222,69 @@ pub enum Commands {
         #[arg(long)]
         idempotency_key: Option<String>,
     },
-    /// Generate shell completions to stdout
+    /// Re-ingest a single session file (a manual trigger of the watch path)
+    ReindexOne {
+        /// Path to the session file
+        path: PathBuf,
+
+        /// Override data dir (index + db). Defaults to platform data dir.
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (for automation)
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove conversations from the database and search index
+    Forget {
+        /// Source path or external id of the conversation(s) to remove
+        target: String,
+
+        /// Skip the confirmation prompt (required when stdin is not a terminal)
+        #[arg(long)]
+        yes: bool,
+
+        /// Override data dir (index + db). Defaults to platform data dir.
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (for automation)
+        #[arg(long)]
+        json: bool,
+    },
+    /// Import an exported transcript (generic JSONL or markdown) as a conversation
+    Import {
+        /// Transcript file: `.jsonl` of {role, content, created_at} objects or a `.md`
+        /// transcript with `## User` / `## Assistant` sections
+        path: PathBuf,
+
+        /// Agent slug to file the conversation under (e.g. my_tool)
+        #[arg(long)]
+        agent: String,
+
+        /// Workspace the conversation belongs to
+        #[arg(long)]
+        workspace: Option<PathBuf>,
+
+        /// Override data dir (index + db). Defaults to platform data dir.
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
+        /// Output as JSON (for automation)
+        #[arg(long)]
+        json: bool,
+    },
+    /// Generate shell completions to stdout (or into a directory with --output)
     Completions {
-        #[arg(value_enum)]
-        shell: clap_complete::Shell,
+        #[arg(value_enum, required_unless_present = "all")]
+        shell: Option<clap_complete::Shell>,
+        /// Write the completion file into DIR under the shell's conventional name
+        /// (e.g. `_cass` for zsh) instead of stdout; prints the written path
+        #[arg(long, value_name = "DIR")]
+        output: Option<PathBuf>,
+        /// Write completions for every supported shell (requires --output)
+        #[arg(long, requires = "output", conflicts_with = "shell")]
+        all: bool,
     },
     /// Generate man page to stdout
     Man,
@@ -149,6 +293,9 @@ pub enum Commands {
         /// Topic to print
         #[arg(value_enum)]
         topic: RobotTopic,
+        /// Print the topic as structured JSON (with `contract_version`) instead of text
+        #[arg(long)]
+        json: bool,
     },
     /// Run a one-off search and print results to stdout
     Search {
@@ -160,6 +307,9 @@ pub enum Commands {
         /// Filter by workspace path (can be specified multiple times)
         #[arg(long)]
         workspace: Vec<String>,
+        /// Restrict to the current directory's workspace (added to any --workspace values)
+        #[arg(long)]
+        here: bool,
         /// Max results
         #[arg(long, default_value_t = 10)]
         limit: usize,
@@ -172,6 +322,9 @@ pub enum Commands {
         /// Robot output format: json (pretty), jsonl (streaming), compact (single-line)
         #[arg(long, value_enum)]
         robot_format: Option<RobotFormat>,
+        /// Print JSON on a single line (with --json; same as --robot-format compact)
+        #[arg(long)]
+        compact: bool,
         /// Include extended metadata in robot output (`elapsed_ms`, `wildcard_fallback`, `cache_stats`)
         #[arg(long)]
         robot_meta: bool,
@@ -192,8 +345,12 @@ pub enum Commands {
         #[arg(long)]
         cursor: Option<String>,
         /// Human-readable display format: table (aligned columns), lines (one-liner), markdown
-        #[arg(long, value_enum)]
+        #[arg(long, value_enum, alias = "format")]
         display: Option<DisplayFormat>,
+        /// How matched terms are marked in human output when color is off:
+        /// asterisks (default), brackets, or none. Ignored for JSON output
+        #[arg(long, value_enum, value_name = "STYLE")]
+        mark: Option<MarkStyle>,
         /// Override data dir
         #[arg(long)]
         data_dir: Option<PathBuf>,
@@ -209,17 +366,22 @@ pub enum Commands {
         /// Filter to last 7 days
         #[arg(long)]
         week: bool,
-        /// Filter to entries since ISO date (YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS)
+        /// Filter to entries since a date (YYYY-MM-DD[THH:MM:SS]) or relative time ('yesterday', '3 days ago', 'last week')
         #[arg(long)]
         since: Option<String>,
-        /// Filter to entries until ISO date
+        /// Filter to entries until a date or relative time (same formats as --since)
         #[arg(long)]
         until: Option<String>,
         /// Server-side aggregation by field(s). Comma-separated: `agent,workspace,date,match_type`
         /// Returns buckets with counts instead of full results. Use with --limit to get both.
         #[arg(long, value_delimiter = ',')]
         aggregate: Option<Vec<String>>,
-        /// Include query explanation in output (shows parsed query, index strategy, cost estimate)
+        /// Group the returned hits by workspace, agent, or day (local date). Applied after
+        /// ranking and --limit; JSON output returns `groups` instead of `hits`
+        #[arg(long, value_enum, value_name = "KEY")]
+        group_by: Option<SearchGroupBy>,
+        /// Include query explanation in output (shows parsed query, index strategy, cost estimate).
+        /// With --json, also attaches a Tantivy scoring breakdown to each returned hit
         #[arg(long)]
         explain: bool,
         /// Validate and analyze query without executing (returns explanation, estimated cost, warnings)
@@ -231,16 +393,101 @@ pub enum Commands {
         /// Highlight matching terms in output (uses **bold** markers in text, <mark> in HTML)
         #[arg(long)]
         highlight: bool,
-        /// Filter by source: 'local', 'remote', 'all', or a specific source hostname
+        /// Maximum characters per snippet fragment (default: backend-specific, ~150)
+        #[arg(long, value_name = "N")]
+        fragment_chars: Option<usize>,
+        /// Number of snippet fragments to return, joined with " … " (default: 1)
+        #[arg(long, value_name = "N", default_value_t = 1)]
+        max_fragments: usize,
+        /// Filter by source: 'local', 'remote', 'all', or a source ID (repeatable)
         #[arg(long)]
-        source: Option<String>,
+        source: Vec<String>,
+        /// Filter by origin host, e.g. user@laptop ('local' = this machine; repeatable)
+        #[arg(long)]
+        host: Vec<String>,
+        /// Only search conversations from this machine, leaving out synced remote sources
+        #[arg(long, conflicts_with_all = ["source", "host"])]
+        local_only: bool,
         /// Filter to sessions from file (one path per line). Use '-' for stdin.
         /// Enables chained searches: `cass search "query1" --robot-format sessions | cass search "query2" --sessions-from -`
         #[arg(long)]
         sessions_from: Option<String>,
+        /// Search only within the conversation stored at this source path
+        /// (pairs with `cass view <path> -n <line>` for follow-up)
+        #[arg(
+            long = "in",
+            value_name = "SOURCE_PATH",
+            conflicts_with = "sessions_from"
+        )]
+        in_session: Option<String>,
         /// Search mode: lexical (default), semantic, or hybrid
         #[arg(long, value_enum)]
         mode: Option<crate::search::query::SearchMode>,
+        /// Blend embedding (k-NN) matches into lexical results (hybrid RRF).
+        /// Falls back to lexical search with a warning if the model or vector index is missing
+        #[arg(long, conflicts_with = "mode")]
+        semantic: bool,
+        /// Blend recency into ranking: 0.0 = pure relevance, 1.0 = pure recency (clamped).
+        /// Recency decays exponentially from the newest hit (see --recency-half-life)
+        #[arg(long, value_name = "0..1")]
+        recency_weight: Option<f32>,
+        /// Half-life in days for the recency component of --recency-weight
+        #[arg(long, value_name = "DAYS", default_value_t = crate::search::query::DEFAULT_RECENCY_HALF_LIFE_DAYS)]
+        recency_half_life: f32,
+        /// Rerank the top lexical hits with a cross-encoder model (adds `rerank_score` to JSON hits).
+        /// Falls back to lexical order with a warning if the reranker model is not installed
+        #[arg(long, conflicts_with = "recency_weight")]
+        rerank: bool,
+        /// Number of top hits to rerank with --rerank
+        #[arg(long, value_name = "N", default_value_t = 50, requires = "rerank")]
+        rerank_top: usize,
+        /// Drop hits scoring below SCORE before --limit applies. Lexical scores are
+        /// unnormalized BM25, typically 1-30; hybrid scores are far smaller
+        #[arg(long, value_name = "SCORE")]
+        min_score: Option<f32>,
+        /// Drop hits scoring below PCT percent of the top hit's score (e.g. 50 keeps hits
+        /// within half of the best) before --limit applies
+        #[arg(long, value_name = "PCT")]
+        min_score_pct: Option<f32>,
+        /// Bypass the on-disk result cache shared across invocations
+        #[arg(long)]
+        no_cache: bool,
+        /// Seconds a cached result stays valid (entries are also dropped on reindex)
+        #[arg(long, value_name = "SECS", default_value_t = crate::search::result_cache::DEFAULT_RESULT_CACHE_TTL_SECS)]
+        cache_ttl: u64,
+        /// Write each hit's conversation as HTML into DIR, plus an index.html linking them
+        #[arg(long, value_name = "DIR")]
+        export_html: Option<PathBuf>,
+        /// Count all matches (lexical mode) and report `total`/`has_more` in JSON output.
+        /// Costs an extra full query; without it `total` and `has_more` are null
+        #[arg(long)]
+        with_total: bool,
+        /// Collapse hits from the same session file into its best-scoring hit, reporting
+        /// the rest as `duplicate_count` (JSON) or "(+N more in this file)" (human)
+        #[arg(long)]
+        dedupe: bool,
+        /// Return one hit per conversation (its best-scoring message) with a `message_matches`
+        /// count. --limit and --offset then count conversations rather than messages
+        #[arg(long, conflicts_with_all = ["dedupe", "with_total"])]
+        by_conversation: bool,
+        /// Exclude tool-output messages (command results, file reads) from matches
+        #[arg(long)]
+        no_tools: bool,
+        /// Return only hit metadata: `snippet` and `content` are empty and no highlighting
+        /// is done. `line_number` is still set for follow-up `cass view`
+        #[arg(long, conflicts_with = "rerank")]
+        no_snippet: bool,
+        /// Show each `source_path` relative to its conversation's workspace in human output
+        /// (absolute when outside it); JSON hits gain a `relative_path` field
+        #[arg(long)]
+        relative_paths: bool,
+        /// Time each search phase (index open, query parse, search, highlight): JSON output
+        /// gains a `timings_ms` object, human output prints the breakdown to stderr
+        #[arg(long)]
+        profile: bool,
+        /// Keep running: print new hits as the index is updated (Ctrl-C to stop). Needs a terminal
+        #[arg(long)]
+        follow: bool,
     },
     /// Show statistics about indexed data
     Stats {
@@ -250,12 +497,21 @@ pub enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Print JSON on a single line (with --json)
+        #[arg(long)]
+        compact: bool,
         /// Filter by source: 'local', 'remote', 'all', or a specific source hostname
         #[arg(long)]
         source: Option<String>,
+        /// Only count conversations from this machine (same as --source local)
+        #[arg(long, conflicts_with = "source")]
+        local_only: bool,
         /// Show breakdown by source
         #[arg(long)]
         by_source: bool,
+        /// Stable `key=value` lines for scripts (see `cass robot-docs contracts`)
+        #[arg(long, conflicts_with = "json")]
+        porcelain: bool,
     },
     /// Output diagnostic information for troubleshooting
     Diag {
@@ -319,7 +575,7 @@ pub enum Commands {
     },
     /// View a source file at a specific line (follow up on search results)
     View {
-        /// Path to the source file
+        /// Path to the source file, or `-` to read from stdin
         path: PathBuf,
         /// Line number to show (1-indexed)
         #[arg(long, short = 'n')]
@@ -331,6 +587,30 @@ pub enum Commands {
         #[arg(long)]
         json: bool,
     },
+    /// Print the normalized conversation cass indexed from a source file
+    Show {
+        /// Source path of the conversation (as shown in search results)
+        #[arg(required_unless_present = "message")]
+        path: Option<String>,
+        /// Show the message with this `message_id` (from search hits) and its neighbours
+        #[arg(long, value_name = "ID", conflicts_with = "path")]
+        message: Option<i64>,
+        /// Messages to show before and after the one picked by --message
+        #[arg(
+            long,
+            short = 'C',
+            value_name = "N",
+            default_value_t = 2,
+            requires = "message"
+        )]
+        context: usize,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Minimal health check (<50ms). Exit 0=healthy, 1=unhealthy. For agent pre-flight checks.
     Health {
         /// Override data dir
@@ -392,6 +672,12 @@ pub enum Commands {
         /// Include tool use details in export
         #[arg(long)]
         include_tools: bool,
+        /// Embed a JSON copy of the conversation in HTML output (for re-import)
+        #[arg(long)]
+        embed_json: bool,
+        /// Color theme for HTML output
+        #[arg(long, value_enum, default_value_t = crate::export::ExportTheme::Light)]
+        theme: crate::export::ExportTheme,
     },
     /// Show messages around a specific line in a session file
     Expand {
@@ -470,6 +756,9 @@ pub enum Commands {
     /// Manage semantic search models
     #[command(subcommand)]
     Models(ModelsCommand),
+    /// Show where cass keeps its database, index, logs and config
+    #[command(subcommand)]
+    Config(ConfigCommand),
 }
 
 /// Subcommands for managing remote sources (P5.x)
@@ -512,6 +801,17 @@ pub enum SourcesCommand {
         #[arg(long, short = 'y')]
         yes: bool,
     },
+    /// Probe remote sources over SSH (reachability, agents, system info, cass status)
+    Probe {
+        /// Probe only this source (defaults to all remote sources)
+        name: Option<String>,
+        /// Connection timeout per host in seconds
+        #[arg(long, default_value_t = crate::sources::probe::DEFAULT_PROBE_TIMEOUT)]
+        timeout: u64,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Diagnose source connectivity and configuration issues
     Doctor {
         /// Check only specific source (defaults to all)
@@ -523,6 +823,8 @@ pub enum SourcesCommand {
     },
     /// Synchronize sessions from remote sources
     Sync {
+        /// Sync only this source (same as --source NAME)
+        name: Option<String>,
         /// Sync only specific source(s)
         #[arg(long, short)]
         source: Option<Vec<String>>,
@@ -535,6 +837,12 @@ pub enum SourcesCommand {
         /// Dry run - show what would be synced without actually syncing
         #[arg(long)]
         dry_run: bool,
+        /// Retry rsync/SSH this many times after transient network errors (0 disables)
+        #[arg(long, default_value_t = 2)]
+        retries: u32,
+        /// Delay before the first retry in milliseconds; doubles on each further retry
+        #[arg(long, value_name = "MS", default_value_t = 1000)]
+        retry_delay: u64,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -703,6 +1011,44 @@ pub enum ModelsCommand {
     },
 }
 
+/// Subcommands for inspecting resolved file locations
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommand {
+    /// Print every resolved path
+    Show {
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print a single resolved absolute path (for scripts)
+    Path {
+        /// Which path to print
+        #[arg(value_enum)]
+        what: ConfigPathKind,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+    },
+}
+
+/// Locations reported by `cass config`
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum ConfigPathKind {
+    /// `SQLite` database (--db, `CASS_DB_PATH`)
+    Db,
+    /// Tantivy index for the current schema
+    Index,
+    /// Data dir (--data-dir, `CASS_DATA_DIR`)
+    Data,
+    /// Remote sources config (sources.toml)
+    Sources,
+    /// TUI log file prefix (rolled daily as cass.log.YYYY-MM-DD)
+    Log,
+}
+
 /// Subcommands for managing path mappings (P6.3)
 #[derive(Subcommand, Debug, Clone)]
 pub enum MappingsAction {
@@ -793,7 +1139,7 @@ pub enum RobotFormat {
 /// Human-readable display format for CLI output (non-JSON)
 #[derive(Copy, Clone, Debug, Default, ValueEnum, PartialEq, Eq)]
 pub enum DisplayFormat {
-    /// Aligned columns with headers (default human-readable)
+    /// Aligned score/agent/time/path/snippet columns sized to the terminal
     #[default]
     Table,
     /// One-liner per result with key info
@@ -802,6 +1148,18 @@ pub enum DisplayFormat {
     Markdown,
 }
 
+/// Text markers around matched terms in human search output when color is off
+#[derive(Copy, Clone, Debug, Default, ValueEnum, PartialEq, Eq)]
+pub enum MarkStyle {
+    /// No markers
+    None,
+    /// `**term**`
+    #[default]
+    Asterisks,
+    /// `[term]`
+    Brackets,
+}
+
 /// Conversation export format (for export command)
 #[derive(Copy, Clone, Debug, Default, ValueEnum, PartialEq, Eq)]
 pub enum ConvExportFormat {
@@ -828,6 +1186,17 @@ pub enum TimelineGrouping {
     None,
 }
 
+/// Grouping key for `search --group-by`
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum SearchGroupBy {
+    /// Group by workspace path
+    Workspace,
+    /// Group by agent slug
+    Agent,
+    /// Group by local date of `created_at`
+    Day,
+}
+
 /// Aggregation field types for --aggregate flag
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AggregateField {
@@ -995,6 +1364,7 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
         "offset",
         "agent",
         "workspace",
+        "here",
         "fields",
         "max-tokens",
         "request-id",
@@ -1013,23 +1383,70 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
         "progress",
         "wrap",
         "nowrap",
+        "no-update-check",
         "db",
         "trace-file",
+        "sources",
         "robot-help",
         "robot-docs",
         "help",
         "version",
         "force",
         "dry-run",
+        "print-generation",
         "no-cache",
+        "cache-ttl",
         "source",
+        "host",
+        "local-only",
         "sessions-from",
+        "in",
         "mode",
+        "semantic",
+        "recency-weight",
+        "recency-half-life",
+        "rerank",
+        "rerank-top",
+        "min-score",
+        "min-score-pct",
         "highlight",
+        "fragment-chars",
+        "max-fragments",
         "timeout",
         "explain",
         "aggregate",
         "display",
+        "format",
+        "mark",
+        "connector",
+        "skip-connector",
+        "watch-debounce",
+        "watch-max-wait",
+        "watch-timeout",
+        "batch-size",
+        "ignore-cache",
+        "jobs",
+        "include",
+        "exclude",
+        "max-file-size",
+        "strict",
+        "optimize",
+        "optimize-segments",
+        "compact",
+        "group-by",
+        "export-html",
+        "output",
+        "all",
+        "with-total",
+        "dedupe",
+        "by-conversation",
+        "no-tools",
+        "no-snippet",
+        "relative-paths",
+        "profile",
+        "follow",
+        "message",
+        "yes",
     ];
 
     // Subcommand aliases for common mistakes
@@ -1053,7 +1470,6 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
         ("idx", "index"),
         ("rebuild", "index"),
         // View aliases
-        ("show", "view"),
         ("get", "view"),
         ("read", "view"),
         // Diag aliases
@@ -1080,7 +1496,7 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
     let global_with_value = |s: &str| {
         matches!(
             s,
-            "--color" | "--progress" | "--wrap" | "--db" | "--trace-file"
+            "--color" | "--progress" | "--wrap" | "--db" | "--trace-file" | "--sources"
         )
     };
 
@@ -1094,6 +1510,7 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
             || s == "--wrap"
             || s.starts_with("--wrap=")
             || s == "--nowrap"
+            || s == "--no-update-check"
             || s == "--db"
             || s.starts_with("--db=")
             || s == "--quiet"
@@ -1102,6 +1519,8 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
             || s == "-v"
             || s == "--trace-file"
             || s.starts_with("--trace-file=")
+            || s == "--sources"
+            || s.starts_with("--sources=")
             || s == "--robot-help"
     };
 
@@ -1531,6 +1950,8 @@ fn heuristic_parse_recovery(
         "status",
         "diag",
         "view",
+        "show",
+        "config",
         "capabilities",
         "introspect",
         "robot-docs",
@@ -1773,15 +2194,26 @@ async fn execute_cli(
         once: false,
         reset_state: false,
         data_dir: None,
+        query: None,
+        agent: Vec::new(),
+        workspace: Vec::new(),
     });
 
+    if let Some(path) = &cli.sources {
+        crate::sources::config::set_config_path_override(path.clone());
+    }
+
     if cli.robot_help {
         print_robot_help(wrap)?;
         return Ok(());
     }
 
-    if let Commands::RobotDocs { topic } = command.clone() {
-        print_robot_docs(topic, wrap)?;
+    if let Commands::RobotDocs { topic, json } = command.clone() {
+        if json {
+            print_robot_docs_json(topic)?;
+        } else {
+            print_robot_docs(topic, wrap)?;
+        }
         return Ok(());
     }
 
@@ -1815,10 +2247,17 @@ async fn execute_cli(
     match &command {
         Commands::Tui { data_dir, .. } => {
             let log_dir = data_dir.clone().unwrap_or_else(default_data_dir);
-            std::fs::create_dir_all(&log_dir).ok();
-
-            let file_appender = tracing_appender::rolling::daily(&log_dir, "cass.log");
-            let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
+            // On a read-only data dir the TUI can still search, but there is
+            // nowhere to write cass.log or the index, so log to stderr instead
+            // and skip background indexing.
+            let writable = crate::storage::sqlite::dir_is_writable(&log_dir);
+            let (non_blocking, _guard) = if writable {
+                std::fs::create_dir_all(&log_dir).ok();
+                let file_appender = tracing_appender::rolling::daily(&log_dir, "cass.log");
+                tracing_appender::non_blocking(file_appender)
+            } else {
+                tracing_appender::non_blocking(std::io::stderr())
+            };
 
             tracing_subscriber::registry()
                 .with(filter)
@@ -1830,63 +2269,104 @@ async fn execute_cli(
                         .with_ansi(false),
                 )
                 .init();
+            if !writable {
+                warn!(
+                    data_dir = %log_dir.display(),
+                    "data directory is read-only; logging to stderr and not indexing"
+                );
+            }
 
-            maybe_prompt_for_update(matches!(command, Commands::Tui { once: true, .. }))
-                .await
-                .map_err(|e| CliError {
-                    code: 9,
-                    kind: "update-check",
-                    message: format!("update check failed: {e}"),
-                    hint: None,
-                    retryable: false,
-                })?;
+            maybe_prompt_for_update(
+                matches!(command, Commands::Tui { once: true, .. }),
+                cli.no_update_check,
+            )
+            .await
+            .map_err(|e| CliError {
+                code: 9,
+                kind: "update-check",
+                message: format!("update check failed: {e}"),
+                hint: None,
+                retryable: false,
+            })?;
 
             if let Commands::Tui {
                 once: false,
                 reset_state,
                 data_dir,
-                ..
+                query,
+                agent,
+                workspace,
             } = command.clone()
             {
                 let bg_data_dir = log_dir.clone();
                 let bg_db = cli.db.clone();
                 // Create shared progress tracker
                 let progress = std::sync::Arc::new(indexer::IndexingProgress::default());
-                spawn_background_indexer(bg_data_dir, bg_db, Some(progress.clone()));
+                if writable {
+                    spawn_background_indexer(bg_data_dir, bg_db, Some(progress.clone()));
+                }
 
-                ui::tui::run_tui(data_dir, false, reset_state, Some(progress), None).map_err(
-                    |e| CliError {
-                        code: 9,
-                        kind: "tui",
-                        message: format!("tui failed: {e}"),
-                        hint: None,
-                        retryable: false,
+                ui::tui::run_tui(
+                    data_dir,
+                    false,
+                    reset_state,
+                    Some(progress),
+                    None,
+                    !cli.no_update_check,
+                    ui::tui::InitialSearch {
+                        query,
+                        agents: agent,
+                        workspaces: workspace,
                     },
-                )?;
+                )
+                .map_err(|e| CliError {
+                    code: 9,
+                    kind: "tui",
+                    message: format!("tui failed: {e}"),
+                    hint: None,
+                    retryable: false,
+                })?;
             } else if let Commands::Tui {
                 once,
                 reset_state,
                 data_dir,
-                ..
+                query,
+                agent,
+                workspace,
             } = command.clone()
             {
-                ui::tui::run_tui(data_dir, once, reset_state, None, None).map_err(|e| {
-                    CliError {
-                        code: 9,
-                        kind: "tui",
-                        message: format!("tui failed: {e}"),
-                        hint: None,
-                        retryable: false,
-                    }
+                ui::tui::run_tui(
+                    data_dir,
+                    once,
+                    reset_state,
+                    None,
+                    None,
+                    !cli.no_update_check,
+                    ui::tui::InitialSearch {
+                        query,
+                        agents: agent,
+                        workspaces: workspace,
+                    },
+                )
+                .map_err(|e| CliError {
+                    code: 9,
+                    kind: "tui",
+                    message: format!("tui failed: {e}"),
+                    hint: None,
+                    retryable: false,
                 })?;
             }
         }
         Commands::Index { .. }
+        | Commands::ReindexOne { .. }
+        | Commands::Forget { .. }
+        | Commands::Import { .. }
         | Commands::Search { .. }
         | Commands::Stats { .. }
         | Commands::Diag { .. }
         | Commands::Status { .. }
-        | Commands::View { .. } => {
+        | Commands::View { .. }
+        | Commands::Show { .. } => {
             tracing_subscriber::fmt()
                 .with_env_filter(filter)
                 .with_writer(std::io::stderr)
@@ -1904,30 +2384,101 @@ async fn execute_cli(
                     force_rebuild,
                     watch,
                     watch_once,
+                    connector,
+                    skip_connector,
+                    watch_debounce,
+                    watch_max_wait,
+                    watch_timeout,
+                    batch_size,
+                    ignore_cache,
+                    jobs,
+                    include,
+                    exclude,
+                    max_file_size,
+                    strict,
+                    optimize,
+                    optimize_segments,
+                    dry_run,
+                    print_generation,
                     data_dir,
                     json,
                     idempotency_key,
                 } => {
-                    run_index_with_data(
-                        cli.db.clone(),
-                        full,
-                        force_rebuild,
-                        watch,
-                        watch_once,
-                        data_dir,
-                        progress,
-                        json,
-                        idempotency_key,
-                    )?;
+                    if print_generation {
+                        run_print_index_generation(data_dir, cli.db.clone())?;
+                    } else if dry_run {
+                        run_index_dry_run(
+                            cli.db.clone(),
+                            full,
+                            force_rebuild,
+                            connector,
+                            skip_connector,
+                            data_dir,
+                            json,
+                        )?;
+                    } else {
+                        run_index_with_data(
+                            cli.db.clone(),
+                            full,
+                            force_rebuild,
+                            watch,
+                            watch_once,
+                            connector,
+                            skip_connector,
+                            watch_debounce,
+                            watch_max_wait,
+                            watch_timeout,
+                            batch_size,
+                            ignore_cache,
+                            jobs,
+                            include,
+                            exclude,
+                            max_file_size,
+                            strict,
+                            optimize,
+                            optimize_segments,
+                            data_dir,
+                            cli.trace_file.clone(),
+                            progress,
+                            json,
+                            idempotency_key,
+                        )?;
+                    }
+                }
+                Commands::ReindexOne {
+                    path,
+                    data_dir,
+                    json,
+                } => {
+                    run_reindex_one(&path, data_dir, cli.db.clone(), json)?;
+                }
+                Commands::Forget {
+                    target,
+                    yes,
+                    data_dir,
+                    json,
+                } => {
+                    run_forget(&target, yes, data_dir, cli.db.clone(), json)?;
+                }
+                Commands::Import {
+                    path,
+                    agent,
+                    workspace,
+                    data_dir,
+                    json,
+                } => {
+                    run_import(&path, &agent, workspace, data_dir, cli.db.clone(), json)?;
                 }
                 Commands::Search {
                     query,
                     agent,
-                    workspace,
+                    mut workspace,
+                    here,
                     limit,
                     offset,
                     json,
                     robot_format,
+                    compact,
                     robot_meta,
                     fields,
                     max_content_length,
@@ -1935,6 +2486,7 @@ async fn execute_cli(
                     request_id,
                     cursor,
                     display,
+                    mark,
                     data_dir,
                     days,
                     today,
@@ -1943,14 +2495,41 @@ async fn execute_cli(
                     since,
                     until,
                     aggregate,
+                    group_by,
                     explain,
                     dry_run,
                     timeout,
                     highlight,
+                    fragment_chars,
+                    max_fragments,
                     source,
+                    host,
+                    local_only,
                     sessions_from,
+                    in_session,
                     mode,
+                    semantic,
+                    recency_weight,
+                    recency_half_life,
+                    rerank,
+                    rerank_top,
+                    min_score,
+                    min_score_pct,
+                    no_cache,
+                    cache_ttl,
+                    export_html,
+                    with_total,
+                    dedupe,
+                    by_conversation,
+                    no_tools,
+                    no_snippet,
+                    relative_paths,
+                    profile,
+                    follow,
                 } => {
+                    if here {
+                        workspace.extend(cwd_workspaces()?);
+                    }
                     run_cli_search(
                         &query,
                         &agent,
@@ -1958,7 +2537,7 @@ async fn execute_cli(
                         &limit,
                         &offset,
                         &json,
-                        robot_format,
+                        compact_robot_format(json, robot_format, compact),
                         robot_meta,
                         fields,
                         max_content_length,
@@ -1966,6 +2545,7 @@ async fn execute_cli(
                         request_id.clone(),
                         cursor.clone(),
                         display,
+                        mark,
                         &data_dir,
                         cli.db.clone(),
                         wrap,
@@ -1978,29 +2558,65 @@ async fn execute_cli(
                             week,
                             since.as_deref(),
                             until.as_deref(),
-                        ),
+                        )?,
                         aggregate,
+                        group_by,
                         explain,
                         dry_run,
                         timeout,
                         highlight,
-                        source,
+                        crate::search::query::HighlightOptions {
+                            fragment_chars,
+                            max_fragments,
+                        },
+                        if local_only {
+                            vec![crate::sources::provenance::LOCAL_SOURCE_ID.to_string()]
+                        } else {
+                            source
+                        },
+                        host,
                         sessions_from,
+                        in_session,
                         mode,
+                        semantic,
+                        recency_weight.map(|w| (w, recency_half_life)),
+                        rerank.then_some(rerank_top),
+                        min_score,
+                        min_score_pct,
+                        (!no_cache).then_some(Duration::from_secs(cache_ttl)),
+                        export_html,
+                        with_total,
+                        dedupe,
+                        by_conversation,
+                        no_tools,
+                        no_snippet,
+                        relative_paths,
+                        profile,
+                        follow,
                     )?;
                 }
                 Commands::Stats {
                     data_dir,
                     json,
+                    compact,
                     source,
+                    local_only,
                     by_source,
+                    porcelain,
                 } => {
+                    let source = if local_only {
+                        Some(crate::sources::provenance::LOCAL_SOURCE_ID)
+                    } else {
+                        source.as_deref()
+                    };
                     run_stats(
                         &data_dir,
                         cli.db.clone(),
                         json,
-                        source.as_deref(),
+                        compact,
+                        source,
                         by_source,
+                        porcelain,
                     )?;
                 }
                 Commands::Diag {
@@ -2026,6 +2642,21 @@ async fn execute_cli(
                 } => {
                     run_view(&path, line, context, json || robot_mode)?;
                 }
+                Commands::Show {
+                    path,
+                    message,
+                    context,
+                    data_dir,
+                    json,
+                } => {
+                    run_show(
+                        path.as_deref(),
+                        message.map(|id| (id, context)),
+                        &data_dir,
+                        cli.db.clone(),
+                        json || robot_mode,
+                    )?;
+                }
                 Commands::Pages {
                     export_only,
                     agents,
@@ -2081,9 +2712,8 @@ async fn execute_cli(
                 .init();
 
             match command {
-                Commands::Completions { shell } => {
-                    let mut cmd = Cli::command();
-                    clap_complete::generate(shell, &mut cmd, "cass", &mut std::io::stdout());
+                Commands::Completions { shell, output, all } => {
+                    run_completions(shell, output, all)?;
                 }
                 Commands::Man => {
                     let cmd = Cli::command();
@@ -2138,8 +2768,17 @@ async fn execute_cli(
                     format,
                     output,
                     include_tools,
+                    embed_json,
+                    theme,
                 } => {
-                    run_export(&path, format, output.as_deref(), include_tools)?;
+                    run_export(
+                        &path,
+                        format,
+                        output.as_deref(),
+                        include_tools,
+                        embed_json,
+                        theme,
+                    )?;
                 }
                 Commands::Expand {
                     path,
@@ -2177,6 +2816,9 @@ async fn execute_cli(
                 Commands::Models(subcmd) => {
                     run_models_command(subcmd)?;
                 }
+                Commands::Config(subcmd) => {
+                    run_config_command(subcmd, cli.db.clone())?;
+                }
                 _ => {}
             }
         }
@@ -2319,18 +2961,22 @@ fn describe_command(cli: &Cli) -> String {
     match &cli.command {
         Some(Commands::Tui { .. }) => "tui".to_string(),
         Some(Commands::Index { .. }) => "index".to_string(),
+        Some(Commands::ReindexOne { .. }) => "reindex-one".to_string(),
+        Some(Commands::Forget { .. }) => "forget".to_string(),
+        Some(Commands::Import { .. }) => "import".to_string(),
         Some(Commands::Search { .. }) => "search".to_string(),
         Some(Commands::Stats { .. }) => "stats".to_string(),
         Some(Commands::Diag { .. }) => "diag".to_string(),
         Some(Commands::Status { .. }) => "status".to_string(),
         Some(Commands::View { .. }) => "view".to_string(),
+        Some(Commands::Show { .. }) => "show".to_string(),
         Some(Commands::Completions { .. }) => "completions".to_string(),
         Some(Commands::Man) => "man".to_string(),
         Some(Commands::Capabilities { .. }) => "capabilities".to_string(),
         Some(Commands::ApiVersion { .. }) => "api-version".to_string(),
         Some(Commands::State { .. }) => "state".to_string(),
         Some(Commands::Introspect { .. }) => "introspect".to_string(),
-        Some(Commands::RobotDocs { topic }) => format!("robot-docs:{topic:?}"),
+        Some(Commands::RobotDocs { topic, .. }) => format!("robot-docs:{topic:?}"),
         Some(Commands::Health { .. }) => "health".to_string(),
         Some(Commands::Doctor { .. }) => "doctor".to_string(),
         Some(Commands::Context { .. }) => "context".to_string(),
@@ -2339,6 +2985,7 @@ fn describe_command(cli: &Cli) -> String {
         Some(Commands::Timeline { .. }) => "timeline".to_string(),
         Some(Commands::Sources(..)) => "sources".to_string(),
         Some(Commands::Models(..)) => "models".to_string(),
+        Some(Commands::Config(..)) => "config".to_string(),
         Some(Commands::Pages { .. }) => "pages".to_string(),
         None => "(default)".to_string(),
     }
@@ -2355,7 +3002,12 @@ fn is_robot_mode(command: &Commands) -> bool {
             ..
         } => *json || robot_format.is_some() || *robot_meta,
         Commands::Index { json, .. } => *json,
-        Commands::Stats { json, .. } => *json,
+        Commands::ReindexOne { json, .. } => *json,
+        Commands::Forget { json, .. } => *json,
+        Commands::Import { json, .. } => *json,
+        Commands::Stats {
+            json, porcelain, ..
+        } => *json || *porcelain,
         Commands::Diag { json, .. } => *json,
         Commands::Status { json, .. } => *json,
         Commands::Health { json, .. } => *json,
@@ -2363,13 +3015,38 @@ fn is_robot_mode(command: &Commands) -> bool {
         Commands::ApiVersion { json, .. } => *json,
         Commands::State { json, .. } => *json,
         Commands::View { json, .. } => *json,
+        Commands::Show { json, .. } => *json,
         Commands::Capabilities { json, .. } => *json,
         Commands::Introspect { json, .. } => *json,
         Commands::Context { json, .. } => *json,
+        Commands::Config(ConfigCommand::Show { json, .. }) => *json,
         _ => false,
     }
 }
 
+/// Whether human output may use OSC-8 hyperlinks: stdout must be a terminal
+/// with color enabled (`--color`, `NO_COLOR`). Set CASS_HYPERLINKS=0 to opt out.
+fn hyperlinks_enabled() -> bool {
+    std::io::stdout().is_terminal()
+        && colored::control::SHOULD_COLORIZE.should_colorize()
+        && dotenvy::var("CASS_HYPERLINKS")
+            .map(|v| v != "0" && v.to_lowercase() != "false")
+            .unwrap_or(true)
+}
+
+/// Show `label` wrapped in an OSC-8 escape linking to `file://<abs_path>` of
+/// `path`; terminals without OSC-8 support show the plain label.
+fn file_hyperlink(path: &str, label: &str) -> String {
+    let abs = std::path::absolute(path).unwrap_or_else(|_| PathBuf::from(path));
+    let abs = abs.to_string_lossy().replace('\\', "/");
+    let encoded: Vec<_> = abs.split('/').map(urlencoding::encode).collect();
+    let sep = if abs.starts_with('/') { "" } else { "/" };
+    format!(
+        "\x1b]8;;file://{sep}{}\x1b\\{label}\x1b]8;;\x1b\\",
+        encoded.join("/")
+    )
+}
+
 fn apply_wrap(line: &str, wrap: WrapConfig) -> String {
     let width = wrap.effective_width();
     if line.trim().is_empty() || width.is_none() {
@@ -2608,57 +3285,174 @@ fn print_robot_help(wrap: WrapConfig) -> CliResult<()> {
     Ok(())
 }
 
+/// Environment variables listed by `robot-docs env`, with what each one does.
+const ROBOT_ENV_VARS: &[(&str, &str)] = &[
+    (
+        "CODING_AGENT_SEARCH_NO_UPDATE_PROMPT=1",
+        "skip update prompt",
+    ),
+    ("TUI_HEADLESS=1", "skip update prompt"),
+    ("CASS_DATA_DIR", "override data dir"),
+    ("CASS_DB_PATH", "override db path"),
+    ("NO_COLOR / CASS_NO_COLOR", "disable color"),
+    (
+        "CASS_MAX_LIMIT",
+        "ceiling for search --limit (default 10000)",
+    ),
+    (
+        "CASS_SQLITE_SYNCHRONOUS / _JOURNAL_MODE / _CACHE_MB",
+        "SQLite pragmas for `cass index` only",
+    ),
+    ("CASS_HYPERLINKS=0", "no clickable file:// paths (TTY only)"),
+    ("CASS_TRACE_FILE", "default trace path"),
+];
+
+/// Process exit codes and their meaning, as documented by `robot-docs exit-codes`.
+const EXIT_CODES: &[(u8, &str)] = &[
+    (0, "ok"),
+    (2, "usage"),
+    (3, "missing index/db"),
+    (4, "network"),
+    (5, "data-corrupt"),
+    (6, "incompatible-version"),
+    (7, "lock/busy, or data dir not writable"),
+    (8, "partial"),
+    (9, "unknown"),
+];
+
 fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
-    let lines: Vec<String> = match topic {
-        RobotTopic::Commands => vec![
-            "commands:".to_string(),
-            "  (global) --quiet / -q  Suppress info logs (auto-enabled in robot mode)".to_string(),
-            "  (global) --verbose/-v  Enable debug logs (overrides auto-quiet)".to_string(),
-            "  Tip: `--robot-docs=<topic>` is normalized to `robot-docs <topic>`; globals can appear before/after subcommands.".to_string(),
-            "  cass search <query> [OPTIONS]".to_string(),
-            "    --agent A         Filter by agent (codex, claude_code, gemini, opencode, amp, cline)".to_string(),
-            "    --workspace W     Filter by workspace path".to_string(),
-            "    --limit N         Max results (default: 10)".to_string(),
-            "    --offset N        Pagination offset (default: 0)".to_string(),
-            "    --json | --robot  JSON output for automation".to_string(),
-            "    --fields F1,F2    Select specific fields in hits (reduces token usage)".to_string(),
-            "                      Presets: minimal (path,line,agent), summary (+title,score), provenance (source_id,origin_kind,origin_host)".to_string(),
-            "                      Fields: score,agent,workspace,source_path,snippet,content,title,created_at,line_number,match_type,source_id,origin_kind,origin_host".to_string(),
-            "    --max-content-length N  Truncate content/snippet/title to N chars (UTF-8 safe, adds '...')".to_string(),
-            "                            Adds *_truncated: true indicator for each truncated field".to_string(),
-            "    --today           Filter to today only".to_string(),
-            "    --yesterday       Filter to yesterday only".to_string(),
-            "    --week            Filter to last 7 days".to_string(),
-            "    --days N          Filter to last N days".to_string(),
+    println!("{}", render_block(&robot_docs_lines(topic), wrap));
+    Ok(())
+}
+
+/// `robot-docs --json`: the topic as structured data inside a versioned envelope.
+/// Topics that are prose (guide, examples, ...) carry their text lines.
+fn print_robot_docs_json(topic: RobotTopic) -> CliResult<()> {
+    use serde_json::{Value, json};
+
+    let data = match topic {
+        RobotTopic::Commands => json!({
+            "global_flags": build_global_flag_schemas(),
+            "commands": build_command_schemas(),
+        }),
+        RobotTopic::Env => Value::Object(
+            ROBOT_ENV_VARS
+                .iter()
+                .map(|(name, meaning)| (name.to_string(), json!(meaning)))
+                .collect(),
+        ),
+        RobotTopic::ExitCodes => Value::Object(
+            EXIT_CODES
+                .iter()
+                .map(|(code, meaning)| (code.to_string(), json!(meaning)))
+                .collect(),
+        ),
+        RobotTopic::Paths => Value::Object(
+            config_paths(None, None)
+                .into_iter()
+                .map(|(name, path)| {
+                    let path = path.map(|p| p.display().to_string());
+                    (name.to_string(), json!(path))
+                })
+                .collect(),
+        ),
+        RobotTopic::Schemas => {
+            let schemas: std::collections::BTreeMap<_, _> =
+                build_response_schemas().into_iter().collect();
+            json!(schemas)
+        }
+        RobotTopic::Guide
+        | RobotTopic::Examples
+        | RobotTopic::Contracts
+        | RobotTopic::Wrap
+        | RobotTopic::Sources => {
+            // Drop the "<topic>:" heading; the envelope names the topic.
+            let lines: Vec<String> = robot_docs_lines(topic).into_iter().skip(1).collect();
+            json!({ "lines": lines })
+        }
+    };
+    let topic_name = topic
+        .to_possible_value()
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default();
+    let payload = json!({
+        "contract_version": CONTRACT_VERSION,
+        "topic": topic_name,
+        "data": data,
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&payload).unwrap_or_default()
+    );
+    Ok(())
+}
+
+fn robot_docs_lines(topic: RobotTopic) -> Vec<String> {
+    match topic {
+        RobotTopic::Commands => vec![
+            "commands:".to_string(),
+            "  (global) --quiet / -q  Suppress info logs (auto-enabled in robot mode)".to_string(),
+            "  (global) --verbose/-v  Enable debug logs (overrides auto-quiet)".to_string(),
+            "  (global) --no-update-check  Skip the startup update check/prompt (no network)".to_string(),
+            "  Tip: `--robot-docs=<topic>` is normalized to `robot-docs <topic>`; globals can appear before/after subcommands.".to_string(),
+            "  cass search <query> [OPTIONS]".to_string(),
+            "    --agent A         Filter by agent (codex, claude_code, gemini, opencode, amp, cline)".to_string(),
+            "    --workspace W     Filter by workspace path".to_string(),
+            "    --source S        Filter by source ID, or 'local'/'remote' (repeatable)".to_string(),
+            "    --host H          Filter by origin host; 'local' = no remote origin (repeatable)".to_string(),
+            "    --in PATH         Search only the conversation stored at this source_path".to_string(),
+            "    --limit N         Max results (default: 10)".to_string(),
+            "    --offset N        Pagination offset (default: 0)".to_string(),
+            "    --json | --robot  JSON output for automation".to_string(),
+            "    --fields F1,F2    Select specific fields in hits (reduces token usage)".to_string(),
+            "                      Presets: minimal (path,line,agent), summary (+title,score), provenance (source_id,origin_kind,origin_host)".to_string(),
+            "                      Fields: score,agent,workspace,source_path,snippet,content,title,created_at,line_number,match_type,source_id,origin_kind,origin_host".to_string(),
+            "    --max-content-length N  Truncate content/snippet/title to N chars (UTF-8 safe, adds '...')".to_string(),
+            "                            Adds *_truncated: true indicator for each truncated field".to_string(),
+            "    --today           Filter to today only".to_string(),
+            "    --yesterday       Filter to yesterday only".to_string(),
+            "    --week            Filter to last 7 days".to_string(),
+            "    --days N          Filter to last N days".to_string(),
             "    --since DATE      Filter from date (YYYY-MM-DD)".to_string(),
             "    --until DATE      Filter to date (YYYY-MM-DD)".to_string(),
             "    --aggregate F1,F2 Server-side aggregation by fields (agent,workspace,date,match_type)".to_string(),
             "                      Returns buckets with counts. Reduces tokens by ~99% for overview queries".to_string(),
+            "    --export-html DIR Write each hit's conversation as HTML plus index.html into DIR".to_string(),
             "  cass stats [--json] [--data-dir DIR]".to_string(),
             "  cass status [--json] [--stale-threshold N] [--data-dir DIR]".to_string(),
             "  cass diag [--json] [--verbose] [--data-dir DIR]".to_string(),
-            "  cass view <path> [-n LINE] [-C CONTEXT] [--json]".to_string(),
+            "  cass view <path|-> [-n LINE] [-C CONTEXT] [--json]  (- reads stdin)".to_string(),
+            "  cass show <source_path> [--json]  Normalized messages as indexed (not raw file bytes)".to_string(),
             "  cass index [--full] [--watch] [--json] [--data-dir DIR]".to_string(),
+            "  cass index --print-generation            # counter bumped on every index commit".to_string(),
             "  cass tui [--once] [--data-dir DIR] [--reset-state]".to_string(),
+            "  cass forget <source_path|external_id> [--yes] [--json]  Remove from db + index".to_string(),
+            "  cass import <file.jsonl|file.md> --agent SLUG [--workspace DIR] [--json]  Index an exported transcript".to_string(),
             "  cass capabilities [--json]".to_string(),
-            "  cass robot-docs <topic>".to_string(),
+            "  cass config show [--json] | cass config path db|index|data|sources|log".to_string(),
+            "  cass robot-docs <topic> [--json]  (--json: structured data + contract_version)".to_string(),
             "  cass --robot-help".to_string(),
         ],
-        RobotTopic::Env => vec![
-            "env:".to_string(),
-            "  CODING_AGENT_SEARCH_NO_UPDATE_PROMPT=1   skip update prompt".to_string(),
-            "  TUI_HEADLESS=1                           skip update prompt".to_string(),
-            "  CASS_DATA_DIR                            override data dir".to_string(),
-            "  CASS_DB_PATH                             override db path".to_string(),
-            "  NO_COLOR / CASS_NO_COLOR                 disable color".to_string(),
-            "  CASS_TRACE_FILE                          default trace path".to_string(),
-        ],
+        RobotTopic::Env => {
+            let mut lines = vec!["env:".to_string()];
+            lines.extend(
+                ROBOT_ENV_VARS
+                    .iter()
+                    .map(|(name, meaning)| format!("  {name:<39}  {meaning}")),
+            );
+            lines.push(
+                "  (flag) --no-update-check                 skip update check + prompt, incl. TUI banner"
+                    .to_string(),
+            );
+            lines
+        }
         RobotTopic::Paths => {
             let mut lines: Vec<String> = vec!["paths:".to_string()];
             lines.push(format!("  data dir default: {}", default_data_dir().display()));
             lines.push(format!("  db path default: {}", default_db_path().display()));
             lines.push("  log path: <data-dir>/cass.log (daily rolling)".to_string());
             lines.push("  trace: user-provided path (JSONL).".to_string());
+            lines.push("  resolved: `cass config show` (or `cass config path <what>`)".to_string());
             lines
         }
         RobotTopic::Guide => vec![
@@ -2671,10 +3465,13 @@ fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
             "  Quick refs: cass --robot-help | cass robot-docs commands | cass robot-docs examples".to_string(),
         ],
         RobotTopic::Schemas => render_schema_docs(),
-        RobotTopic::ExitCodes => vec![
-            "exit-codes:".to_string(),
-            " 0 ok | 2 usage | 3 missing index/db | 4 network | 5 data-corrupt | 6 incompatible-version | 7 lock/busy | 8 partial | 9 unknown".to_string(),
-        ],
+        RobotTopic::ExitCodes => {
+            let codes: Vec<String> = EXIT_CODES
+                .iter()
+                .map(|(code, meaning)| format!("{code} {meaning}"))
+                .collect();
+            vec!["exit-codes:".to_string(), format!(" {}", codes.join(" | "))]
+        }
         RobotTopic::Examples => vec![
             "examples:".to_string(),
             String::new(),
@@ -2707,6 +3504,14 @@ fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
             "  cass search \"error\" --json --aggregate agent    # count by agent".to_string(),
             "  cass search \"*\" --json --aggregate agent,workspace  # multi-field agg".to_string(),
             "  cass search \"bug\" --json --aggregate date --week  # time distribution".to_string(),
+            "  cass search \"bug\" --group-by day --limit 50  # top hits sectioned by day".to_string(),
+            "  cass search \"bug\" --dedupe --json       # best hit per session file".to_string(),
+            "  cass search \"bug\" --by-conversation     # one hit per conversation".to_string(),
+            "  cass search \"bug\" --no-tools --json     # skip tool output messages".to_string(),
+            "  cass search \"bug\" --json --no-snippet   # metadata only, no snippets".to_string(),
+            "  cass search \"bug\" --min-score-pct 50    # only hits within half the top score".to_string(),
+            "  cass search \"bug\" --relative-paths      # paths relative to each workspace".to_string(),
+            "  cass search \"bug\" --json --profile      # per-phase timings_ms".to_string(),
             String::new(),
             "# Quick health check (ideal for agents)".to_string(),
             "  cass status --json                       # health check JSON".to_string(),
@@ -2732,6 +3537,13 @@ fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
             "  Color auto off when non-TTY unless forced.".to_string(),
             "  Use --quiet to silence info logs in robot runs.".to_string(),
             "  JSON errors only to stderr.".to_string(),
+            "  Pretty-printed JSON by default; --compact (search, stats) prints one line.".to_string(),
+            "  Error JSON on stderr is always a single line.".to_string(),
+            "  stats --porcelain: one key=value per line, in this order; keys are only ever added:".to_string(),
+            "    conversations, messages, agent.<slug>*, workspace.<path>* (top 10),".to_string(),
+            "    source.<id>.conversations/.messages* (--by-source), oldest, newest, last_scan,".to_string(),
+            "    source_filter (--source). * = sorted by name. Values are integers or RFC 3339".to_string(),
+            "    timestamps (empty when unknown) and never contain '='; split at the last '='.".to_string(),
         ],
         RobotTopic::Wrap => vec![
             "wrap:".to_string(),
@@ -2814,10 +3626,7 @@ fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
             "  cass sources discover     Just discover hosts (no setup)".to_string(),
             "  cass sources add          Manually add a source".to_string(),
         ],
-    };
-
-    println!("{}", render_block(&lines, wrap));
-    Ok(())
+    }
 }
 
 /// Render schema docs from live response schemas
@@ -2912,6 +3721,63 @@ fn write_trace_line(
     Ok(())
 }
 
+/// Print completions for `shell` to stdout, or write them into `output` using each
+/// shell's conventional file name (`--all` covers every supported shell).
+fn run_completions(
+    shell: Option<clap_complete::Shell>,
+    output: Option<PathBuf>,
+    all: bool,
+) -> CliResult<()> {
+    use clap::ValueEnum;
+    use clap_complete::Shell;
+
+    let mut cmd = Cli::command();
+    let Some(dir) = output else {
+        if let Some(shell) = shell {
+            clap_complete::generate(shell, &mut cmd, "cass", &mut std::io::stdout());
+        }
+        return Ok(());
+    };
+
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| CliError::unknown(format!("failed to create {}: {e}", dir.display())))?;
+    let shells: Vec<Shell> = if all {
+        Shell::value_variants().to_vec()
+    } else {
+        shell.into_iter().collect()
+    };
+    for shell in shells {
+        let path = clap_complete::generate_to(shell, &mut cmd, "cass", &dir)
+            .map_err(|e| CliError::unknown(format!("failed to write {shell} completions: {e}")))?;
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+/// Span sink for `cass index --trace-file`: appends one JSONL record per
+/// indexing phase ahead of the command's summary record.
+fn trace_span_sink(path: PathBuf) -> indexer::SpanCallback {
+    std::sync::Arc::new(move |span: indexer::PhaseSpan| {
+        let payload = serde_json::json!({
+            "type": "span",
+            "cmd": "index",
+            "ts": Utc::now().to_rfc3339(),
+            "phase": span.phase,
+            "connector": span.connector,
+            "duration_ms": span.duration_ms,
+            "conversations": span.conversations,
+        });
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{payload}"));
+        if let Err(e) = written {
+            tracing::debug!(error = %e, "trace span write failed");
+        }
+    })
+}
+
 /// Time filter helper for search commands
 #[derive(Debug, Clone, Default)]
 pub struct TimeFilter {
@@ -2927,7 +3793,7 @@ impl TimeFilter {
         week: bool,
         since_str: Option<&str>,
         until_str: Option<&str>,
-    ) -> Self {
+    ) -> CliResult<Self> {
         use chrono::{Datelike, Duration, Local, TimeZone};
 
         let now = Local::now();
@@ -2954,14 +3820,36 @@ impl TimeFilter {
             (None, None)
         };
 
-        // Explicit --since/--until override convenience flags when they parse successfully
-        let since = since_str.and_then(parse_datetime_str).or(since);
-        let until = until_str.and_then(parse_datetime_str).or(until);
+        // Explicit --since/--until override convenience flags
+        let since = match since_str {
+            Some(s) => Some(parse_time_arg(s, "--since")?),
+            None => since,
+        };
+        let until = match until_str {
+            Some(s) => Some(parse_time_arg(s, "--until")?),
+            None => until,
+        };
 
-        TimeFilter { since, until }
+        Ok(TimeFilter { since, until })
     }
 }
 
+/// Parse a `--since`/`--until` value: the strict ISO forms first, then the
+/// TUI's natural-language parser ("yesterday", "3 days ago", "last week", "-7d").
+fn parse_time_arg(s: &str, flag: &str) -> CliResult<i64> {
+    parse_datetime_str(s)
+        .or_else(|| crate::ui::time_parser::parse_time_input(s))
+        .ok_or_else(|| {
+            CliError::usage(
+                format!("could not parse {flag} value '{s}'"),
+                Some(
+                    "Use YYYY-MM-DD[THH:MM:SS], 'today', 'yesterday', '3 days ago' or 'last week'"
+                        .to_string(),
+                ),
+            )
+        })
+}
+
 fn parse_datetime_str(s: &str) -> Option<i64> {
     use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
 
@@ -3062,6 +3950,264 @@ fn parse_aggregate_fields(fields: &[String]) -> Vec<AggregateField> {
         .collect()
 }
 
+/// Bucket hits for `search --group-by`. Groups appear in the order of their
+/// best-ranked hit and hold indices into `hits`, preserving rank order.
+fn group_hit_indices(
+    hits: &[crate::search::query::SearchHit],
+    by: SearchGroupBy,
+) -> Vec<(String, Vec<usize>)> {
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for (i, hit) in hits.iter().enumerate() {
+        let key = match by {
+            SearchGroupBy::Workspace if hit.workspace.is_empty() => "unknown".to_string(),
+            SearchGroupBy::Workspace => hit.workspace.clone(),
+            SearchGroupBy::Agent => hit.agent.clone(),
+            SearchGroupBy::Day => hit
+                .created_at
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|d| d.with_timezone(&chrono::Local).date_naive().to_string())
+                .unwrap_or_else(|| "unknown".to_string()),
+        };
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, indices)) => indices.push(i),
+            None => groups.push((key, vec![i])),
+        }
+    }
+    groups
+}
+
+/// Collapse hits sharing a `source_path` for `search --dedupe`. Each file keeps
+/// its highest-scoring hit, at the position of the file's first hit, with the
+/// number of hits folded into it in `duplicate_count`.
+fn dedupe_hits_by_source(
+    hits: Vec<crate::search::query::SearchHit>,
+) -> Vec<crate::search::query::SearchHit> {
+    use std::collections::HashMap;
+
+    let mut kept: Vec<crate::search::query::SearchHit> = Vec::new();
+    let mut slots: HashMap<String, usize> = HashMap::new();
+    for hit in hits {
+        match slots.get(&hit.source_path) {
+            Some(&slot) => {
+                let best = &mut kept[slot];
+                let folded = best.duplicate_count.unwrap_or(0) + 1;
+                if hit.score > best.score {
+                    *best = hit;
+                }
+                best.duplicate_count = Some(folded);
+            }
+            None => {
+                slots.insert(hit.source_path.clone(), kept.len());
+                kept.push(hit);
+            }
+        }
+    }
+    kept
+}
+
+/// Candidate messages fetched per requested conversation for `search --by-conversation`.
+const CONVERSATION_CANDIDATES_PER_HIT: usize = 10;
+
+/// Keep one hit per conversation for `search --by-conversation`. `hits` is in
+/// rank order, so each conversation's first hit is its best-scoring message; it
+/// stays in place and `message_matches` counts every fetched match from that
+/// conversation (a lower bound when the candidate window was exhausted).
+///
+/// Conversations are keyed by source, file and external id, so the sessions of
+/// a single-file store (Cursor, Aider, Copilot) stay separate.
+fn group_hits_by_conversation(
+    hits: Vec<crate::search::query::SearchHit>,
+) -> Vec<crate::search::query::SearchHit> {
+    use std::collections::HashMap;
+
+    let mut kept: Vec<crate::search::query::SearchHit> = Vec::new();
+    let mut slots: HashMap<(String, String, Option<String>), usize> = HashMap::new();
+    for mut hit in hits {
+        let key = (
+            hit.source_id.clone(),
+            hit.source_path.clone(),
+            hit.conversation_external_id.clone(),
+        );
+        match slots.get(&key) {
+            Some(&slot) => {
+                let best = &mut kept[slot];
+                best.message_matches = Some(best.message_matches.unwrap_or(1) + 1);
+            }
+            None => {
+                slots.insert(key, kept.len());
+                hit.message_matches = Some(1);
+                kept.push(hit);
+            }
+        }
+    }
+    kept
+}
+
+/// `source_path` relative to the hit's workspace (or its pre-rewrite original)
+/// for `search --relative-paths`; `None` when the file is not under either.
+fn workspace_relative_path(hit: &crate::search::query::SearchHit) -> Option<String> {
+    [
+        Some(hit.workspace.as_str()),
+        hit.workspace_original.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|ws| !ws.is_empty())
+    .find_map(|ws| Path::new(&hit.source_path).strip_prefix(ws).ok())
+    .filter(|rel| !rel.as_os_str().is_empty())
+    .map(|rel| rel.to_string_lossy().into_owned())
+}
+
+/// Path shown for a hit in human output: its workspace-relative path when
+/// `--relative-paths` computed one, otherwise the absolute `source_path`.
+fn display_source_path(hit: &crate::search::query::SearchHit) -> &str {
+    hit.relative_path.as_deref().unwrap_or(&hit.source_path)
+}
+
+/// Number of ranked hits `hits` stands for, counting those folded away by `--dedupe`.
+fn ranked_hit_count(hits: &[crate::search::query::SearchHit]) -> usize {
+    hits.iter()
+        .map(|hit| 1 + hit.duplicate_count.unwrap_or(0))
+        .sum()
+}
+
+/// Summarize on-disk result cache usage for robot output.
+///
+/// `status` is `hit` when every lookup was served from disk, `miss` when any
+/// lookup went to the index, and `bypass` when the cache was not consulted
+/// (`--no-cache`, `--explain`, or a non-lexical search mode).
+fn result_cache_json(stats: &crate::search::query::CacheStats) -> serde_json::Value {
+    let status = match (stats.disk_hits, stats.disk_miss) {
+        (0, 0) => "bypass",
+        (_, 0) => "hit",
+        _ => "miss",
+    };
+    serde_json::json!({
+        "status": status,
+        "hits": stats.disk_hits,
+        "misses": stats.disk_miss,
+    })
+}
+
+/// `search --profile` phase timings in milliseconds, to the microsecond.
+fn search_timings_json(timings: &crate::search::query::SearchTimings) -> serde_json::Value {
+    let ms = |d: Duration| (duration_ms(d) * 1000.0).round() / 1000.0;
+    serde_json::json!({
+        "open": ms(timings.open),
+        "parse": ms(timings.parse),
+        "search": ms(timings.search),
+        "highlight": ms(timings.highlight),
+        "total": ms(timings.total),
+    })
+}
+
+fn duration_ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Default ceiling for `search --limit`; override with `CASS_MAX_LIMIT`.
+const DEFAULT_MAX_SEARCH_LIMIT: usize = 10_000;
+
+/// Largest `--limit` a search may request, so a runaway value can't try to
+/// materialize the whole index.
+fn max_search_limit() -> usize {
+    dotenvy::var("CASS_MAX_LIMIT")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_MAX_SEARCH_LIMIT)
+}
+
+/// Apply `search --compact`: pretty JSON output becomes single-line JSON.
+/// Line-oriented formats (jsonl, sessions) are already compact, and without
+/// `--json` or `--robot-format` there is no JSON to compact.
+fn compact_robot_format(
+    json: bool,
+    robot_format: Option<RobotFormat>,
+    compact: bool,
+) -> Option<RobotFormat> {
+    match robot_format {
+        Some(RobotFormat::Json) if compact => Some(RobotFormat::Compact),
+        None if compact && json => Some(RobotFormat::Compact),
+        other => other,
+    }
+}
+
+/// A requested agent that matches no indexed slug, directly or through an alias,
+/// would silently return nothing. When an indexed slug is close enough to be the
+/// intended one, fail with it as a suggestion instead; robot output carries it
+/// as `error.suggestion` so callers can retry with it.
+/// Workspace paths for `--here`: the canonicalized current directory, plus the
+/// path as the shell reports it when a symlink makes the two differ.
+fn cwd_workspaces() -> CliResult<Vec<String>> {
+    let cwd = std::env::current_dir().map_err(|e| {
+        CliError::usage(
+            format!("--here: cannot read the current directory: {e}"),
+            Some("Pass --workspace <path> instead".to_string()),
+        )
+    })?;
+    let canonical = std::fs::canonicalize(&cwd).unwrap_or_else(|_| cwd.clone());
+    let mut paths = vec![canonical.to_string_lossy().into_owned()];
+    if canonical != cwd {
+        paths.push(cwd.to_string_lossy().into_owned());
+    }
+    Ok(paths)
+}
+
+fn unknown_agent_error(
+    requested: &std::collections::HashSet<String>,
+    indexed: &std::collections::BTreeSet<String>,
+    aliases: &crate::search::agent_alias::AgentAliases,
+    robot: bool,
+) -> Option<CliError> {
+    let mut requested: Vec<_> = requested.iter().collect();
+    requested.sort();
+    let (agent, suggestion) = requested.into_iter().find_map(|agent| {
+        let known = aliases
+            .expand(agent)
+            .iter()
+            .any(|slug| indexed.contains(slug));
+        if known {
+            return None;
+        }
+        let wanted = agent.to_lowercase();
+        let (distance, closest) = indexed
+            .iter()
+            .map(|slug| (strsim::levenshtein(&wanted, slug), slug))
+            .min()?;
+        let close = distance <= 2 || wanted.contains(closest.as_str()) || closest.contains(&wanted);
+        close.then(|| (agent.clone(), closest.clone()))
+    })?;
+
+    let message = format!("unknown agent '{agent}'; did you mean '{suggestion}'?");
+    let hint = format!(
+        "Indexed agents: {}",
+        indexed.iter().cloned().collect::<Vec<_>>().join(", ")
+    );
+    let message = if robot {
+        serde_json::json!({
+            "error": {
+                "code": 2,
+                "kind": "unknown-agent",
+                "message": message,
+                "hint": hint,
+                "retryable": false,
+                "suggestion": suggestion,
+            }
+        })
+        .to_string()
+    } else {
+        message
+    };
+    Some(CliError {
+        code: 2,
+        kind: "unknown-agent",
+        message,
+        hint: Some(hint),
+        retryable: false,
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_cli_search(
     query: &str,
@@ -3078,6 +4224,7 @@ fn run_cli_search(
     request_id: Option<String>,
     cursor: Option<String>,
     display_format: Option<DisplayFormat>,
+    mark: Option<MarkStyle>,
     data_dir_override: &Option<PathBuf>,
     db_override: Option<PathBuf>,
     wrap: WrapConfig,
@@ -3085,32 +4232,69 @@ fn run_cli_search(
     robot_auto: bool,
     time_filter: TimeFilter,
     aggregate: Option<Vec<String>>,
+    group_by: Option<SearchGroupBy>,
     explain: bool,
     dry_run: bool,
     timeout_ms: Option<u64>,
     highlight: bool,
-    source: Option<String>,
+    highlight_opts: crate::search::query::HighlightOptions,
+    sources: Vec<String>,
+    hosts: Vec<String>,
     sessions_from: Option<String>,
+    in_session: Option<String>,
     mode: Option<crate::search::query::SearchMode>,
+    semantic: bool,
+    recency_blend: Option<(f32, f32)>,
+    rerank_top: Option<usize>,
+    min_score: Option<f32>,
+    min_score_pct: Option<f32>,
+    cache_ttl: Option<Duration>,
+    export_html: Option<PathBuf>,
+    with_total: bool,
+    dedupe: bool,
+    by_conversation: bool,
+    no_tools: bool,
+    no_snippet: bool,
+    relative_paths: bool,
+    profile: bool,
+    follow: bool,
 ) -> CliResult<()> {
+    use crate::search::agent_alias::AgentAliases;
+    use crate::search::model_manager::load_semantic_context;
     use crate::search::query::{QueryExplanation, SearchClient, SearchFilters, SearchMode};
-    use crate::search::tantivy::index_dir;
-    use crate::sources::provenance::SourceFilter;
+    use crate::search::result_cache::ResultCache;
+    use crate::search::tantivy::index_dir_path;
     use std::collections::HashSet;
 
     // Start timing for robot_meta elapsed_ms
     let start_time = Instant::now();
 
     let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
-    let index_path = index_dir(&data_dir).map_err(|e| CliError {
-        code: 9,
-        kind: "path",
-        message: format!("failed to open index dir: {e}"),
-        hint: None,
-        retryable: false,
-    })?;
+    // Searching only reads, so don't create anything here: the data dir may be read-only.
+    let index_path = index_dir_path(&data_dir);
     let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
 
+    // Fail before searching if the export target is unusable.
+    if let Some(dir) = &export_html {
+        std::fs::create_dir_all(dir).map_err(|e| {
+            CliError::usage(
+                format!("cannot create export directory {}: {e}", dir.display()),
+                Some("Pass a writable path to --export-html".to_string()),
+            )
+        })?;
+    }
+
+    let missing_index = || CliError {
+        code: 3,
+        kind: "missing-index",
+        message: format!(
+            "Index not found at {}. Run 'cass index --full' first.",
+            index_path.display()
+        ),
+        hint: None,
+        retryable: true,
+    };
+    let open_started = profile.then(Instant::now);
     let client = SearchClient::open(&index_path, Some(&db_path))
         .map_err(|e| CliError {
             code: 9,
@@ -3119,31 +4303,56 @@ fn run_cli_search(
             hint: Some("try cass index --full".to_string()),
             retryable: true,
         })?
-        .ok_or_else(|| CliError {
-            code: 3,
-            kind: "missing-index",
-            message: format!(
-                "Index not found at {}. Run 'cass index --full' first.",
-                index_path.display()
-            ),
-            hint: None,
-            retryable: true,
-        })?;
+        .ok_or_else(missing_index)?;
+    let open_elapsed = open_started.map(|started| started.elapsed());
+    // No Tantivy index: serve from the database's FTS table if it has anything in it.
+    let sqlite_fallback = client.uses_sqlite_fallback();
+    if sqlite_fallback && !client.sqlite_has_indexed_messages() {
+        return Err(missing_index());
+    }
+    let client = match cache_ttl {
+        Some(ttl) => client.with_result_cache(ResultCache::new(&data_dir, &index_path, ttl)),
+        None => client,
+    };
+    let client = if profile {
+        client.with_profiling()
+    } else {
+        client
+    };
 
+    let agent_aliases = AgentAliases::load();
     let mut filters = SearchFilters::default();
-    if !agents.is_empty() {
-        filters.agents = HashSet::from_iter(agents.iter().cloned());
-    }
-    if !workspaces.is_empty() {
-        filters.workspaces = HashSet::from_iter(workspaces.iter().cloned());
+    // Leading `agent:`/`ws:`/`role:`/`source:` terms become filters; the rest is searched.
+    let scoped_query = crate::search::query::apply_field_scopes(query, &mut filters);
+    let query = scoped_query.as_str();
+    filters.agents.extend(agents.iter().cloned());
+    if !filters.agents.is_empty() {
+        let indexed = client.indexed_agents().unwrap_or_default();
+        if let Some(err) = unknown_agent_error(
+            &filters.agents,
+            &indexed,
+            &agent_aliases,
+            *json || robot_format.is_some(),
+        ) {
+            return Err(err);
+        }
+        filters.expand_agent_aliases(&agent_aliases);
     }
+    filters.workspaces.extend(workspaces.iter().cloned());
     filters.created_from = time_filter.since;
     filters.created_to = time_filter.until;
+    filters.highlight = highlight_opts;
+    // Per-hit score explanations are expensive; only compute them for JSON output.
+    filters.explain = explain && (*json || robot_format.is_some());
+    filters.exclude_tool_messages = no_tools;
+    filters.skip_snippets = no_snippet;
 
-    // Apply source filter (P3.1)
-    if let Some(ref source_str) = source {
-        filters.source_filter = SourceFilter::parse(source_str);
+    // Apply source filter (P3.1): 'remote'/'all' select by kind, anything else
+    // (including 'local') is a source ID.
+    for source_str in &sources {
+        filters.add_source(source_str);
     }
+    filters.hosts = hosts.into_iter().collect();
 
     // Apply session paths filter (for chained searches)
     if let Some(ref sessions_from_arg) = sessions_from {
@@ -3156,6 +4365,10 @@ fn run_cli_search(
         })?;
         filters.session_paths = session_paths;
     }
+    if let Some(path) = in_session {
+        filters.session_paths = HashSet::from([session_source_path(&path)]);
+    }
+    let filters_json = search_filters_json(&filters, &data_dir, &db_path);
 
     // Apply cursor overrides (base64-encoded JSON { "offset": usize, "limit": usize })
     let mut limit_val = *limit;
@@ -3187,6 +4400,24 @@ fn run_cli_search(
         }
     }
 
+    let max_limit = max_search_limit();
+    if limit_val > max_limit {
+        return Err(CliError::usage(
+            format!("--limit {limit_val} exceeds the maximum of {max_limit}"),
+            Some(format!(
+                "Use --limit {max_limit} or less and page with --offset/--cursor (ceiling set by CASS_MAX_LIMIT)"
+            )),
+        ));
+    }
+    if let Some(pct) = min_score_pct
+        && !(0.0..=100.0).contains(&pct)
+    {
+        return Err(CliError::usage(
+            format!("--min-score-pct {pct} is out of range"),
+            Some("Pass a percentage between 0 and 100".to_string()),
+        ));
+    }
+
     // Determine the effective output format
     // Priority: robot_format > json flag > display format > default plain
     let effective_robot = robot_format
@@ -3199,6 +4430,13 @@ fn run_cli_search(
             }
         });
 
+    if follow && (effective_robot.is_some() || !std::io::stdout().is_terminal()) {
+        return Err(CliError::usage(
+            "--follow needs an interactive terminal and plain output",
+            Some("Drop --json/--robot, or poll `cass search` from your script instead".to_string()),
+        ));
+    }
+
     // Parse aggregate fields if provided
     let agg_fields = aggregate
         .as_ref()
@@ -3239,6 +4477,15 @@ fn run_cli_search(
     // Fetch up to 1000 for aggregation starting at offset 0, then apply offset/limit
     let (search_limit, search_offset) = if has_aggregation {
         (1000.max(limit_val + offset_val), 0)
+    } else if by_conversation {
+        // Grouping pages over conversations, so fetch from the start with enough
+        // extra candidates to fill `limit` conversations
+        let window = (limit_val + offset_val).saturating_mul(CONVERSATION_CANDIDATES_PER_HIT);
+        (window.max(rerank_top.unwrap_or(0)), 0)
+    } else if recency_blend.is_some() || rerank_top.is_some() || min_score_pct.is_some() {
+        // Blending/reranking reorders the whole window, and --min-score-pct needs the
+        // top hit, so fetch from the start and page afterwards
+        ((limit_val + offset_val).max(rerank_top.unwrap_or(0)), 0)
     } else {
         (limit_val, offset_val)
     };
@@ -3260,10 +4507,37 @@ fn run_cli_search(
         });
     }
 
-    // Determine effective search mode (default to Lexical)
-    let effective_mode = mode.unwrap_or(SearchMode::Lexical);
+    // Determine effective search mode (default to Lexical; --semantic implies Hybrid)
+    let mut effective_mode = if semantic {
+        SearchMode::Hybrid
+    } else {
+        mode.unwrap_or(SearchMode::Lexical)
+    };
+
+    // Load the embedder and vector index for semantic/hybrid modes.
+    if effective_mode != SearchMode::Lexical {
+        let setup = load_semantic_context(&data_dir, &db_path);
+        let loaded = match setup.context {
+            Some(context) => client
+                .set_semantic_context(
+                    context.embedder,
+                    context.index,
+                    context.filter_maps,
+                    context.roles,
+                )
+                .map_err(|e| format!("set context: {e}")),
+            None => Err(setup.availability.summary()),
+        };
+        if let Err(reason) = loaded
+            && semantic
+        {
+            // --semantic is best-effort: degrade to lexical rather than failing.
+            eprintln!("warning: semantic search unavailable ({reason}); using lexical search");
+            effective_mode = SearchMode::Lexical;
+        }
+    }
 
-    let result = match effective_mode {
+    let mut result = match effective_mode {
         SearchMode::Lexical => client
             .search_with_fallback(query, filters.clone(), search_limit, search_offset, sparse_threshold)
             .map_err(|e| CliError {
@@ -3332,6 +4606,94 @@ fn run_cli_search(
                 }
             })?,
     };
+    // Noted only once the search succeeded, so a structured error stays alone on stderr.
+    if sqlite_fallback {
+        eprintln!(
+            "note: no search index at {}; results come from the SQLite FTS fallback (run 'cass index --full' to rebuild)",
+            index_path.display()
+        );
+    }
+
+    // --profile: the client's phase timings, plus index open and the end-to-end
+    // time from opening the index through the search.
+    let timings = open_started.zip(open_elapsed).map(|(started, open)| {
+        let mut timings = client.timings().unwrap_or_default();
+        timings.open = open;
+        timings.total = started.elapsed();
+        timings
+    });
+
+    // Thresholds apply to retrieval scores, before blending/reranking replaces them
+    // and before paging, so --limit still counts qualifying hits.
+    crate::search::query::retain_min_score(&mut result.hits, min_score, min_score_pct);
+    if let Some((weight, half_life_days)) = recency_blend {
+        crate::search::query::blend_recency(&mut result.hits, weight, half_life_days);
+    }
+    if let Some(top_n) = rerank_top {
+        use crate::search::fastembed_reranker::FastReranker;
+        match FastReranker::load_from_dir(&FastReranker::default_model_dir(&data_dir)) {
+            Ok(reranker) => {
+                crate::search::query::rerank_hits(&mut result.hits, query, &reranker, top_n)
+                    .map_err(|e| CliError {
+                        code: 9,
+                        kind: "rerank",
+                        message: format!("rerank failed: {e}"),
+                        hint: Some("Retry without --rerank".to_string()),
+                        retryable: true,
+                    })?;
+            }
+            // --rerank is best-effort: keep lexical order rather than failing.
+            Err(e) => eprintln!("warning: reranker unavailable ({e}); using lexical order"),
+        }
+    }
+    if (recency_blend.is_some() || rerank_top.is_some() || min_score_pct.is_some())
+        && !has_aggregation
+        && !by_conversation
+    {
+        result.hits.drain(..offset_val.min(result.hits.len()));
+        result.hits.truncate(limit_val);
+    }
+
+    // Report aliased agents under their canonical slug.
+    for hit in &mut result.hits {
+        hit.agent = agent_aliases.canonical(&hit.agent).to_string();
+    }
+
+    if by_conversation {
+        result.hits = group_hits_by_conversation(std::mem::take(&mut result.hits));
+    }
+    if relative_paths {
+        for hit in &mut result.hits {
+            hit.relative_path = workspace_relative_path(hit);
+        }
+    }
+
+    // Exact match count for paging (--with-total). Semantic and hybrid results
+    // have no fixed result set to count, and the index can't count only hits
+    // above a score threshold, so those report no total.
+    let total = if with_total
+        && effective_mode == SearchMode::Lexical
+        && min_score.is_none()
+        && min_score_pct.is_none()
+    {
+        let counted_query = if result.wildcard_fallback {
+            crate::search::query::wildcard_fallback_query(query)
+        } else {
+            query.to_string()
+        };
+        let count = client
+            .count_matches(&counted_query, &filters)
+            .map_err(|e| CliError {
+                code: 9,
+                kind: "search",
+                message: format!("counting matches failed: {e}"),
+                hint: None,
+                retryable: true,
+            })?;
+        Some(count)
+    } else {
+        None
+    };
 
     // Check if search exceeded timeout - return partial results with timeout indicator
     let timed_out = timeout_duration.is_some_and(|t| start_time.elapsed() > t);
@@ -3347,7 +4709,7 @@ fn run_cli_search(
     };
 
     // Compute aggregations and create display result based on mode
-    let (aggregations, display_result, total_matches) = if has_aggregation {
+    let (aggregations, mut display_result, total_matches) = if has_aggregation {
         // Compute aggregations from all fetched results
         let aggs = compute_aggregations(&result.hits, &agg_fields);
         let total = result.hits.len();
@@ -3371,9 +4733,20 @@ fn run_cli_search(
     } else {
         // No aggregation - use result as-is
         let total = result.hits.len();
+        if by_conversation {
+            // Conversations were grouped from offset 0; page over them here.
+            result.hits.drain(..offset_val.min(total));
+            result.hits.truncate(limit_val);
+        }
         (Aggregations::default(), result, total)
     };
 
+    // Collapse the page only after ranking and paging so order and offsets are unchanged.
+    if dedupe {
+        display_result.hits = dedupe_hits_by_source(std::mem::take(&mut display_result.hits));
+    }
+    let page_len = ranked_hit_count(&display_result.hits);
+
     let elapsed_ms = start_time.elapsed().as_millis() as u64;
 
     // Derive per-field budgets, preferring snippet > content > title
@@ -3404,9 +4777,9 @@ fn run_cli_search(
     };
 
     // Build next cursor if more results remain
-    let next_cursor = if total_matches > offset_val + display_result.hits.len() {
+    let next_cursor = if total.unwrap_or(total_matches) > offset_val + page_len {
         let payload = serde_json::json!({
-            "offset": offset_val + display_result.hits.len(),
+            "offset": offset_val + page_len,
             "limit": limit_val,
         })
         .to_string();
@@ -3464,6 +4837,29 @@ fn run_cli_search(
         meta
     });
 
+    if let Some(dir) = &export_html {
+        let (written, skipped) = export_hits_html(dir, query, &display_result.hits)?;
+        if effective_robot.is_some() {
+            let payload = serde_json::json!({
+                "exported": written,
+                "dir": dir.display().to_string(),
+                "index": dir.join("index.html").display().to_string(),
+                "skipped": skipped,
+            });
+            println!("{}", serde_json::to_string(&payload).unwrap_or_default());
+        } else {
+            println!(
+                "Exported {written} conversation(s) to {}",
+                dir.join("index.html").display()
+            );
+            for path in &skipped {
+                eprintln!("warning: could not load {path}; skipped");
+            }
+        }
+        return Ok(());
+    }
+
+    let marks = snippet_marks(mark);
     if let Some(format) = effective_robot {
         // Robot output mode (JSON)
         output_robot_results(
@@ -3485,81 +4881,496 @@ fn run_cli_search(
             warning,
             &aggregations,
             total_matches,
+            total,
             explanation.as_ref(),
             timed_out,
             timeout_ms,
             effective_mode,
+            group_by,
+            timings.as_ref(),
+            &filters_json,
         )?;
     } else if display_result.hits.is_empty() {
         eprintln!("No results found.");
-    } else if let Some(display) = display_format {
-        // Human-readable display formats
-        output_display_results(&display_result.hits, display, wrap, query, highlight)?;
+    } else if let Some(by) = group_by {
+        for (key, indices) in group_hit_indices(&display_result.hits, by) {
+            let hits: Vec<_> = indices
+                .into_iter()
+                .map(|i| display_result.hits[i].clone())
+                .collect();
+            println!();
+            println!("=== {key} ({} hit(s)) ===", hits.len());
+            output_human_hits(&hits, display_format, wrap, query, highlight, marks)?;
+        }
     } else {
-        // Default plain text output
-        for hit in &display_result.hits {
-            println!("----------------------------------------------------------------");
-            println!(
-                "Score: {:.2} | Agent: {} | WS: {}",
-                hit.score, hit.agent, hit.workspace
-            );
-            println!("Path: {}", hit.source_path);
-            let snippet = hit.snippet.replace('\n', " ");
-            let snippet = if highlight {
-                highlight_matches(&snippet, query, "**", "**")
-            } else {
-                snippet
+        output_human_hits(
+            &display_result.hits,
+            display_format,
+            wrap,
+            query,
+            highlight,
+            marks,
+        )?;
+    }
+
+    // Human output has no has_more field, so say when the page may be cut short.
+    if effective_robot.is_none()
+        && limit_val > 0
+        && page_len == limit_val
+        && total.is_none_or(|t| offset_val + limit_val < t)
+    {
+        eprintln!(
+            "showing first {limit_val} of possibly more; use --limit/--offset to see the rest"
+        );
+    }
+    if let Some(t) = &timings
+        && effective_robot.is_none()
+    {
+        eprintln!(
+            "timings: open {:.1}ms, parse {:.1}ms, search {:.1}ms, highlight {:.1}ms, total {:.1}ms",
+            duration_ms(t.open),
+            duration_ms(t.parse),
+            duration_ms(t.search),
+            duration_ms(t.highlight),
+            duration_ms(t.total)
+        );
+    }
+
+    if follow {
+        follow_search(
+            &client,
+            &FollowOptions {
+                query,
+                filters: &filters,
+                limit: limit_val,
+                agent_aliases: &agent_aliases,
+                relative_paths,
+            },
+            &display_result.hits,
+            &index_path,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The search `--follow` re-runs, and how it prints new hits.
+struct FollowOptions<'a> {
+    query: &'a str,
+    filters: &'a crate::search::query::SearchFilters,
+    limit: usize,
+    agent_aliases: &'a crate::search::agent_alias::AgentAliases,
+    relative_paths: bool,
+}
+
+/// `cass search --follow`: poll the index generation once a second and, when
+/// it changes, re-run the query and print hits not seen before. Runs until
+/// interrupted (Ctrl-C).
+fn follow_search(
+    client: &crate::search::query::SearchClient,
+    opts: &FollowOptions<'_>,
+    initial: &[crate::search::query::SearchHit],
+    index_path: &Path,
+) -> CliResult<()> {
+    use crate::search::result_cache::index_generation;
+    use std::collections::HashSet;
+
+    let FollowOptions {
+        query,
+        filters,
+        limit,
+        agent_aliases,
+        relative_paths,
+    } = *opts;
+
+    let key = |hit: &crate::search::query::SearchHit| {
+        (
+            hit.source_path.clone(),
+            hit.line_number,
+            hit.content.clone(),
+        )
+    };
+    let mut seen: HashSet<_> = initial.iter().map(key).collect();
+    let mut generation = index_generation(index_path);
+    eprintln!("Following index updates (Ctrl-C to stop)...");
+    loop {
+        std::thread::sleep(Duration::from_secs(1));
+        let current = index_generation(index_path);
+        if current == generation {
+            continue;
+        }
+        generation = current;
+        client.clear_cache();
+        let hits = match client.search(query, filters.clone(), limit, 0) {
+            Ok(hits) => hits,
+            Err(e) => {
+                eprintln!("warning: follow search failed ({e}); retrying on next update");
+                continue;
+            }
+        };
+        let stamp = chrono::Local::now().format("%H:%M:%S");
+        for mut hit in hits {
+            if !seen.insert(key(&hit)) {
+                continue;
+            }
+            if relative_paths {
+                hit.relative_path = workspace_relative_path(&hit);
+            }
+            println!(
+                "[{stamp}] {:.2} {} {}",
+                hit.score,
+                agent_aliases.canonical(&hit.agent),
+                display_source_path(&hit)
+            );
+            println!("    {}", hit.snippet.replace('\n', " "));
+        }
+    }
+}
+
+/// Write the conversation behind each hit as an HTML page in `dir` (once per
+/// session file) plus an `index.html` listing the hits in rank order.
+/// Returns the number of conversation pages written and the source paths
+/// that could not be loaded.
+fn export_hits_html(
+    dir: &Path,
+    query: &str,
+    hits: &[crate::search::query::SearchHit],
+) -> CliResult<(usize, Vec<String>)> {
+    let write = |path: &Path, contents: &str| {
+        std::fs::write(path, contents).map_err(|e| CliError {
+            code: 9,
+            kind: "file-write",
+            message: format!("Failed to write {}: {e}", path.display()),
+            hint: None,
+            retryable: false,
+        })
+    };
+
+    let mut pages: std::collections::HashMap<&str, Option<String>> =
+        std::collections::HashMap::new();
+    let mut skipped = Vec::new();
+    let mut rows = String::new();
+    for hit in hits {
+        if !pages.contains_key(hit.source_path.as_str()) {
+            let page = match load_session_for_export(Path::new(&hit.source_path)) {
+                Ok((title, start, messages)) => {
+                    let title = title.or_else(|| Some(hit.title.clone()));
+                    let filename = export_html_filename(pages.len() + 1, &hit.agent, &title);
+                    write(
+                        &dir.join(&filename),
+                        &format_as_html(
+                            &messages,
+                            &title,
+                            start,
+                            false,
+                            false,
+                            crate::export::ExportTheme::default(),
+                        ),
+                    )?;
+                    Some(filename)
+                }
+                Err(_) => {
+                    skipped.push(hit.source_path.clone());
+                    None
+                }
             };
-            println!("Snippet: {}", apply_wrap(&snippet, wrap));
+            pages.insert(hit.source_path.as_str(), page);
         }
-        println!("----------------------------------------------------------------");
+        let Some(Some(filename)) = pages.get(hit.source_path.as_str()) else {
+            continue;
+        };
+        let title = if hit.title.is_empty() {
+            &hit.source_path
+        } else {
+            &hit.title
+        };
+        rows.push_str(&format!(
+            "    <li><a href=\"{}\">{}</a> <span class=\"meta\">{} · score {:.2}</span><p>{}</p></li>\n",
+            html_escape(filename),
+            html_escape(title),
+            html_escape(&hit.agent),
+            hit.score,
+            html_escape(&hit.snippet)
+        ));
     }
 
-    Ok(())
+    let query = html_escape(query);
+    write(
+        &dir.join("index.html"),
+        &format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <title>cass: {query}</title>
+    <style>
+        body {{ font-family: system-ui, sans-serif; max-width: 800px; margin: 0 auto; padding: 20px; background: #f5f5f5; }}
+        li {{ background: white; border-radius: 8px; padding: 12px 16px; margin: 8px 0; list-style: none; }}
+        p {{ white-space: pre-wrap; color: #374151; margin: 6px 0 0; }}
+        .meta {{ color: #6b7280; font-size: 0.9em; }}
+    </style>
+</head>
+<body>
+    <h1>Results for “{query}”</h1>
+    <ol>
+{rows}    </ol>
+</body>
+</html>
+"#
+        ),
+    )?;
+
+    let written = pages.values().filter(|p| p.is_some()).count();
+    Ok((written, skipped))
+}
+
+/// File name for an exported conversation: rank, agent and a slug of the title.
+fn export_html_filename(rank: usize, agent: &str, title: &Option<String>) -> String {
+    let mut slug = String::new();
+    for c in title.as_deref().unwrap_or_default().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= 40 {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    let slug = if slug.is_empty() { "session" } else { slug };
+    format!("{rank:03}-{agent}-{slug}.html")
+}
+
+/// Start/end markers for matched terms in human snippets: ANSI bold yellow
+/// when stdout is a color terminal, otherwise the `--mark` style.
+fn snippet_marks(mark: Option<MarkStyle>) -> Option<(&'static str, &'static str)> {
+    let style = mark.unwrap_or_default();
+    if style == MarkStyle::None {
+        return None;
+    }
+    if std::io::stdout().is_terminal() && colored::control::SHOULD_COLORIZE.should_colorize() {
+        return Some(("\x1b[1;33m", "\x1b[0m"));
+    }
+    match style {
+        MarkStyle::None => None,
+        MarkStyle::Asterisks => Some(("**", "**")),
+        MarkStyle::Brackets => Some(("[", "]")),
+    }
+}
+
+/// Mark matched terms in a human snippet; `--highlight` always uses `**`.
+/// Snippets from the SQLite fallback and multi-fragment highlighting already
+/// wrap matches in `**`; those markers are dropped so only the chosen style shows.
+fn mark_snippet(
+    snippet: &str,
+    query: &str,
+    highlight: bool,
+    marks: Option<(&str, &str)>,
+) -> String {
+    let snippet = &snippet.replace("**", "");
+    match (highlight, marks) {
+        (true, _) => highlight_matches(snippet, query, "**", "**"),
+        (false, Some((start, end))) => highlight_matches(snippet, query, start, end),
+        (false, None) => snippet.to_string(),
+    }
 }
 
 /// Output search results in human-readable display format
+/// Print hits in a human display format, or the default plain layout.
+fn output_human_hits(
+    hits: &[crate::search::query::SearchHit],
+    display_format: Option<DisplayFormat>,
+    wrap: WrapConfig,
+    query: &str,
+    highlight: bool,
+    marks: Option<(&str, &str)>,
+) -> CliResult<()> {
+    if let Some(display) = display_format {
+        return output_display_results(hits, display, wrap, query, highlight, marks);
+    }
+    let hyperlinks = hyperlinks_enabled();
+    for hit in hits {
+        println!("----------------------------------------------------------------");
+        println!(
+            "Score: {:.2} {} | Agent: {} | WS: {}",
+            hit.score,
+            match_type_tag(hit.match_type),
+            hit.agent,
+            hit.workspace
+        );
+        if hit.source_id == "local" && hyperlinks {
+            println!(
+                "Path: {}{}",
+                file_hyperlink(&hit.source_path, display_source_path(hit)),
+                duplicate_suffix(hit)
+            );
+        } else {
+            println!(
+                "Path: {}{}",
+                display_source_path(hit),
+                duplicate_suffix(hit)
+            );
+        }
+        // Local hits are the common case; only call out where remote ones came from.
+        if hit.source_id != "local" {
+            println!(
+                "Host: {}",
+                hit.origin_host.as_deref().unwrap_or(&hit.source_id)
+            );
+        }
+        let snippet = mark_snippet(&hit.snippet.replace('\n', " "), query, highlight, marks);
+        println!("Snippet: {}", apply_wrap(&snippet, wrap));
+    }
+    println!("----------------------------------------------------------------");
+    Ok(())
+}
+
+/// " (+N more in this file)" for hits that `--dedupe` folded others into, or
+/// " (N matching messages)" for `--by-conversation` hits.
+fn duplicate_suffix(hit: &crate::search::query::SearchHit) -> String {
+    match (hit.duplicate_count, hit.message_matches) {
+        (Some(n), _) if n > 0 => format!(" (+{n} more in this file)"),
+        (_, Some(n)) if n > 1 => format!(" ({n} matching messages)"),
+        _ => String::new(),
+    }
+}
+
+/// `[exact]`-style tag for how a hit matched, colored from literal (green)
+/// through wildcard (yellow) to fallback/semantic (magenta) when color is on.
+fn match_type_tag(match_type: crate::search::query::MatchType) -> String {
+    use crate::search::query::MatchType;
+    use colored::Colorize;
+
+    let tag = format!("[{}]", match_type.as_str());
+    match match_type {
+        MatchType::Exact => tag.green(),
+        MatchType::Prefix | MatchType::Suffix | MatchType::Substring => tag.yellow(),
+        MatchType::ImplicitWildcard | MatchType::Semantic => tag.magenta(),
+    }
+    .to_string()
+}
+
+/// `--display table`: one aligned row per hit (score, agent, time, path, snippet).
+///
+/// Columns are sized to the `--wrap` width, or the terminal's when none is set,
+/// and long paths and snippets are ellipsized to fit. With `--nowrap` nothing is
+/// truncated and rows may overflow. Low-score rows are dimmed like in the TUI.
+fn output_table(
+    hits: &[crate::search::query::SearchHit],
+    wrap: WrapConfig,
+    query: &str,
+    highlight: bool,
+    marks: Option<(&str, &str)>,
+) {
+    use colored::Colorize;
+    use ratatui::style::Modifier;
+
+    const SCORE_W: usize = 6;
+    const TIME_W: usize = 16;
+    let width = if wrap.nowrap {
+        None
+    } else {
+        wrap.width
+            .or_else(|| {
+                std::io::stdout()
+                    .is_terminal()
+                    .then(crossterm::terminal::size)
+                    .and_then(Result::ok)
+                    .map(|(cols, _)| usize::from(cols))
+            })
+            .or(Some(120))
+    };
+    let rows: Vec<(String, &str, String)> = hits
+        .iter()
+        .map(|hit| {
+            let time = hit
+                .created_at
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|dt| {
+                    dt.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_else(|| "-".to_string());
+            let snippet = hit.snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+            (time, display_source_path(hit), snippet)
+        })
+        .collect();
+    let agent_w = hits
+        .iter()
+        .map(|hit| hit.agent.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(5, 16);
+    let longest_path = rows
+        .iter()
+        .map(|(_, path, _)| path.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    // Path and snippet share what the fixed columns leave: the path takes up to
+    // 40% of it, the snippet the rest.
+    let (path_w, snippet_w) = match width {
+        Some(width) => {
+            let rest = width.saturating_sub(SCORE_W + agent_w + TIME_W + 3);
+            let path_w = longest_path.min((rest * 2 / 5).max(16));
+            (path_w, Some(rest.saturating_sub(path_w + 1).max(10)))
+        }
+        None => (longest_path, None),
+    };
+
+    let header = format!(
+        "{:<SCORE_W$} {:<agent_w$} {:<TIME_W$} {:<path_w$} SNIPPET",
+        "SCORE", "AGENT", "TIME", "PATH"
+    );
+    println!("{}", header.cyan().bold());
+    for (hit, (time, path, snippet)) in hits.iter().zip(rows) {
+        // Truncate before marking so escapes and markers are never cut in half
+        let snippet = match snippet_w {
+            Some(w) => truncate_end(&snippet, w),
+            None => snippet,
+        };
+        let line = format!(
+            "{:<SCORE_W$.2} {:<agent_w$} {:<TIME_W$} {:<path_w$} {}",
+            hit.score,
+            truncate_end(&hit.agent, agent_w),
+            time,
+            truncate_start(path, path_w),
+            mark_snippet(&snippet, query, highlight, marks)
+        );
+        if crate::ui::tui::score_style(hit.score).contains(Modifier::DIM) {
+            println!("{}", line.dimmed());
+        } else {
+            println!("{line}");
+        }
+    }
+    println!("\n{} results", hits.len());
+}
+
 fn output_display_results(
     hits: &[crate::search::query::SearchHit],
     format: DisplayFormat,
     wrap: WrapConfig,
     query: &str,
     highlight: bool,
+    marks: Option<(&str, &str)>,
 ) -> CliResult<()> {
     match format {
-        DisplayFormat::Table => {
-            // Aligned columns with headers
-            println!("{:<6} {:<12} {:<25} SNIPPET", "SCORE", "AGENT", "WORKSPACE");
-            println!("{}", "-".repeat(80));
-            for hit in hits {
-                let workspace = truncate_start(&hit.workspace, 24);
-                let snippet = hit.snippet.replace('\n', " ");
-                let snippet = if highlight {
-                    highlight_matches(&snippet, query, "**", "**")
-                } else {
-                    snippet
-                };
-                let snippet_display = truncate_end(&snippet, 50);
-                println!(
-                    "{:<6.2} {:<12} {:<25} {}",
-                    hit.score, hit.agent, workspace, snippet_display
-                );
-            }
-            println!("\n{} results", hits.len());
-        }
+        DisplayFormat::Table => output_table(hits, wrap, query, highlight, marks),
         DisplayFormat::Lines => {
             // One-liner per result
             for hit in hits {
-                let snippet = hit.snippet.replace('\n', " ");
-                let snippet = if highlight {
-                    highlight_matches(&snippet, query, "**", "**")
-                } else {
-                    snippet
-                };
-                let snippet_short = truncate_end(&snippet, 60);
+                let snippet = truncate_end(&hit.snippet.replace('\n', " "), 60);
+                let snippet_short = mark_snippet(&snippet, query, highlight, marks);
                 println!(
-                    "[{:.1}] {} | {} | {}",
-                    hit.score, hit.agent, hit.source_path, snippet_short
+                    "[{:.1}] {} {} | {}{} | {}",
+                    hit.score,
+                    match_type_tag(hit.match_type),
+                    hit.agent,
+                    display_source_path(hit),
+                    duplicate_suffix(hit),
+                    snippet_short
                 );
             }
         }
@@ -3570,7 +5381,7 @@ fn output_display_results(
             for (i, hit) in hits.iter().enumerate() {
                 println!("## {}. {} (score: {:.2})\n", i + 1, hit.agent, hit.score);
                 println!("- **Workspace**: `{}`", hit.workspace);
-                println!("- **Path**: `{}`", hit.source_path);
+                println!("- **Path**: `{}`", display_source_path(hit));
                 if let Some(ts) = hit.created_at {
                     let dt = chrono::DateTime::from_timestamp_millis(ts).map_or_else(
                         || "unknown".to_string(),
@@ -3628,7 +5439,13 @@ fn filter_hit_fields(
     hit: &crate::search::query::SearchHit,
     fields: &Option<Vec<String>>,
 ) -> serde_json::Value {
-    let all_fields = serde_json::to_value(hit).unwrap_or_default();
+    let mut all_fields = serde_json::to_value(hit).unwrap_or_default();
+    if let Some(obj) = all_fields.as_object_mut() {
+        obj.insert(
+            "permalink".to_string(),
+            serde_json::Value::String(crate::search::query::permalink(hit)),
+        );
+    }
 
     match fields {
         None => all_fields,                                      // No filtering
@@ -3646,10 +5463,18 @@ fn filter_hit_fields(
                 "created_at",
                 "line_number",
                 "match_type",
+                "permalink",
                 // Provenance fields (P3.4)
                 "source_id",
                 "origin_kind",
                 "origin_host",
+                "explanation",
+                "rerank_score",
+                "duplicate_count",
+                "message_matches",
+                "relative_path",
+                "message_id",
+                "conversation_external_id",
             ];
 
             for field in field_list {
@@ -3660,6 +5485,20 @@ fn filter_hit_fields(
                     warn!(unknown_field = %field, "Unknown field in --fields, ignoring");
                 }
             }
+            // Explanations, rerank scores and match counts were explicitly requested
+            // (--explain/--rerank/--dedupe/--by-conversation/--relative-paths); keep them
+            // under any preset.
+            for key in [
+                "explanation",
+                "rerank_score",
+                "duplicate_count",
+                "message_matches",
+                "relative_path",
+            ] {
+                if let Some(value) = all_fields.get(key) {
+                    filtered.insert(key.to_string(), value.clone());
+                }
+            }
             serde_json::Value::Object(filtered)
         }
     }
@@ -3747,6 +5586,39 @@ fn clamp_hits_to_budget(
     (kept, est, clamped)
 }
 
+/// The filters a search actually applied (after `agent:`-style query scopes and
+/// alias expansion) plus the data it read, so saved JSON results are
+/// self-describing.
+fn search_filters_json(
+    filters: &crate::search::query::SearchFilters,
+    data_dir: &Path,
+    db_path: &Path,
+) -> serde_json::Value {
+    let sorted = |set: &std::collections::HashSet<String>| {
+        let mut values: Vec<_> = set.iter().cloned().collect();
+        values.sort();
+        values
+    };
+    let iso = |ms: Option<i64>| {
+        ms.and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|dt| dt.to_rfc3339())
+    };
+    serde_json::json!({
+        "agents": sorted(&filters.agents),
+        "workspaces": sorted(&filters.workspaces),
+        "created_from": iso(filters.created_from),
+        "created_to": iso(filters.created_to),
+        "roles": sorted(&filters.roles),
+        "exclude_tool_messages": filters.exclude_tool_messages,
+        "source_filter": filters.source_filter,
+        "sources": sorted(&filters.source_ids),
+        "hosts": sorted(&filters.hosts),
+        "session_paths": filters.session_paths.len(),
+        "data_dir": data_dir.display().to_string(),
+        "db_path": db_path.display().to_string(),
+    })
+}
+
 /// Output search results in robot-friendly format
 #[allow(clippy::too_many_arguments, unused_variables)]
 fn output_robot_results(
@@ -3768,10 +5640,14 @@ fn output_robot_results(
     warning: Option<String>,
     aggregations: &Aggregations,
     total_matches: usize,
+    total: Option<usize>,
     explanation: Option<&crate::search::query::QueryExplanation>,
     timed_out: bool,
     timeout_ms: Option<u64>,
     search_mode: crate::search::query::SearchMode,
+    group_by: Option<SearchGroupBy>,
+    timings: Option<&crate::search::query::SearchTimings>,
+    filters_json: &serde_json::Value,
 ) -> CliResult<()> {
     if matches!(format, RobotFormat::Sessions) {
         // Output unique session paths only, one per line.
@@ -3803,6 +5679,26 @@ fn output_robot_results(
     let (filtered_hits, tokens_estimated, hits_clamped) =
         clamp_hits_to_budget(filtered_hits, max_tokens);
 
+    // Paging hints; null unless --with-total counted the full result set.
+    // Hits folded by --dedupe were still consumed from the ranked list.
+    let consumed = ranked_hit_count(&result.hits[..filtered_hits.len()]);
+    let has_more = total.map(|t| offset + consumed < t);
+
+    // --group-by: bucket the (already ranked and clamped) hits, keeping rank order
+    let groups = group_by.map(|by| {
+        let kept = &result.hits[..filtered_hits.len()];
+        group_hit_indices(kept, by)
+            .into_iter()
+            .map(|(key, indices)| {
+                serde_json::json!({
+                    "key": key,
+                    "count": indices.len(),
+                    "hits": indices.iter().map(|&i| &filtered_hits[i]).collect::<Vec<_>>(),
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+
     // Serialize aggregations if present
     let agg_json = if aggregations.is_empty() {
         None
@@ -3818,12 +5714,23 @@ fn output_robot_results(
                 "offset": offset,
                 "count": filtered_hits.len(),
                 "total_matches": total_matches,
+                "total": total,
+                "has_more": has_more,
                 "hits": filtered_hits,
                 "max_tokens": max_tokens,
                 "request_id": request_id,
                 "cursor": input_cursor,
                 "hits_clamped": hits_clamped,
+                "cache": result_cache_json(&result.cache_stats),
+                "filters": filters_json,
             });
+            if let (Some(groups), serde_json::Value::Object(map)) = (&groups, &mut payload) {
+                map.remove("hits");
+                map.insert("groups".to_string(), serde_json::json!(groups));
+            }
+            if let (Some(t), serde_json::Value::Object(map)) = (timings, &mut payload) {
+                map.insert("timings_ms".to_string(), search_timings_json(t));
+            }
 
             // Add suggestions if present
             if !result.suggestions.is_empty()
@@ -3923,6 +5830,7 @@ fn output_robot_results(
                 || agg_json.is_some()
                 || !result.suggestions.is_empty()
                 || explanation.is_some()
+                || timings.is_some()
             {
                 let mut meta = serde_json::json!({
                     "_meta": {
@@ -3931,6 +5839,8 @@ fn output_robot_results(
                         "offset": offset,
                         "count": filtered_hits.len(),
                         "total_matches": total_matches,
+                        "total": total,
+                        "has_more": has_more,
                         "elapsed_ms": elapsed_ms,
                         "search_mode": search_mode,
                         "wildcard_fallback": result.wildcard_fallback,
@@ -3978,6 +5888,9 @@ fn output_robot_results(
                         serde_json::to_value(exp).unwrap_or_default(),
                     );
                 }
+                if let (Some(t), serde_json::Value::Object(map)) = (timings, &mut meta) {
+                    map.insert("timings_ms".to_string(), search_timings_json(t));
+                }
                 if let Some(warn) = &warning
                     && let Some(m) = meta.get_mut("_meta").and_then(|v| v.as_object_mut())
                 {
@@ -4011,9 +5924,25 @@ fn output_robot_results(
                 }
                 println!("{}", serde_json::to_string(&meta).unwrap_or_default());
             }
-            // One hit per line (with field filtering applied)
-            for hit in &filtered_hits {
-                println!("{}", serde_json::to_string(hit).unwrap_or_default());
+            // One hit per line (with field filtering applied); --group-by emits
+            // group by group and tags each line with its group key
+            match &groups {
+                Some(groups) => {
+                    for group in groups {
+                        for hit in group["hits"].as_array().into_iter().flatten() {
+                            let mut hit = hit.clone();
+                            if let serde_json::Value::Object(ref mut map) = hit {
+                                map.insert("group".to_string(), group["key"].clone());
+                            }
+                            println!("{}", serde_json::to_string(&hit).unwrap_or_default());
+                        }
+                    }
+                }
+                None => {
+                    for hit in &filtered_hits {
+                        println!("{}", serde_json::to_string(hit).unwrap_or_default());
+                    }
+                }
             }
         }
         RobotFormat::Compact => {
@@ -4024,12 +5953,23 @@ fn output_robot_results(
                 "offset": offset,
                 "count": filtered_hits.len(),
                 "total_matches": total_matches,
+                "total": total,
+                "has_more": has_more,
                 "hits": filtered_hits,
                 "max_tokens": max_tokens,
                 "request_id": request_id,
                 "cursor": input_cursor,
                 "hits_clamped": hits_clamped,
+                "cache": result_cache_json(&result.cache_stats),
+                "filters": filters_json,
             });
+            if let (Some(groups), serde_json::Value::Object(map)) = (&groups, &mut payload) {
+                map.remove("hits");
+                map.insert("groups".to_string(), serde_json::json!(groups));
+            }
+            if let (Some(t), serde_json::Value::Object(map)) = (timings, &mut payload) {
+                map.insert("timings_ms".to_string(), search_timings_json(t));
+            }
 
             // Add suggestions if present
             if !result.suggestions.is_empty()
@@ -4124,12 +6064,27 @@ fn output_robot_results(
     Ok(())
 }
 
+/// Human-readable "N minutes ago" for an age in seconds.
+fn format_age(age_secs: u64) -> String {
+    if age_secs < 60 {
+        format!("{age_secs} seconds ago")
+    } else if age_secs < 3600 {
+        format!("{} minutes ago", age_secs / 60)
+    } else if age_secs < 86400 {
+        format!("{} hours ago", age_secs / 3600)
+    } else {
+        format!("{} days ago", age_secs / 86400)
+    }
+}
+
 fn run_stats(
     data_dir_override: &Option<PathBuf>,
     db_override: Option<PathBuf>,
     json: bool,
+    compact: bool,
     source: Option<&str>,
     by_source: bool,
+    porcelain: bool,
 ) -> CliResult<()> {
     use crate::sources::provenance::SourceFilter;
     use rusqlite::Connection;
@@ -4230,6 +6185,22 @@ fn run_stats(
             .collect()
     };
 
+    // Merge aliased slugs (e.g. gemini_cli) under their canonical agent.
+    let agent_aliases = crate::search::agent_alias::AgentAliases::load();
+    let mut merged_agents: Vec<(String, i64)> = Vec::new();
+    for (slug, count) in agent_rows {
+        let canonical = agent_aliases.canonical(&slug).to_string();
+        match merged_agents
+            .iter_mut()
+            .find(|(agent, _)| *agent == canonical)
+        {
+            Some(entry) => entry.1 += count,
+            None => merged_agents.push((canonical, count)),
+        }
+    }
+    merged_agents.sort_by_key(|(slug, count)| (std::cmp::Reverse(*count), slug.clone()));
+    let agent_rows = merged_agents;
+
     // Get workspace breakdown with source filter (top 10)
     let ws_sql = format!(
         "SELECT w.path, COUNT(*) FROM conversations c JOIN workspaces w ON c.workspace_id = w.id{source_where} GROUP BY w.path ORDER BY COUNT(*) DESC LIMIT 10"
@@ -4291,7 +6262,62 @@ fn run_stats(
         Vec::new()
     };
 
-    if json {
+    // Indexer freshness: last completed scan and watch-mode watermarks
+    let last_scan_ts: Option<i64> = conn
+        .query_row(
+            "SELECT value FROM meta WHERE key = 'last_scan_ts'",
+            [],
+            |r| r.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|v| v.parse().ok());
+    let last_scan_dt = last_scan_ts.and_then(chrono::DateTime::from_timestamp_millis);
+    let index_generation: u64 = conn
+        .query_row(
+            "SELECT value FROM meta WHERE key = 'index_generation'",
+            [],
+            |r| r.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let watch_state = indexer::watch_state_watermarks(&data_dir);
+
+    if porcelain {
+        // Keys are additive-only and always printed in this order; see the
+        // `robot-docs contracts` topic before changing anything here.
+        let iso = |ts: Option<i64>| {
+            ts.and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default()
+        };
+        let one_line = |s: &str| s.replace('\n', "\\n");
+        let mut agents = agent_rows.clone();
+        agents.sort();
+        let mut workspaces = ws_rows.clone();
+        workspaces.sort();
+        let mut sources = source_rows.clone();
+        sources.sort();
+
+        println!("conversations={conversation_count}");
+        println!("messages={message_count}");
+        for (agent, count) in &agents {
+            println!("agent.{}={count}", one_line(agent));
+        }
+        for (ws, count) in &workspaces {
+            println!("workspace.{}={count}", one_line(ws));
+        }
+        for (src, convs, msgs) in &sources {
+            println!("source.{}.conversations={convs}", one_line(src));
+            println!("source.{}.messages={msgs}", one_line(src));
+        }
+        println!("oldest={}", iso(oldest));
+        println!("newest={}", iso(newest));
+        println!("last_scan={}", iso(last_scan_ts));
+        if let Some(ref filter) = source_filter {
+            println!("source_filter={filter}");
+        }
+    } else if json {
         let mut payload = serde_json::json!({
             "conversations": conversation_count,
             "messages": message_count,
@@ -4302,6 +6328,24 @@ fn run_stats(
                 "newest": newest.map(|ts| chrono::DateTime::from_timestamp_millis(ts).map(|d| d.to_rfc3339())),
             },
             "db_path": db_path.display().to_string(),
+            "last_scan": {
+                "ts": last_scan_ts,
+                "iso": last_scan_dt.map(|d| d.to_rfc3339()),
+            },
+            "index_generation": index_generation,
+            "watch_state": watch_state.as_ref().map(|w| {
+                w.iter()
+                    .map(|(connector, ts)| {
+                        (
+                            connector.to_string(),
+                            serde_json::json!({
+                                "ts": ts,
+                                "iso": chrono::DateTime::from_timestamp_millis(*ts).map(|d| d.to_rfc3339()),
+                            }),
+                        )
+                    })
+                    .collect::<serde_json::Map<_, _>>()
+            }),
         });
 
         // Add source filter info if specified (P3.7)
@@ -4325,10 +6369,12 @@ fn run_stats(
             );
         }
 
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&payload).unwrap_or_default()
-        );
+        let rendered = if compact {
+            serde_json::to_string(&payload)
+        } else {
+            serde_json::to_string_pretty(&payload)
+        };
+        println!("{}", rendered.unwrap_or_default());
     } else {
         // Header with source filter indicator
         let title = if let Some(ref filter) = source_filter {
@@ -4339,6 +6385,31 @@ fn run_stats(
         println!("{title}");
         println!("{}", "=".repeat(title.len()));
         println!("Database: {}", db_path.display());
+        match last_scan_dt {
+            Some(dt) => {
+                let age = (chrono::Utc::now() - dt).num_seconds().max(0) as u64;
+                println!(
+                    "Last scan: {} ({})",
+                    dt.format("%Y-%m-%d %H:%M:%S UTC"),
+                    format_age(age)
+                );
+            }
+            None => println!("Last scan: never"),
+        }
+        println!("Index generation: {index_generation}");
+        match &watch_state {
+            Some(w) if !w.is_empty() => {
+                println!("Watch state:");
+                for (connector, ts) in w {
+                    let when = chrono::DateTime::from_timestamp_millis(*ts)
+                        .map(|d| d.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                        .unwrap_or_else(|| ts.to_string());
+                    println!("  {connector}: {when}");
+                }
+            }
+            Some(_) => println!("Watch state: empty"),
+            None => println!("Watch state: none (watch mode has not run)"),
+        }
         println!();
 
         // Show by_source breakdown if requested (P3.7)
@@ -4751,15 +6822,7 @@ fn run_status(
         println!("Index:");
         if index_exists {
             if let Some(age) = index_age_secs {
-                let age_str = if age < 60 {
-                    format!("{age} seconds ago")
-                } else if age < 3600 {
-                    format!("{} minutes ago", age / 60)
-                } else if age < 86400 {
-                    format!("{} hours ago", age / 3600)
-                } else {
-                    format!("{} days ago", age / 86400)
-                };
+                let age_str = format_age(age);
                 let stale_indicator = if is_stale { " (stale)" } else { "" };
                 println!("  Last indexed: {age_str}{stale_indicator}");
             } else {
@@ -5529,10 +7592,12 @@ fn run_doctor(
     }
 
     // 6. Check sources.toml
-    let sources_path = dirs::config_dir()
-        .unwrap_or_else(|| data_dir.clone())
-        .join("cass")
-        .join("sources.toml");
+    let sources_path = crate::sources::config::SourcesConfig::config_path().unwrap_or_else(|_| {
+        dirs::config_dir()
+            .unwrap_or_else(|| data_dir.clone())
+            .join("cass")
+            .join("sources.toml")
+    });
     if sources_path.exists() {
         match std::fs::read_to_string(&sources_path) {
             Ok(content) => match toml::from_str::<toml::Value>(&content) {
@@ -5566,34 +7631,42 @@ fn run_doctor(
         );
     }
 
-    // 7. Check common session directories exist
-    let mut session_dirs_found = 0usize;
-    let home = dirs::home_dir().unwrap_or_default();
-    let session_paths = [
-        home.join(".claude"),        // Claude Code
-        home.join(".codex"),         // Codex
-        home.join(".cursor"),        // Cursor
-        home.join(".aider"),         // Aider
-        home.join(".chatgpt"),       // ChatGPT
-        home.join(".config/gemini"), // Gemini
-    ];
-    for path in &session_paths {
-        if path.exists() {
-            session_dirs_found += 1;
-        }
+    // 7. Run every connector's detection so missing agents can be explained
+    #[derive(serde::Serialize)]
+    struct ConnectorDetection {
+        name: &'static str,
+        #[serde(flatten)]
+        detection: crate::connectors::DetectionResult,
     }
-    if session_dirs_found > 0 {
+
+    let connectors: Vec<ConnectorDetection> = indexer::get_connector_factories()
+        .into_iter()
+        .map(|(name, factory)| ConnectorDetection {
+            name,
+            detection: factory().detect(),
+        })
+        .collect();
+    let detected_connectors: Vec<&str> = connectors
+        .iter()
+        .filter(|c| c.detection.detected)
+        .map(|c| c.name)
+        .collect();
+    if detected_connectors.is_empty() {
         add_check!(
             "sessions",
-            "pass",
-            format!("Found {} agent session directories", session_dirs_found),
+            "warn",
+            "No agent session directories found (see connectors below for paths checked)",
             false
         );
     } else {
         add_check!(
             "sessions",
-            "warn",
-            "No agent session directories found",
+            "pass",
+            format!(
+                "Detected {} connector(s): {}",
+                detected_connectors.len(),
+                detected_connectors.join(", ")
+            ),
             false
         );
     }
@@ -5722,13 +7795,9 @@ fn run_doctor(
                 needs_rebuild = true;
             } else {
                 let index_opts = indexer::IndexOptions {
-                    full: false,
                     force_rebuild,
-                    watch: false,
-                    watch_once_paths: None,
-                    db_path: db_path.clone(),
-                    data_dir: data_dir.clone(),
                     progress: Some(progress.clone()),
+                    ..indexer::IndexOptions::new(db_path.clone(), data_dir.clone())
                 };
 
                 let rebuild_handle = std::thread::spawn(move || {
@@ -5811,6 +7880,7 @@ fn run_doctor(
             "auto_fix_applied": auto_fix_applied,
             "auto_fix_actions": auto_fix_actions,
             "checks": checks,
+            "connectors": connectors,
             "_meta": {
                 "elapsed_ms": elapsed_ms,
                 "data_dir": data_dir.display().to_string(),
@@ -5857,6 +7927,25 @@ fn run_doctor(
             );
         }
 
+        // Detection evidence is what explains "nothing indexed"; always show it then.
+        if verbose || detected_connectors.is_empty() {
+            println!();
+            println!("{}", "Connectors:".bold());
+            for c in &connectors {
+                let icon = if c.detection.detected {
+                    "✓".green()
+                } else {
+                    "·".dimmed()
+                };
+                println!(
+                    "  {} {}: {}",
+                    icon,
+                    c.name.bold(),
+                    c.detection.evidence.join("; ")
+                );
+            }
+        }
+
         println!();
         if all_pass {
             println!("{} All checks passed ({elapsed_ms}ms)", "✓".green());
@@ -6320,6 +8409,18 @@ fn build_global_flag_schemas() -> Vec<ArgumentSchema> {
             enum_values: None,
             repeatable: None,
         },
+        ArgumentSchema {
+            name: "sources".to_string(),
+            short: None,
+            description: "Path to the remote sources config (overrides CASS_SOURCES_CONFIG)"
+                .to_string(),
+            arg_type: "option".to_string(),
+            value_type: Some("path".to_string()),
+            required: false,
+            default: None,
+            enum_values: None,
+            repeatable: None,
+        },
         ArgumentSchema {
             name: "quiet".to_string(),
             short: Some('q'),
@@ -6395,6 +8496,17 @@ fn build_global_flag_schemas() -> Vec<ArgumentSchema> {
             enum_values: None,
             repeatable: None,
         },
+        ArgumentSchema {
+            name: "no-update-check".to_string(),
+            short: None,
+            description: "Skip the startup update check and prompt (no network access)".to_string(),
+            arg_type: "flag".to_string(),
+            value_type: None,
+            required: false,
+            default: None,
+            enum_values: None,
+            repeatable: None,
+        },
     ]
 }
 
@@ -6439,9 +8551,10 @@ fn run_capabilities(json: bool) -> CliResult<()> {
             "cursor".to_string(),
             "chatgpt".to_string(),
             "pi_agent".to_string(),
+            "copilot".to_string(),
         ],
         limits: CapabilitiesLimits {
-            max_limit: 10000,
+            max_limit: max_search_limit(),
             max_content_length: 0, // 0 = unlimited
             max_fields: 50,
             max_agg_buckets: 10,
@@ -6750,6 +8863,111 @@ fn build_response_schemas() -> std::collections::HashMap<String, serde_json::Val
     use serde_json::json;
     let mut schemas = std::collections::HashMap::new();
 
+    // Kept out of the envelope literal below so `json!` stays under the
+    // default macro recursion limit.
+    let hit_schema = json!({
+        "type": "object",
+        "properties": {
+            "source_path": { "type": "string" },
+            "line_number": { "type": ["integer", "null"] },
+            "agent": { "type": "string" },
+            "workspace": { "type": ["string", "null"] },
+            "title": { "type": ["string", "null"] },
+            "content": { "type": ["string", "null"] },
+            "snippet": { "type": ["string", "null"] },
+            "score": { "type": ["number", "null"] },
+            "created_at": { "type": ["integer", "string", "null"] },
+            "match_type": {
+                "type": ["string", "null"],
+                "enum": ["exact", "prefix", "suffix", "substring", "implicit_wildcard", "semantic", null],
+                "description": "How the hit matched: literal term, wildcard expansion, automatic wildcard fallback, or embedding similarity"
+            },
+            "permalink": { "type": "string", "description": "Stable message link: agent/session#msg-idx" },
+            "source_id": { "type": "string", "description": "Source identifier (e.g., 'local', 'work-laptop'); filter with --source" },
+            "origin_kind": { "type": "string", "description": "Origin kind ('local' or 'ssh')" },
+            "origin_host": { "type": ["string", "null"], "description": "Host label for remote sources; filter with --host" },
+            "explanation": { "type": "object", "description": "Tantivy scoring breakdown (only with --explain)" },
+            "rerank_score": { "type": "number", "description": "Cross-encoder score (only with --rerank)" },
+            "duplicate_count": { "type": "integer", "description": "Other hits from the same source_path collapsed into this one (only with --dedupe)" },
+            "message_matches": { "type": "integer", "description": "Matching messages in this hit's conversation (only with --by-conversation)" },
+            "relative_path": { "type": "string", "description": "source_path relative to the hit's workspace (only with --relative-paths, when under it)" },
+            "message_id": { "type": "integer", "description": "Database id of the matched message; stable across reindexing. Open it with 'cass show --message <id>'" },
+            "conversation_external_id": { "type": "string", "description": "Connector-assigned conversation id (e.g. session UUID), when known" }
+        }
+    });
+    let filters_schema = json!({
+        "type": "object",
+        "description": "Effective filters and data location that produced these results",
+        "properties": {
+            "agents": { "type": "array", "items": { "type": "string" } },
+            "workspaces": { "type": "array", "items": { "type": "string" } },
+            "created_from": { "type": ["string", "null"], "description": "RFC 3339 lower time bound" },
+            "created_to": { "type": ["string", "null"], "description": "RFC 3339 upper time bound" },
+            "roles": { "type": "array", "items": { "type": "string" } },
+            "exclude_tool_messages": { "type": "boolean" },
+            "source_filter": { "type": ["string", "object"], "description": "'all' or 'remote' (source kind)" },
+            "sources": { "type": "array", "items": { "type": "string" }, "description": "Source ids from --source" },
+            "hosts": { "type": "array", "items": { "type": "string" } },
+            "session_paths": { "type": "integer", "description": "Sessions selected by --sessions-from/--in-session (0 = no restriction)" },
+            "data_dir": { "type": "string" },
+            "db_path": { "type": "string" }
+        }
+    });
+    let meta_schema = json!({
+        "type": "object",
+        "properties": {
+            "elapsed_ms": { "type": "integer" },
+            "wildcard_fallback": { "type": "boolean" },
+            "cache_stats": {
+                "type": "object",
+                "properties": {
+                    "hits": { "type": "integer" },
+                    "misses": { "type": "integer" },
+                    "shortfall": { "type": "integer" }
+                }
+            },
+            "tokens_estimated": { "type": ["integer", "null"] },
+            "max_tokens": { "type": ["integer", "null"] },
+            "request_id": { "type": ["string", "null"] },
+            "next_cursor": { "type": ["string", "null"] },
+            "hits_clamped": { "type": "boolean" },
+            "state": {
+                "type": "object",
+                "properties": {
+                    "index": {
+                        "type": "object",
+                        "properties": {
+                            "exists": { "type": "boolean" },
+                            "fresh": { "type": "boolean" },
+                            "last_indexed_at": { "type": ["string", "null"] },
+                            "age_seconds": { "type": ["integer", "null"] },
+                            "stale": { "type": "boolean" },
+                            "stale_threshold_seconds": { "type": "integer" }
+                        }
+                    },
+                    "database": {
+                        "type": "object",
+                        "properties": {
+                            "exists": { "type": "boolean" },
+                            "conversations": { "type": "integer" },
+                            "messages": { "type": "integer" }
+                        }
+                    }
+                }
+            },
+            "index_freshness": {
+                "type": "object",
+                "properties": {
+                    "last_indexed_at": { "type": ["string", "null"] },
+                    "age_seconds": { "type": ["integer", "null"] },
+                    "stale": { "type": "boolean" },
+                    "pending_sessions": { "type": "integer" },
+                    "fresh": { "type": "boolean" }
+                }
+            }
+        }
+    });
+
     schemas.insert(
         "search".to_string(),
         json!({
@@ -6760,31 +8978,49 @@ fn build_response_schemas() -> std::collections::HashMap<String, serde_json::Val
                 "offset": { "type": "integer" },
                 "count": { "type": "integer" },
                 "total_matches": { "type": "integer" },
+                "total": { "type": ["integer", "null"], "description": "All matching messages (only with --with-total, lexical mode)" },
+                "has_more": { "type": ["boolean", "null"], "description": "offset + count < total (only with --with-total)" },
                 "max_tokens": { "type": ["integer", "null"] },
                 "request_id": { "type": ["string", "null"] },
                 "cursor": { "type": ["string", "null"] },
                 "hits_clamped": { "type": "boolean" },
+                "filters": filters_schema,
                 "hits": {
                     "type": "array",
+                    "items": hit_schema
+                },
+                "groups": {
+                    "type": "array",
+                    "description": "Hits bucketed by --group-by (replaces `hits`), in order of each group's best hit",
                     "items": {
                         "type": "object",
                         "properties": {
-                            "source_path": { "type": "string" },
-                            "line_number": { "type": ["integer", "null"] },
-                            "agent": { "type": "string" },
-                            "workspace": { "type": ["string", "null"] },
-                            "title": { "type": ["string", "null"] },
-                            "content": { "type": ["string", "null"] },
-                            "snippet": { "type": ["string", "null"] },
-                            "score": { "type": ["number", "null"] },
-                            "created_at": { "type": ["integer", "string", "null"] },
-                            "match_type": { "type": ["string", "null"] },
-                            "source_id": { "type": "string", "description": "Source identifier (e.g., 'local', 'work-laptop')" },
-                            "origin_kind": { "type": "string", "description": "Origin kind ('local' or 'ssh')" },
-                            "origin_host": { "type": ["string", "null"], "description": "Host label for remote sources" }
+                            "key": { "type": "string", "description": "Workspace, agent, or local YYYY-MM-DD ('unknown' when missing)" },
+                            "count": { "type": "integer" },
+                            "hits": { "type": "array", "description": "Hits in rank order, same shape as `hits`" }
                         }
                     }
                 },
+                "timings_ms": {
+                    "type": "object",
+                    "description": "Milliseconds spent per search phase (only with --profile)",
+                    "properties": {
+                        "open": { "type": "number" },
+                        "parse": { "type": "number" },
+                        "search": { "type": "number" },
+                        "highlight": { "type": "number" },
+                        "total": { "type": "number" }
+                    }
+                },
+                "cache": {
+                    "type": "object",
+                    "description": "On-disk result cache usage",
+                    "properties": {
+                        "status": { "type": "string", "enum": ["hit", "miss", "bypass"] },
+                        "hits": { "type": "integer" },
+                        "misses": { "type": "integer" }
+                    }
+                },
                 "aggregations": {
                     "type": ["object", "null"],
                     "additionalProperties": {
@@ -6799,60 +9035,7 @@ fn build_response_schemas() -> std::collections::HashMap<String, serde_json::Val
                     }
                 },
                 "_warning": { "type": ["string", "null"] },
-                "_meta": {
-                    "type": "object",
-                    "properties": {
-                        "elapsed_ms": { "type": "integer" },
-                        "wildcard_fallback": { "type": "boolean" },
-                        "cache_stats": {
-                            "type": "object",
-                            "properties": {
-                                "hits": { "type": "integer" },
-                                "misses": { "type": "integer" },
-                                "shortfall": { "type": "integer" }
-                            }
-                        },
-                        "tokens_estimated": { "type": ["integer", "null"] },
-                        "max_tokens": { "type": ["integer", "null"] },
-                        "request_id": { "type": ["string", "null"] },
-                        "next_cursor": { "type": ["string", "null"] },
-                        "hits_clamped": { "type": "boolean" },
-                        "state": {
-                            "type": "object",
-                            "properties": {
-                                "index": {
-                                    "type": "object",
-                                    "properties": {
-                                        "exists": { "type": "boolean" },
-                                        "fresh": { "type": "boolean" },
-                                        "last_indexed_at": { "type": ["string", "null"] },
-                                        "age_seconds": { "type": ["integer", "null"] },
-                                        "stale": { "type": "boolean" },
-                                        "stale_threshold_seconds": { "type": "integer" }
-                                    }
-                                },
-                                "database": {
-                                    "type": "object",
-                                    "properties": {
-                                        "exists": { "type": "boolean" },
-                                        "conversations": { "type": "integer" },
-                                        "messages": { "type": "integer" }
-                                    }
-                                }
-                            }
-                        },
-                        "index_freshness": {
-                            "type": "object",
-                            "properties": {
-                                "last_indexed_at": { "type": ["string", "null"] },
-                                "age_seconds": { "type": ["integer", "null"] },
-                                "stale": { "type": "boolean" },
-                                "pending_sessions": { "type": "integer" },
-                                "fresh": { "type": "boolean" }
-                            }
-                        }
-                    }
-                }
+                "_meta": meta_schema
             }
         }),
     );
@@ -7056,6 +9239,7 @@ fn build_response_schemas() -> std::collections::HashMap<String, serde_json::Val
                 "db_path": { "type": ["string", "null"] },
                 "conversations": { "type": ["integer", "null"] },
                 "messages": { "type": ["integer", "null"] },
+                "skipped_files": { "type": ["integer", "null"], "description": "Session files skipped for exceeding --max-file-size" },
                 "error": { "type": ["string", "null"] }
             }
         }),
@@ -7113,6 +9297,52 @@ fn build_response_schemas() -> std::collections::HashMap<String, serde_json::Val
         }),
     );
 
+    schemas.insert(
+        "config".to_string(),
+        json!({
+            "type": "object",
+            "properties": {
+                "data": { "type": "string" },
+                "db": { "type": "string" },
+                "index": { "type": "string" },
+                "sources": { "type": ["string", "null"] },
+                "log": { "type": "string" }
+            }
+        }),
+    );
+
+    schemas.insert(
+        "show".to_string(),
+        json!({
+            "type": "object",
+            "properties": {
+                "source_path": { "type": "string" },
+                "agent": { "type": "string" },
+                "external_id": { "type": ["string", "null"] },
+                "title": { "type": ["string", "null"] },
+                "workspace": { "type": ["string", "null"] },
+                "started_at": { "type": ["integer", "null"] },
+                "ended_at": { "type": ["integer", "null"] },
+                "source_id": { "type": "string" },
+                "origin_host": { "type": ["string", "null"] },
+                "message_count": { "type": "integer" },
+                "messages": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "idx": { "type": "integer" },
+                            "role": { "type": "string" },
+                            "author": { "type": ["string", "null"] },
+                            "created_at": { "type": ["integer", "null"] },
+                            "content": { "type": "string" }
+                        }
+                    }
+                }
+            }
+        }),
+    );
+
     schemas.insert(
         "view".to_string(),
         json!({
@@ -7171,7 +9401,22 @@ fn build_response_schemas() -> std::collections::HashMap<String, serde_json::Val
                         "newest": { "type": ["string", "null"] }
                     }
                 },
-                "db_path": { "type": "string" }
+                "db_path": { "type": "string" },
+                "last_scan": {
+                    "type": "object",
+                    "properties": {
+                        "ts": { "type": ["integer", "null"], "description": "Last completed index scan (ms since epoch)" },
+                        "iso": { "type": ["string", "null"] }
+                    }
+                },
+                "index_generation": {
+                    "type": "integer",
+                    "description": "Bumped on every index commit (never reset); compare to invalidate caches"
+                },
+                "watch_state": {
+                    "type": ["object", "null"],
+                    "description": "Per-connector watch watermarks ({ts, iso}); null when watch mode has not run"
+                }
             }
         }),
     );
@@ -7226,6 +9471,23 @@ fn build_response_schemas() -> std::collections::HashMap<String, serde_json::Val
         }),
     );
 
+    schemas.insert(
+        "trace-spans".to_string(),
+        json!({
+            "type": "object",
+            "description": "Per-phase records that `cass index --trace-file` writes before its summary record",
+            "properties": {
+                "type": { "type": "string", "description": "Always \"span\"" },
+                "cmd": { "type": "string", "description": "Always \"index\"" },
+                "ts": { "type": "string", "description": "RFC3339 time the phase ended" },
+                "phase": { "type": "string", "enum": ["scan", "ingest", "commit"] },
+                "connector": { "type": ["string", "null"], "description": "Connector slug; null for commit" },
+                "duration_ms": { "type": "integer" },
+                "conversations": { "type": "integer", "description": "Conversations scanned, ingested or committed" }
+            }
+        }),
+    );
+
     schemas
 }
 
@@ -7233,32 +9495,43 @@ fn run_view(path: &PathBuf, line: Option<usize>, context: usize, json: bool) ->
     use std::fs::File;
     use std::io::{BufRead, BufReader};
 
-    if !path.exists() {
-        return Err(CliError {
-            code: 3,
-            kind: "file-not-found",
-            message: format!("File not found: {}", path.display()),
+    // `-` reads the content from stdin, e.g. `some-tool | cass view - -n 42`.
+    let from_stdin = path.as_os_str() == "-";
+    let display_path = if from_stdin {
+        "<stdin>".to_string()
+    } else {
+        path.display().to_string()
+    };
+
+    let reader: Box<dyn BufRead> = if from_stdin {
+        Box::new(std::io::stdin().lock())
+    } else {
+        if !path.exists() {
+            return Err(CliError {
+                code: 3,
+                kind: "file-not-found",
+                message: format!("File not found: {display_path}"),
+                hint: None,
+                retryable: false,
+            });
+        }
+        let file = File::open(path).map_err(|e| CliError {
+            code: 9,
+            kind: "file-open",
+            message: format!("Failed to open file: {e}"),
             hint: None,
             retryable: false,
-        });
-    }
-
-    let file = File::open(path).map_err(|e| CliError {
-        code: 9,
-        kind: "file-open",
-        message: format!("Failed to open file: {e}"),
-        hint: None,
-        retryable: false,
-    })?;
+        })?;
+        Box::new(BufReader::new(file))
+    };
 
-    let reader = BufReader::new(file);
     let lines: Vec<String> = reader.lines().map_while(Result::ok).collect();
 
     if lines.is_empty() {
         return Err(CliError {
             code: 9,
             kind: "empty-file",
-            message: format!("File is empty: {}", path.display()),
+            message: format!("File is empty: {display_path}"),
             hint: None,
             retryable: false,
         });
@@ -7313,7 +9586,7 @@ fn run_view(path: &PathBuf, line: Option<usize>, context: usize, json: bool) ->
             .collect();
 
         let payload = serde_json::json!({
-            "path": path.display().to_string(),
+            "path": display_path,
             "target_line": if highlight_line { Some(target_line) } else { None::<usize> },
             "context": context,
             "lines": content_lines,
@@ -7324,7 +9597,7 @@ fn run_view(path: &PathBuf, line: Option<usize>, context: usize, json: bool) ->
             serde_json::to_string_pretty(&payload).unwrap_or_default()
         );
     } else {
-        println!("File: {}", path.display());
+        println!("File: {display_path}");
         if highlight_line {
             println!("Line: {target_line} (context: {context})");
         }
@@ -7361,16 +9634,23 @@ fn spawn_background_indexer(
     std::thread::spawn(move || {
         let db_path = db.unwrap_or_else(|| data_dir.join("agent_search.db"));
         let opts = IndexOptions {
-            full: false,
-            force_rebuild: false,
             watch: true,
             watch_once_paths: read_watch_once_paths_env(),
-            db_path,
-            data_dir,
             progress,
+            ..IndexOptions::new(db_path, data_dir)
         };
         // Pass the receiver to run_index so it can listen for commands
         if let Err(e) = indexer::run_index(opts, Some((tx_clone, rx))) {
+            if let Some(indexer::lock::IndexLockError::Busy { pid, .. }) =
+                e.downcast_ref::<indexer::lock::IndexLockError>()
+            {
+                // Another indexer (e.g. a manual `cass index`) owns the data dir; skip quietly.
+                tracing::info!(pid, "background indexer skipped: index lock held");
+                if let Some(p) = progress_for_error {
+                    p.phase.store(0, std::sync::atomic::Ordering::Relaxed);
+                }
+                return;
+            }
             warn!("Background indexer failed: {}", e);
             if let Some(p) = progress_for_error {
                 if let Ok(mut last_error) = p.last_error.lock() {
@@ -7385,6 +9665,114 @@ fn spawn_background_indexer(
     Some(tx)
 }
 
+/// Map an index-lock conflict to the documented exit code 7 (lock/busy).
+fn lock_busy_error(e: &anyhow::Error) -> Option<CliError> {
+    use indexer::lock::IndexLockError;
+    let IndexLockError::Busy { pid, path, .. } = e.downcast_ref::<IndexLockError>()? else {
+        return None;
+    };
+    Some(CliError {
+        code: 7,
+        kind: "lock-busy",
+        message: format!("another indexer (pid {pid}) is running; lock: {}", path.display()),
+        hint: Some(
+            "Wait for the running indexer (e.g. `cass tui` or `cass index --watch`) to finish, then retry"
+                .to_string(),
+        ),
+        retryable: true,
+    })
+}
+
+/// Indexing needs to write the database and index; say so instead of failing
+/// partway with an opaque I/O error.
+fn read_only_data_dir_error(dir: &Path) -> CliError {
+    CliError {
+        code: 7,
+        kind: "read-only-data-dir",
+        message: format!("cannot index: {} is not writable", dir.display()),
+        hint: Some(
+            "Pass --data-dir (and --db) pointing at a writable directory, or fix its permissions; searching a read-only data dir still works"
+                .to_string(),
+        ),
+        retryable: false,
+    }
+}
+
+/// Map a database written by a newer cass to the documented exit code 6 (incompatible-version).
+fn incompatible_version_error(e: &anyhow::Error) -> Option<CliError> {
+    use crate::storage::sqlite::MigrationError;
+    let MigrationError::IncompatibleVersion { found, supported } =
+        e.chain().find_map(|c| c.downcast_ref::<MigrationError>())?
+    else {
+        return None;
+    };
+    Some(CliError {
+        code: 6,
+        kind: "incompatible-version",
+        message: format!(
+            "database schema version {found} is newer than this cass supports ({supported})"
+        ),
+        hint: Some(
+            "Upgrade cass, or point --data-dir at a separate directory for this version"
+                .to_string(),
+        ),
+        retryable: false,
+    })
+}
+
+fn filtered_rebuild_error(e: &anyhow::Error) -> Option<CliError> {
+    let indexer::IndexError::FilteredRebuild { .. } = e.downcast_ref::<indexer::IndexError>()?
+    else {
+        return None;
+    };
+    Some(CliError::usage(
+        e.to_string(),
+        Some(
+            "Run `cass index` without --connector/--skip-connector/--include/--exclude to rebuild everything"
+                .to_string(),
+        ),
+    ))
+}
+
+/// Single-file writes refuse an index that needs a full rebuild; point at `cass index`.
+fn stale_index_error(e: &anyhow::Error) -> Option<CliError> {
+    let indexer::IndexError::StaleIndex { .. } = e.downcast_ref::<indexer::IndexError>()? else {
+        return None;
+    };
+    Some(CliError {
+        code: 9,
+        kind: "index-stale",
+        message: e.to_string(),
+        hint: Some("Run `cass index` to rebuild the search index, then retry".to_string()),
+        retryable: false,
+    })
+}
+
+/// Validate connector slugs passed to `--connector`/`--skip-connector`.
+///
+/// Returns `None` when no slugs were given, or a usage error naming the valid connectors.
+fn parse_connector_slugs(
+    slugs: &[String],
+    flag: &str,
+) -> CliResult<Option<std::collections::HashSet<String>>> {
+    if slugs.is_empty() {
+        return Ok(None);
+    }
+    let valid = indexer::connector_slugs();
+    let mut set = std::collections::HashSet::new();
+    for slug in slugs {
+        let normalized = slug.trim().to_lowercase();
+        if !valid.contains(&normalized.as_str()) {
+            return Err(CliError::usage(
+                format!("Unknown connector '{slug}' for {flag}"),
+                Some(format!("Valid connectors: {}", valid.join(", "))),
+            ));
+        }
+        set.insert(normalized);
+    }
+    Ok(Some(set))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_index_with_data(
     db_override: Option<PathBuf>,
@@ -7392,7 +9780,22 @@ fn run_index_with_data(
     force_rebuild: bool,
     watch: bool,
     watch_once: Option<Vec<PathBuf>>,
+    connector: Vec<String>,
+    skip_connector: Vec<String>,
+    watch_debounce_ms: u64,
+    watch_max_wait_ms: Option<u64>,
+    watch_timeout: Option<u64>,
+    batch_size: Option<usize>,
+    ignore_cache: bool,
+    jobs: Option<usize>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    max_file_size_mb: Option<u64>,
+    strict: bool,
+    optimize: bool,
+    optimize_segments: usize,
     data_dir_override: Option<PathBuf>,
+    trace_file: Option<PathBuf>,
     progress: ProgressResolved,
     json: bool,
     idempotency_key: Option<String>,
@@ -7403,6 +9806,52 @@ fn run_index_with_data(
     let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
     let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
 
+    let only_connectors = parse_connector_slugs(&connector, "--connector")?;
+    let skip_connectors =
+        parse_connector_slugs(&skip_connector, "--skip-connector")?.unwrap_or_default();
+    if batch_size == Some(0) {
+        return Err(CliError::usage(
+            "--batch-size must be at least 1",
+            Some("Omit --batch-size to ingest each connector's scan in one batch".to_string()),
+        ));
+    }
+    if jobs == Some(0) {
+        return Err(CliError::usage(
+            "--jobs must be at least 1",
+            Some("Omit --jobs to scan with one thread per CPU".to_string()),
+        ));
+    }
+    if max_file_size_mb == Some(0) {
+        return Err(CliError::usage(
+            "--max-file-size must be at least 1",
+            Some("Omit --max-file-size to index files of any size".to_string()),
+        ));
+    }
+    if optimize_segments == 0 {
+        return Err(CliError::usage(
+            "--optimize-segments must be at least 1",
+            Some("Omit --optimize-segments to merge down to a single segment".to_string()),
+        ));
+    }
+    for (flag, pattern) in include
+        .iter()
+        .map(|p| ("--include", p))
+        .chain(exclude.iter().map(|p| ("--exclude", p)))
+    {
+        if let Err(e) = glob::Pattern::new(pattern) {
+            return Err(CliError::usage(
+                format!("Invalid glob '{pattern}' for {flag}: {e}"),
+                Some("Patterns match absolute paths, e.g. '**/archive/**'".to_string()),
+            ));
+        }
+    }
+    let db_dir = db_path.parent().filter(|p| !p.as_os_str().is_empty());
+    for dir in std::iter::once(data_dir.as_path()).chain(db_dir) {
+        if !crate::storage::sqlite::dir_is_writable(dir) {
+            return Err(read_only_data_dir_error(dir));
+        }
+    }
+
     // Generate params hash for idempotency validation
     let params_hash = {
         use std::hash::{Hash, Hasher};
@@ -7410,6 +9859,17 @@ fn run_index_with_data(
         full.hash(&mut hasher);
         force_rebuild.hash(&mut hasher);
         watch.hash(&mut hasher);
+        let mut only_sorted: Vec<_> = only_connectors.iter().flatten().collect();
+        only_sorted.sort();
+        only_sorted.hash(&mut hasher);
+        let mut skip_sorted: Vec<_> = skip_connectors.iter().collect();
+        skip_sorted.sort();
+        skip_sorted.hash(&mut hasher);
+        include.hash(&mut hasher);
+        exclude.hash(&mut hasher);
+        max_file_size_mb.hash(&mut hasher);
+        strict.hash(&mut hasher);
+        optimize.then_some(optimize_segments).hash(&mut hasher);
         format!("{}", data_dir.display()).hash(&mut hasher);
         hasher.finish()
     };
@@ -7495,9 +9955,23 @@ fn run_index_with_data(
         force_rebuild,
         watch,
         watch_once_paths: watch_once_paths.clone(),
-        db_path: db_path.clone(),
-        data_dir: data_dir.clone(),
         progress: Some(index_progress.clone()),
+        only: only_connectors,
+        skip: skip_connectors,
+        watch_debounce_ms,
+        watch_max_wait_ms: watch_max_wait_ms
+            .unwrap_or_else(|| watch_debounce_ms.max(indexer::DEFAULT_WATCH_MAX_WAIT_MS)),
+        watch_timeout: watch_timeout.map(Duration::from_secs),
+        batch_max_conversations: batch_size,
+        ignore_parse_cache: ignore_cache,
+        jobs,
+        include_globs: include,
+        exclude_globs: exclude,
+        max_file_bytes: max_file_size_mb.map(|mb| mb.saturating_mul(1024 * 1024)),
+        strict,
+        optimize_segments: optimize.then_some(optimize_segments),
+        on_span: trace_file.map(trace_span_sink),
+        ..IndexOptions::new(db_path.clone(), data_dir.clone())
     };
 
     // Set up progress display
@@ -7718,6 +10192,12 @@ fn run_index_with_data(
             retryable: true,
         })?
         .map_err(|e| {
+            if let Some(known) = lock_busy_error(&e)
+                .or_else(|| incompatible_version_error(&e))
+                .or_else(|| filtered_rebuild_error(&e))
+            {
+                return known;
+            }
             let chain = e
                 .chain()
                 .map(std::string::ToString::to_string)
@@ -7732,6 +10212,9 @@ fn run_index_with_data(
             }
         });
     let elapsed_ms = start.elapsed().as_millis();
+    let skipped_files = index_progress
+        .skipped_oversized
+        .load(std::sync::atomic::Ordering::Relaxed);
 
     if let Err(err) = &res {
         if json {
@@ -7769,35 +10252,544 @@ fn run_index_with_data(
             "db_path": db_path.display().to_string(),
             "conversations": conversations,
             "messages": messages,
+            "skipped_files": skipped_files,
+        });
+
+        // Store idempotency key if provided
+        if let Some(key) = &idempotency_key {
+            payload["idempotency_key"] = serde_json::json!(key);
+            payload["cached"] = serde_json::json!(false);
+
+            if let Ok(conn) = Connection::open(&db_path) {
+                let now_ms = chrono::Utc::now().timestamp_millis();
+                let expires_ms = now_ms + 24 * 60 * 60 * 1000; // 24 hours
+                let result_json = serde_json::to_string(&payload).unwrap_or_default();
+                let _ = conn.execute(
+                    "INSERT OR REPLACE INTO idempotency_keys (key, params_hash, result_json, created_at, expires_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    rusqlite::params![key, params_hash.to_string(), result_json, now_ms, expires_ms],
+                );
+            }
+        }
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&payload).unwrap_or_default()
+        );
+    }
+
+    if show_plain {
+        eprintln!("index completed");
+    }
+    if !json && res.is_ok() && skipped_files > 0 {
+        eprintln!("Skipped {skipped_files} session file(s) larger than --max-file-size");
+    }
+
+    res
+}
+
+/// Preview an index run: detect connectors and count candidate files without
+/// writing to SQLite or Tantivy.
+/// `cass index --print-generation`: the bare counter, 0 when nothing has been indexed.
+fn run_print_index_generation(
+    data_dir_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+) -> CliResult<()> {
+    use crate::storage::sqlite::SqliteStorage;
+
+    let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
+    let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
+    let generation = if db_path.exists() {
+        SqliteStorage::open_readonly(&db_path)
+            .and_then(|storage| storage.get_index_generation())
+            .map_err(|e| CliError {
+                code: 9,
+                kind: "db-open",
+                message: format!("Failed to read index generation: {e}"),
+                hint: None,
+                retryable: false,
+            })?
+    } else {
+        0
+    };
+    println!("{generation}");
+    Ok(())
+}
+
+fn run_index_dry_run(
+    db_override: Option<PathBuf>,
+    full: bool,
+    force_rebuild: bool,
+    connector: Vec<String>,
+    skip_connector: Vec<String>,
+    data_dir_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
+    let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
+    let only_connectors = parse_connector_slugs(&connector, "--connector")?;
+    let skip_connectors =
+        parse_connector_slugs(&skip_connector, "--skip-connector")?.unwrap_or_default();
+
+    let opts = IndexOptions {
+        full,
+        force_rebuild,
+        only: only_connectors,
+        skip: skip_connectors,
+        ..IndexOptions::new(db_path, data_dir)
+    };
+    let report = indexer::dry_run(&opts).map_err(|e| CliError {
+        code: 9,
+        kind: "index",
+        message: format!("dry run failed: {e}"),
+        hint: None,
+        retryable: false,
+    })?;
+
+    if json {
+        let payload = serde_json::json!({
+            "dry_run": true,
+            "since_ts": report.since_ts,
+            "connectors": report.connectors,
+            "remote_roots": report.remote_roots,
+            "total_candidates": report.total_candidates,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&payload).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    match report.since_ts {
+        Some(ts) => println!(
+            "Dry run (incremental since {}):",
+            chrono::DateTime::from_timestamp_millis(ts)
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_else(|| ts.to_string())
+        ),
+        None => println!("Dry run (full scan):"),
+    }
+    for c in &report.connectors {
+        if c.detected {
+            println!(
+                "  {:<12} {:>7} files  {}",
+                c.name,
+                c.candidate_files,
+                c.roots
+                    .iter()
+                    .map(|r| r.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        } else {
+            println!("  {:<12} not detected", c.name);
+        }
+    }
+    for r in &report.remote_roots {
+        println!(
+            "  {:<12} {:>7} files  {}",
+            r.source_id,
+            r.candidate_files,
+            r.path.display()
+        );
+    }
+    println!("Total candidate files: {}", report.total_candidates);
+    Ok(())
+}
+
+/// Re-ingest one session file and report what changed.
+fn run_reindex_one(
+    path: &Path,
+    data_dir_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    use std::time::Instant;
+
+    let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
+    let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
+    let opts = IndexOptions::new(db_path, data_dir);
+
+    let start = Instant::now();
+    let report = indexer::reindex_one(&opts, path).map_err(|e| {
+        lock_busy_error(&e)
+            .or_else(|| incompatible_version_error(&e))
+            .or_else(|| stale_index_error(&e))
+            .unwrap_or_else(|| CliError {
+                code: 9,
+                kind: "reindex-one",
+                message: format!("reindex-one failed: {e}"),
+                hint: Some(
+                    "Pass a session file inside a detected agent directory (see `cass diag`)"
+                        .to_string(),
+                ),
+                retryable: false,
+            })
+    })?;
+    let elapsed_ms = start.elapsed().as_millis();
+
+    if json {
+        let payload = serde_json::json!({
+            "success": true,
+            "path": path.display().to_string(),
+            "connector": report.connector,
+            "conversations": report.conversations,
+            "conversations_updated": report.conversations_updated,
+            "messages_added": report.messages_added,
+            "elapsed_ms": elapsed_ms,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&payload).unwrap_or_default()
+        );
+    } else {
+        println!(
+            "Reindexed {} ({}): {} conversation(s), {} updated, {} new message(s)",
+            path.display(),
+            report.connector,
+            report.conversations,
+            report.conversations_updated,
+            report.messages_added
+        );
+    }
+    Ok(())
+}
+
+/// Import an exported transcript as one conversation and report what was added.
+fn run_import(
+    path: &Path,
+    agent: &str,
+    workspace: Option<PathBuf>,
+    data_dir_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
+    let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
+    let opts = IndexOptions::new(db_path, data_dir);
+
+    let report = indexer::import_transcript(&opts, path, agent, workspace).map_err(|e| {
+        lock_busy_error(&e)
+            .or_else(|| incompatible_version_error(&e))
+            .or_else(|| stale_index_error(&e))
+            .unwrap_or_else(|| CliError {
+                code: 9,
+                kind: "import",
+                message: format!("import failed: {e:#}"),
+                hint: Some(
+                    "Expected JSONL lines like {\"role\":\"user\",\"content\":\"...\",\"created_at\":\"2024-05-01T10:00:00Z\"} or a markdown file with `## User` / `## Assistant` sections"
+                        .to_string(),
+                ),
+                retryable: false,
+            })
+    })?;
+
+    if json {
+        let payload = serde_json::json!({
+            "success": true,
+            "agent": agent,
+            "source_path": report.source_path.display().to_string(),
+            "messages": report.messages,
+            "messages_added": report.messages_added,
         });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&payload).unwrap_or_default()
+        );
+    } else {
+        println!(
+            "Imported {} as {}: {} message(s), {} new",
+            report.source_path.display(),
+            agent,
+            report.messages,
+            report.messages_added
+        );
+    }
+    Ok(())
+}
+
+/// Remove the conversations matching `target` from SQLite and the Tantivy index.
+fn run_forget(
+    target: &str,
+    yes: bool,
+    data_dir_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    use crate::storage::sqlite::SqliteStorage;
+
+    let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
+    let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
+    if !db_path.exists() {
+        return Err(CliError {
+            code: 3,
+            kind: "missing-db",
+            message: format!("Database not found at {}", db_path.display()),
+            hint: Some("Run 'cass index --full' first".to_string()),
+            retryable: true,
+        });
+    }
+
+    let matches = SqliteStorage::open_readonly(&db_path)
+        .and_then(|storage| storage.find_conversations(target))
+        .map_err(|e| CliError {
+            code: 9,
+            kind: "db-error",
+            message: format!("failed to look up conversations: {e}"),
+            hint: None,
+            retryable: false,
+        })?;
+    if matches.is_empty() {
+        return Err(CliError {
+            code: 13,
+            kind: "not_found",
+            message: format!("No conversation with source path or external id '{target}'"),
+            hint: Some("Use the source_path from 'cass search' results".to_string()),
+            retryable: false,
+        });
+    }
+
+    if !yes {
+        if !io::stdin().is_terminal() {
+            return Err(CliError::usage(
+                format!(
+                    "refusing to forget {} conversation(s) without confirmation",
+                    matches.len()
+                ),
+                Some("Pass --yes to confirm".to_string()),
+            ));
+        }
+        for conv in &matches {
+            eprintln!(
+                "  {} · {} · {}",
+                conv.agent_slug,
+                conv.title.as_deref().unwrap_or("(untitled)"),
+                conv.source_path.display()
+            );
+        }
+        eprint!(
+            "Forget {} conversation(s)? This cannot be undone. [y/N]: ",
+            matches.len()
+        );
+        io::stderr().flush().ok();
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).map_err(|e| CliError {
+            code: 14,
+            kind: "io",
+            message: format!("Failed to read input: {e}"),
+            hint: None,
+            retryable: false,
+        })?;
+        let input = input.trim().to_lowercase();
+        if input != "y" && input != "yes" {
+            eprintln!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let opts = IndexOptions::new(db_path, data_dir);
+    let report = indexer::forget(&opts, target).map_err(|e| {
+        lock_busy_error(&e)
+            .or_else(|| incompatible_version_error(&e))
+            .or_else(|| stale_index_error(&e))
+            .unwrap_or_else(|| CliError {
+                code: 9,
+                kind: "forget",
+                message: format!("forget failed: {e}"),
+                hint: None,
+                retryable: false,
+            })
+    })?;
+
+    if json {
+        let payload = serde_json::json!({
+            "success": true,
+            "target": target,
+            "conversations": report.conversations,
+            "messages": report.messages,
+            "source_paths": report.source_paths,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&payload).unwrap_or_default()
+        );
+    } else {
+        println!(
+            "Forgot {} conversation(s), {} message(s)",
+            report.conversations, report.messages
+        );
+    }
+    Ok(())
+}
 
-        // Store idempotency key if provided
-        if let Some(key) = &idempotency_key {
-            payload["idempotency_key"] = serde_json::json!(key);
-            payload["cached"] = serde_json::json!(false);
+/// Print the conversation stored for `path` as cass normalized and indexed it
+/// (loaded the same way as the TUI detail pane), rather than the raw file.
+/// With `message` = `(message_id, context)` the conversation is found from the
+/// message instead, and only that message and `context` neighbours each side
+/// are printed.
+fn run_show(
+    path: Option<&str>,
+    message: Option<(i64, usize)>,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    use crate::model::types::MessageRole;
+    use crate::storage::sqlite::SqliteStorage;
+    use rusqlite::OptionalExtension;
 
-            if let Ok(conn) = Connection::open(&db_path) {
-                let now_ms = chrono::Utc::now().timestamp_millis();
-                let expires_ms = now_ms + 24 * 60 * 60 * 1000; // 24 hours
-                let result_json = serde_json::to_string(&payload).unwrap_or_default();
-                let _ = conn.execute(
-                    "INSERT OR REPLACE INTO idempotency_keys (key, params_hash, result_json, created_at, expires_at) VALUES (?1, ?2, ?3, ?4, ?5)",
-                    rusqlite::params![key, params_hash.to_string(), result_json, now_ms, expires_ms],
-                );
-            }
-        }
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
+    if !db_path.exists() {
+        return Err(CliError {
+            code: 3,
+            kind: "missing-db",
+            message: format!("Database not found at {}", db_path.display()),
+            hint: Some("Run 'cass index --full' first".to_string()),
+            retryable: true,
+        });
+    }
+
+    let db_error = |e: anyhow::Error| CliError {
+        code: 9,
+        kind: "db-error",
+        message: format!("failed to load conversation: {e}"),
+        hint: None,
+        retryable: false,
+    };
+    let storage = SqliteStorage::open_readonly(&db_path).map_err(db_error)?;
+    let source_path = match message {
+        Some((message_id, _)) => storage
+            .raw()
+            .query_row(
+                "SELECT c.source_path FROM messages m JOIN conversations c ON m.conversation_id = c.id WHERE m.id = ?1",
+                [message_id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|e| db_error(e.into()))?
+            .ok_or_else(|| CliError {
+                code: 13,
+                kind: "not_found",
+                message: format!("No indexed message with id {message_id}"),
+                hint: Some(
+                    "Use the message_id from 'cass search --json' results; ids from a deleted database do not carry over"
+                        .to_string(),
+                ),
+                retryable: false,
+            })?,
+        None => session_source_path(path.unwrap_or_default()),
+    };
+    let mut view = crate::ui::data::load_conversation(&storage, &source_path)
+        .map_err(db_error)?
+        .ok_or_else(|| CliError {
+            code: 13,
+            kind: "not_found",
+            message: format!("No indexed conversation with source path '{source_path}'"),
+            hint: Some("Use the source_path from 'cass search' results".to_string()),
+            retryable: false,
+        })?;
+    let message_count = view.messages.len();
+    if let Some((message_id, context)) = message
+        && let Some(pos) = view.messages.iter().position(|m| m.id == Some(message_id))
+    {
+        let end = (pos + context + 1).min(message_count);
+        view.messages.truncate(end);
+        view.messages.drain(..pos.saturating_sub(context));
+    }
+    let focus = message.map(|(message_id, _)| message_id);
+
+    let role_name = |role: &MessageRole| match role {
+        MessageRole::User => "user".to_string(),
+        MessageRole::Agent => "assistant".to_string(),
+        MessageRole::Tool => "tool".to_string(),
+        MessageRole::System => "system".to_string(),
+        MessageRole::Other(other) => other.clone(),
+    };
+    let convo = &view.convo;
 
+    if json {
+        let messages: Vec<_> = view
+            .messages
+            .iter()
+            .map(|m| {
+                serde_json::json!({
+                    "id": m.id,
+                    "idx": m.idx,
+                    "role": role_name(&m.role),
+                    "author": m.author,
+                    "created_at": m.created_at,
+                    "content": m.content,
+                })
+            })
+            .collect();
+        let payload = serde_json::json!({
+            "source_path": convo.source_path.display().to_string(),
+            "agent": convo.agent_slug,
+            "external_id": convo.external_id,
+            "title": convo.title,
+            "workspace": convo.workspace.as_ref().map(|w| w.display().to_string()),
+            "started_at": convo.started_at,
+            "ended_at": convo.ended_at,
+            "source_id": convo.source_id,
+            "origin_host": convo.origin_host,
+            "message_count": message_count,
+            "message_id": focus,
+            "messages": messages,
+        });
         println!(
             "{}",
             serde_json::to_string_pretty(&payload).unwrap_or_default()
         );
+        return Ok(());
     }
 
-    if show_plain {
-        eprintln!("index completed");
+    let format_ts = |ts: Option<i64>| {
+        ts.and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|d| {
+                d.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+    };
+    println!("{}", convo.title.as_deref().unwrap_or("(untitled)"));
+    println!(
+        "Agent: {} | WS: {}",
+        convo.agent_slug,
+        convo
+            .workspace
+            .as_ref()
+            .map_or_else(|| "-".to_string(), |w| w.display().to_string())
+    );
+    println!("Path: {}", convo.source_path.display());
+    if convo.source_id != "local" {
+        println!(
+            "Host: {}",
+            convo.origin_host.as_deref().unwrap_or(&convo.source_id)
+        );
     }
-
-    res
+    if let Some(started) = format_ts(convo.started_at) {
+        println!("Started: {started}");
+    }
+    println!("Messages: {message_count}");
+    for m in &view.messages {
+        println!("----------------------------------------------------------------");
+        let marker = if focus.is_some() && m.id == focus {
+            "> "
+        } else {
+            ""
+        };
+        let mut header = format!("{marker}[{}] {}", m.idx, role_name(&m.role));
+        if let Some(author) = &m.author {
+            header.push_str(&format!(" ({author})"));
+        }
+        if let Some(ts) = format_ts(m.created_at) {
+            header.push_str(&format!(" · {ts}"));
+        }
+        println!("{header}");
+        println!("{}", m.content);
+    }
+    Ok(())
 }
 
 pub fn default_db_path() -> PathBuf {
@@ -7819,6 +10811,20 @@ pub fn default_data_dir() -> PathBuf {
 
 /// Read session paths from a file or stdin (when path is "-").
 /// Returns a HashSet of session paths for filtering.
+/// Source path as stored in the index for a `--in` argument: local files given
+/// relative to the working directory are made absolute, anything else (e.g. a
+/// remote session's path) is used verbatim.
+fn session_source_path(path: &str) -> String {
+    let p = Path::new(path);
+    if p.is_relative()
+        && p.exists()
+        && let Ok(abs) = std::path::absolute(p)
+    {
+        return abs.to_string_lossy().into_owned();
+    }
+    path.to_string()
+}
+
 fn read_session_paths(source: &str) -> Result<std::collections::HashSet<String>, std::io::Error> {
     use std::collections::HashSet;
     use std::io::{BufRead, BufReader};
@@ -7847,8 +10853,11 @@ struct ReleaseInfo {
     tag_name: String,
 }
 
-async fn maybe_prompt_for_update(once: bool) -> Result<()> {
+/// Offer a self-update when a newer release exists. Returns before any network
+/// access when disabled by `--no-update-check`, `--once` or the env vars.
+async fn maybe_prompt_for_update(once: bool, no_update_check: bool) -> Result<()> {
     if once
+        || no_update_check
         || dotenvy::var("CI").is_ok()
         || dotenvy::var("TUI_HEADLESS").is_ok()
         || dotenvy::var("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT").is_ok()
@@ -7857,12 +10866,7 @@ async fn maybe_prompt_for_update(once: bool) -> Result<()> {
         return Ok(());
     }
 
-    let client = Client::builder()
-        .user_agent("coding-agent-search (update-check)")
-        .timeout(Duration::from_secs(3))
-        .build()?;
-
-    let Some((latest_tag, latest_ver)) = latest_release_version(&client).await else {
+    let Some((latest_tag, latest_ver)) = latest_release_version().await else {
         return Ok(());
     };
 
@@ -7903,17 +10907,33 @@ async fn maybe_prompt_for_update(once: bool) -> Result<()> {
     Ok(())
 }
 
-async fn latest_release_version(client: &Client) -> Option<(String, Version)> {
+/// Latest release tag and version. GitHub is only queried once per update check
+/// interval; in between (and when offline) the tag cached in `update_state.json`
+/// is used.
+async fn latest_release_version() -> Option<(String, Version)> {
+    let tag = update_check::cached_latest_tag(
+        &update_check::state_path(),
+        update_check::check_interval(),
+        fetch_latest_release_tag,
+    )
+    .await?;
+    let version = Version::parse(tag.trim_start_matches('v')).ok()?;
+    Some((tag, version))
+}
+
+async fn fetch_latest_release_tag() -> Option<String> {
+    let client = Client::builder()
+        .user_agent("coding-agent-search (update-check)")
+        .timeout(Duration::from_secs(3))
+        .build()
+        .ok()?;
     let url = format!("https://api.github.com/repos/{OWNER}/{REPO}/releases/latest");
     let resp = client.get(url).send().await.ok()?;
     if !resp.status().is_success() {
         return None;
     }
     let info: ReleaseInfo = resp.json().await.ok()?;
-    let tag = info.tag_name;
-    let version_str = tag.trim_start_matches('v');
-    let version = Version::parse(version_str).ok()?;
-    Some((tag, version))
+    Some(info.tag_name)
 }
 
 #[cfg(windows)]
@@ -8181,15 +11201,14 @@ fn load_opencode_session_for_export(
     Ok((session_title, start, end, sorted_messages))
 }
 
-/// Export a conversation to markdown or other formats
-fn run_export(
+/// Load a session file's raw messages for export, returning the session
+/// title (first user line unless the source provides one), start time and
+/// messages.
+fn load_session_for_export(
     path: &Path,
-    format: ConvExportFormat,
-    output: Option<&Path>,
-    include_tools: bool,
-) -> CliResult<()> {
+) -> CliResult<(Option<String>, Option<i64>, Vec<serde_json::Value>)> {
     use std::fs::File;
-    use std::io::{BufRead, BufReader, Write};
+    use std::io::{BufRead, BufReader};
 
     if !path.exists() {
         return Err(CliError {
@@ -8300,15 +11319,37 @@ fn run_export(
         }
     }
 
+    Ok((session_title, session_start, messages))
+}
+
+/// Export a conversation to markdown or other formats
+fn run_export(
+    path: &Path,
+    format: ConvExportFormat,
+    output: Option<&Path>,
+    include_tools: bool,
+    embed_json: bool,
+    theme: crate::export::ExportTheme,
+) -> CliResult<()> {
+    use std::fs::File;
+    use std::io::Write;
+
+    let (session_title, session_start, messages) = load_session_for_export(path)?;
+
     let formatted = match format {
         ConvExportFormat::Markdown => {
             format_as_markdown(&messages, &session_title, session_start, include_tools)
         }
         ConvExportFormat::Text => format_as_text(&messages, include_tools),
         ConvExportFormat::Json => serde_json::to_string_pretty(&messages).unwrap_or_default(),
-        ConvExportFormat::Html => {
-            format_as_html(&messages, &session_title, session_start, include_tools)
-        }
+        ConvExportFormat::Html => format_as_html(
+            &messages,
+            &session_title,
+            session_start,
+            include_tools,
+            embed_json,
+            theme,
+        ),
     };
 
     if let Some(out_path) = output {
@@ -8451,11 +11492,13 @@ fn format_as_text(messages: &[serde_json::Value], include_tools: bool) -> String
     text
 }
 
-fn format_as_html(
+pub(crate) fn format_as_html(
     messages: &[serde_json::Value],
     title: &Option<String>,
     start_ts: Option<i64>,
     include_tools: bool,
+    embed_json: bool,
+    theme: crate::export::ExportTheme,
 ) -> String {
     use chrono::{TimeZone, Utc};
     let title_str = title.as_deref().unwrap_or("Conversation Export");
@@ -8463,6 +11506,7 @@ fn format_as_html(
         .and_then(|ts| Utc.timestamp_millis_opt(ts).single())
         .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
         .unwrap_or_default();
+    let theme_css = crate::export::theme_css(theme);
 
     let mut html = format!(
         r#"<!DOCTYPE html>
@@ -8471,15 +11515,16 @@ fn format_as_html(
     <meta charset="UTF-8">
     <title>{title_str}</title>
     <style>
-        body {{ font-family: system-ui, sans-serif; max-width: 800px; margin: 0 auto; padding: 20px; background: #f5f5f5; }}
-        .message {{ background: white; border-radius: 8px; padding: 16px; margin: 12px 0; box-shadow: 0 1px 3px rgba(0,0,0,0.1); }}
-        .user {{ border-left: 4px solid #2563eb; }}
-        .assistant {{ border-left: 4px solid #16a34a; }}
-        .role {{ font-weight: bold; color: #374151; margin-bottom: 8px; }}
+        {theme_css}
+        body {{ font-family: system-ui, sans-serif; max-width: 800px; margin: 0 auto; padding: 20px; background: var(--bg); color: var(--text); }}
+        .message {{ background: var(--surface); border-radius: 8px; padding: 16px; margin: 12px 0; box-shadow: 0 1px 3px rgba(0,0,0,0.1); }}
+        .user {{ border-left: 4px solid var(--user); }}
+        .assistant {{ border-left: 4px solid var(--assistant); }}
+        .role {{ font-weight: bold; color: var(--role); margin-bottom: 8px; }}
         .content {{ white-space: pre-wrap; line-height: 1.6; }}
-        .tool {{ background: #f3f4f6; padding: 8px; border-radius: 4px; font-family: monospace; font-size: 0.9em; margin: 8px 0; }}
-        h1 {{ color: #1f2937; }}
-        .meta {{ color: #6b7280; font-size: 0.9em; }}
+        .tool {{ background: var(--tool-bg); padding: 8px; border-radius: 4px; font-family: monospace; font-size: 0.9em; margin: 8px 0; }}
+        h1 {{ color: var(--heading); }}
+        .meta {{ color: var(--muted); font-size: 0.9em; }}
     </style>
 </head>
 <body>
@@ -8488,9 +11533,10 @@ fn format_as_html(
 "#
     );
 
-    for msg in messages {
+    for (idx, msg) in messages.iter().enumerate() {
         let role = extract_role(msg);
         let role_class = if role == "user" { "user" } else { "assistant" };
+        let anchor = crate::search::query::message_anchor(idx);
         let role_display = match role.as_str() {
             "user" => "👤 User",
             "assistant" => "🤖 Assistant",
@@ -8499,7 +11545,7 @@ fn format_as_html(
         };
 
         html.push_str(&format!(
-            r#"    <div class="message {role_class}">
+            r#"    <div class="message {role_class}" id="{anchor}">
         <div class="role">{role_display}</div>
         <div class="content">"#
         ));
@@ -8530,6 +11576,27 @@ fn format_as_html(
 
         html.push_str("</div>\n    </div>\n");
     }
+    if embed_json {
+        let conv = crate::export::ExportedConversation {
+            title: title.clone(),
+            started_at: start_ts,
+            messages: messages
+                .iter()
+                .enumerate()
+                .map(|(idx, msg)| crate::export::ExportedMessage {
+                    idx,
+                    role: extract_role(msg),
+                    content: extract_text_content(msg),
+                    created_at: msg
+                        .get("timestamp")
+                        .and_then(crate::connectors::parse_timestamp),
+                })
+                .collect(),
+        };
+        html.push_str("    ");
+        html.push_str(&crate::export::conversation_script_block(&conv));
+        html.push('\n');
+    }
     html.push_str("</body>\n</html>\n");
     html
 }
@@ -9096,17 +12163,40 @@ fn run_sources_command(cmd: SourcesCommand) -> CliResult<()> {
         SourcesCommand::Remove { name, purge, yes } => {
             run_sources_remove(&name, purge, yes)?;
         }
+        SourcesCommand::Probe {
+            name,
+            timeout,
+            json,
+        } => {
+            run_sources_probe(name.as_deref(), timeout, json)?;
+        }
         SourcesCommand::Doctor { source, json } => {
             run_sources_doctor(source.as_deref(), json)?;
         }
         SourcesCommand::Sync {
+            name,
             source,
             no_index,
             verbose,
             dry_run,
+            retries,
+            retry_delay,
             json,
         } => {
-            run_sources_sync(source, no_index, verbose, dry_run, json)?;
+            let source = match (name, source) {
+                (Some(name), Some(mut names)) => {
+                    names.push(name);
+                    Some(names)
+                }
+                (Some(name), None) => Some(vec![name]),
+                (None, source) => source,
+            };
+            let retry = crate::sources::sync::RetryPolicy {
+                max_attempts: retries.saturating_add(1),
+                base_delay: Duration::from_millis(retry_delay),
+                ..Default::default()
+            };
+            run_sources_sync(source, no_index, verbose, dry_run, retry, json)?;
         }
         SourcesCommand::Mappings(action) => {
             run_mappings_command(action)?;
@@ -9150,6 +12240,7 @@ fn run_sources_command(cmd: SourcesCommand) -> CliResult<()> {
 /// List configured sources (P5.3)
 fn run_sources_list(verbose: bool, json: bool) -> CliResult<()> {
     use crate::sources::config::SourcesConfig;
+    use crate::sources::sync::SyncStatus;
 
     let config = SourcesConfig::load().map_err(|e| CliError {
         code: 9,
@@ -9165,6 +12256,16 @@ fn run_sources_list(verbose: bool, json: bool) -> CliResult<()> {
         .map(|p| p.display().to_string())
         .unwrap_or_else(|| "unknown".into());
 
+    // Last sync per source, recorded by `cass sources sync`
+    let sync_status = SyncStatus::load(&default_data_dir()).unwrap_or_default();
+    let last_sync = |name: &str| {
+        sync_status
+            .sources
+            .get(name)
+            .and_then(|info| info.last_sync)
+            .and_then(chrono::DateTime::from_timestamp_millis)
+    };
+
     if json {
         let sources_json: Vec<serde_json::Value> = config
             .sources
@@ -9177,6 +12278,8 @@ fn run_sources_list(verbose: bool, json: bool) -> CliResult<()> {
                     "paths": s.paths,
                     "sync_schedule": s.sync_schedule.to_string(),
                     "platform": s.platform.map(|p| p.to_string()),
+                    "last_sync": last_sync(&s.name).map(|d| d.to_rfc3339()),
+                    "last_result": sync_status.sources.get(&s.name).map(|info| &info.last_result),
                 })
             })
             .collect();
@@ -9216,6 +12319,10 @@ fn run_sources_list(verbose: bool, json: bool) -> CliResult<()> {
                 if let Some(platform) = source.platform {
                     println!("  Platform: {platform}");
                 }
+                match last_sync(&source.name) {
+                    Some(dt) => println!("  Last sync: {}", dt.format("%Y-%m-%d %H:%M")),
+                    None => println!("  Last sync: never"),
+                }
                 if !source.paths.is_empty() {
                     println!("  Paths:");
                     for path in &source.paths {
@@ -9241,8 +12348,11 @@ fn run_sources_list(verbose: bool, json: bool) -> CliResult<()> {
             }
         } else {
             // Table output
-            println!("  {:15} {:8} {:30} {:>5}", "NAME", "TYPE", "HOST", "PATHS");
-            println!("  {}", "-".repeat(62));
+            println!(
+                "  {:15} {:8} {:30} {:>5}  {:10} {:16}",
+                "NAME", "TYPE", "HOST", "PATHS", "PLATFORM", "LAST SYNC"
+            );
+            println!("  {}", "-".repeat(91));
             for source in &config.sources {
                 let host = source.host.as_deref().unwrap_or("-");
                 let host_truncated = if host.len() > 30 {
@@ -9250,12 +12360,21 @@ fn run_sources_list(verbose: bool, json: bool) -> CliResult<()> {
                 } else {
                     host.to_string()
                 };
+                let platform = source
+                    .platform
+                    .map(|p| p.to_string())
+                    .unwrap_or_else(|| "-".into());
+                let synced = last_sync(&source.name)
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "never".into());
                 println!(
-                    "  {:15} {:8} {:30} {:>5}",
+                    "  {:15} {:8} {:30} {:>5}  {:10} {:16}",
                     source.name,
                     source.source_type.as_str(),
                     host_truncated,
-                    source.paths.len()
+                    source.paths.len(),
+                    platform,
+                    synced
                 );
             }
             println!();
@@ -9267,6 +12386,153 @@ fn run_sources_list(verbose: bool, json: bool) -> CliResult<()> {
     Ok(())
 }
 
+/// Probe remote sources over SSH. Unreachable hosts are reported per-host;
+/// only config errors or an unknown source name fail the command.
+fn run_sources_probe(name: Option<&str>, timeout: u64, json: bool) -> CliResult<()> {
+    use crate::sources::config::{DiscoveredHost, SourcesConfig};
+    use crate::sources::probe::{CassStatus, probe_hosts_parallel};
+    use colored::Colorize;
+
+    let config = SourcesConfig::load().map_err(|e| CliError {
+        code: 9,
+        kind: "config",
+        message: format!("Failed to load sources config: {e}"),
+        hint: Some("Run 'cass sources add' to configure a source".into()),
+        retryable: false,
+    })?;
+
+    let sources: Vec<_> = config
+        .remote_sources()
+        .filter(|s| name.is_none_or(|n| s.name == n))
+        .filter(|s| s.host.is_some())
+        .collect();
+
+    if sources.is_empty() {
+        if let Some(n) = name {
+            return Err(CliError {
+                code: 13,
+                kind: "not_found",
+                message: format!("Remote source '{n}' not found"),
+                hint: Some("Run 'cass sources list' to see configured sources".into()),
+                retryable: false,
+            });
+        }
+        if json {
+            println!(
+                "{}",
+                serde_json::json!({ "hosts": [], "total": 0, "reachable": 0 })
+            );
+        } else {
+            println!("{}", "No remote sources configured.".yellow());
+        }
+        return Ok(());
+    }
+
+    // Probe by the configured host string; SSH config resolves aliases.
+    let hosts: Vec<DiscoveredHost> = sources
+        .iter()
+        .map(|s| DiscoveredHost {
+            name: s.host.clone().unwrap_or_default(),
+            hostname: None,
+            user: None,
+            port: None,
+            identity_file: None,
+        })
+        .collect();
+
+    let results = probe_hosts_parallel(&hosts, timeout, |done, total, host| {
+        if !json {
+            eprintln!("  probed {host} ({done}/{total})");
+        }
+    });
+    let reachable = results.iter().filter(|r| r.reachable).count();
+
+    if json {
+        let hosts_json: Vec<serde_json::Value> = sources
+            .iter()
+            .zip(&results)
+            .map(|(source, result)| {
+                let mut value = serde_json::to_value(result).unwrap_or_default();
+                if let Some(obj) = value.as_object_mut() {
+                    obj.insert("source".into(), serde_json::json!(source.name));
+                }
+                value
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "hosts": hosts_json,
+                "total": results.len(),
+                "reachable": reachable,
+            }))
+            .unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    println!();
+    for (source, result) in sources.iter().zip(&results) {
+        if !result.reachable {
+            println!(
+                "{} {} ({}): {}",
+                "✗".red(),
+                source.name.white().bold(),
+                result.host_name,
+                result.error.as_deref().unwrap_or("unreachable").red()
+            );
+            continue;
+        }
+        println!(
+            "{} {} ({}) {}ms",
+            "✓".green(),
+            source.name.white().bold(),
+            result.host_name,
+            result.connection_time_ms
+        );
+        if let Some(info) = &result.system_info {
+            let distro = info
+                .distro
+                .as_deref()
+                .map(|d| format!(" ({d})"))
+                .unwrap_or_default();
+            println!("  System: {} {}{}", info.os, info.arch, distro);
+        }
+        let cass = match &result.cass_status {
+            CassStatus::Indexed {
+                version,
+                session_count,
+                ..
+            } => format!("{version}, {session_count} sessions indexed"),
+            CassStatus::InstalledNotIndexed { version } => format!("{version}, not indexed"),
+            CassStatus::NotFound => "not installed".to_string(),
+            CassStatus::Unknown => "unknown".to_string(),
+        };
+        println!("  cass: {cass}");
+        if result.detected_agents.is_empty() {
+            println!("  Agents: {}", "none detected".dimmed());
+        } else {
+            println!("  Agents:");
+            for agent in &result.detected_agents {
+                let sessions = agent
+                    .estimated_sessions
+                    .map(|n| format!(" ({n} sessions)"))
+                    .unwrap_or_default();
+                println!(
+                    "    {} {}{}",
+                    agent.agent_type,
+                    agent.path.dimmed(),
+                    sessions
+                );
+            }
+        }
+    }
+    println!();
+    println!("{reachable}/{} host(s) reachable", results.len());
+
+    Ok(())
+}
+
 /// Add a new remote source (P5.2)
 fn run_sources_add(
     url: &str,
@@ -9927,6 +13193,7 @@ fn run_sources_sync(
     no_index: bool,
     verbose: bool,
     dry_run: bool,
+    retry: crate::sources::sync::RetryPolicy,
     json_output: bool,
 ) -> CliResult<()> {
     use crate::sources::config::SourcesConfig;
@@ -9992,7 +13259,7 @@ fn run_sources_sync(
     let data_dir = default_data_dir();
 
     // Create sync engine
-    let engine = SyncEngine::new(&data_dir);
+    let engine = SyncEngine::new(&data_dir).with_retry_policy(retry);
 
     // Load existing sync status
     let mut status = SyncStatus::load(&data_dir).unwrap_or_default();
@@ -10005,6 +13272,8 @@ fn run_sources_sync(
     let mut all_reports = Vec::new();
     let mut total_files = 0u64;
     let mut total_bytes = 0u64;
+    // Sources that errored or had failing paths; one failure never aborts the rest.
+    let mut failed_sources: Vec<String> = Vec::new();
 
     for source in &sources_to_sync {
         if !json_output {
@@ -10030,6 +13299,7 @@ fn run_sources_sync(
         let report = match engine.sync_source(source) {
             Ok(r) => r,
             Err(e) => {
+                failed_sources.push(source.name.clone());
                 if json_output {
                     all_reports.push(serde_json::json!({
                         "source": source.name,
@@ -10045,6 +13315,9 @@ fn run_sources_sync(
 
         // Update status
         status.update(&source.name, &report);
+        if !report.all_succeeded {
+            failed_sources.push(source.name.clone());
+        }
 
         // Print results
         if json_output {
@@ -10058,13 +13331,26 @@ fn run_sources_sync(
                     "files": r.files_transferred,
                     "bytes": r.bytes_transferred,
                     "error": r.error,
+                    "attempts": r.attempts,
                 })).collect::<Vec<_>>(),
                 "total_files": report.total_files(),
                 "total_bytes": report.total_bytes(),
+                "retries": report.total_retries(),
                 "duration_ms": report.total_duration_ms,
             }));
         } else {
             for result in &report.path_results {
+                if result.attempts > 1 {
+                    println!(
+                        "  {}: {}",
+                        result.remote_path.dimmed(),
+                        format!(
+                            "retried {} time(s) after network errors",
+                            result.attempts - 1
+                        )
+                        .yellow()
+                    );
+                }
                 if result.success {
                     if verbose || result.files_transferred > 0 {
                         println!(
@@ -10089,10 +13375,11 @@ fn run_sources_sync(
                 }
             }
             println!(
-                "  {} {} files, {}",
+                "  {} {} files, {} in {:.1}s",
                 "Total:".dimmed(),
                 report.total_files(),
-                format_bytes(report.total_bytes())
+                format_bytes(report.total_bytes()),
+                report.total_duration_ms as f64 / 1000.0
             );
             println!();
         }
@@ -10111,9 +13398,10 @@ fn run_sources_sync(
         println!(
             "{}",
             serde_json::to_string_pretty(&serde_json::json!({
-                "status": "complete",
+                "status": if failed_sources.is_empty() { "complete" } else { "partial" },
                 "dry_run": dry_run,
                 "sources": all_reports,
+                "failed": failed_sources,
                 "total_files": total_files,
                 "total_bytes": total_bytes,
                 "will_reindex": !no_index && !dry_run,
@@ -10142,19 +13430,53 @@ fn run_sources_sync(
         };
 
         run_index_with_data(
-            None,           // db_override (uses data_dir default)
-            false,          // full
-            false,          // force_rebuild
-            false,          // watch
-            None,           // watch_once
-            Some(data_dir), // data_dir
+            None,                               // db_override (uses data_dir default)
+            false,                              // full
+            false,                              // force_rebuild
+            false,                              // watch
+            None,                               // watch_once
+            Vec::new(),                         // connector
+            Vec::new(),                         // skip_connector
+            indexer::DEFAULT_WATCH_DEBOUNCE_MS, // watch_debounce
+            None,                               // watch_max_wait
+            None,                               // watch_timeout
+            None,                               // batch_size
+            false,                              // ignore_cache
+            None,                               // jobs
+            Vec::new(),                         // include
+            Vec::new(),                         // exclude
+            None,                               // max_file_size
+            false,                              // strict
+            false,                              // optimize
+            1,                                  // optimize_segments
+            Some(data_dir),                     // data_dir
+            None,                               // trace_file
             progress,
             json_output,
             None, // idempotency_key
         )?;
     }
 
-    Ok(())
+    if failed_sources.is_empty() {
+        return Ok(());
+    }
+    let failed_list = failed_sources.join(", ");
+    if failed_sources.len() == sources_to_sync.len() {
+        return Err(CliError {
+            code: 9,
+            kind: "sync",
+            message: format!("Sync failed for all sources: {failed_list}"),
+            hint: Some("Run 'cass sources doctor' to check connectivity".into()),
+            retryable: true,
+        });
+    }
+    Err(CliError {
+        code: 8,
+        kind: "partial",
+        message: format!("Sync failed for some sources: {failed_list}"),
+        hint: Some("Run 'cass sources doctor' on the failed sources".into()),
+        retryable: true,
+    })
 }
 
 /// Auto-discover SSH hosts from ~/.ssh/config (P5.6)
@@ -10358,6 +13680,78 @@ fn run_sources_setup(opts: sources::setup::SetupOptions) -> CliResult<()> {
 }
 
 /// Handle models subcommands
+fn run_config_command(cmd: ConfigCommand, db_override: Option<PathBuf>) -> CliResult<()> {
+    match cmd {
+        ConfigCommand::Show { data_dir, json } => {
+            let paths = config_paths(data_dir, db_override);
+            if json {
+                let map: serde_json::Map<String, serde_json::Value> = paths
+                    .iter()
+                    .map(|(name, path)| {
+                        let value = path
+                            .as_ref()
+                            .map(|p| serde_json::Value::String(p.display().to_string()))
+                            .unwrap_or(serde_json::Value::Null);
+                        (name.to_string(), value)
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&map).unwrap_or_default());
+            } else {
+                for (name, path) in &paths {
+                    let shown = path
+                        .as_ref()
+                        .map_or_else(|| "(unavailable)".to_string(), |p| p.display().to_string());
+                    println!("{name:<8} {shown}");
+                }
+            }
+            Ok(())
+        }
+        ConfigCommand::Path { what, data_dir } => {
+            let name = match what {
+                ConfigPathKind::Db => "db",
+                ConfigPathKind::Index => "index",
+                ConfigPathKind::Data => "data",
+                ConfigPathKind::Sources => "sources",
+                ConfigPathKind::Log => "log",
+            };
+            let path = config_paths(data_dir, db_override)
+                .into_iter()
+                .find(|(n, _)| *n == name)
+                .and_then(|(_, p)| p)
+                .ok_or_else(|| CliError {
+                    code: 9,
+                    kind: "config",
+                    message: format!("could not resolve the {name} path"),
+                    hint: Some("Set XDG_CONFIG_HOME or CASS_SOURCES_CONFIG".to_string()),
+                    retryable: false,
+                })?;
+            println!("{}", path.display());
+            Ok(())
+        }
+    }
+}
+
+/// Absolute locations for `cass config`, resolved the same way other commands
+/// resolve them. `None` only for a sources config with no config dir.
+fn config_paths(
+    data_dir_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+) -> Vec<(&'static str, Option<PathBuf>)> {
+    use crate::search::tantivy::index_dir_path;
+    use crate::sources::config::SourcesConfig;
+
+    let absolute = |p: PathBuf| std::path::absolute(&p).unwrap_or(p);
+    let data_dir = absolute(data_dir_override.unwrap_or_else(default_data_dir));
+    let db_path = absolute(db_override.unwrap_or_else(|| data_dir.join("agent_search.db")));
+    vec![
+        ("data", Some(data_dir.clone())),
+        ("db", Some(db_path)),
+        ("index", Some(index_dir_path(&data_dir))),
+        ("sources", SourcesConfig::config_path().ok().map(absolute)),
+        ("log", Some(data_dir.join("cass.log"))),
+    ]
+}
+
 fn run_models_command(cmd: ModelsCommand) -> CliResult<()> {
     match cmd {
         ModelsCommand::Status { json } => run_models_status(json),
@@ -0,0 +1,147 @@
+//! Agent slug aliases.
+//!
+//! The same tool can end up indexed under several slugs (older cass versions,
+//! remote machines, renamed connectors), e.g. `gemini` and `gemini_cli`. Aliases
+//! map alternates onto one canonical slug so `--agent` filters match every
+//! spelling and stats group them together. Built-in aliases can be extended
+//! from the `[agent_aliases]` table in `sources.toml`:
+//!
+//! ```toml
+//! [agent_aliases]
+//! cursor = ["cursor_ide"]
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::sources::config::SourcesConfig;
+
+/// Canonical slug → alternates that should be treated as the same agent.
+pub const BUILTIN_AGENT_ALIASES: &[(&str, &[&str])] = &[
+    ("claude_code", &["claude", "claude-code"]),
+    ("codex", &["codex_cli", "codex-cli"]),
+    ("gemini", &["gemini_cli", "gemini-cli"]),
+    ("pi_agent", &["pi-agent"]),
+];
+
+/// Resolved alias table.
+#[derive(Debug, Clone, Default)]
+pub struct AgentAliases {
+    /// Any known slug (canonical or alternate) → canonical slug.
+    canonical: HashMap<String, String>,
+    /// Canonical slug → all slugs in its group, canonical first.
+    groups: HashMap<String, Vec<String>>,
+}
+
+impl AgentAliases {
+    /// Built-in aliases only.
+    pub fn builtin() -> Self {
+        Self::with_config(&BTreeMap::new())
+    }
+
+    /// Built-in aliases extended by `extra` (canonical → alternates). A slug
+    /// listed in `extra` moves to that group even if a built-in claims it.
+    pub fn with_config(extra: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut aliases = Self::default();
+        for (canonical, alternates) in BUILTIN_AGENT_ALIASES {
+            for alt in *alternates {
+                aliases.insert(canonical, alt);
+            }
+        }
+        for (canonical, alternates) in extra {
+            for alt in alternates {
+                aliases.insert(canonical, alt);
+            }
+        }
+        aliases
+    }
+
+    /// Built-in aliases plus any from the sources config. A missing or
+    /// unreadable config falls back to the built-ins.
+    pub fn load() -> Self {
+        match SourcesConfig::load() {
+            Ok(config) => Self::with_config(&config.agent_aliases),
+            Err(e) => {
+                tracing::debug!(error = %e, "agent aliases: using built-ins only");
+                Self::builtin()
+            }
+        }
+    }
+
+    fn insert(&mut self, canonical: &str, alt: &str) {
+        let canonical = canonical.trim().to_lowercase();
+        let alt = alt.trim().to_lowercase();
+        if canonical.is_empty() || alt.is_empty() || canonical == alt {
+            return;
+        }
+        self.detach(&canonical, &canonical);
+        self.detach(&alt, &canonical);
+        self.canonical.insert(canonical.clone(), canonical.clone());
+        self.canonical.insert(alt.clone(), canonical.clone());
+        let group = self
+            .groups
+            .entry(canonical.clone())
+            .or_insert_with(|| vec![canonical]);
+        if !group.contains(&alt) {
+            group.push(alt);
+        }
+    }
+
+    /// Remove `slug` from the group it belongs to unless that group is `canonical`'s.
+    fn detach(&mut self, slug: &str, canonical: &str) {
+        if let Some(prev) = self.canonical.get(slug).cloned()
+            && prev != canonical
+            && let Some(group) = self.groups.get_mut(&prev)
+        {
+            group.retain(|s| s != slug);
+        }
+    }
+
+    /// Canonical slug for `slug` (the slug itself when it has no alias).
+    pub fn canonical<'a>(&'a self, slug: &'a str) -> &'a str {
+        self.canonical
+            .get(&slug.to_lowercase())
+            .map_or(slug, String::as_str)
+    }
+
+    /// Every slug that means the same agent as `slug`, canonical first.
+    pub fn expand(&self, slug: &str) -> Vec<String> {
+        self.groups
+            .get(self.canonical(slug))
+            .cloned()
+            .unwrap_or_else(|| vec![slug.to_string()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_aliases_resolve_both_ways() {
+        let aliases = AgentAliases::builtin();
+        assert_eq!(aliases.canonical("gemini_cli"), "gemini");
+        assert_eq!(aliases.canonical("gemini"), "gemini");
+        assert_eq!(aliases.canonical("aider"), "aider");
+        assert_eq!(
+            aliases.expand("gemini_cli"),
+            vec!["gemini", "gemini_cli", "gemini-cli"]
+        );
+        assert_eq!(aliases.expand("aider"), vec!["aider"]);
+    }
+
+    #[test]
+    fn config_extends_and_overrides_builtins() {
+        let extra = BTreeMap::from([
+            ("cursor".to_string(), vec!["cursor_ide".to_string()]),
+            ("claude_desktop".to_string(), vec!["claude".to_string()]),
+        ]);
+        let aliases = AgentAliases::with_config(&extra);
+        assert_eq!(aliases.canonical("cursor_ide"), "cursor");
+        assert_eq!(aliases.canonical("claude"), "claude_desktop");
+        assert!(
+            !aliases
+                .expand("claude_code")
+                .contains(&"claude".to_string())
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! FastEmbed-based cross-encoder reranker (MS MARCO MiniLM).
+//!
+//! Scores (query, document) pairs jointly, which is slower than bi-encoder
+//! similarity but considerably more precise for reordering a short candidate
+//! list. Like [`super::fastembed_embedder`], this never downloads model assets;
+//! it expects the model files on disk and returns a clear error when missing.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use fastembed::{
+    RerankInitOptionsUserDefined, TextRerank, TokenizerFiles, UserDefinedRerankingModel,
+};
+
+use super::embedder::{EmbedderError, EmbedderResult};
+
+const RERANKER_ID: &str = "ms-marco-minilm-l6-v2";
+const MODEL_DIR_NAME: &str = "ms-marco-MiniLM-L-6-v2";
+
+const MODEL_FILE: &str = "model.onnx";
+const TOKENIZER_JSON: &str = "tokenizer.json";
+const CONFIG_JSON: &str = "config.json";
+const SPECIAL_TOKENS_JSON: &str = "special_tokens_map.json";
+const TOKENIZER_CONFIG_JSON: &str = "tokenizer_config.json";
+
+/// Scores documents against a query; higher scores are more relevant.
+pub trait Reranker: Send + Sync {
+    /// Stable identifier for this reranker.
+    fn id(&self) -> &str;
+
+    /// Score each document against `query`, returning one score per document
+    /// in input order.
+    fn score(&self, query: &str, documents: &[&str]) -> EmbedderResult<Vec<f32>>;
+}
+
+/// FastEmbed-backed cross-encoder reranker.
+pub struct FastReranker {
+    model: Mutex<TextRerank>,
+}
+
+impl FastReranker {
+    /// Stable reranker identifier.
+    pub fn reranker_id_static() -> &'static str {
+        RERANKER_ID
+    }
+
+    /// Required model files (must all exist locally).
+    pub fn required_model_files() -> &'static [&'static str] {
+        &[
+            MODEL_FILE,
+            TOKENIZER_JSON,
+            CONFIG_JSON,
+            SPECIAL_TOKENS_JSON,
+            TOKENIZER_CONFIG_JSON,
+        ]
+    }
+
+    /// Default model directory relative to the cass data dir.
+    pub fn default_model_dir(data_dir: &Path) -> PathBuf {
+        data_dir.join("models").join(MODEL_DIR_NAME)
+    }
+
+    /// Load the cross-encoder model + tokenizer from a local directory.
+    ///
+    /// This never downloads; it returns `EmbedderError::Unavailable` if any
+    /// required file is missing.
+    pub fn load_from_dir(model_dir: &Path) -> EmbedderResult<Self> {
+        if !model_dir.is_dir() {
+            return Err(EmbedderError::Unavailable(format!(
+                "reranker model directory not found: {}",
+                model_dir.display()
+            )));
+        }
+
+        let missing: Vec<&str> = Self::required_model_files()
+            .iter()
+            .filter(|name| !model_dir.join(name).is_file())
+            .copied()
+            .collect();
+        if !missing.is_empty() {
+            return Err(EmbedderError::Unavailable(format!(
+                "reranker model files missing in {}: {}",
+                model_dir.display(),
+                missing.join(", ")
+            )));
+        }
+
+        let read = |label: &str| -> EmbedderResult<Vec<u8>> {
+            let path = model_dir.join(label);
+            fs::read(&path).map_err(|e| {
+                EmbedderError::Unavailable(format!(
+                    "unable to read {label} at {}: {e}",
+                    path.display()
+                ))
+            })
+        };
+
+        let tokenizer_files = TokenizerFiles {
+            tokenizer_file: read(TOKENIZER_JSON)?,
+            config_file: read(CONFIG_JSON)?,
+            special_tokens_map_file: read(SPECIAL_TOKENS_JSON)?,
+            tokenizer_config_file: read(TOKENIZER_CONFIG_JSON)?,
+        };
+        let model = UserDefinedRerankingModel::new(read(MODEL_FILE)?, tokenizer_files);
+
+        let model =
+            TextRerank::try_new_from_user_defined(model, RerankInitOptionsUserDefined::default())
+                .map_err(|e| {
+                EmbedderError::EmbeddingFailed(format!("fastembed rerank init failed: {e}"))
+            })?;
+
+        Ok(Self {
+            model: Mutex::new(model),
+        })
+    }
+}
+
+impl Reranker for FastReranker {
+    fn id(&self) -> &str {
+        RERANKER_ID
+    }
+
+    fn score(&self, query: &str, documents: &[&str]) -> EmbedderResult<Vec<f32>> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut model = self
+            .model
+            .lock()
+            .map_err(|_| EmbedderError::Internal("fastembed lock poisoned".to_string()))?;
+
+        let results = model
+            .rerank(query, documents, false, None)
+            .map_err(|e| EmbedderError::EmbeddingFailed(format!("fastembed rerank failed: {e}")))?;
+
+        // fastembed returns results sorted by score; restore input order.
+        let mut scores = vec![f32::NEG_INFINITY; documents.len()];
+        for result in results {
+            if let Some(slot) = scores.get_mut(result.index) {
+                *slot = result.score;
+            }
+        }
+        Ok(scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reranker_missing_files_returns_unavailable() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let err = match FastReranker::load_from_dir(tmp.path()) {
+            Ok(_) => panic!("expected missing-model error"),
+            Err(err) => err,
+        };
+        match err {
+            EmbedderError::Unavailable(msg) => {
+                assert!(msg.contains("reranker model files missing"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
@@ -3,33 +3,39 @@ use lru::LruCache;
 use once_cell::sync::Lazy;
 use std::cell::RefCell;
 use std::cmp::Ordering as CmpOrdering;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::num::NonZeroUsize;
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tantivy::collector::TopDocs;
+use tantivy::collector::{Count, TopDocs};
 use tantivy::query::{
     AllQuery, BooleanQuery, Occur, PhraseQuery, Query, RangeQuery, RegexQuery, TermQuery,
 };
 use tantivy::schema::{IndexRecordOption, Term, Value};
 use tantivy::snippet::SnippetGenerator;
-use tantivy::{Index, IndexReader, Searcher, TantivyDocument};
+use tantivy::{
+    DocAddress, DocId, Index, IndexReader, Score, Searcher, SegmentReader, TantivyDocument,
+};
 use tokio::runtime::Handle;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 
 use rusqlite::Connection;
 
+use crate::search::agent_alias::AgentAliases;
 use crate::search::canonicalize::canonicalize_for_embedding;
 use crate::search::embedder::Embedder;
+use crate::search::fastembed_reranker::Reranker;
+use crate::search::result_cache::ResultCache;
 use crate::search::tantivy::fields_from_schema;
 use crate::search::vector_index::{
-    SemanticFilter, SemanticFilterMaps, VectorIndex, VectorSearchResult,
+    ROLE_TOOL, SemanticFilter, SemanticFilterMaps, VectorIndex, VectorSearchResult,
+    role_code_from_str,
 };
 
-use crate::sources::provenance::SourceFilter;
+use crate::sources::provenance::{LOCAL_SOURCE_ID, SourceFilter};
 
 #[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct SearchFilters {
@@ -40,9 +46,149 @@ pub struct SearchFilters {
     /// Filter by conversation source (local, remote, or specific source ID)
     #[serde(skip_serializing_if = "SourceFilter::is_all")]
     pub source_filter: SourceFilter,
+    /// Restrict to these source IDs (`local` matches conversations with no remote origin)
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    pub source_ids: HashSet<String>,
+    /// Restrict to these origin hosts (`local` matches conversations with no remote origin)
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    pub hosts: HashSet<String>,
     /// Filter to specific session source paths (for chained searches)
     #[serde(skip_serializing_if = "HashSet::is_empty")]
     pub session_paths: HashSet<String>,
+    /// Snippet highlighting configuration (fragment size and count)
+    #[serde(skip_serializing_if = "HighlightOptions::is_default")]
+    pub highlight: HighlightOptions,
+    /// Attach Tantivy score explanations to returned hits (expensive; bypasses the cache)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub explain: bool,
+    /// Drop tool-output messages (`role == "tool"`) from results
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub exclude_tool_messages: bool,
+    /// Restrict to messages with these roles (`user`, `assistant`, `system`, `tool`)
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    pub roles: HashSet<String>,
+    /// Return hits with empty `snippet`/`content`, skipping all highlighting work
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub skip_snippets: bool,
+}
+
+impl SearchFilters {
+    /// True when `source_ids` or `hosts` restrict results.
+    pub fn has_provenance_filter(&self) -> bool {
+        !self.source_ids.is_empty() || !self.hosts.is_empty()
+    }
+
+    /// Whether `hit` passes the `source_ids` and `hosts` filters.
+    pub fn matches_provenance(&self, hit: &SearchHit) -> bool {
+        let is_local = hit.origin_kind == "local";
+        let source_ok = self.source_ids.is_empty()
+            || self.source_ids.contains(&hit.source_id)
+            || (is_local && self.source_ids.contains(LOCAL_SOURCE_ID));
+        let host_ok = self.hosts.is_empty()
+            || hit
+                .origin_host
+                .as_ref()
+                .is_some_and(|h| self.hosts.contains(h))
+            || (is_local && self.hosts.contains(LOCAL_SOURCE_ID));
+        source_ok && host_ok
+    }
+
+    /// Widen `agents` so each requested slug also matches its aliases.
+    pub fn expand_agent_aliases(&mut self, aliases: &AgentAliases) {
+        let expanded: HashSet<String> = self
+            .agents
+            .iter()
+            .flat_map(|agent| aliases.expand(agent))
+            .collect();
+        self.agents = expanded;
+    }
+
+    /// Add a `--source` value: `remote` selects remote sources by kind, `all` is
+    /// a no-op, and anything else (including `local`) is a source ID.
+    pub fn add_source(&mut self, source: &str) {
+        match SourceFilter::parse(source) {
+            SourceFilter::All => {}
+            SourceFilter::Remote => self.source_filter = SourceFilter::Remote,
+            SourceFilter::Local => {
+                self.source_ids.insert(LOCAL_SOURCE_ID.to_string());
+            }
+            SourceFilter::SourceId(id) => {
+                self.source_ids.insert(id);
+            }
+        }
+    }
+}
+
+/// Roles accepted by the `role:` query scope.
+const SCOPED_ROLES: &[&str] = &["user", "assistant", "system", "tool"];
+
+/// Move leading field-scoped terms from `query` into `filters` and return the
+/// remaining free text: `agent:codex`, `ws:PATH` (or `workspace:PATH`),
+/// `role:user|assistant|system|tool` and `source:ID`.
+///
+/// Scanning stops at the first token that is not a recognized scope, so
+/// `foo:bar` and everything after it stay in the searched text.
+pub fn apply_field_scopes(query: &str, filters: &mut SearchFilters) -> String {
+    let mut rest = query.trim_start();
+    while let Some((field, value)) = rest
+        .split(char::is_whitespace)
+        .next()
+        .and_then(|token| token.split_once(':'))
+        .filter(|(_, value)| !value.is_empty())
+    {
+        match field.to_ascii_lowercase().as_str() {
+            "agent" => {
+                filters.agents.insert(value.to_string());
+            }
+            "ws" | "workspace" => {
+                filters.workspaces.insert(value.to_string());
+            }
+            "role" if SCOPED_ROLES.contains(&value.to_ascii_lowercase().as_str()) => {
+                filters.roles.insert(value.to_ascii_lowercase());
+            }
+            "source" => filters.add_source(value),
+            _ => break,
+        }
+        let token_len = field.len() + 1 + value.len();
+        rest = rest[token_len..].trim_start();
+    }
+    rest.to_string()
+}
+
+/// Separator placed between snippet fragments when `max_fragments > 1`.
+pub const FRAGMENT_SEPARATOR: &str = " … ";
+
+/// Controls how `SearchHit::snippet` is cut around query matches.
+///
+/// The default reproduces each backend's built-in behaviour: a single fragment
+/// sized by the engine (Tantivy ~150 chars, prefix fast path 160 chars, SQLite
+/// FTS 64 tokens).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct HighlightOptions {
+    /// Maximum characters per fragment; `None` keeps the backend default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fragment_chars: Option<usize>,
+    /// Maximum number of fragments joined with `FRAGMENT_SEPARATOR`.
+    pub max_fragments: usize,
+}
+
+impl Default for HighlightOptions {
+    fn default() -> Self {
+        Self {
+            fragment_chars: None,
+            max_fragments: 1,
+        }
+    }
+}
+
+impl HighlightOptions {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn multi_fragment(&self) -> bool {
+        self.max_fragments > 1
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, clap::ValueEnum)]
@@ -69,6 +215,11 @@ impl SearchMode {
 
 const RRF_K: f32 = 60.0;
 const HYBRID_CANDIDATE_MULTIPLIER: usize = 3;
+/// Default half-life (in days) of the recency component used by `blend_recency`.
+pub const DEFAULT_RECENCY_HALF_LIFE_DAYS: f32 = 30.0;
+const MS_PER_DAY: f32 = 86_400_000.0;
+/// Most docs past the page end fetched to order hits tied with the last one on the page.
+const MAX_TIE_FETCH: usize = 1_000;
 
 // ============================================================================
 // Query Explanation types (--explain flag support)
@@ -280,7 +431,10 @@ impl QueryExplanation {
             || !filters.workspaces.is_empty()
             || filters.created_from.is_some()
             || filters.created_to.is_some()
-            || !filters.source_filter.is_all();
+            || !filters.source_filter.is_all()
+            || filters.has_provenance_filter()
+            || filters.exclude_tool_messages
+            || !filters.roles.is_empty();
 
         if has_filters {
             return QueryType::Filtered;
@@ -386,6 +540,11 @@ impl QueryExplanation {
         if has_time_filter {
             parts.push("time range".to_string());
         }
+        if !filters.roles.is_empty() {
+            let mut roles: Vec<_> = filters.roles.iter().map(String::as_str).collect();
+            roles.sort_unstable();
+            parts.push(format!("role {}", roles.join("/")));
+        }
 
         let description = if parts.is_empty() {
             None
@@ -474,7 +633,7 @@ impl QueryExplanation {
 
 /// Indicates how a search result matched the query.
 /// Used for ranking: exact matches rank higher than wildcard matches.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum MatchType {
     /// No wildcards - matched via exact term or edge n-gram prefix
@@ -488,6 +647,8 @@ pub enum MatchType {
     Substring,
     /// Matched via automatic wildcard fallback when exact search was sparse
     ImplicitWildcard,
+    /// Matched via embedding similarity only (no lexical term match)
+    Semantic,
 }
 
 impl MatchType {
@@ -499,6 +660,19 @@ impl MatchType {
             MatchType::Suffix => 0.8,
             MatchType::Substring => 0.7,
             MatchType::ImplicitWildcard => 0.6,
+            MatchType::Semantic => 0.6,
+        }
+    }
+
+    /// Name used in JSON output and human tags (e.g. `implicit_wildcard`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MatchType::Exact => "exact",
+            MatchType::Prefix => "prefix",
+            MatchType::Suffix => "suffix",
+            MatchType::Substring => "substring",
+            MatchType::ImplicitWildcard => "implicit_wildcard",
+            MatchType::Semantic => "semantic",
         }
     }
 }
@@ -588,7 +762,7 @@ impl QuerySuggestion {
     }
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SearchHit {
     pub title: String,
     pub snippet: String,
@@ -616,6 +790,29 @@ pub struct SearchHit {
     /// Origin host label for remote sources
     #[serde(skip_serializing_if = "Option::is_none")]
     pub origin_host: Option<String>,
+    /// Tantivy scoring breakdown, present only when `SearchFilters::explain` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<serde_json::Value>,
+    /// Cross-encoder relevance score, present only for hits reordered by `rerank_hits`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rerank_score: Option<f32>,
+    /// Other hits from the same `source_path` folded into this one, present only with `search --dedupe`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_count: Option<usize>,
+    /// Matching messages in this hit's conversation, present only with `search --by-conversation`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_matches: Option<usize>,
+    /// `source_path` relative to the hit's workspace, present only with `search --relative-paths`
+    /// when the file lies under that workspace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_path: Option<String>,
+    /// Database row id of the matched message; stable across reindexing, unlike
+    /// `line_number` in a file that keeps growing. Look it up with `cass show --message`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<i64>,
+    /// Connector-assigned id of the hit's conversation (e.g. a session UUID), when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversation_external_id: Option<String>,
 }
 
 fn default_source_id() -> String {
@@ -626,6 +823,65 @@ fn default_origin_kind() -> String {
     "local".to_string()
 }
 
+/// Anchor for a message within a session (`msg-{idx}`, 0-indexed).
+///
+/// Shared by search hit permalinks and HTML exports so a permalink fragment
+/// resolves to the matching element in an exported transcript.
+pub fn message_anchor(msg_idx: usize) -> String {
+    format!("msg-{msg_idx}")
+}
+
+/// Stable session identifier for a conversation.
+///
+/// Uses the connector's external id, so conversations sharing one store file
+/// (Cursor `state.vscdb`, Aider chat history) get distinct ids, and falls back
+/// to the source path's file stem when the id is unknown. Ids that aren't
+/// link-safe (e.g. paths) are replaced by a hash. Remote sources are prefixed
+/// with their source id so the same session synced from two machines differs.
+pub fn session_permalink_id(
+    source_id: &str,
+    external_id: Option<&str>,
+    source_path: &str,
+) -> String {
+    let link_safe = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    let session = match external_id.filter(|id| !id.is_empty()) {
+        Some(id) if link_safe(id) => id.to_string(),
+        Some(id) => format!("{:016x}", stable_content_hash(id)),
+        None => std::path::Path::new(source_path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .filter(|s| link_safe(s))
+            .unwrap_or_else(|| format!("{:016x}", stable_content_hash(source_path))),
+    };
+    if source_id.is_empty() || source_id == LOCAL_SOURCE_ID {
+        session
+    } else {
+        format!("{source_id}:{session}")
+    }
+}
+
+/// Deterministic permalink for a search hit: `agent/session#msg-idx`.
+///
+/// The message index is derived from `line_number` (1-indexed) and defaults to
+/// the first message when unknown.
+pub fn permalink(hit: &SearchHit) -> String {
+    let msg_idx = hit.line_number.unwrap_or(1).saturating_sub(1);
+    format!(
+        "{}/{}#{}",
+        hit.agent,
+        session_permalink_id(
+            &hit.source_id,
+            hit.conversation_external_id.as_deref(),
+            &hit.source_path
+        ),
+        message_anchor(msg_idx)
+    )
+}
+
 /// Result of a search operation with metadata about how matches were found
 #[derive(Debug, Clone)]
 pub struct SearchResult {
@@ -677,6 +933,31 @@ impl PartialOrd for SearchHitKey {
     }
 }
 
+/// Secondary ordering for hits with equal scores: newest first, then by
+/// source path and message index, so ties come back in the same order every run.
+#[derive(Debug, PartialEq, Eq)]
+struct TieBreakKey {
+    created_at: Option<i64>,
+    source_path: String,
+    msg_idx: Option<u64>,
+}
+
+impl Ord for TieBreakKey {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        other
+            .created_at
+            .cmp(&self.created_at)
+            .then_with(|| self.source_path.cmp(&other.source_path))
+            .then_with(|| self.msg_idx.cmp(&other.msg_idx))
+    }
+}
+
+impl PartialOrd for TieBreakKey {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 struct HybridScore {
     rrf: f32,
@@ -771,6 +1052,109 @@ pub fn rrf_fuse_hits(
     results
 }
 
+/// Re-rank hits by blending relevance with recency:
+/// `score * (1 - w) + recency * w`, with `w` clamped to `[0, 1]`
+/// (0.0 = pure relevance, 1.0 = pure recency).
+///
+/// Both components are normalized to `[0, 1]` so the blend is meaningful across
+/// backends:
+/// - relevance is `score / max_score` over `hits` (non-positive scores count as 0);
+/// - recency decays exponentially, `0.5^(age_days / half_life_days)`, where age is
+///   measured from the newest `created_at` in `hits` rather than wall-clock time,
+///   so the same hits always produce the same order. Hits without `created_at`
+///   get recency 0.
+///
+/// A non-positive or NaN `half_life_days` falls back to
+/// `DEFAULT_RECENCY_HALF_LIFE_DAYS`. Each hit's `score` is replaced by its blended
+/// value and hits are sorted by it (descending, stable for ties).
+pub fn blend_recency(hits: &mut [SearchHit], recency_weight: f32, half_life_days: f32) {
+    if hits.is_empty() {
+        return;
+    }
+    let weight = if recency_weight.is_nan() {
+        0.0
+    } else {
+        recency_weight.clamp(0.0, 1.0)
+    };
+    let half_life_days = if half_life_days > 0.0 {
+        half_life_days
+    } else {
+        DEFAULT_RECENCY_HALF_LIFE_DAYS
+    };
+    let max_score = hits.iter().map(|h| h.score).fold(0.0f32, f32::max);
+    let newest = hits.iter().filter_map(|h| h.created_at).max();
+
+    for hit in hits.iter_mut() {
+        let relevance = if max_score > 0.0 {
+            (hit.score / max_score).max(0.0)
+        } else {
+            0.0
+        };
+        let recency = match (hit.created_at, newest) {
+            (Some(ts), Some(newest)) => {
+                let age_days = (newest - ts) as f32 / MS_PER_DAY;
+                0.5f32.powf(age_days / half_life_days)
+            }
+            _ => 0.0,
+        };
+        hit.score = relevance * (1.0 - weight) + recency * weight;
+    }
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+}
+
+/// Drop hits whose `score` is below `min_score`, or below `min_score_pct` percent
+/// of the best score in `hits`; when both are given the stricter bound applies.
+///
+/// Lexical scores are unnormalized BM25 (usually around 1-30, higher for rare
+/// terms and short messages), so the percentage form is easier to reuse across
+/// queries. Order is preserved.
+pub fn retain_min_score(
+    hits: &mut Vec<SearchHit>,
+    min_score: Option<f32>,
+    min_score_pct: Option<f32>,
+) {
+    let best = hits
+        .iter()
+        .map(|h| h.score)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let relative = min_score_pct.map(|pct| best * (pct / 100.0));
+    let threshold = match (min_score, relative) {
+        (Some(a), Some(b)) => a.max(b),
+        (Some(t), None) | (None, Some(t)) => t,
+        (None, None) => return,
+    };
+    hits.retain(|h| h.score >= threshold);
+}
+
+/// Rerank the first `top_n` hits with a cross-encoder against the raw `query`.
+///
+/// Those hits keep their original `score`, gain a `rerank_score`, and are
+/// reordered by it (descending, stable for ties). Hits beyond `top_n` follow in
+/// their original order without a `rerank_score`, so paging past the reranked
+/// window still returns results.
+pub fn rerank_hits(
+    hits: &mut [SearchHit],
+    query: &str,
+    reranker: &dyn Reranker,
+    top_n: usize,
+) -> Result<()> {
+    let top_n = top_n.min(hits.len());
+    let top = &mut hits[..top_n];
+    let documents: Vec<&str> = top.iter().map(|h| h.content.as_str()).collect();
+    let scores = reranker
+        .score(query, &documents)
+        .map_err(|e| anyhow!("rerank failed: {e}"))?;
+    for (hit, score) in top.iter_mut().zip(scores) {
+        hit.rerank_score = Some(score);
+    }
+    top.sort_by(|a, b| {
+        b.rerank_score
+            .unwrap_or(f32::NEG_INFINITY)
+            .total_cmp(&a.rerank_score.unwrap_or(f32::NEG_INFINITY))
+    });
+    Ok(())
+}
+
 struct QueryCache {
     embedder_id: String,
     embeddings: LruCache<String, Vec<f32>>,
@@ -825,6 +1209,9 @@ pub struct SearchClient {
     metrics: Metrics,
     cache_namespace: String,
     semantic: Mutex<Option<SemanticSearchState>>,
+    result_cache: Option<ResultCache>,
+    /// Phase timings summed over every `search` call; `None` unless profiling.
+    profile: Option<Mutex<SearchTimings>>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -842,6 +1229,69 @@ pub struct CacheStats {
     pub approx_bytes: usize,
     /// Byte cap if set (0 = no byte limit)
     pub byte_cap: usize,
+    /// Lookups served from the on-disk result cache
+    pub disk_hits: u64,
+    /// Lookups that missed the on-disk result cache
+    pub disk_miss: u64,
+}
+
+/// Wall-clock time spent in each phase of a search, collected when the client
+/// was built with [`SearchClient::with_profiling`].
+///
+/// `open` is left at zero by the client, since the index is opened before the
+/// client exists; callers that time [`SearchClient::open`] fill it in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchTimings {
+    pub open: Duration,
+    /// Query sanitizing and building the Tantivy query
+    pub parse: Duration,
+    /// Cache lookups, index execution, and loading matched documents
+    pub search: Duration,
+    /// Snippet generation and fragment highlighting
+    pub highlight: Duration,
+    pub total: Duration,
+}
+
+impl SearchTimings {
+    fn add(&mut self, other: &SearchTimings) {
+        self.open += other.open;
+        self.parse += other.parse;
+        self.search += other.search;
+        self.highlight += other.highlight;
+        self.total += other.total;
+    }
+}
+
+/// Slice of the ranking a Tantivy search returns, and where to record its
+/// phase timings.
+struct TantivyPage<'a> {
+    limit: usize,
+    offset: usize,
+    timings: &'a mut Option<SearchTimings>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SearchPhase {
+    Parse,
+    Search,
+    Highlight,
+}
+
+/// Run `f`, adding its duration to `phase` when profiling. Without a timings
+/// slot no clock is read, so unprofiled searches pay nothing.
+fn timed<T>(timings: &mut Option<SearchTimings>, phase: SearchPhase, f: impl FnOnce() -> T) -> T {
+    let Some(timings) = timings else {
+        return f();
+    };
+    let started = Instant::now();
+    let out = f();
+    let slot = match phase {
+        SearchPhase::Parse => &mut timings.parse,
+        SearchPhase::Search => &mut timings.search,
+        SearchPhase::Highlight => &mut timings.highlight,
+    };
+    *slot += started.elapsed();
+    out
 }
 
 // Cache tuning: read from env to allow runtime override without recompiling.
@@ -1431,6 +1881,333 @@ fn build_boolean_query_clauses(
 
 /// Determine the dominant match type from a query string.
 /// Returns the "loosest" pattern used (Substring > Suffix > Prefix > Exact).
+/// Build the Tantivy query for `query` with every filter applied as a clause.
+fn build_tantivy_query(
+    query: &str,
+    filters: &SearchFilters,
+    fields: &crate::search::tantivy::Fields,
+) -> Box<dyn Query> {
+    let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+
+    // Parse query with boolean operator support (AND, OR, NOT, "phrases")
+    // Falls back to simple whitespace split for plain queries (implicit AND)
+    let tokens = parse_boolean_query(query);
+    if tokens.is_empty() {
+        clauses.push((Occur::Must, Box::new(AllQuery)));
+    } else if has_boolean_operators(query) {
+        // Use boolean query builder for complex queries
+        let bool_clauses = build_boolean_query_clauses(&tokens, fields);
+        clauses.extend(bool_clauses);
+    } else {
+        // Simple query: treat each term as MUST (implicit AND)
+        for token in tokens {
+            if let QueryToken::Term(term_str) = token {
+                let pattern = WildcardPattern::parse(&term_str);
+                let term_shoulds = build_term_query_clauses(&pattern, fields);
+                if !term_shoulds.is_empty() {
+                    clauses.push((Occur::Must, Box::new(BooleanQuery::new(term_shoulds))));
+                }
+            }
+        }
+    }
+
+    if !filters.agents.is_empty() {
+        let terms = filters
+            .agents
+            .iter()
+            .map(|agent| {
+                (
+                    Occur::Should,
+                    Box::new(TermQuery::new(
+                        Term::from_field_text(fields.agent, agent),
+                        IndexRecordOption::Basic,
+                    )) as Box<dyn Query>,
+                )
+            })
+            .collect();
+        clauses.push((Occur::Must, Box::new(BooleanQuery::new(terms))));
+    }
+
+    if !filters.workspaces.is_empty() {
+        let terms = filters
+            .workspaces
+            .iter()
+            .flat_map(|ws| {
+                [fields.workspace, fields.workspace_original].map(|field| {
+                    (
+                        Occur::Should,
+                        Box::new(TermQuery::new(
+                            Term::from_field_text(field, ws),
+                            IndexRecordOption::Basic,
+                        )) as Box<dyn Query>,
+                    )
+                })
+            })
+            .collect();
+        clauses.push((Occur::Must, Box::new(BooleanQuery::new(terms))));
+    }
+
+    if filters.created_from.is_some() || filters.created_to.is_some() {
+        use std::ops::Bound::{Included, Unbounded};
+        let lower = filters.created_from.map_or(Unbounded, |v| {
+            Included(Term::from_field_i64(fields.created_at, v))
+        });
+        let upper = filters.created_to.map_or(Unbounded, |v| {
+            Included(Term::from_field_i64(fields.created_at, v))
+        });
+        let range = RangeQuery::new(lower, upper);
+        clauses.push((Occur::Must, Box::new(range)));
+    }
+
+    // Source filter (P3.1)
+    match &filters.source_filter {
+        SourceFilter::All => {
+            // No filtering needed
+        }
+        SourceFilter::Local => {
+            // Filter to local sources only (origin_kind == "local")
+            let term = Term::from_field_text(fields.origin_kind, "local");
+            clauses.push((
+                Occur::Must,
+                Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
+            ));
+        }
+        SourceFilter::Remote => {
+            // Filter to remote sources only (origin_kind == "ssh")
+            // We use "ssh" since that's the only remote kind currently
+            let term = Term::from_field_text(fields.origin_kind, "ssh");
+            clauses.push((
+                Occur::Must,
+                Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
+            ));
+        }
+        SourceFilter::SourceId(source_id) => {
+            // Filter to specific source by ID
+            let term = Term::from_field_text(fields.source_id, source_id);
+            clauses.push((
+                Occur::Must,
+                Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
+            ));
+        }
+    }
+
+    if filters.exclude_tool_messages {
+        let term = Term::from_field_text(fields.role, "tool");
+        clauses.push((
+            Occur::MustNot,
+            Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
+        ));
+    }
+
+    if !filters.roles.is_empty() {
+        let terms = filters
+            .roles
+            .iter()
+            .map(|role| {
+                (
+                    Occur::Should,
+                    Box::new(TermQuery::new(
+                        Term::from_field_text(fields.role, role),
+                        IndexRecordOption::Basic,
+                    )) as Box<dyn Query>,
+                )
+            })
+            .collect();
+        clauses.push((Occur::Must, Box::new(BooleanQuery::new(terms))));
+    }
+
+    // Provenance filters: any listed value matches; `local` means no remote origin.
+    for (values, field) in [
+        (&filters.source_ids, fields.source_id),
+        (&filters.hosts, fields.origin_host),
+    ] {
+        if values.is_empty() {
+            continue;
+        }
+        let terms = values
+            .iter()
+            .map(|value| {
+                let term = if value == LOCAL_SOURCE_ID {
+                    Term::from_field_text(fields.origin_kind, "local")
+                } else {
+                    Term::from_field_text(field, value)
+                };
+                (
+                    Occur::Should,
+                    Box::new(TermQuery::new(term, IndexRecordOption::Basic)) as Box<dyn Query>,
+                )
+            })
+            .collect();
+        clauses.push((Occur::Must, Box::new(BooleanQuery::new(terms))));
+    }
+
+    if !filters.session_paths.is_empty() {
+        let terms = filters
+            .session_paths
+            .iter()
+            .map(|path| {
+                (
+                    Occur::Should,
+                    Box::new(TermQuery::new(
+                        Term::from_field_text(fields.source_path, path),
+                        IndexRecordOption::Basic,
+                    )) as Box<dyn Query>,
+                )
+            })
+            .collect();
+        clauses.push((Occur::Must, Box::new(BooleanQuery::new(terms))));
+    }
+
+    if clauses.is_empty() {
+        Box::new(AllQuery)
+    } else if clauses.len() == 1 {
+        let (occur, query_box) = clauses.pop().unwrap();
+        match occur {
+            // For Must, we can safely unwrap and use the inner query directly
+            Occur::Must => query_box,
+            // For MustNot or Should, we must preserve the Occur by wrapping
+            // in a BooleanQuery. A lone MustNot (e.g., "NOT foo") should match
+            // nothing, not match "foo".
+            _ => Box::new(BooleanQuery::new(vec![(occur, query_box)])),
+        }
+    } else {
+        Box::new(BooleanQuery::new(clauses))
+    }
+}
+
+/// Row sources for `conversations c` and `sources s` in the FTS fallback.
+/// Databases written before provenance was tracked have neither the
+/// `source_id`/`origin_host` columns nor the `sources` table; all of their
+/// conversations are local, so NULL stand-ins keep the same SQL valid.
+fn sqlite_provenance_tables(conn: &Connection) -> (&'static str, &'static str) {
+    let conversations = if conn
+        .prepare("SELECT source_id, origin_host FROM conversations LIMIT 0")
+        .is_ok()
+    {
+        "conversations"
+    } else {
+        "(SELECT *, NULL AS source_id, NULL AS origin_host FROM conversations)"
+    };
+    let sources = if conn.prepare("SELECT id, kind FROM sources LIMIT 0").is_ok() {
+        "sources"
+    } else {
+        "(SELECT NULL AS id, NULL AS kind)"
+    };
+    (conversations, sources)
+}
+
+/// Append `AND ...` clauses (and their parameters) for every filter to an
+/// `fts_messages f` query joined with `conversations c`.
+fn push_sqlite_filters(
+    filters: &SearchFilters,
+    sql: &mut String,
+    params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+) {
+    if !filters.agents.is_empty() {
+        let placeholders = (0..filters.agents.len())
+            .map(|_| "?".to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        sql.push_str(&format!(" AND f.agent IN ({placeholders})"));
+        for a in &filters.agents {
+            params.push(Box::new(a.clone()));
+        }
+    }
+
+    if !filters.workspaces.is_empty() {
+        let placeholders = (0..filters.workspaces.len())
+            .map(|_| "?".to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        // Remote sessions keep their pre-rewrite workspace in metadata (P6.2).
+        sql.push_str(&format!(
+            " AND (f.workspace IN ({placeholders}) OR json_extract(c.metadata_json, '$.cass.workspace_original') IN ({placeholders}))"
+        ));
+        for _ in 0..2 {
+            for w in &filters.workspaces {
+                params.push(Box::new(w.clone()));
+            }
+        }
+    }
+
+    if let Some(created_from) = filters.created_from {
+        sql.push_str(" AND f.created_at >= ?");
+        params.push(Box::new(created_from));
+    }
+    if let Some(created_to) = filters.created_to {
+        sql.push_str(" AND f.created_at <= ?");
+        params.push(Box::new(created_to));
+    }
+
+    // `local` matches conversations with no remote origin.
+    for (values, column) in [
+        (&filters.source_ids, "c.source_id"),
+        (&filters.hosts, "c.origin_host"),
+    ] {
+        if values.is_empty() {
+            continue;
+        }
+        let placeholders = (0..values.len())
+            .map(|_| "?".to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let local = if values.contains(LOCAL_SOURCE_ID) {
+            " OR COALESCE(c.source_id, 'local') = 'local'"
+        } else {
+            ""
+        };
+        sql.push_str(&format!(" AND ({column} IN ({placeholders}){local})"));
+        for v in values {
+            params.push(Box::new(v.clone()));
+        }
+    }
+
+    if filters.exclude_tool_messages {
+        sql.push_str(" AND COALESCE(m.role, '') != 'tool'");
+    }
+
+    if !filters.roles.is_empty() {
+        let placeholders = (0..filters.roles.len())
+            .map(|_| "?".to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        sql.push_str(&format!(" AND LOWER(m.role) IN ({placeholders})"));
+        for role in &filters.roles {
+            params.push(Box::new(role.clone()));
+        }
+    }
+
+    if !filters.session_paths.is_empty() {
+        let placeholders = (0..filters.session_paths.len())
+            .map(|_| "?".to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        sql.push_str(&format!(" AND f.source_path IN ({placeholders})"));
+        for p in &filters.session_paths {
+            params.push(Box::new(p.clone()));
+        }
+    }
+}
+
+/// Query retried by [`SearchClient::search_with_fallback`] when results are sparse:
+/// each term wrapped as `*term*`.
+pub fn wildcard_fallback_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|term| format!("*{}*", term.trim_matches('*')))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// FTS5 requires balanced double quotes; strip them when unbalanced to avoid a syntax error.
+fn fts_safe_query(query: &str) -> String {
+    if query.matches('"').count().is_multiple_of(2) {
+        query.to_string()
+    } else {
+        query.replace('"', "")
+    }
+}
+
 fn dominant_match_type(query: &str) -> MatchType {
     let mut worst = MatchType::Exact;
     for term in query.split_whitespace() {
@@ -1544,6 +2321,38 @@ fn snippet_from_content(content: &str) -> String {
     format!("{preview}...")
 }
 
+/// Deferred Tantivy score explanations for a single search.
+///
+/// Explanations are expensive, so `search_tantivy` only records doc addresses and
+/// the caller explains the hits that remain after dedup and truncation.
+struct ScoreExplainer {
+    searcher: Searcher,
+    query: Box<dyn Query>,
+    docs: HashMap<(String, Option<usize>, String), DocAddress>,
+}
+
+impl ScoreExplainer {
+    fn key(hit: &SearchHit) -> (String, Option<usize>, String) {
+        (
+            hit.source_path.clone(),
+            hit.line_number,
+            hit.content.clone(),
+        )
+    }
+
+    fn annotate(&self, hits: &mut [SearchHit]) {
+        for hit in hits {
+            let Some(addr) = self.docs.get(&Self::key(hit)) else {
+                continue;
+            };
+            match self.query.explain(&self.searcher, *addr) {
+                Ok(explanation) => hit.explanation = serde_json::to_value(&explanation).ok(),
+                Err(e) => tracing::debug!(error = %e, "score explanation failed"),
+            }
+        }
+    }
+}
+
 /// Deduplicate search hits by (source_id, content), keeping only the highest-scored hit
 /// for each unique content within a source.
 ///
@@ -1551,6 +2360,15 @@ fn snippet_from_content(content: &str) -> String {
 /// appears as separate results, since they represent distinct conversations.
 ///
 /// Also filters out tool invocation noise that isn't useful for search results.
+/// Clear `snippet` and `content` for snippet-less searches. Runs after
+/// deduplication, which compares content.
+fn strip_hit_text(hits: &mut [SearchHit]) {
+    for hit in hits {
+        hit.snippet.clear();
+        hit.content.clear();
+    }
+}
+
 fn deduplicate_hits(hits: Vec<SearchHit>) -> Vec<SearchHit> {
     // Key: (source_id, normalized_content) -> index in deduped
     let mut seen: HashMap<(String, String), usize> = HashMap::new();
@@ -1583,7 +2401,21 @@ fn deduplicate_hits(hits: Vec<SearchHit>) -> Vec<SearchHit> {
     deduped
 }
 
-impl SearchClient {
+/// Index `convs` into a throwaway in-memory index and return a client over it,
+/// for tests and for callers that want to search conversations without
+/// persisting them. Lexical search only: there is no database or vector index.
+pub fn build_ephemeral_index(
+    convs: &[crate::connectors::NormalizedConversation],
+) -> Result<SearchClient> {
+    let mut index = crate::search::tantivy::TantivyIndex::open_in_ram()?;
+    for conv in convs {
+        index.add_conversation(conv)?;
+    }
+    index.commit()?;
+    SearchClient::from_index(&index.index, None)
+}
+
+impl SearchClient {
     pub fn open(index_path: &Path, db_path: Option<&Path>) -> Result<Option<Self>> {
         let tantivy = Index::open_in_dir(index_path).ok().and_then(|mut idx| {
             // Register custom tokenizer so searches work
@@ -1593,12 +2425,30 @@ impl SearchClient {
             idx.reader().ok().map(|reader| (reader, fields))
         });
 
-        let sqlite = db_path.and_then(|p| Connection::open(p).ok());
+        // Searches only read, so this also works on a read-only data directory.
+        let sqlite = db_path.and_then(|p| crate::storage::sqlite::open_readonly_connection(p).ok());
 
         if tantivy.is_none() && sqlite.is_none() {
             return Ok(None);
         }
+        Ok(Some(Self::from_parts(tantivy, sqlite)))
+    }
 
+    /// Search an already open `index`, such as one from
+    /// [`TantivyIndex::open_in_ram`](crate::search::tantivy::TantivyIndex::open_in_ram),
+    /// with an optional database connection serving the same role as in [`Self::open`].
+    pub fn from_index(index: &Index, sqlite: Option<Connection>) -> Result<Self> {
+        let mut index = index.clone();
+        crate::search::tantivy::ensure_tokenizer(&mut index);
+        let fields = fields_from_schema(&index.schema())?;
+        let reader = index.reader()?;
+        Ok(Self::from_parts(Some((reader, fields)), sqlite))
+    }
+
+    fn from_parts(
+        tantivy: Option<(IndexReader, crate::search::tantivy::Fields)>,
+        sqlite: Option<Connection>,
+    ) -> Self {
         let shared_filters = Arc::new(Mutex::new(()));
         let reload_epoch = Arc::new(AtomicU64::new(0));
         let metrics = Metrics::default();
@@ -1620,7 +2470,7 @@ impl SearchClient {
             None
         };
 
-        Ok(Some(Self {
+        Self {
             reader: tantivy,
             sqlite,
             prefix_cache: Mutex::new(CacheShards::new(*CACHE_TOTAL_CAP, *CACHE_BYTE_CAP)),
@@ -1633,7 +2483,108 @@ impl SearchClient {
             metrics,
             cache_namespace,
             semantic: Mutex::new(None),
-        }))
+            result_cache: None,
+            profile: None,
+        }
+    }
+
+    /// True when there is no Tantivy index and searches are served from the
+    /// SQLite `fts_messages` table instead.
+    pub fn uses_sqlite_fallback(&self) -> bool {
+        self.reader.is_none() && self.sqlite.is_some()
+    }
+
+    /// Whether the SQLite FTS table has any rows to search.
+    pub fn sqlite_has_indexed_messages(&self) -> bool {
+        self.sqlite.as_ref().is_some_and(|conn| {
+            conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM fts_messages LIMIT 1)",
+                [],
+                |row| row.get::<_, bool>(0),
+            )
+            .unwrap_or(false)
+        })
+    }
+
+    /// Agent slugs present in the Tantivy index, or in the database when there
+    /// is no index.
+    pub fn indexed_agents(&self) -> Result<BTreeSet<String>> {
+        let mut agents = BTreeSet::new();
+        if let Some((reader, fields)) = &self.reader {
+            self.maybe_reload_reader(reader)?;
+            let searcher = self.searcher_for_thread(reader);
+            for segment in searcher.segment_readers() {
+                let inverted = segment.inverted_index(fields.agent)?;
+                let mut terms = inverted.terms().stream()?;
+                while terms.advance() {
+                    agents.insert(String::from_utf8_lossy(terms.key()).into_owned());
+                }
+            }
+        } else if let Some(conn) = &self.sqlite {
+            let mut stmt = conn.prepare("SELECT slug FROM agents")?;
+            for slug in stmt.query_map([], |row| row.get::<_, String>(0))? {
+                agents.insert(slug?);
+            }
+        }
+        Ok(agents)
+    }
+
+    /// Fill in `message_id` for Tantivy hits, which only know their conversation
+    /// and message index, from the database. Hits whose message isn't in the
+    /// database keep `None`.
+    fn attach_message_ids(&self, hits: &mut [SearchHit]) {
+        let Some(conn) = &self.sqlite else {
+            return;
+        };
+        let Ok(mut stmt) = conn.prepare_cached(
+            "SELECT m.id
+             FROM conversations c
+             JOIN messages m ON m.conversation_id = c.id
+             WHERE c.source_path = ?1 AND m.idx = ?2
+               AND (?4 IS NULL OR c.external_id = ?4)
+             ORDER BY c.source_id = ?3 DESC
+             LIMIT 1",
+        ) else {
+            return;
+        };
+        for hit in hits.iter_mut().filter(|h| h.message_id.is_none()) {
+            let Some(idx) = hit.line_number.and_then(|n| n.checked_sub(1)) else {
+                continue;
+            };
+            let row = stmt.query_row(
+                rusqlite::params![
+                    hit.source_path,
+                    idx as i64,
+                    hit.source_id,
+                    hit.conversation_external_id
+                ],
+                |row| row.get(0),
+            );
+            if let Ok(id) = row {
+                hit.message_id = Some(id);
+            }
+        }
+    }
+
+    /// Persist final results in `cache` so later processes can reuse them.
+    pub fn with_result_cache(mut self, cache: ResultCache) -> Self {
+        self.result_cache = Some(cache);
+        self
+    }
+
+    /// Record per-phase timings for every [`Self::search`]; read them back with
+    /// [`Self::timings`].
+    pub fn with_profiling(mut self) -> Self {
+        self.profile = Some(Mutex::new(SearchTimings::default()));
+        self
+    }
+
+    /// Timings summed over all searches so far, or `None` unless the client was
+    /// built with [`Self::with_profiling`].
+    pub fn timings(&self) -> Option<SearchTimings> {
+        self.profile
+            .as_ref()
+            .map(|p| p.lock().map(|t| *t).unwrap_or_default())
     }
 
     pub fn search(
@@ -1643,7 +2594,28 @@ impl SearchClient {
         limit: usize,
         offset: usize,
     ) -> Result<Vec<SearchHit>> {
-        let sanitized = sanitize_query(query);
+        let Some(profile) = &self.profile else {
+            return self.search_phases(query, filters, limit, offset, &mut None);
+        };
+        let started = Instant::now();
+        let mut timings = Some(SearchTimings::default());
+        let result = self.search_phases(query, filters, limit, offset, &mut timings);
+        if let (Some(mut timings), Ok(mut total)) = (timings, profile.lock()) {
+            timings.total = started.elapsed();
+            total.add(&timings);
+        }
+        result
+    }
+
+    fn search_phases(
+        &self,
+        query: &str,
+        filters: SearchFilters,
+        limit: usize,
+        offset: usize,
+        timings: &mut Option<SearchTimings>,
+    ) -> Result<Vec<SearchHit>> {
+        let sanitized = timed(timings, SearchPhase::Parse, || sanitize_query(query));
 
         // Schedule warmup for likely prefixes when user pauses typing.
         if offset == 0
@@ -1655,9 +2627,28 @@ impl SearchClient {
             });
         }
 
+        // Results persisted by an earlier process (explained searches are never cached).
+        let disk_key = self
+            .result_cache
+            .as_ref()
+            .filter(|_| !filters.explain)
+            .map(|_| format!("{}|{limit}|{offset}", self.cache_key(&sanitized, &filters)));
+        if let (Some(cache), Some(key)) = (&self.result_cache, &disk_key) {
+            if let Some(hits) = timed(timings, SearchPhase::Search, || cache.get(key)) {
+                self.metrics.inc_disk_hits();
+                return Ok(hits);
+            }
+            self.metrics.inc_disk_miss();
+        }
+
         // Fast path: reuse cached prefix when user is typing forward (offset 0 only).
-        if offset == 0 {
-            if let Some(cached) = self.cached_prefix_hits(&sanitized, &filters) {
+        // Explained searches skip the cache: cached hits carry no explanations.
+        // Neither do snippet-less searches: prefix matching needs the content.
+        if offset == 0 && !filters.explain && !filters.skip_snippets {
+            let cached = timed(timings, SearchPhase::Search, || {
+                self.cached_prefix_hits(&sanitized, &filters)
+            });
+            if let Some(cached) = cached {
                 let mut filtered: Vec<SearchHit> = cached
                     .into_iter()
                     .filter(|h| hit_matches_query_cached(h, &sanitized))
@@ -1688,22 +2679,35 @@ impl SearchClient {
                 offset = offset,
                 "search_start"
             );
-            let hits = self.search_tantivy(
+            let (hits, explainer) = self.search_tantivy(
                 reader,
                 fields,
                 &sanitized,
                 filters.clone(),
-                limit * 3,
-                offset,
+                TantivyPage {
+                    limit: limit * 3,
+                    offset,
+                    timings,
+                },
             )?;
             if !hits.is_empty() {
                 let mut deduped = deduplicate_hits(hits);
-                // Apply session_paths filter (post-search since source_path is not indexed)
-                if !filters.session_paths.is_empty() {
-                    deduped.retain(|h| filters.session_paths.contains(&h.source_path));
-                }
                 deduped.truncate(limit);
-                self.put_cache(&sanitized, &filters, &deduped);
+                self.attach_message_ids(&mut deduped);
+                if filters.skip_snippets {
+                    strip_hit_text(&mut deduped);
+                } else {
+                    timed(timings, SearchPhase::Highlight, || {
+                        apply_fragments(&mut deduped, &sanitized, &filters.highlight)
+                    });
+                }
+                if let Some(explainer) = explainer {
+                    // Only explain hits that survived dedup/truncation.
+                    explainer.annotate(&mut deduped);
+                } else {
+                    self.put_cache(&sanitized, &filters, &deduped);
+                    self.put_result_cache(disk_key.as_deref(), &deduped);
+                }
                 return Ok(deduped);
             }
             // If Tantivy yields 0 results, we can optionally fall back to SQLite FTS
@@ -1731,14 +2735,22 @@ impl SearchClient {
                 offset = offset,
                 "search_start"
             );
-            let hits = self.search_sqlite(conn, &sanitized, filters.clone(), limit * 3, offset)?;
+            let hits = timed(timings, SearchPhase::Search, || {
+                self.search_sqlite(conn, &sanitized, filters.clone(), limit * 3, offset)
+            })?;
             let mut deduped = deduplicate_hits(hits);
-            // Apply session_paths filter (post-search since source_path is not indexed)
-            if !filters.session_paths.is_empty() {
-                deduped.retain(|h| filters.session_paths.contains(&h.source_path));
-            }
             deduped.truncate(limit);
-            self.put_cache(&sanitized, &filters, &deduped);
+            if filters.skip_snippets {
+                strip_hit_text(&mut deduped);
+            } else {
+                timed(timings, SearchPhase::Highlight, || {
+                    apply_fragments(&mut deduped, &sanitized, &filters.highlight)
+                });
+            }
+            if !filters.explain {
+                self.put_cache(&sanitized, &filters, &deduped);
+                self.put_result_cache(disk_key.as_deref(), &deduped);
+            }
             return Ok(deduped);
         }
 
@@ -1746,6 +2758,52 @@ impl SearchClient {
         Ok(Vec::new())
     }
 
+    /// Total number of indexed messages matching `query` under `filters`,
+    /// independent of paging. Runs a separate full count, so callers should only
+    /// ask for it when needed. Duplicate collapsing in [`Self::search`] is not
+    /// applied, so the total can slightly exceed the hits a full paging run returns.
+    pub fn count_matches(&self, query: &str, filters: &SearchFilters) -> Result<usize> {
+        let sanitized = sanitize_query(query);
+
+        if let Some((reader, fields)) = &self.reader {
+            self.maybe_reload_reader(reader)?;
+            let searcher = self.searcher_for_thread(reader);
+            let q = build_tantivy_query(&sanitized, filters, fields);
+            let count = searcher.search(&q, &Count)?;
+            // `search` falls back to SQLite only when Tantivy finds nothing.
+            if count > 0 {
+                return Ok(count);
+            }
+        }
+
+        let Some(conn) = &self.sqlite else {
+            return Ok(0);
+        };
+        // Same restrictions as the SQLite branch of `search`.
+        if sanitized.trim().is_empty()
+            || sanitized.contains('*')
+            || !matches!(filters.source_filter, SourceFilter::All)
+        {
+            return Ok(0);
+        }
+        let (conversations, _) = sqlite_provenance_tables(conn);
+        let mut sql = format!(
+            "SELECT COUNT(*)
+             FROM fts_messages f
+             LEFT JOIN messages m ON f.message_id = m.id
+             LEFT JOIN {conversations} c ON m.conversation_id = c.id
+             WHERE fts_messages MATCH ?",
+        );
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(fts_safe_query(&sanitized))];
+        push_sqlite_filters(filters, &mut sql, &mut params);
+        let count: i64 = conn.query_row(
+            &sql,
+            rusqlite::params_from_iter(params.iter().map(|b| &**b)),
+            |row| row.get(0),
+        )?;
+        Ok(count.max(0) as usize)
+    }
+
     pub fn set_semantic_context(
         &self,
         embedder: Arc<dyn Embedder>,
@@ -1822,6 +2880,20 @@ impl SearchClient {
         if let Some(roles) = state.roles.clone() {
             semantic_filter = semantic_filter.with_roles(Some(roles));
         }
+        if filters.exclude_tool_messages {
+            semantic_filter = semantic_filter.without_role(ROLE_TOOL);
+        }
+        if !filters.roles.is_empty() {
+            let wanted: HashSet<u8> = filters
+                .roles
+                .iter()
+                .filter_map(|role| role_code_from_str(role))
+                .collect();
+            semantic_filter.roles = Some(match semantic_filter.roles.take() {
+                Some(roles) => roles.intersection(&wanted).copied().collect(),
+                None => wanted,
+            });
+        }
 
         let fetch = limit.saturating_add(offset);
         if fetch == 0 {
@@ -1837,10 +2909,16 @@ impl SearchClient {
         }
 
         let mut hits = self.hydrate_semantic_hits(&results)?;
-        // Apply session_paths filter (not supported at SemanticFilter level)
+        // Apply session_paths and provenance filters (not supported at SemanticFilter level)
         if !filters.session_paths.is_empty() {
             hits.retain(|h| filters.session_paths.contains(&h.source_path));
         }
+        if filters.has_provenance_filter() {
+            hits.retain(|h| filters.matches_provenance(h));
+        }
+        if filters.skip_snippets {
+            strip_hit_text(&mut hits);
+        }
         Ok(hits)
     }
 
@@ -1864,7 +2942,7 @@ impl SearchClient {
         }
 
         let sql = format!(
-            "SELECT m.id, m.content, m.created_at, m.idx, m.role, c.title, c.source_path, c.source_id, c.origin_host, a.slug, w.path, COALESCE(s.kind, 'local')
+            "SELECT m.id, m.content, m.created_at, m.idx, m.role, c.title, c.source_path, c.source_id, c.origin_host, a.slug, w.path, COALESCE(s.kind, 'local'), c.external_id
              FROM messages m
              JOIN conversations c ON m.conversation_id = c.id
              JOIN agents a ON c.agent_id = a.id
@@ -1888,6 +2966,7 @@ impl SearchClient {
                 let agent: String = row.get(9)?;
                 let workspace: Option<String> = row.get(10)?;
                 let origin_kind: String = row.get(11)?;
+                let conversation_external_id: Option<String> = row.get(12)?;
 
                 let line_number = idx.map(|i| (i + 1) as usize);
                 let snippet = snippet_from_content(&content);
@@ -1903,10 +2982,17 @@ impl SearchClient {
                     workspace_original: None,
                     created_at,
                     line_number,
-                    match_type: MatchType::Exact,
+                    match_type: MatchType::Semantic,
                     source_id: source_id.unwrap_or_else(default_source_id),
                     origin_kind,
                     origin_host,
+                    explanation: None,
+                    rerank_score: None,
+                    duplicate_count: None,
+                    message_matches: None,
+                    relative_path: None,
+                    message_id: Some(message_id),
+                    conversation_external_id,
                 };
 
                 Ok((message_id as u64, hit))
@@ -1967,12 +3053,7 @@ impl SearchClient {
             });
         }
 
-        // Try wildcard fallback: wrap each term in *term*
-        let wildcard_query = query
-            .split_whitespace()
-            .map(|term| format!("*{}*", term.trim_matches('*')))
-            .collect::<Vec<_>>()
-            .join(" ");
+        let wildcard_query = wildcard_fallback_query(query);
 
         tracing::info!(
             original_query = query,
@@ -2178,147 +3259,89 @@ impl SearchClient {
         fields: &crate::search::tantivy::Fields,
         query: &str,
         filters: SearchFilters,
-        limit: usize,
-        offset: usize,
-    ) -> Result<Vec<SearchHit>> {
+        page: TantivyPage<'_>,
+    ) -> Result<(Vec<SearchHit>, Option<ScoreExplainer>)> {
+        let TantivyPage {
+            limit,
+            offset,
+            timings,
+        } = page;
         self.maybe_reload_reader(reader)?;
         let searcher = self.searcher_for_thread(reader);
         self.track_generation(searcher.generation().generation_id());
 
-        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
-
-        // Parse query with boolean operator support (AND, OR, NOT, "phrases")
-        // Falls back to simple whitespace split for plain queries (implicit AND)
-        let tokens = parse_boolean_query(query);
-        if tokens.is_empty() {
-            clauses.push((Occur::Must, Box::new(AllQuery)));
-        } else if has_boolean_operators(query) {
-            // Use boolean query builder for complex queries
-            let bool_clauses = build_boolean_query_clauses(&tokens, fields);
-            clauses.extend(bool_clauses);
-        } else {
-            // Simple query: treat each term as MUST (implicit AND)
-            for token in tokens {
-                if let QueryToken::Term(term_str) = token {
-                    let pattern = WildcardPattern::parse(&term_str);
-                    let term_shoulds = build_term_query_clauses(&pattern, fields);
-                    if !term_shoulds.is_empty() {
-                        clauses.push((Occur::Must, Box::new(BooleanQuery::new(term_shoulds))));
-                    }
-                }
-            }
-        }
-
-        if !filters.agents.is_empty() {
-            let terms = filters
-                .agents
-                .into_iter()
-                .map(|agent| {
-                    (
-                        Occur::Should,
-                        Box::new(TermQuery::new(
-                            Term::from_field_text(fields.agent, &agent),
-                            IndexRecordOption::Basic,
-                        )) as Box<dyn Query>,
-                    )
-                })
-                .collect();
-            clauses.push((Occur::Must, Box::new(BooleanQuery::new(terms))));
-        }
-
-        if !filters.workspaces.is_empty() {
-            let terms = filters
-                .workspaces
-                .into_iter()
-                .map(|ws| {
-                    (
-                        Occur::Should,
-                        Box::new(TermQuery::new(
-                            Term::from_field_text(fields.workspace, &ws),
-                            IndexRecordOption::Basic,
-                        )) as Box<dyn Query>,
-                    )
-                })
-                .collect();
-            clauses.push((Occur::Must, Box::new(BooleanQuery::new(terms))));
-        }
-
-        if filters.created_from.is_some() || filters.created_to.is_some() {
-            use std::ops::Bound::{Included, Unbounded};
-            let lower = filters.created_from.map_or(Unbounded, |v| {
-                Included(Term::from_field_i64(fields.created_at, v))
-            });
-            let upper = filters.created_to.map_or(Unbounded, |v| {
-                Included(Term::from_field_i64(fields.created_at, v))
-            });
-            let range = RangeQuery::new(lower, upper);
-            clauses.push((Occur::Must, Box::new(range)));
-        }
-
-        // Source filter (P3.1)
-        match &filters.source_filter {
-            SourceFilter::All => {
-                // No filtering needed
-            }
-            SourceFilter::Local => {
-                // Filter to local sources only (origin_kind == "local")
-                let term = Term::from_field_text(fields.origin_kind, "local");
-                clauses.push((
-                    Occur::Must,
-                    Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
-                ));
-            }
-            SourceFilter::Remote => {
-                // Filter to remote sources only (origin_kind == "ssh")
-                // We use "ssh" since that's the only remote kind currently
-                let term = Term::from_field_text(fields.origin_kind, "ssh");
-                clauses.push((
-                    Occur::Must,
-                    Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
-                ));
-            }
-            SourceFilter::SourceId(source_id) => {
-                // Filter to specific source by ID
-                let term = Term::from_field_text(fields.source_id, source_id);
-                clauses.push((
-                    Occur::Must,
-                    Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
-                ));
-            }
-        }
-
-        // NOTE: session_paths filtering is applied post-search since source_path
-        // is STORED but not indexed. See apply_session_paths_filter().
-
-        let q: Box<dyn Query> = if clauses.is_empty() {
-            Box::new(AllQuery)
-        } else if clauses.len() == 1 {
-            let (occur, query_box) = clauses.pop().unwrap();
-            match occur {
-                // For Must, we can safely unwrap and use the inner query directly
-                Occur::Must => query_box,
-                // For MustNot or Should, we must preserve the Occur by wrapping
-                // in a BooleanQuery. A lone MustNot (e.g., "NOT foo") should match
-                // nothing, not match "foo".
-                _ => Box::new(BooleanQuery::new(vec![(occur, query_box)])),
-            }
-        } else {
-            Box::new(BooleanQuery::new(clauses))
-        };
+        let q = timed(timings, SearchPhase::Parse, || {
+            build_tantivy_query(query, &filters, fields)
+        });
 
         let prefix_only = is_prefix_only(query);
-        let snippet_generator = if prefix_only {
+        let snippet_generator = if prefix_only || filters.skip_snippets {
             None
         } else {
-            Some(SnippetGenerator::create(&searcher, &*q, fields.content)?)
+            let mut generator = timed(timings, SearchPhase::Highlight, || {
+                SnippetGenerator::create(&searcher, &*q, fields.content)
+            })?;
+            if let Some(chars) = filters.highlight.fragment_chars {
+                generator.set_max_num_chars(chars);
+            }
+            Some(generator)
         };
+        let prefix_snippet_chars = filters.highlight.fragment_chars.unwrap_or(160);
+
+        // Page after tie-breaking: Tantivy orders equal scores by doc address, which
+        // shifts as segments merge. The collector breaks ties on the `created_at` fast
+        // field, newest first; docs still tied with the last one on the page (up to
+        // MAX_TIE_FETCH more) are fetched and ordered by source path and message index,
+        // so the cut falls at the same place every run.
+        let page_end = limit.saturating_add(offset);
+        let fetch_cap = page_end.saturating_add(MAX_TIE_FETCH);
+        let ranked = timed(timings, SearchPhase::Search, || -> Result<_> {
+            let mut fetch = page_end.saturating_add(1);
+            let top_docs = loop {
+                let collector =
+                    TopDocs::with_limit(fetch).tweak_score(|segment: &SegmentReader| {
+                        let created_at = segment
+                            .fast_fields()
+                            .column_opt::<i64>("created_at")
+                            .ok()
+                            .flatten();
+                        move |doc: DocId, score: Score| {
+                            (score, created_at.as_ref().and_then(|c| c.first(doc)))
+                        }
+                    });
+                let docs = searcher.search(&q, &collector)?;
+                let tie_continues = docs.len() == fetch
+                    && page_end > 0
+                    && docs.last().map(|d| d.0) == docs.get(page_end - 1).map(|d| d.0);
+                if !tie_continues || fetch >= fetch_cap {
+                    break docs;
+                }
+                fetch = fetch.saturating_mul(2).min(fetch_cap);
+            };
+            let mut ranked = Vec::with_capacity(top_docs.len());
+            for ((score, _), addr) in top_docs {
+                let doc: TantivyDocument = searcher.doc(addr)?;
+                let key = TieBreakKey {
+                    created_at: doc.get_first(fields.created_at).and_then(|v| v.as_i64()),
+                    source_path: doc
+                        .get_first(fields.source_path)
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .to_string(),
+                    msg_idx: doc.get_first(fields.msg_idx).and_then(|v| v.as_u64()),
+                };
+                ranked.push((score, key, addr, doc));
+            }
+            ranked.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+            ranked.truncate(page_end);
+            Ok(ranked)
+        })?;
 
-        let top_docs = searcher.search(&q, &TopDocs::with_limit(limit).and_offset(offset))?;
         // Compute match type once for all results (not per-hit)
         let query_match_type = dominant_match_type(query);
         let mut hits = Vec::new();
-        for (score, addr) in top_docs {
-            let doc: TantivyDocument = searcher.doc(addr)?;
+        let mut explain_docs = HashMap::new();
+        for (score, _, addr, doc) in ranked.into_iter().skip(offset) {
             let title = doc
                 .get_first(fields.title)
                 .and_then(|v| v.as_str())
@@ -2335,16 +3358,24 @@ impl SearchClient {
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string();
-            let snippet = if let Some(r#gen) = &snippet_generator {
-                r#gen
-                    .snippet_from_doc(&doc)
-                    .to_html()
-                    .replace("<b>", "**")
-                    .replace("</b>", "**")
-            } else if let Some(sn) = cached_prefix_snippet(&content, query, 160) {
-                sn
+            let snippet = if filters.skip_snippets {
+                String::new()
             } else {
-                quick_prefix_snippet(&content, query, 160)
+                timed(timings, SearchPhase::Highlight, || {
+                    if let Some(r#gen) = &snippet_generator {
+                        r#gen
+                            .snippet_from_doc(&doc)
+                            .to_html()
+                            .replace("<b>", "**")
+                            .replace("</b>", "**")
+                    } else if let Some(sn) =
+                        cached_prefix_snippet(&content, query, prefix_snippet_chars)
+                    {
+                        sn
+                    } else {
+                        quick_prefix_snippet(&content, query, prefix_snippet_chars)
+                    }
+                })
             };
             let source = doc
                 .get_first(fields.source_path)
@@ -2383,6 +3414,10 @@ impl SearchClient {
                 .and_then(|v| v.as_str())
                 .filter(|s| !s.is_empty())
                 .map(String::from);
+            let conversation_external_id = doc
+                .get_first(fields.external_id)
+                .and_then(|v| v.as_str())
+                .map(String::from);
             hits.push(SearchHit {
                 title,
                 snippet,
@@ -2398,9 +3433,26 @@ impl SearchClient {
                 source_id,
                 origin_kind,
                 origin_host,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id,
             });
+            if filters.explain
+                && let Some(hit) = hits.last()
+            {
+                explain_docs.insert(ScoreExplainer::key(hit), addr);
+            }
         }
-        Ok(hits)
+        let explainer = filters.explain.then(|| ScoreExplainer {
+            searcher,
+            query: q,
+            docs: explain_docs,
+        });
+        Ok((hits, explainer))
     }
 
     fn search_sqlite(
@@ -2418,53 +3470,27 @@ impl SearchClient {
         // Compute match type once for all results
         let query_match_type = dominant_match_type(query);
 
-        // FTS5 requires balanced double quotes.
-        // If unbalanced, strip them to avoid syntax error.
-        let mut safe_query = query.to_string();
-        if safe_query.matches('"').count() % 2 != 0 {
-            safe_query = safe_query.replace('"', "");
-        }
+        let safe_query = fts_safe_query(query);
 
-        let mut sql = String::from(
-            "SELECT f.title, f.content, f.agent, f.workspace, f.source_path, f.created_at, bm25(fts_messages) AS score, snippet(fts_messages, 0, '**', '**', '...', 64) AS snippet, m.idx
+        // FTS5 sizes snippets in tokens (max 64); approximate ~6 chars per token.
+        let snippet_tokens = filters
+            .highlight
+            .fragment_chars
+            .map_or(64, |chars| (chars / 6).clamp(4, 64));
+        let (conversations, sources) = sqlite_provenance_tables(conn);
+        let mut sql = format!(
+            "SELECT f.title, f.content, f.agent, f.workspace, f.source_path, f.created_at, bm25(fts_messages) AS score, snippet(fts_messages, 0, '**', '**', '...', {snippet_tokens}) AS snippet, m.idx, c.source_id, c.origin_host, s.kind, f.message_id, c.external_id
              FROM fts_messages f
              LEFT JOIN messages m ON f.message_id = m.id
+             LEFT JOIN {conversations} c ON m.conversation_id = c.id
+             LEFT JOIN {sources} s ON c.source_id = s.id
              WHERE fts_messages MATCH ?",
         );
         let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(safe_query)];
+        push_sqlite_filters(&filters, &mut sql, &mut params);
 
-        if !filters.agents.is_empty() {
-            let placeholders = (0..filters.agents.len())
-                .map(|_| "?".to_string())
-                .collect::<Vec<_>>()
-                .join(",");
-            sql.push_str(&format!(" AND f.agent IN ({placeholders})"));
-            for a in filters.agents {
-                params.push(Box::new(a));
-            }
-        }
-
-        if !filters.workspaces.is_empty() {
-            let placeholders = (0..filters.workspaces.len())
-                .map(|_| "?".to_string())
-                .collect::<Vec<_>>()
-                .join(",");
-            sql.push_str(&format!(" AND f.workspace IN ({placeholders})"));
-            for w in filters.workspaces {
-                params.push(Box::new(w));
-            }
-        }
-
-        if let Some(created_from) = filters.created_from {
-            sql.push_str(" AND f.created_at >= ?");
-            params.push(Box::new(created_from));
-        }
-        if let Some(created_to) = filters.created_to {
-            sql.push_str(" AND f.created_at <= ?");
-            params.push(Box::new(created_to));
-        }
-
-        sql.push_str(" ORDER BY score LIMIT ? OFFSET ?");
+        // Same tie-breaking as the Tantivy path so equal scores page stably.
+        sql.push_str(" ORDER BY score, f.created_at DESC, f.source_path, m.idx LIMIT ? OFFSET ?");
         params.push(Box::new(limit as i64));
         params.push(Box::new(offset as i64));
 
@@ -2483,7 +3509,12 @@ impl SearchClient {
                 // idx is 0-indexed message index; convert to 1-indexed line number for JSONL files
                 let idx: Option<i64> = row.get(8).ok();
                 let line_number = idx.map(|i| (i + 1) as usize);
-                // SQLite FTS doesn't have provenance or workspace_original - use defaults
+                let source_id: Option<String> = row.get(9)?;
+                let origin_host: Option<String> = row.get(10)?;
+                let origin_kind: Option<String> = row.get(11)?;
+                let message_id: Option<i64> = row.get(12)?;
+                let conversation_external_id: Option<String> = row.get(13)?;
+                // SQLite FTS doesn't have workspace_original - use default
                 Ok(SearchHit {
                     title,
                     snippet,
@@ -2496,9 +3527,16 @@ impl SearchClient {
                     created_at,
                     line_number,
                     match_type: query_match_type,
-                    source_id: default_source_id(),
-                    origin_kind: default_origin_kind(),
-                    origin_host: None,
+                    source_id: source_id.unwrap_or_else(default_source_id),
+                    origin_kind: origin_kind.unwrap_or_else(default_origin_kind),
+                    origin_host,
+                    explanation: None,
+                    rerank_score: None,
+                    duplicate_count: None,
+                    message_matches: None,
+                    relative_path: None,
+                    message_id,
+                    conversation_external_id,
                 })
             },
         )?;
@@ -2518,6 +3556,8 @@ struct Metrics {
     cache_shortfall: Arc<AtomicU64>,
     reloads: Arc<AtomicU64>,
     reload_ms_total: Arc<AtomicU64>,
+    disk_hits: Arc<AtomicU64>,
+    disk_miss: Arc<AtomicU64>,
 }
 
 impl Metrics {
@@ -2530,6 +3570,12 @@ impl Metrics {
     fn inc_cache_shortfall(&self) {
         self.cache_shortfall.fetch_add(1, Ordering::Relaxed);
     }
+    fn inc_disk_hits(&self) {
+        self.disk_hits.fetch_add(1, Ordering::Relaxed);
+    }
+    fn inc_disk_miss(&self) {
+        self.disk_miss.fetch_add(1, Ordering::Relaxed);
+    }
     fn inc_reload(&self) {
         self.reloads.fetch_add(1, Ordering::Relaxed);
     }
@@ -2549,6 +3595,13 @@ impl Metrics {
         )
     }
 
+    fn snapshot_disk(&self) -> (u64, u64) {
+        (
+            self.disk_hits.load(Ordering::Relaxed),
+            self.disk_miss.load(Ordering::Relaxed),
+        )
+    }
+
     #[cfg(test)]
     #[allow(dead_code)]
     fn reset(&self) {
@@ -2746,6 +3799,102 @@ fn quick_prefix_snippet(content: &str, query: &str, max_chars: usize) -> String
     }
 }
 
+/// Replace each hit's snippet with up to `max_fragments` highlighted fragments.
+/// Hits whose content has no term match keep the backend snippet.
+fn apply_fragments(hits: &mut [SearchHit], query: &str, opts: &HighlightOptions) {
+    if !opts.multi_fragment() {
+        return;
+    }
+    let chars = opts.fragment_chars.unwrap_or(150);
+    for hit in hits.iter_mut() {
+        if let Some(sn) = build_fragments(&hit.content, query, chars, opts.max_fragments) {
+            hit.snippet = sn;
+        }
+    }
+}
+
+/// Build up to `max_fragments` non-overlapping windows of `fragment_chars` around
+/// query term matches, wrapping matched terms in `**` and joining windows with
+/// `FRAGMENT_SEPARATOR`. Returns `None` when no term occurs in `content`.
+fn build_fragments(
+    content: &str,
+    query: &str,
+    fragment_chars: usize,
+    max_fragments: usize,
+) -> Option<String> {
+    let terms: Vec<Vec<char>> = query
+        .split_whitespace()
+        .filter(|t| !matches!(*t, "AND" | "OR" | "NOT" | "&&" | "||"))
+        .map(|t| {
+            t.trim_matches(|c: char| !c.is_alphanumeric())
+                .chars()
+                .flat_map(char::to_lowercase)
+                .collect::<Vec<_>>()
+        })
+        .filter(|t| !t.is_empty())
+        .collect();
+    if terms.is_empty() || fragment_chars == 0 {
+        return None;
+    }
+
+    let chars: Vec<char> = content.chars().collect();
+    // Lowercase 1:1 per char so match offsets index directly into `chars`.
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    let mut matches: Vec<(usize, usize)> = Vec::new();
+    for term in &terms {
+        if term.len() > lower.len() {
+            continue;
+        }
+        for start in 0..=lower.len() - term.len() {
+            if lower[start..start + term.len()] == term[..] {
+                matches.push((start, start + term.len()));
+            }
+        }
+    }
+    if matches.is_empty() {
+        return None;
+    }
+    matches.sort_unstable();
+
+    let mut windows: Vec<(usize, usize)> = Vec::new();
+    for &(m_start, _) in &matches {
+        if windows.len() >= max_fragments {
+            break;
+        }
+        if windows.last().is_some_and(|&(_, end)| m_start < end) {
+            continue;
+        }
+        let start = m_start.saturating_sub(15);
+        let end = (start + fragment_chars).min(chars.len());
+        windows.push((start, end));
+    }
+
+    let fragments: Vec<String> = windows
+        .into_iter()
+        .map(|(start, end)| {
+            let mut out = String::new();
+            let mut pos = start;
+            for &(m_start, m_end) in &matches {
+                if m_start < pos || m_end > end {
+                    continue;
+                }
+                out.extend(&chars[pos..m_start]);
+                out.push_str("**");
+                out.extend(&chars[m_start..m_end]);
+                out.push_str("**");
+                pos = m_end;
+            }
+            out.extend(&chars[pos..end]);
+            out.trim().to_string()
+        })
+        .collect();
+    Some(fragments.join(FRAGMENT_SEPARATOR))
+}
+
 fn cached_prefix_snippet(content: &str, query: &str, max_chars: usize) -> Option<String> {
     if query.trim().is_empty() {
         return None;
@@ -2795,12 +3944,39 @@ fn filters_fingerprint(filters: &SearchFilters) -> String {
     ) {
         parts.push(format!("src:{:?}", filters.source_filter));
     }
+    if !filters.source_ids.is_empty() {
+        let mut v: Vec<_> = filters.source_ids.iter().cloned().collect();
+        v.sort();
+        parts.push(format!("sid:{v:?}"));
+    }
+    if !filters.hosts.is_empty() {
+        let mut v: Vec<_> = filters.hosts.iter().cloned().collect();
+        v.sort();
+        parts.push(format!("host:{v:?}"));
+    }
     // Include session_paths in cache key (for chained searches)
     if !filters.session_paths.is_empty() {
         let mut v: Vec<_> = filters.session_paths.iter().cloned().collect();
         v.sort();
         parts.push(format!("sp:{v:?}"));
     }
+    if filters.exclude_tool_messages {
+        parts.push("notools".to_string());
+    }
+    if filters.skip_snippets {
+        parts.push("nosnippet".to_string());
+    }
+    if !filters.roles.is_empty() {
+        let mut v: Vec<_> = filters.roles.iter().cloned().collect();
+        v.sort();
+        parts.push(format!("role:{v:?}"));
+    }
+    if !filters.highlight.is_default() {
+        parts.push(format!(
+            "hl:{:?}x{}",
+            filters.highlight.fragment_chars, filters.highlight.max_fragments
+        ));
+    }
     parts.join("|")
 }
 
@@ -2894,7 +4070,7 @@ impl SearchClient {
     }
 
     fn put_cache(&self, query: &str, filters: &SearchFilters, hits: &[SearchHit]) {
-        if query.is_empty() || hits.is_empty() {
+        if query.is_empty() || hits.is_empty() || filters.skip_snippets {
             return;
         }
         if let Ok(mut cache) = self.prefix_cache.lock() {
@@ -2905,8 +4081,23 @@ impl SearchClient {
         }
     }
 
+    fn put_result_cache(&self, key: Option<&str>, hits: &[SearchHit]) {
+        if let (Some(cache), Some(key)) = (&self.result_cache, key) {
+            cache.put(key, hits);
+        }
+    }
+
+    /// Drop the in-memory prefix cache so the next search reads the index again.
+    /// Long-lived clients call this when they know the index has changed.
+    pub fn clear_cache(&self) {
+        if let Ok(mut cache) = self.prefix_cache.lock() {
+            cache.clear();
+        }
+    }
+
     pub fn cache_stats(&self) -> CacheStats {
         let (hits, miss, shortfall, reloads, reload_ms_total) = self.metrics.snapshot_all();
+        let (disk_hits, disk_miss) = self.metrics.snapshot_disk();
         let (total_cap, total_cost, eviction_count, approx_bytes, byte_cap) =
             if let Ok(cache) = self.prefix_cache.lock() {
                 (
@@ -2930,6 +4121,8 @@ impl SearchClient {
             eviction_count,
             approx_bytes,
             byte_cap,
+            disk_hits,
+            disk_miss,
         }
     }
 }
@@ -2956,6 +4149,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         let hits = vec![SearchHit {
@@ -2973,88 +4168,464 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
         }];
 
-        client.put_cache("こん", &SearchFilters::default(), &hits);
+        client.put_cache("こん", &SearchFilters::default(), &hits);
+
+        let cached = client
+            .cached_prefix_hits("こんにちは", &SearchFilters::default())
+            .unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].hit.title, "こんにちは");
+    }
+
+    #[test]
+    fn bloom_gate_rejects_missing_terms() {
+        let hit = SearchHit {
+            title: "hello world".into(),
+            snippet: "hello world".into(),
+            content: "hello world".into(),
+            score: 1.0,
+            source_path: "p".into(),
+            agent: "a".into(),
+            workspace: "w".into(),
+            workspace_original: None,
+            created_at: None,
+            line_number: None,
+            match_type: MatchType::Exact,
+            source_id: "local".into(),
+            origin_kind: "local".into(),
+            origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
+        };
+        let cached = cached_hit_from(&hit);
+        assert!(hit_matches_query_cached(&cached, "hello"));
+        assert!(!hit_matches_query_cached(&cached, "missing"));
+
+        let metrics = Metrics::default();
+        metrics.inc_cache_hits();
+        metrics.inc_cache_miss();
+        metrics.inc_cache_shortfall();
+        metrics.inc_reload();
+        let (hits, miss, shortfall, reloads, _) = metrics.snapshot_all();
+        assert_eq!((hits, miss, shortfall, reloads), (1, 1, 1, 1));
+    }
+
+    #[test]
+    fn search_returns_results_with_filters_and_pagination() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+        let conv = NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: None,
+            title: Some("hello world convo".into()),
+            workspace: Some(std::path::PathBuf::from("/tmp/workspace")),
+            source_path: dir.path().join("rollout-1.jsonl"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: None,
+            metadata: serde_json::json!({}),
+            messages: vec![NormalizedMessage {
+                idx: 0,
+                role: "user".into(),
+                author: Some("me".into()),
+                created_at: Some(1_700_000_000_000),
+                content: "hello rust world".into(),
+                extra: serde_json::json!({}),
+                snippets: vec![NormalizedSnippet {
+                    file_path: None,
+                    start_line: None,
+                    end_line: None,
+                    language: None,
+                    snippet_text: None,
+                }],
+            }],
+        };
+        index.add_conversation(&conv)?;
+        index.commit()?;
+
+        let client = SearchClient::open(dir.path(), None)?.expect("index present");
+        let mut filters = SearchFilters::default();
+        filters.agents.insert("codex".into());
+
+        let hits = client.search("hello", filters, 10, 0)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].agent, "codex");
+        assert!(hits[0].snippet.contains("hello"));
+        Ok(())
+    }
+
+    #[test]
+    fn profiling_records_phase_timings_only_when_enabled() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+        let conv = NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: None,
+            title: Some("profiled".into()),
+            workspace: None,
+            source_path: dir.path().join("rollout-1.jsonl"),
+            started_at: Some(1_700_000_000_000),
+            ended_at: None,
+            metadata: serde_json::json!({}),
+            messages: vec![NormalizedMessage {
+                idx: 0,
+                role: "user".into(),
+                author: None,
+                created_at: Some(1_700_000_000_000),
+                content: "hello profiling world".into(),
+                extra: serde_json::json!({}),
+                snippets: vec![],
+            }],
+        };
+        index.add_conversation(&conv)?;
+        index.commit()?;
+
+        let plain = SearchClient::open(dir.path(), None)?.expect("index present");
+        plain.search("hello", SearchFilters::default(), 10, 0)?;
+        assert_eq!(plain.timings(), None);
+
+        let client = SearchClient::open(dir.path(), None)?
+            .expect("index present")
+            .with_profiling();
+        let hits = client.search("hello", SearchFilters::default(), 10, 0)?;
+        assert_eq!(hits.len(), 1);
+        let timings = client.timings().expect("profiling enabled");
+        assert!(timings.search > Duration::ZERO);
+        assert!(timings.total >= timings.parse + timings.search + timings.highlight);
+        assert_eq!(timings.open, Duration::ZERO);
+        Ok(())
+    }
+
+    #[test]
+    fn skip_snippets_returns_bare_hits_without_highlighting() -> Result<()> {
+        let msg = |idx: i64, content: &str| NormalizedMessage {
+            idx,
+            role: "assistant".into(),
+            author: None,
+            created_at: Some(1_700_000_000_000 + idx),
+            content: content.into(),
+            extra: serde_json::json!({}),
+            snippets: vec![],
+        };
+        let conv = NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: None,
+            title: Some("metadata only".into()),
+            workspace: None,
+            source_path: "/tmp/rollout-1.jsonl".into(),
+            started_at: Some(1_700_000_000_000),
+            ended_at: None,
+            metadata: serde_json::json!({}),
+            messages: vec![
+                msg(0, "the parser panics on empty input"),
+                msg(1, "fixed the parser by checking length"),
+            ],
+        };
+        let client = build_ephemeral_index(&[conv])?.with_profiling();
+        let filters = SearchFilters {
+            skip_snippets: true,
+            highlight: HighlightOptions {
+                fragment_chars: Some(40),
+                max_fragments: 3,
+            },
+            ..Default::default()
+        };
+        let hits = client.search("parser", filters.clone(), 10, 0)?;
+        assert_eq!(hits.len(), 2);
+        assert!(
+            hits.iter()
+                .all(|h| h.snippet.is_empty() && h.content.is_empty())
+        );
+        let mut lines: Vec<_> = hits.iter().map(|h| h.line_number).collect();
+        lines.sort();
+        assert_eq!(lines, [Some(1), Some(2)]);
+        let timings = client.timings().expect("profiling enabled");
+        assert_eq!(timings.highlight, Duration::ZERO);
+
+        let filters = SearchFilters {
+            skip_snippets: false,
+            ..filters
+        };
+        let hits = client.search("parser", filters, 10, 0)?;
+        assert!(hits.iter().all(|h| h.snippet.contains("**parser**")));
+        assert!(client.timings().expect("profiling enabled").highlight > Duration::ZERO);
+        Ok(())
+    }
+
+    #[test]
+    fn build_fragments_joins_separate_matches() {
+        let filler = "x".repeat(200);
+        let content = format!("first needle here {filler} second needle there");
+        let out = build_fragments(&content, "needle", 40, 2).expect("matches");
+        let parts: Vec<&str> = out.split(FRAGMENT_SEPARATOR).collect();
+        assert_eq!(parts.len(), 2, "got {out}");
+        assert!(parts[0].contains("**needle**"));
+        assert!(parts[1].contains("second **needle**"));
+        assert!(parts.iter().all(|p| p.chars().count() <= 40 + 4));
+    }
+
+    #[test]
+    fn build_fragments_respects_max_and_misses() {
+        let content = "alpha beta alpha beta alpha";
+        let out = build_fragments(content, "alpha", 5, 2).expect("matches");
+        assert_eq!(out, format!("**alpha**{FRAGMENT_SEPARATOR}**alpha**"));
+        assert!(build_fragments(content, "gamma", 50, 3).is_none());
+    }
+
+    #[test]
+    fn highlight_options_change_snippet_and_cache_key() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+        let filler = "lorem ipsum dolor sit amet ".repeat(20);
+        let conv = NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: None,
+            title: Some("fragments".into()),
+            workspace: None,
+            source_path: dir.path().join("frag.jsonl"),
+            started_at: Some(1),
+            ended_at: None,
+            metadata: serde_json::json!({}),
+            messages: vec![NormalizedMessage {
+                idx: 0,
+                role: "user".into(),
+                author: None,
+                created_at: Some(1),
+                content: format!("start needle {filler} end needle"),
+                extra: serde_json::json!({}),
+                snippets: vec![],
+            }],
+        };
+        index.add_conversation(&conv)?;
+        index.commit()?;
+
+        let client = SearchClient::open(dir.path(), None)?.expect("index present");
+        let default_hits = client.search("needle", SearchFilters::default(), 5, 0)?;
+        assert_eq!(default_hits.len(), 1);
+        assert!(!default_hits[0].snippet.contains(FRAGMENT_SEPARATOR));
+
+        let short = SearchFilters {
+            highlight: HighlightOptions {
+                fragment_chars: Some(30),
+                max_fragments: 1,
+            },
+            ..Default::default()
+        };
+        assert_ne!(
+            filters_fingerprint(&short),
+            filters_fingerprint(&SearchFilters::default())
+        );
+        let short_hits = client.search("needle", short, 5, 0)?;
+        assert!(short_hits[0].snippet.chars().count() < default_hits[0].snippet.chars().count());
+
+        let multi = SearchFilters {
+            highlight: HighlightOptions {
+                fragment_chars: Some(40),
+                max_fragments: 2,
+            },
+            ..Default::default()
+        };
+        let multi_hits = client.search("needle", multi, 5, 0)?;
+        let parts: Vec<&str> = multi_hits[0].snippet.split(FRAGMENT_SEPARATOR).collect();
+        assert_eq!(parts.len(), 2, "snippet: {}", multi_hits[0].snippet);
+        assert!(parts.iter().all(|p| p.contains("**needle**")));
+        Ok(())
+    }
+
+    #[test]
+    fn explain_attaches_score_breakdown_to_returned_hits() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+        let conv = NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: None,
+            title: Some("explain".into()),
+            workspace: None,
+            source_path: dir.path().join("explain.jsonl"),
+            started_at: Some(1),
+            ended_at: None,
+            metadata: serde_json::json!({}),
+            messages: (0..3)
+                .map(|i| NormalizedMessage {
+                    idx: i,
+                    role: "user".into(),
+                    author: None,
+                    created_at: Some(1 + i),
+                    content: format!("needle message number {i}"),
+                    extra: serde_json::json!({}),
+                    snippets: vec![],
+                })
+                .collect(),
+        };
+        index.add_conversation(&conv)?;
+        index.commit()?;
+
+        let client = SearchClient::open(dir.path(), None)?.expect("index present");
+        let plain = client.search("needle", SearchFilters::default(), 10, 0)?;
+        assert!(plain.iter().all(|h| h.explanation.is_none()));
+
+        let filters = SearchFilters {
+            explain: true,
+            ..Default::default()
+        };
+        let hits = client.search("needle", filters, 2, 0)?;
+        assert_eq!(hits.len(), 2);
+        for hit in &hits {
+            let explanation = hit.explanation.as_ref().expect("explanation attached");
+            let value = explanation["value"].as_f64().expect("explanation value");
+            assert!((value as f32 - hit.score).abs() < 1e-3);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn count_matches_ignores_paging_and_honors_filters() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+        for (i, agent) in ["codex", "codex", "claude"].iter().enumerate() {
+            let conv = NormalizedConversation {
+                agent_slug: (*agent).into(),
+                external_id: None,
+                title: Some(format!("count-{i}")),
+                workspace: None,
+                source_path: dir.path().join(format!("{i}.jsonl")),
+                started_at: Some(100),
+                ended_at: None,
+                metadata: serde_json::json!({}),
+                messages: vec![NormalizedMessage {
+                    idx: 0,
+                    role: "user".into(),
+                    author: None,
+                    created_at: Some(100),
+                    content: format!("counttest message {i}"),
+                    extra: serde_json::json!({}),
+                    snippets: vec![],
+                }],
+            };
+            index.add_conversation(&conv)?;
+        }
+        index.commit()?;
+
+        let client = SearchClient::open(dir.path(), None)?.expect("index present");
+        let hits = client.search("counttest", SearchFilters::default(), 1, 0)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(
+            client.count_matches("counttest", &SearchFilters::default())?,
+            3
+        );
 
-        let cached = client
-            .cached_prefix_hits("こんにちは", &SearchFilters::default())
-            .unwrap();
-        assert_eq!(cached.len(), 1);
-        assert_eq!(cached[0].hit.title, "こんにちは");
+        let mut filters = SearchFilters::default();
+        filters.agents.insert("codex".into());
+        assert_eq!(client.count_matches("counttest", &filters)?, 2);
+
+        let mut filters = SearchFilters::default();
+        filters
+            .session_paths
+            .insert(dir.path().join("2.jsonl").to_string_lossy().into_owned());
+        assert_eq!(client.count_matches("counttest", &filters)?, 1);
+        assert_eq!(client.count_matches("absentterm", &filters)?, 0);
+        Ok(())
     }
 
     #[test]
-    fn bloom_gate_rejects_missing_terms() {
-        let hit = SearchHit {
-            title: "hello world".into(),
-            snippet: "hello world".into(),
-            content: "hello world".into(),
-            score: 1.0,
-            source_path: "p".into(),
-            agent: "a".into(),
-            workspace: "w".into(),
-            workspace_original: None,
-            created_at: None,
-            line_number: None,
-            match_type: MatchType::Exact,
-            source_id: "local".into(),
-            origin_kind: "local".into(),
-            origin_host: None,
+    fn equal_scores_break_ties_before_paging() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+        // Same title and same-length content, so every doc scores identically.
+        for (path, created_at, word) in [
+            ("b.jsonl", 100, "alpha"),
+            ("c.jsonl", 200, "gamma"),
+            ("a.jsonl", 100, "delta"),
+        ] {
+            let conv = NormalizedConversation {
+                agent_slug: "codex".into(),
+                external_id: None,
+                title: Some("tie".into()),
+                workspace: None,
+                source_path: dir.path().join(path),
+                started_at: Some(created_at),
+                ended_at: None,
+                metadata: serde_json::json!({}),
+                messages: vec![NormalizedMessage {
+                    idx: 0,
+                    role: "user".into(),
+                    author: None,
+                    created_at: Some(created_at),
+                    content: format!("tiebreak {word}"),
+                    extra: serde_json::json!({}),
+                    snippets: vec![],
+                }],
+            };
+            index.add_conversation(&conv)?;
+        }
+        index.commit()?;
+
+        let client = SearchClient::open(dir.path(), None)?.expect("index present");
+        let names = |hits: &[SearchHit]| -> Vec<String> {
+            hits.iter()
+                .map(|h| h.source_path.rsplit('/').next().unwrap_or("").to_string())
+                .collect()
         };
-        let cached = cached_hit_from(&hit);
-        assert!(hit_matches_query_cached(&cached, "hello"));
-        assert!(!hit_matches_query_cached(&cached, "missing"));
+        let hits = client.search("tiebreak", SearchFilters::default(), 3, 0)?;
+        assert!(hits.windows(2).all(|w| w[0].score == w[1].score));
+        assert_eq!(names(&hits), ["c.jsonl", "a.jsonl", "b.jsonl"]);
 
-        let metrics = Metrics::default();
-        metrics.inc_cache_hits();
-        metrics.inc_cache_miss();
-        metrics.inc_cache_shortfall();
-        metrics.inc_reload();
-        let (hits, miss, shortfall, reloads, _) = metrics.snapshot_all();
-        assert_eq!((hits, miss, shortfall, reloads), (1, 1, 1, 1));
+        let page = client.search("tiebreak", SearchFilters::default(), 1, 1)?;
+        assert_eq!(names(&page), ["a.jsonl"]);
+        Ok(())
     }
 
     #[test]
-    fn search_returns_results_with_filters_and_pagination() -> Result<()> {
+    fn ties_past_the_page_end_are_fetched_before_paging() -> Result<()> {
         let dir = TempDir::new()?;
         let mut index = TantivyIndex::open_or_create(dir.path())?;
-        let conv = NormalizedConversation {
-            agent_slug: "codex".into(),
-            external_id: None,
-            title: Some("hello world convo".into()),
-            workspace: Some(std::path::PathBuf::from("/tmp/workspace")),
-            source_path: dir.path().join("rollout-1.jsonl"),
-            started_at: Some(1_700_000_000_000),
-            ended_at: None,
-            metadata: serde_json::json!({}),
-            messages: vec![NormalizedMessage {
-                idx: 0,
-                role: "user".into(),
-                author: Some("me".into()),
-                created_at: Some(1_700_000_000_000),
-                content: "hello rust world".into(),
-                extra: serde_json::json!({}),
-                snippets: vec![NormalizedSnippet {
-                    file_path: None,
-                    start_line: None,
-                    end_line: None,
-                    language: None,
-                    snippet_text: None,
+        // The newest doc is indexed last; on equal scores Tantivy prefers earlier
+        // docs, so a fetch that stops at the page end would never see it.
+        for (i, word) in ["alpha", "gamma", "delta", "kappa", "omega", "sigma"]
+            .into_iter()
+            .enumerate()
+        {
+            let created_at = if i == 5 { 900 } else { 100 };
+            let conv = NormalizedConversation {
+                agent_slug: "codex".into(),
+                external_id: None,
+                title: Some("tie".into()),
+                workspace: None,
+                source_path: dir.path().join(format!("{word}.jsonl")),
+                started_at: Some(created_at),
+                ended_at: None,
+                metadata: serde_json::json!({}),
+                messages: vec![NormalizedMessage {
+                    idx: 0,
+                    role: "user".into(),
+                    author: None,
+                    created_at: Some(created_at),
+                    content: format!("tiebreak {word}"),
+                    extra: serde_json::json!({}),
+                    snippets: vec![],
                 }],
-            }],
-        };
-        index.add_conversation(&conv)?;
+            };
+            index.add_conversation(&conv)?;
+        }
         index.commit()?;
 
         let client = SearchClient::open(dir.path(), None)?.expect("index present");
-        let mut filters = SearchFilters::default();
-        filters.agents.insert("codex".into());
-
-        let hits = client.search("hello", filters, 10, 0)?;
+        let hits = client.search("tiebreak", SearchFilters::default(), 1, 0)?;
         assert_eq!(hits.len(), 1);
-        assert_eq!(hits[0].agent, "codex");
-        assert!(hits[0].snippet.contains("hello"));
+        assert!(hits[0].source_path.ends_with("sigma.jsonl"));
         Ok(())
     }
 
@@ -3426,6 +4997,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         let hits = client.search("*handler", SearchFilters::default(), 5, 0)?;
@@ -3541,6 +5114,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         let hit = SearchHit {
@@ -3558,6 +5133,13 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
         };
         let hits = vec![hit];
 
@@ -3595,6 +5177,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         let hit = SearchHit {
@@ -3612,6 +5196,13 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
         };
         let hits = vec![hit.clone()];
 
@@ -3645,6 +5236,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         client.metrics.inc_cache_hits();
@@ -3677,6 +5270,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         let hit = SearchHit {
@@ -3694,6 +5289,13 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
         };
 
         // Put 3 entries - should trigger 1 eviction (cap is 2)
@@ -3738,6 +5340,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         // Large content to exceed byte cap quickly
@@ -3756,6 +5360,13 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
         };
 
         // Put 3 large entries - should trigger byte-based evictions
@@ -4006,7 +5617,7 @@ mod tests {
         // Short tool invocations are noise
         assert!(is_tool_invocation_noise("[Tool: Bash]"));
         assert!(is_tool_invocation_noise("[Tool: Read]"));
-        
+
         // Useful content should NOT be filtered
         assert!(!is_tool_invocation_noise("[Tool: Bash - Check status]"));
         assert!(!is_tool_invocation_noise("  [Tool: Grep - Search files]  "));
@@ -4027,7 +5638,7 @@ mod tests {
     fn is_tool_invocation_noise_detects_tool_markers() {
         assert!(is_tool_invocation_noise("[Tool: Bash]"));
         assert!(is_tool_invocation_noise("[Tool: Read]"));
-        
+
         // Useful content allowed
         assert!(!is_tool_invocation_noise("[Tool: Bash - Check status]"));
         assert!(!is_tool_invocation_noise("  [Tool: Write - description]  "));
@@ -4051,6 +5662,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
             SearchHit {
                 title: "title2".into(),
@@ -4067,6 +5685,13 @@ mod tests {
                 source_id: "local".into(), // same source_id = will dedupe
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
         ];
 
@@ -4094,6 +5719,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
             SearchHit {
                 title: "title2".into(),
@@ -4110,6 +5742,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
         ];
 
@@ -4137,6 +5776,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
             SearchHit {
                 title: "title2".into(),
@@ -4153,6 +5799,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
         ];
 
@@ -4178,6 +5831,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
             SearchHit {
                 title: "title2".into(),
@@ -4194,6 +5854,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
         ];
 
@@ -4220,6 +5887,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
             SearchHit {
                 title: "title2".into(),
@@ -4236,6 +5910,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
             SearchHit {
                 title: "title3".into(),
@@ -4252,6 +5933,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
         ];
 
@@ -4279,6 +5967,13 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
             SearchHit {
                 title: "remote title".into(),
@@ -4295,6 +5990,13 @@ mod tests {
                 source_id: "work-laptop".into(), // different source = no dedupe
                 origin_kind: "ssh".into(),
                 origin_host: Some("work-laptop.local".into()),
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
             },
         ];
 
@@ -4520,6 +6222,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: "vtest|schema:none".into(),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         let result = client.search_with_fallback("ghost", SearchFilters::default(), 5, 0, 3)?;
@@ -4595,6 +6299,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: "vtest|schema:none".into(),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         let result = client.search_with_fallback("ghost", SearchFilters::default(), 5, 10, 3)?;
@@ -4630,6 +6336,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: "vtest|schema:none".into(),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         let mut filters = SearchFilters::default();
@@ -5070,6 +6778,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn agent_filter_matches_aliased_slugs() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+
+        for (i, agent) in ["gemini_cli", "codex"].iter().enumerate() {
+            let conv = NormalizedConversation {
+                agent_slug: (*agent).into(),
+                external_id: None,
+                title: Some(format!("alias-{i}")),
+                workspace: None,
+                source_path: dir.path().join(format!("{i}.jsonl")),
+                started_at: Some(100),
+                ended_at: None,
+                metadata: serde_json::json!({}),
+                messages: vec![NormalizedMessage {
+                    idx: 0,
+                    role: "user".into(),
+                    author: None,
+                    created_at: Some(100),
+                    content: "aliastest needle".into(),
+                    extra: serde_json::json!({}),
+                    snippets: vec![],
+                }],
+            };
+            index.add_conversation(&conv)?;
+        }
+        index.commit()?;
+
+        let client = SearchClient::open(dir.path(), None)?.expect("index present");
+        let mut filters = SearchFilters::default();
+        filters.agents.insert("gemini".into());
+        filters.expand_agent_aliases(&AgentAliases::builtin());
+
+        let hits = client.search("aliastest", filters, 10, 0)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].agent, "gemini_cli");
+        Ok(())
+    }
+
     #[test]
     fn filter_fidelity_combined_filters_respected() -> Result<()> {
         // Combine agent + workspace + date filters
@@ -5210,6 +6958,161 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn exclude_tool_messages_filters_at_query_time() -> Result<()> {
+        let msg = |idx: i64, role: &str, content: &str| NormalizedMessage {
+            idx,
+            role: role.into(),
+            author: None,
+            created_at: Some(100 + idx),
+            content: content.into(),
+            extra: serde_json::json!({}),
+            snippets: vec![],
+        };
+        let conv = NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: None,
+            title: Some("mixed roles".into()),
+            workspace: None,
+            source_path: "/tmp/mixed.jsonl".into(),
+            started_at: Some(100),
+            ended_at: None,
+            metadata: serde_json::json!({}),
+            messages: vec![
+                msg(0, "user", "why does rolecheck fail"),
+                msg(1, "tool", "rolecheck output: 3 failures"),
+                msg(2, "assistant", "rolecheck fails because of a typo"),
+            ],
+        };
+        let client = build_ephemeral_index(&[conv])?;
+        let all = client.search("rolecheck", SearchFilters::default(), 10, 0)?;
+        assert_eq!(all.len(), 3);
+
+        let filters = SearchFilters {
+            exclude_tool_messages: true,
+            ..Default::default()
+        };
+        let hits = client.search("rolecheck", filters.clone(), 10, 0)?;
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|h| !h.content.contains("output")));
+        assert_eq!(client.count_matches("rolecheck", &filters)?, 2);
+
+        let page = client.search("rolecheck", filters, 1, 1)?;
+        assert_eq!(page.len(), 1);
+        assert!(!page[0].content.contains("output"));
+
+        let mut scoped = SearchFilters::default();
+        let text = apply_field_scopes("role:user rolecheck", &mut scoped);
+        let hits = client.search(&text, scoped, 10, 0)?;
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].content.starts_with("why does"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn field_scopes_move_leading_terms_into_filters() {
+        let mut filters = SearchFilters::default();
+        let text = apply_field_scopes(
+            "agent:codex  ws:/home/dev/app role:Assistant source:local error foo:bar agent:x",
+            &mut filters,
+        );
+        assert_eq!(text, "error foo:bar agent:x");
+        assert_eq!(filters.agents, HashSet::from(["codex".to_string()]));
+        assert_eq!(
+            filters.workspaces,
+            HashSet::from(["/home/dev/app".to_string()])
+        );
+        assert_eq!(filters.roles, HashSet::from(["assistant".to_string()]));
+        assert_eq!(
+            filters.source_ids,
+            HashSet::from([LOCAL_SOURCE_ID.to_string()])
+        );
+
+        let mut filters = SearchFilters::default();
+        assert_eq!(
+            apply_field_scopes("foo:bar agent:codex", &mut filters),
+            "foo:bar agent:codex"
+        );
+        assert_eq!(apply_field_scopes("role:bot x", &mut filters), "role:bot x");
+        assert_eq!(apply_field_scopes("agent: x", &mut filters), "agent: x");
+        assert_eq!(apply_field_scopes("source:remote", &mut filters), "");
+        assert_eq!(filters.source_filter, SourceFilter::Remote);
+        assert!(filters.agents.is_empty() && filters.roles.is_empty());
+    }
+
+    #[test]
+    fn provenance_filters_match_source_and_host() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+        let conv = |name: &str, metadata: serde_json::Value| NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: None,
+            title: Some(name.into()),
+            workspace: None,
+            source_path: dir.path().join(format!("{name}.jsonl")),
+            started_at: Some(100),
+            ended_at: None,
+            metadata,
+            messages: vec![NormalizedMessage {
+                idx: 0,
+                role: "user".into(),
+                author: None,
+                created_at: Some(100),
+                content: format!("provenance probe {name}"),
+                extra: serde_json::json!({}),
+                snippets: vec![],
+            }],
+        };
+        index.add_conversation(&conv("here", serde_json::json!({})))?;
+        index.add_conversation(&conv(
+            "laptop",
+            serde_json::json!({"cass": {"origin": {
+                "source_id": "laptop", "kind": "ssh", "host": "me@laptop.local"
+            }}}),
+        ))?;
+        index.add_conversation(&conv(
+            "buildbox",
+            serde_json::json!({"cass": {"origin": {
+                "source_id": "buildbox", "kind": "ssh", "host": "ci@buildbox"
+            }}}),
+        ))?;
+        index.commit()?;
+
+        let client = SearchClient::open(dir.path(), None)?.expect("index present");
+        let titles = |filters: SearchFilters| -> Result<Vec<String>> {
+            let mut t: Vec<_> = client
+                .search("provenance", filters, 10, 0)?
+                .into_iter()
+                .map(|h| h.title)
+                .collect();
+            t.sort();
+            Ok(t)
+        };
+
+        let by_source = SearchFilters {
+            source_ids: HashSet::from(["laptop".to_string(), "local".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(titles(by_source)?, vec!["here", "laptop"]);
+
+        let by_host = SearchFilters {
+            hosts: HashSet::from(["ci@buildbox".to_string()]),
+            ..Default::default()
+        };
+        let hits = client.search("provenance", by_host.clone(), 10, 0)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].origin_host.as_deref(), Some("ci@buildbox"));
+        assert!(by_host.matches_provenance(&hits[0]));
+
+        let local_host = SearchFilters {
+            hosts: HashSet::from(["local".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(titles(local_host)?, vec!["here"]);
+        Ok(())
+    }
+
     #[test]
     fn filter_fidelity_cache_key_isolation() {
         // Different filters should have different cache keys
@@ -5226,6 +7129,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         let filters_empty = SearchFilters::default();
@@ -6006,6 +7911,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         // Initial metrics should be zero
@@ -6042,6 +7949,8 @@ mod tests {
             metrics: Metrics::default(),
             cache_namespace: format!("v{CACHE_KEY_VERSION}|schema:test"),
             semantic: Mutex::new(None),
+            result_cache: None,
+            profile: None,
         };
 
         let filters1 = SearchFilters::default();
@@ -6443,9 +8352,166 @@ mod tests {
             source_id: "local".to_string(),
             origin_kind: "local".to_string(),
             origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
+        }
+    }
+
+    #[test]
+    fn blend_recency_interpolates_between_relevance_and_recency() {
+        let day_ms = 86_400_000;
+        let mut relevant_old = make_test_hit("relevant_old", 10.0);
+        relevant_old.created_at = Some(1_700_000_000_000 - 60 * day_ms);
+        let mut weak_new = make_test_hit("weak_new", 2.0);
+        weak_new.created_at = Some(1_700_000_000_000);
+
+        let mut hits = vec![weak_new.clone(), relevant_old.clone()];
+        blend_recency(&mut hits, 0.0, 30.0);
+        assert_eq!(hits[0].title, "relevant_old");
+        assert!((hits[0].score - 1.0).abs() < 1e-6);
+
+        let mut hits = vec![relevant_old.clone(), weak_new.clone()];
+        blend_recency(&mut hits, 1.0, 30.0);
+        assert_eq!(hits[0].title, "weak_new");
+        // Two half-lives old -> recency 0.25
+        assert!((hits[1].score - 0.25).abs() < 1e-4);
+
+        // Out-of-range weights are clamped
+        let mut clamped = vec![relevant_old.clone(), weak_new.clone()];
+        blend_recency(&mut clamped, 7.0, 30.0);
+        assert_eq!(clamped[0].title, "weak_new");
+        assert_eq!(clamped[1].score, hits[1].score);
+    }
+
+    #[test]
+    fn blend_recency_treats_missing_timestamps_as_oldest() {
+        let mut undated = make_test_hit("undated", 5.0);
+        undated.created_at = None;
+        let dated = make_test_hit("dated", 5.0);
+        let mut hits = vec![undated, dated];
+        blend_recency(&mut hits, 0.5, 0.0);
+        assert_eq!(hits[0].title, "dated");
+        assert!((hits[0].score - 1.0).abs() < 1e-6);
+        assert!((hits[1].score - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn retain_min_score_drops_hits_below_threshold() {
+        let scored = || {
+            vec![
+                make_test_hit("a", 12.0),
+                make_test_hit("b", 7.0),
+                make_test_hit("c", 5.5),
+                make_test_hit("d", 0.8),
+            ]
+        };
+        let titles = |hits: &[SearchHit]| hits.iter().map(|h| h.title.clone()).collect::<Vec<_>>();
+
+        let mut hits = scored();
+        retain_min_score(&mut hits, Some(5.5), None);
+        assert_eq!(titles(&hits), ["a", "b", "c"]);
+
+        let mut hits = scored();
+        retain_min_score(&mut hits, None, Some(50.0));
+        assert_eq!(titles(&hits), ["a", "b"]);
+
+        // The stricter of the two bounds wins.
+        let mut hits = scored();
+        retain_min_score(&mut hits, Some(10.0), Some(50.0));
+        assert_eq!(titles(&hits), ["a"]);
+
+        let mut hits = scored();
+        retain_min_score(&mut hits, None, None);
+        assert_eq!(hits.len(), 4);
+    }
+
+    struct LengthReranker;
+
+    impl Reranker for LengthReranker {
+        fn id(&self) -> &str {
+            "length"
+        }
+
+        fn score(
+            &self,
+            _query: &str,
+            documents: &[&str],
+        ) -> crate::search::embedder::EmbedderResult<Vec<f32>> {
+            Ok(documents.iter().map(|d| d.len() as f32).collect())
         }
     }
 
+    #[test]
+    fn rerank_hits_reorders_top_n_and_keeps_original_score() -> Result<()> {
+        let mut hits = vec![
+            make_test_hit("a", 3.0),
+            make_test_hit("ccc", 2.0),
+            make_test_hit("bb", 1.0),
+            make_test_hit("dddd", 0.5),
+        ];
+        rerank_hits(&mut hits, "query", &LengthReranker, 3)?;
+        let order: Vec<&str> = hits.iter().map(|h| h.title.as_str()).collect();
+        // Hits past top_n keep lexical order instead of being dropped
+        assert_eq!(order, vec!["ccc", "bb", "a", "dddd"]);
+        assert_eq!(hits[0].score, 2.0);
+        assert_eq!(hits[0].rerank_score, Some(3.0));
+        assert_eq!(hits[3].rerank_score, None);
+        Ok(())
+    }
+
+    #[test]
+    fn permalink_is_stable_and_uses_message_index() {
+        let mut hit = make_test_hit("sess-123", 1.0);
+        hit.line_number = Some(4);
+        assert_eq!(permalink(&hit), "test/sess-123#msg-3");
+
+        // Score and snippet don't affect the link
+        let mut other = hit.clone();
+        other.score = 0.1;
+        other.snippet = "different".to_string();
+        assert_eq!(permalink(&hit), permalink(&other));
+
+        // The same session synced from another machine gets its own link
+        other.source_id = "laptop".to_string();
+        assert_eq!(permalink(&other), "test/laptop:sess-123#msg-3");
+    }
+
+    #[test]
+    fn permalink_distinguishes_conversations_sharing_a_store_file() {
+        let mut first = make_test_hit("state", 1.0);
+        first.source_path = "/home/u/.cursor/state.vscdb".to_string();
+        first.conversation_external_id = Some("composer-a".to_string());
+        let mut second = first.clone();
+        second.conversation_external_id = Some("composer-b".to_string());
+        assert_eq!(permalink(&first), "test/composer-a#msg-0");
+        assert_ne!(permalink(&first), permalink(&second));
+
+        // Path-like external ids are hashed rather than embedded
+        second.conversation_external_id = Some("/repo/.aider.chat.history.md".to_string());
+        let link = permalink(&second);
+        assert!(!link.contains(".aider"));
+        assert_eq!(link, permalink(&second));
+    }
+
+    #[test]
+    fn permalink_defaults_to_first_message_and_hashes_odd_paths() {
+        let mut hit = make_test_hit("sess", 1.0);
+        hit.line_number = None;
+        assert_eq!(permalink(&hit), "test/sess#msg-0");
+
+        hit.source_path = "/".to_string();
+        let first = permalink(&hit);
+        assert!(first.starts_with("test/"));
+        assert!(first.ends_with("#msg-0"));
+        assert_eq!(first, permalink(&hit));
+        assert_eq!(message_anchor(7), "msg-7");
+    }
+
     #[test]
     fn test_rrf_fusion_ordering() {
         // Test that RRF correctly combines rankings from both lists
@@ -0,0 +1,278 @@
+//! On-disk search result cache shared across CLI invocations.
+//!
+//! The in-memory prefix cache in [`super::query`] only lives as long as a
+//! `SearchClient`, so every `cass search` process starts cold. This cache
+//! persists final hit lists under `<data_dir>/query_cache/`, one JSON file per
+//! key. Entries are tagged with the index generation (a digest of Tantivy's
+//! `meta.json`); any commit or rebuild changes the digest, so results from a
+//! previous index are never served. Every write prunes entries from older
+//! generations or past their TTL, and keeps at most
+//! [`DEFAULT_RESULT_CACHE_MAX_ENTRIES`] files, dropping the oldest first.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::query::SearchHit;
+
+/// Default time-to-live for cached results.
+pub const DEFAULT_RESULT_CACHE_TTL_SECS: u64 = 600;
+
+/// Default cap on cached result files.
+pub const DEFAULT_RESULT_CACHE_MAX_ENTRIES: usize = 256;
+
+const CACHE_DIR_NAME: &str = "query_cache";
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    key: String,
+    generation: String,
+    created_at: u64,
+    hits: Vec<SearchHit>,
+}
+
+/// Disk-backed cache of search results keyed by query, filters and paging.
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    dir: PathBuf,
+    meta_path: PathBuf,
+    ttl: Duration,
+    max_entries: usize,
+}
+
+impl ResultCache {
+    /// Cache stored under `data_dir`, validated against the index at `index_path`.
+    pub fn new(data_dir: &Path, index_path: &Path, ttl: Duration) -> Self {
+        Self {
+            dir: data_dir.join(CACHE_DIR_NAME),
+            meta_path: index_path.join("meta.json"),
+            ttl,
+            max_entries: DEFAULT_RESULT_CACHE_MAX_ENTRIES,
+        }
+    }
+
+    /// Keep at most `max_entries` cached results on disk.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Current index generation, or `None` when there is no Tantivy index
+    /// (in which case nothing is cached).
+    pub fn index_generation(&self) -> Option<String> {
+        generation_of(&self.meta_path)
+    }
+
+    /// Look up `key`, returning hits only if the entry is fresh and was
+    /// written against the current index generation. Stale entries are removed.
+    pub fn get(&self, key: &str) -> Option<Vec<SearchHit>> {
+        let generation = self.index_generation()?;
+        let path = self.entry_path(&generation, key);
+        let bytes = fs::read(&path).ok()?;
+        let entry: CacheEntry = match serde_json::from_slice(&bytes) {
+            Ok(entry) => entry,
+            Err(_) => {
+                let _ = fs::remove_file(&path);
+                return None;
+            }
+        };
+        if entry.key != key {
+            // Digest collision; leave the other key's entry alone.
+            return None;
+        }
+        let age = now_secs().saturating_sub(entry.created_at);
+        if entry.generation != generation || age >= self.ttl.as_secs() {
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+        Some(entry.hits)
+    }
+
+    /// Store `hits` for `key`. Failures are logged and otherwise ignored: the
+    /// cache is an optimization and must never fail a search.
+    pub fn put(&self, key: &str, hits: &[SearchHit]) {
+        let Some(generation) = self.index_generation() else {
+            return;
+        };
+        let entry = CacheEntry {
+            key: key.to_string(),
+            generation,
+            created_at: now_secs(),
+            hits: hits.to_vec(),
+        };
+        if let Err(e) = self.write_entry(key, &entry) {
+            tracing::debug!(error = %e, "result_cache_write_failed");
+        }
+        if let Err(e) = self.prune(&entry.generation) {
+            tracing::debug!(error = %e, "result_cache_prune_failed");
+        }
+    }
+
+    fn write_entry(&self, key: &str, entry: &CacheEntry) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.entry_path(&entry.generation, key);
+        // Write-then-rename so concurrent readers never see a partial file.
+        let tmp = path.with_extension(format!("tmp.{}", std::process::id()));
+        fs::write(&tmp, serde_json::to_vec(entry)?)?;
+        fs::rename(&tmp, &path)
+    }
+
+    /// Remove entries from other generations or past the TTL, then the oldest
+    /// entries beyond `max_entries`.
+    fn prune(&self, generation: &str) -> std::io::Result<()> {
+        let prefix = format!("{generation}-");
+        let mut kept = Vec::new();
+        for dir_entry in fs::read_dir(&self.dir)? {
+            let dir_entry = dir_entry?;
+            let name = dir_entry.file_name();
+            let name = name.to_string_lossy();
+            if !name.starts_with(&prefix) {
+                let _ = fs::remove_file(dir_entry.path());
+                continue;
+            }
+            if !name.ends_with(".json") {
+                // A write in progress.
+                continue;
+            }
+            let modified = dir_entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(UNIX_EPOCH);
+            if modified.elapsed().is_ok_and(|age| age >= self.ttl) {
+                let _ = fs::remove_file(dir_entry.path());
+            } else {
+                kept.push((modified, dir_entry.path()));
+            }
+        }
+        if kept.len() > self.max_entries {
+            kept.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+            for (_, path) in kept.drain(self.max_entries..) {
+                let _ = fs::remove_file(path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Entries are named `<generation>-<key digest>.json`, so superseded
+    /// generations can be pruned without reading them.
+    fn entry_path(&self, generation: &str, key: &str) -> PathBuf {
+        let digest = Sha256::digest(key.as_bytes());
+        self.dir
+            .join(format!("{generation}-{}.json", hex::encode(&digest[..16])))
+    }
+}
+
+/// Generation of the Tantivy index at `index_path`: a digest of its
+/// `meta.json`, which changes on every commit. `None` when there is no index.
+pub fn index_generation(index_path: &Path) -> Option<String> {
+    generation_of(&index_path.join("meta.json"))
+}
+
+fn generation_of(meta_path: &Path) -> Option<String> {
+    let meta = fs::read(meta_path).ok()?;
+    Some(hex::encode(&Sha256::digest(&meta)[..8]))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::query::MatchType;
+
+    fn hit(title: &str) -> SearchHit {
+        SearchHit {
+            title: title.into(),
+            snippet: String::new(),
+            content: "content".into(),
+            score: 1.0,
+            source_path: "/tmp/session.jsonl".into(),
+            agent: "codex".into(),
+            workspace: String::new(),
+            workspace_original: None,
+            created_at: Some(1),
+            line_number: None,
+            match_type: MatchType::Exact,
+            source_id: "local".into(),
+            origin_kind: "local".into(),
+            origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_until_generation_changes() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let index = tmp.path().join("index");
+        fs::create_dir_all(&index).unwrap();
+        fs::write(index.join("meta.json"), r#"{"opstamp":1}"#).unwrap();
+        let cache = ResultCache::new(tmp.path(), &index, Duration::from_secs(60));
+
+        cache.put("q|limit:10", &[hit("a")]);
+        let cached = cache.get("q|limit:10").expect("cache hit");
+        assert_eq!(cached[0].title, "a");
+        assert!(cache.get("q|limit:20").is_none());
+
+        fs::write(index.join("meta.json"), r#"{"opstamp":2}"#).unwrap();
+        assert!(cache.get("q|limit:10").is_none());
+    }
+
+    #[test]
+    fn expired_entries_are_ignored() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let index = tmp.path().join("index");
+        fs::create_dir_all(&index).unwrap();
+        fs::write(index.join("meta.json"), "{}").unwrap();
+        let cache = ResultCache::new(tmp.path(), &index, Duration::ZERO);
+
+        cache.put("q", &[hit("a")]);
+        assert!(cache.get("q").is_none());
+    }
+
+    #[test]
+    fn writes_prune_old_generations_and_cap_entries() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let index = tmp.path().join("index");
+        fs::create_dir_all(&index).unwrap();
+        fs::write(index.join("meta.json"), r#"{"opstamp":1}"#).unwrap();
+        let cache =
+            ResultCache::new(tmp.path(), &index, Duration::from_secs(60)).with_max_entries(3);
+        let entries = || {
+            fs::read_dir(tmp.path().join(CACHE_DIR_NAME))
+                .unwrap()
+                .count()
+        };
+
+        for q in ["a", "b", "c", "d", "e"] {
+            cache.put(q, &[hit(q)]);
+        }
+        assert_eq!(entries(), 3);
+
+        fs::write(index.join("meta.json"), r#"{"opstamp":2}"#).unwrap();
+        cache.put("f", &[hit("f")]);
+        assert_eq!(entries(), 1);
+        assert!(cache.get("f").is_some());
+    }
+
+    #[test]
+    fn no_index_means_no_caching() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let cache = ResultCache::new(tmp.path(), &tmp.path().join("missing"), Duration::MAX);
+        cache.put("q", &[hit("a")]);
+        assert!(cache.get("q").is_none());
+    }
+}
@@ -3,17 +3,19 @@ use std::sync::atomic::{AtomicI64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{Result, anyhow};
+use once_cell::sync::Lazy;
+use tantivy::query::{BooleanQuery, Occur, Query, TermQuery};
 use tantivy::schema::{
     FAST, Field, INDEXED, IndexRecordOption, STORED, STRING, Schema, TEXT, TextFieldIndexing,
     TextOptions,
 };
-use tantivy::{Index, IndexReader, IndexWriter, doc};
+use tantivy::{Index, IndexReader, IndexWriter, Term, doc};
 use tracing::{debug, info, warn};
 
 use crate::connectors::NormalizedConversation;
 use crate::sources::provenance::LOCAL_SOURCE_ID;
 
-const SCHEMA_VERSION: &str = "v6";
+const SCHEMA_VERSION: &str = "v7";
 
 /// Minimum time (ms) between merge operations
 const MERGE_COOLDOWN_MS: i64 = 300_000; // 5 minutes
@@ -24,6 +26,15 @@ const MERGE_SEGMENT_THRESHOLD: usize = 4;
 /// Global last merge timestamp (ms since epoch)
 static LAST_MERGE_TS: AtomicI64 = AtomicI64::new(0);
 
+/// Commits between opportunistic background merges; `CASS_MERGE_EVERY` overrides
+/// it and 0 disables them.
+static MERGE_EVERY_COMMITS: Lazy<usize> = Lazy::new(|| {
+    dotenvy::var("CASS_MERGE_EVERY")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .unwrap_or(50)
+});
+
 /// Debug status for segment merge operations
 #[derive(Debug, Clone)]
 pub struct MergeStatus {
@@ -48,7 +59,7 @@ impl MergeStatus {
 }
 
 // Bump this when schema/tokenizer changes. Used to trigger rebuilds.
-pub const SCHEMA_HASH: &str = "tantivy-schema-v6-provenance-indexed";
+pub const SCHEMA_HASH: &str = "tantivy-schema-v10-external-id";
 
 #[derive(Clone, Copy)]
 pub struct Fields {
@@ -67,12 +78,16 @@ pub struct Fields {
     pub source_id: Field,
     pub origin_kind: Field,
     pub origin_host: Field,
+    pub role: Field,
+    pub external_id: Field,
 }
 
 pub struct TantivyIndex {
     pub index: Index,
     writer: IndexWriter,
     pub fields: Fields,
+    /// Commits since the last opportunistic merge check (see `CASS_MERGE_EVERY`).
+    commits_since_merge: usize,
 }
 
 impl TantivyIndex {
@@ -98,7 +113,7 @@ impl TantivyIndex {
             std::fs::create_dir_all(path)?;
         }
 
-        let mut index = if path.join("meta.json").exists() && !needs_rebuild {
+        let index = if path.join("meta.json").exists() && !needs_rebuild {
             // We believe the schema hash matches; try to open. If this fails
             // (e.g. corrupted meta.json / index), fall back to a clean rebuild.
             match Index::open_in_dir(path) {
@@ -117,11 +132,22 @@ impl TantivyIndex {
             Index::create_in_dir(path, schema.clone())?
         };
 
-        ensure_tokenizer(&mut index);
-
         // Always write the current schema hash so future runs can detect mismatches.
         std::fs::write(&meta_path, format!("{{\"schema_hash\":\"{SCHEMA_HASH}\"}}"))?;
 
+        Self::from_index(index)
+    }
+
+    /// An index held entirely in memory, for tests and throwaway searches.
+    /// Nothing touches disk; the documents are gone once the last handle to
+    /// [`Self::index`] is dropped.
+    pub fn open_in_ram() -> Result<Self> {
+        Self::from_index(Index::create_in_ram(build_schema()))
+    }
+
+    fn from_index(mut index: Index) -> Result<Self> {
+        ensure_tokenizer(&mut index);
+
         // Use the schema actually attached to this index to derive field ids.
         // This avoids subtle field-id mismatches if the on-disk index was created
         // by a slightly different binary.
@@ -134,6 +160,7 @@ impl TantivyIndex {
             index,
             writer,
             fields,
+            commits_since_merge: 0,
         })
     }
 
@@ -146,8 +173,58 @@ impl TantivyIndex {
         Ok(())
     }
 
+    /// Delete every document indexed from `source_path`. Takes effect on the
+    /// next [`commit`](Self::commit).
+    pub fn delete_source_path(&mut self, source_path: &str) {
+        self.writer
+            .delete_term(Term::from_field_text(self.fields.source_path, source_path));
+    }
+
+    /// Delete the document for message `msg_idx` of `conv`, e.g. before re-adding
+    /// a message whose content changed. Takes effect on the next
+    /// [`commit`](Self::commit) and never touches documents added afterwards.
+    pub fn delete_message(&mut self, conv: &NormalizedConversation, msg_idx: i64) -> Result<()> {
+        let term_query = |term: Term| -> Box<dyn Query> {
+            Box::new(TermQuery::new(term, IndexRecordOption::Basic))
+        };
+        let mut clauses = vec![
+            (
+                Occur::Must,
+                term_query(Term::from_field_text(
+                    self.fields.source_path,
+                    &conv.source_path.to_string_lossy(),
+                )),
+            ),
+            (
+                Occur::Must,
+                term_query(Term::from_field_u64(self.fields.msg_idx, msg_idx as u64)),
+            ),
+        ];
+        if let Some(external_id) = &conv.external_id {
+            clauses.push((
+                Occur::Must,
+                term_query(Term::from_field_text(self.fields.external_id, external_id)),
+            ));
+        }
+        self.writer
+            .delete_query(Box::new(BooleanQuery::new(clauses)))?;
+        Ok(())
+    }
+
+    /// Commit pending changes. Every `CASS_MERGE_EVERY` commits this also starts a
+    /// background merge via [`optimize_if_idle`](Self::optimize_if_idle), so
+    /// long-running watch sessions don't pile up small segments.
     pub fn commit(&mut self) -> Result<()> {
         self.writer.commit()?;
+        self.commits_since_merge += 1;
+        let merge_every = *MERGE_EVERY_COMMITS;
+        if merge_every > 0 && self.commits_since_merge >= merge_every {
+            self.commits_since_merge = 0;
+            // Merging is an optimization; never fail the commit over it.
+            if let Err(e) = self.optimize_if_idle() {
+                warn!(error = %e, "Opportunistic segment merge failed");
+            }
+        }
         Ok(())
     }
 
@@ -233,29 +310,40 @@ impl TantivyIndex {
     /// Force immediate segment merge and wait for completion.
     /// Use sparingly - blocks until merge finishes.
     pub fn force_merge(&mut self) -> Result<()> {
-        let segment_ids = self.index.searchable_segment_ids()?;
-        if segment_ids.is_empty() {
-            return Ok(());
+        self.merge_segments(1).map(|_| ())
+    }
+
+    /// Merge searchable segments down to at most `target` (minimum 1), blocking
+    /// until every merge finishes. Returns the resulting segment count.
+    ///
+    /// Merged segments are rewritten on disk, so this is I/O-heavy on large indexes.
+    pub fn merge_segments(&mut self, target: usize) -> Result<usize> {
+        let target = target.max(1);
+        let mut metas = self.index.searchable_segment_metas()?;
+        if metas.len() <= target {
+            return Ok(metas.len());
         }
-        info!(segments = segment_ids.len(), "Force merging all segments");
+        info!(segments = metas.len(), target, "Merging segments");
         let now_ms = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_millis() as i64)
             .unwrap_or(0);
 
-        // Start merge and wait for completion
-        let merge_future = self.writer.merge(&segment_ids);
-        match merge_future.wait() {
-            Ok(_) => {
-                LAST_MERGE_TS.store(now_ms, Ordering::Relaxed);
-                info!("Force merge completed");
-                Ok(())
-            }
-            Err(e) => {
-                warn!(error = %e, "Force merge failed");
-                Err(anyhow!("merge failed: {e}"))
+        // Group similar-sized segments so small ones are merged with each other
+        // rather than each rewriting the largest segment.
+        metas.sort_by_key(|meta| meta.num_docs());
+        let group_size = metas.len().div_ceil(target);
+        for group in metas.chunks(group_size).filter(|group| group.len() > 1) {
+            let segment_ids: Vec<_> = group.iter().map(|meta| meta.id()).collect();
+            if let Err(e) = self.writer.merge(&segment_ids).wait() {
+                warn!(error = %e, "Segment merge failed");
+                return Err(anyhow!("merge failed: {e}"));
             }
         }
+        LAST_MERGE_TS.store(now_ms, Ordering::Relaxed);
+        let segments = self.segment_count();
+        info!(segments, "Segment merge completed");
+        Ok(segments)
     }
 
     pub fn add_messages(
@@ -297,6 +385,7 @@ impl TantivyIndex {
                 self.fields.content => msg.content.clone(),
                 self.fields.source_id => source_id,
                 self.fields.origin_kind => origin_kind,
+                self.fields.role => msg.role.to_lowercase(),
             };
             if let Some(host) = origin_host
                 && !host.is_empty()
@@ -306,6 +395,9 @@ impl TantivyIndex {
             if let Some(ws) = &workspace {
                 d.add_text(self.fields.workspace, ws.as_ref());
             }
+            if let Some(external_id) = &conv.external_id {
+                d.add_text(self.fields.external_id, external_id);
+            }
             // workspace_original from metadata.cass.workspace_original (P6.2)
             if let Some(ws_orig) = workspace_original {
                 d.add_text(self.fields.workspace_original, ws_orig);
@@ -383,9 +475,11 @@ pub fn build_schema() -> Schema {
     // This ensures exact match filtering works correctly with TermQuery.
     schema_builder.add_text_field("agent", STRING | STORED);
     schema_builder.add_text_field("workspace", STRING | STORED);
-    // workspace_original stores the pre-rewrite path for audit/display (P6.2)
-    schema_builder.add_text_field("workspace_original", STORED);
-    schema_builder.add_text_field("source_path", STORED);
+    // workspace_original stores the pre-rewrite path (P6.2); indexed so workspace
+    // filters also match the path a remote session was recorded under.
+    schema_builder.add_text_field("workspace_original", STRING | STORED);
+    // STRING so a conversation's documents can be deleted by exact path (`cass forget`).
+    schema_builder.add_text_field("source_path", STRING | STORED);
     schema_builder.add_u64_field("msg_idx", INDEXED | STORED);
     schema_builder.add_i64_field("created_at", INDEXED | STORED | FAST);
     schema_builder.add_text_field("title", text.clone());
@@ -397,6 +491,11 @@ pub fn build_schema() -> Schema {
     schema_builder.add_text_field("source_id", STRING | STORED);
     schema_builder.add_text_field("origin_kind", STRING | STORED);
     schema_builder.add_text_field("origin_host", STRING | STORED);
+    // Normalized message role (user/assistant/tool/system), for role filters such as --no-tools.
+    schema_builder.add_text_field("role", STRING);
+    // Connector-assigned conversation id; tells apart conversations that share a
+    // store file (Cursor, Aider, Copilot).
+    schema_builder.add_text_field("external_id", STRING | STORED);
     schema_builder.build()
 }
 
@@ -421,6 +520,8 @@ pub fn fields_from_schema(schema: &Schema) -> Result<Fields> {
         source_id: get("source_id")?,
         origin_kind: get("origin_kind")?,
         origin_host: get("origin_host")?,
+        role: get("role")?,
+        external_id: get("external_id")?,
     })
 }
 
@@ -447,11 +548,16 @@ fn build_preview(content: &str, max_chars: usize) -> String {
 }
 
 pub fn index_dir(base: &Path) -> Result<std::path::PathBuf> {
-    let dir = base.join("index").join(SCHEMA_VERSION);
+    let dir = index_dir_path(base);
     std::fs::create_dir_all(&dir)?;
     Ok(dir)
 }
 
+/// Location of the index for the current schema, without creating it.
+pub fn index_dir_path(base: &Path) -> std::path::PathBuf {
+    base.join("index").join(SCHEMA_VERSION)
+}
+
 pub fn ensure_tokenizer(index: &mut Index) {
     use tantivy::tokenizer::{LowerCaser, RemoveLongFilter, SimpleTokenizer, TextAnalyzer};
     let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
@@ -700,6 +806,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merge_segments_reduces_segment_count() {
+        let dir = TempDir::new().unwrap();
+        let mut index = TantivyIndex::open_or_create(dir.path()).unwrap();
+        // Keep Tantivy from merging on its own so every commit leaves a segment.
+        index
+            .writer
+            .set_merge_policy(Box::new(tantivy::indexer::NoMergePolicy));
+        for i in 0..6u64 {
+            let doc = doc! {
+                index.fields.agent => "test_agent",
+                index.fields.source_path => format!("/test/path-{i}"),
+                index.fields.msg_idx => i,
+                index.fields.content => format!("segment content {i}"),
+            };
+            index.writer.add_document(doc).unwrap();
+            index.commit().unwrap();
+        }
+        let segment_readers = |index: &TantivyIndex| {
+            let reader = index.reader().unwrap();
+            reader.reload().unwrap();
+            reader.searcher().segment_readers().len()
+        };
+        assert_eq!(segment_readers(&index), 6);
+
+        assert_eq!(index.merge_segments(2).unwrap(), 2);
+        assert_eq!(segment_readers(&index), 2);
+        assert_eq!(index.merge_segments(1).unwrap(), 1);
+        let reader = index.reader().unwrap();
+        assert_eq!(reader.searcher().num_docs(), 6);
+    }
+
     #[test]
     fn build_schema_returns_valid_schema() {
         let schema = build_schema();
@@ -408,6 +408,17 @@ impl SemanticFilter {
         self.roles = roles;
         self
     }
+
+    /// Exclude `role` from matches, on top of any existing role restriction.
+    pub fn without_role(mut self, role: u8) -> Self {
+        let mut roles = self
+            .roles
+            .take()
+            .unwrap_or_else(|| HashSet::from([ROLE_USER, ROLE_ASSISTANT, ROLE_SYSTEM, ROLE_TOOL]));
+        roles.remove(&role);
+        self.roles = Some(roles);
+        self
+    }
 }
 
 pub const ROLE_USER: u8 = 0;
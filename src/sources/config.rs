@@ -34,7 +34,9 @@
 //! ```
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
 use thiserror::Error;
 
 use super::provenance::SourceKind;
@@ -64,6 +66,11 @@ pub struct SourcesConfig {
     /// List of configured sources.
     #[serde(default)]
     pub sources: Vec<SourceDefinition>,
+
+    /// Extra agent slug aliases: canonical slug → alternates (see
+    /// [`crate::search::agent_alias`]).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub agent_aliases: BTreeMap<String, Vec<String>>,
 }
 
 /// A single path mapping rule for rewriting paths.
@@ -256,7 +263,7 @@ impl SourceDefinition {
             .iter()
             .filter(|m| m.applies_to_agent(agent))
             .collect();
-        mappings.sort_by(|a, b| b.from.len().cmp(&a.from.len()));
+        mappings.sort_by_key(|m| std::cmp::Reverse(m.from.len()));
 
         for mapping in mappings {
             if let Some(rewritten) = mapping.apply(path) {
@@ -337,28 +344,27 @@ impl std::fmt::Display for Platform {
     }
 }
 
+/// Process-wide config path set by the global `--sources` flag.
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Point [`SourcesConfig::config_path`] at `path` for the rest of the process.
+///
+/// Only the first call takes effect. `CASS_IGNORE_SOURCES_CONFIG` still
+/// short-circuits config loading in the indexer when this is set.
+pub fn set_config_path_override(path: PathBuf) {
+    let _ = CONFIG_PATH_OVERRIDE.set(path);
+}
+
 impl SourcesConfig {
-    /// Load configuration from the default location.
+    /// Load configuration from [`Self::config_path`].
     ///
     /// Returns an empty config if the file doesn't exist.
     pub fn load() -> Result<Self, ConfigError> {
-        let config_path = Self::config_path()?;
-
-        if !config_path.exists() {
-            return Ok(Self::default());
-        }
-
-        let content = std::fs::read_to_string(&config_path)?;
-        let config: Self = toml::from_str(&content)?;
-
-        // Validate all sources
-        config.validate()?;
-
-        Ok(config)
+        Self::load_from(&Self::config_path()?)
     }
 
     /// Load configuration from a specific path.
-    pub fn load_from(path: &PathBuf) -> Result<Self, ConfigError> {
+    pub fn load_from(path: &Path) -> Result<Self, ConfigError> {
         if !path.exists() {
             return Ok(Self::default());
         }
@@ -397,13 +403,24 @@ impl SourcesConfig {
         Ok(())
     }
 
-    /// Get the default configuration file path.
+    /// Get the configuration file path.
     ///
-    /// Uses XDG conventions:
-    /// - Primary: `$XDG_CONFIG_HOME/cass/sources.toml`
-    /// - Fallback: platform-specific config dir (e.g., `~/.config/cass/sources.toml` on Linux)
+    /// Resolution order:
+    /// - Explicit override from `--sources` (see [`set_config_path_override`])
+    /// - `$CASS_SOURCES_CONFIG`
+    /// - `$XDG_CONFIG_HOME/cass/sources.toml`
+    /// - Platform-specific config dir (e.g., `~/.config/cass/sources.toml` on Linux)
     pub fn config_path() -> Result<PathBuf, ConfigError> {
-        // Respect XDG_CONFIG_HOME first (important for testing and Linux users)
+        if let Some(path) = CONFIG_PATH_OVERRIDE.get() {
+            return Ok(path.clone());
+        }
+        if let Ok(path) = dotenvy::var("CASS_SOURCES_CONFIG")
+            && !path.is_empty()
+        {
+            return Ok(PathBuf::from(path));
+        }
+
+        // Respect XDG_CONFIG_HOME (important for testing and Linux users)
         if let Ok(xdg_config) = dotenvy::var("XDG_CONFIG_HOME") {
             return Ok(PathBuf::from(xdg_config).join("cass").join("sources.toml"));
         }
@@ -1222,6 +1239,7 @@ mod tests {
             sync_schedule: SyncSchedule::Daily,
             path_mappings: vec![PathMapping::new("/home/user", "/Users/me")],
             platform: Some(Platform::Linux),
+            rsync_path: None,
         });
 
         let serialized = toml::to_string_pretty(&config).unwrap();
@@ -1249,6 +1267,7 @@ mod tests {
                 PathMapping::with_agents("/opt/work", "/Volumes/Work", vec!["claude-code".into()]),
             ],
             platform: None,
+            rsync_path: None,
         });
 
         let serialized = toml::to_string_pretty(&config).unwrap();
@@ -25,8 +25,8 @@
 //! ```
 
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::time::Instant;
+use std::process::{Command, Output};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use thiserror::Error;
 
@@ -95,6 +95,8 @@ pub struct PathSyncResult {
     pub error: Option<String>,
     /// Duration of the sync operation.
     pub duration_ms: u64,
+    /// Times the transfer was attempted (more than 1 when transient failures were retried).
+    pub attempts: u32,
 }
 
 /// Report from syncing an entire source.
@@ -166,6 +168,104 @@ impl SyncReport {
     pub fn failed_paths(&self) -> usize {
         self.path_results.iter().filter(|r| !r.success).count()
     }
+
+    /// Get total retries (attempts beyond the first) across all paths.
+    pub fn total_retries(&self) -> u32 {
+        self.path_results
+            .iter()
+            .map(|r| r.attempts.saturating_sub(1))
+            .sum()
+    }
+}
+
+/// How often rsync/SSH invocations are retried after transient network errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts per invocation, including the first (1 = never retry).
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each further retry.
+    pub base_delay: Duration,
+    /// Upper bound for a single delay, before jitter.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that runs each invocation exactly once.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based): exponential backoff capped at
+    /// `max_delay`, plus up to 25% jitter so parallel syncs don't retry in lockstep.
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        delay + delay.mul_f64(f64::from(nanos % 250) / 1000.0)
+    }
+}
+
+/// Runs the external commands (rsync, ssh) the sync engine depends on.
+///
+/// Swappable via [`SyncEngine::with_command_runner`] so tests can simulate
+/// flaky connections.
+pub trait CommandRunner: Send + Sync {
+    /// Run `cmd` to completion and capture its output.
+    fn output(&self, cmd: &mut Command) -> std::io::Result<Output>;
+}
+
+/// [`CommandRunner`] that spawns real processes.
+pub struct SystemCommandRunner;
+
+impl CommandRunner for SystemCommandRunner {
+    fn output(&self, cmd: &mut Command) -> std::io::Result<Output> {
+        cmd.output()
+    }
+}
+
+/// Whether a failed rsync/ssh run looks like a network blip worth retrying.
+///
+/// Authentication, host key and missing-path errors are never retried.
+fn is_transient_failure(exit_code: Option<i32>, stderr: &str) -> bool {
+    const PERMANENT: &[&str] = &[
+        "permission denied",
+        "host key verification failed",
+        "no such file or directory",
+        "name or service not known",
+    ];
+    const TRANSIENT: &[&str] = &[
+        "connection reset",
+        "connection timed out",
+        "operation timed out",
+        "broken pipe",
+        "connection closed",
+        "connection unexpectedly closed",
+        "network is unreachable",
+        "no route to host",
+        "temporary failure in name resolution",
+    ];
+    let stderr = stderr.to_ascii_lowercase();
+    if PERMANENT.iter().any(|p| stderr.contains(p)) {
+        return false;
+    }
+    // rsync exit codes 30/35: timeout in data send/receive / waiting for the daemon.
+    matches!(exit_code, Some(30 | 35)) || TRANSIENT.iter().any(|p| stderr.contains(p))
 }
 
 /// Statistics parsed from rsync output.
@@ -184,6 +284,10 @@ pub struct SyncEngine {
     connection_timeout: u64,
     /// Transfer timeout in seconds (0 = no timeout).
     transfer_timeout: u64,
+    /// Retries for rsync/SSH invocations that fail transiently.
+    retry: RetryPolicy,
+    /// Runs rsync/ssh; replaced in tests.
+    runner: Box<dyn CommandRunner>,
 }
 
 impl SyncEngine {
@@ -196,6 +300,8 @@ impl SyncEngine {
             local_store: data_dir.to_path_buf(),
             connection_timeout: 10,
             transfer_timeout: 300, // 5 minutes
+            retry: RetryPolicy::default(),
+            runner: Box::new(SystemCommandRunner),
         }
     }
 
@@ -211,6 +317,50 @@ impl SyncEngine {
         self
     }
 
+    /// Set the retry policy for transient rsync/SSH failures.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Replace how rsync/ssh are run (for tests).
+    pub fn with_command_runner(mut self, runner: impl CommandRunner + 'static) -> Self {
+        self.runner = Box::new(runner);
+        self
+    }
+
+    /// Run `cmd`, retrying transient failures per the retry policy.
+    ///
+    /// Returns the last result together with the number of attempts made.
+    fn output_with_retry(&self, cmd: &mut Command, what: &str) -> (std::io::Result<Output>, u32) {
+        let mut attempt = 1;
+        loop {
+            let result = self.runner.output(cmd);
+            let transient = match &result {
+                Ok(out) => {
+                    !out.status.success()
+                        && is_transient_failure(
+                            out.status.code(),
+                            &String::from_utf8_lossy(&out.stderr),
+                        )
+                }
+                Err(_) => false,
+            };
+            if !transient || attempt >= self.retry.max_attempts {
+                return (result, attempt);
+            }
+            let delay = self.retry.delay_for(attempt);
+            tracing::warn!(
+                command = what,
+                attempt,
+                delay_ms = delay.as_millis() as u64,
+                "transient failure, retrying"
+            );
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
     /// Get the local mirror directory for a source.
     pub fn mirror_dir(&self, source_name: &str) -> PathBuf {
         self.local_store
@@ -228,12 +378,14 @@ impl SyncEngine {
             self.connection_timeout
         );
 
-        let output = Command::new("ssh")
-            .args(ssh_opts.split_whitespace())
+        let mut cmd = Command::new("ssh");
+        cmd.args(ssh_opts.split_whitespace())
             .arg("--")
             .arg(host)
-            .arg("echo $HOME")
-            .output()
+            .arg("echo $HOME");
+        let output = self
+            .output_with_retry(&mut cmd, "ssh")
+            .0
             .map_err(|e| SyncError::SshFailed(format!("Failed to execute ssh: {}", e)))?;
 
         if !output.status.success() {
@@ -332,9 +484,10 @@ impl SyncEngine {
                 SyncMethod::Rsync => {
                     self.sync_path_rsync(host, remote_path, &mirror_dir, remote_home.as_deref(), source.rsync_path.as_deref())
                 }
-                SyncMethod::Sftp => {
-                    self.sync_path_sftp(host, remote_path, &mirror_dir, remote_home.as_deref())
-                }
+                SyncMethod::Sftp => PathSyncResult {
+                    attempts: 1,
+                    ..self.sync_path_sftp(host, remote_path, &mirror_dir, remote_home.as_deref())
+                },
             };
             report.add_path_result(result);
         }
@@ -442,7 +595,8 @@ impl SyncEngine {
             "starting rsync"
         );
 
-        let output = match cmd.output() {
+        let (output, attempts) = self.output_with_retry(&mut cmd, "rsync");
+        let output = match output {
             Ok(o) => o,
             Err(e) => {
                 return PathSyncResult {
@@ -451,6 +605,7 @@ impl SyncEngine {
                     success: false,
                     error: Some(format!("Failed to execute rsync: {}", e)),
                     duration_ms: start.elapsed().as_millis() as u64,
+                    attempts,
                     ..Default::default()
                 };
             }
@@ -487,6 +642,7 @@ impl SyncEngine {
                 success: false,
                 error: Some(error_msg),
                 duration_ms,
+                attempts,
                 ..Default::default()
             };
         }
@@ -511,6 +667,7 @@ impl SyncEngine {
             success: true,
             error: None,
             duration_ms,
+            attempts,
         }
     }
 
@@ -678,6 +835,7 @@ impl SyncEngine {
                 success: false,
                 error: Some(format!("SFTP download failed: {}", e)),
                 duration_ms: start.elapsed().as_millis() as u64,
+                ..Default::default()
             };
         }
 
@@ -700,6 +858,7 @@ impl SyncEngine {
             success: true,
             error: None,
             duration_ms,
+            ..Default::default()
         }
     }
 
@@ -1302,6 +1461,135 @@ Total transferred file size: 1,234 bytes
         assert_eq!(engine.transfer_timeout, 600);
     }
 
+    /// Runner that fails `failures` times with `stderr`, then succeeds.
+    #[cfg(unix)]
+    struct FlakyRunner {
+        failures: u32,
+        stderr: &'static str,
+        calls: std::sync::Arc<std::sync::atomic::AtomicU32>,
+    }
+
+    #[cfg(unix)]
+    impl CommandRunner for FlakyRunner {
+        fn output(&self, _cmd: &mut Command) -> std::io::Result<Output> {
+            use std::os::unix::process::ExitStatusExt;
+            use std::sync::atomic::Ordering;
+
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failures {
+                return Ok(Output {
+                    status: std::process::ExitStatus::from_raw(255 << 8),
+                    stdout: Vec::new(),
+                    stderr: self.stderr.as_bytes().to_vec(),
+                });
+            }
+            Ok(Output {
+                status: std::process::ExitStatus::from_raw(0),
+                stdout: b"Number of regular files transferred: 3\nTotal transferred file size: 512 bytes\n"
+                    .to_vec(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    #[cfg(unix)]
+    fn flaky_engine(
+        dir: &Path,
+        failures: u32,
+        stderr: &'static str,
+    ) -> (SyncEngine, std::sync::Arc<std::sync::atomic::AtomicU32>) {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let engine = SyncEngine::new(dir)
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 3,
+                base_delay: Duration::ZERO,
+                max_delay: Duration::ZERO,
+            })
+            .with_command_runner(FlakyRunner {
+                failures,
+                stderr,
+                calls: calls.clone(),
+            });
+        (engine, calls)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rsync_retries_transient_failures() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (engine, calls) = flaky_engine(
+            tmp.path(),
+            2,
+            "Connection reset by peer\nrsync: connection unexpectedly closed",
+        );
+
+        let result = engine.sync_path_rsync("host", "/remote/sessions", tmp.path(), None, None);
+
+        assert!(result.success, "third attempt should succeed: {result:?}");
+        assert_eq!(result.attempts, 3);
+        assert_eq!(result.files_transferred, 3);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        let mut report = SyncReport::new("host", SyncMethod::Rsync);
+        report.add_path_result(result);
+        assert_eq!(report.total_retries(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rsync_does_not_retry_permanent_failures() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (engine, calls) =
+            flaky_engine(tmp.path(), 2, "user@host: Permission denied (publickey).");
+
+        let result = engine.sync_path_rsync("host", "/remote/sessions", tmp.path(), None, None);
+
+        assert!(!result.success);
+        assert_eq!(result.attempts, 1);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_is_transient_failure() {
+        assert!(is_transient_failure(Some(255), "Connection reset by peer"));
+        assert!(is_transient_failure(
+            Some(30),
+            "rsync error: timeout in data send/receive"
+        ));
+        assert!(is_transient_failure(
+            Some(255),
+            "ssh: Could not resolve hostname box: Temporary failure in name resolution"
+        ));
+        assert!(!is_transient_failure(
+            Some(255),
+            "Permission denied (publickey)."
+        ));
+        assert!(!is_transient_failure(
+            Some(255),
+            "Host key verification failed."
+        ));
+        assert!(!is_transient_failure(
+            Some(23),
+            "rsync: change_dir \"/nope\" failed: No such file or directory (2)"
+        ));
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_with_bounded_jitter() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+        };
+        let within = |d: Duration, base: u64| {
+            d >= Duration::from_millis(base) && d <= Duration::from_millis(base * 5 / 4)
+        };
+        assert!(within(policy.delay_for(1), 100));
+        assert!(within(policy.delay_for(2), 200));
+        assert!(within(policy.delay_for(3), 300));
+        assert!(within(policy.delay_for(10), 300));
+    }
+
     #[test]
     fn test_sync_error_display() {
         assert_eq!(
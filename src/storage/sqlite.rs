@@ -4,6 +4,7 @@ use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole,
 use crate::sources::provenance::{LOCAL_SOURCE_ID, Source, SourceKind};
 use anyhow::{Context, Result, anyhow};
 use rusqlite::{Connection, OptionalExtension, Transaction, params};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -23,6 +24,10 @@ pub enum MigrationError {
         backup_path: Option<std::path::PathBuf>,
     },
 
+    /// The database was written by a newer cass whose schema this binary cannot read.
+    #[error("database schema version {found} is newer than supported version {supported}")]
+    IncompatibleVersion { found: i64, supported: i64 },
+
     /// A database error occurred during migration.
     #[error("Database error: {0}")]
     Database(#[from] rusqlite::Error),
@@ -45,6 +50,12 @@ impl From<anyhow::Error> for MigrationError {
 /// Maximum number of backup files to retain.
 const MAX_BACKUPS: usize = 3;
 
+/// SQL condition on `conversations` matching transcripts added with
+/// `cass import`. No connector can rescan those, so full resets and index
+/// rebuilds must keep them.
+pub const IMPORTED_CONVERSATION_SQL: &str = "CASE WHEN json_valid(metadata_json) \
+     THEN json_extract(metadata_json, '$.source') END = 'import'";
+
 /// Files that contain user-authored state and must NEVER be deleted during rebuild.
 const USER_DATA_FILES: &[&str] = &["bookmarks.db", "tui_state.json", "sources.toml", ".env"];
 
@@ -56,6 +67,44 @@ pub fn is_user_data_file(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// Whether new files can be created in `dir`, or in its nearest existing
+/// ancestor when `dir` would still have to be created.
+pub fn dir_is_writable(dir: &Path) -> bool {
+    dir.ancestors()
+        .find(|p| p.exists())
+        .is_some_and(|existing| tempfile::tempfile_in(existing).is_ok())
+}
+
+/// Open the database at `path` for reading only.
+///
+/// Reading a WAL database normally creates a `-shm` file next to it. When the
+/// directory isn't writable that fails, so the database is opened as immutable
+/// instead (it must then not change while open, which holds on read-only media).
+pub fn open_readonly_connection(path: &Path) -> Result<Connection> {
+    use rusqlite::OpenFlags;
+    let flags = OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    if !path.exists() || dir_is_writable(dir) {
+        return Connection::open_with_flags(path, flags)
+            .with_context(|| format!("opening sqlite db readonly at {}", path.display()));
+    }
+    let mut uri = String::from("file:");
+    for c in path.to_string_lossy().chars() {
+        match c {
+            '%' => uri.push_str("%25"),
+            '?' => uri.push_str("%3f"),
+            '#' => uri.push_str("%23"),
+            c => uri.push(c),
+        }
+    }
+    uri.push_str("?immutable=1");
+    Connection::open_with_flags(&uri, flags | OpenFlags::SQLITE_OPEN_URI)
+        .with_context(|| format!("opening sqlite db immutable at {}", path.display()))
+}
+
 /// Create a timestamped backup of the database file.
 ///
 /// Returns the path to the backup file, or None if the source doesn't exist.
@@ -109,7 +158,7 @@ pub fn cleanup_old_backups(db_path: &Path, keep_count: usize) -> Result<(), std:
     }
 
     // Sort by modification time, newest first
-    backups.sort_by(|a, b| b.1.cmp(&a.1));
+    backups.sort_by_key(|b| std::cmp::Reverse(b.1));
 
     // Delete oldest backups beyond keep_count
     for (path, _) in backups.into_iter().skip(keep_count) {
@@ -120,7 +169,7 @@ pub fn cleanup_old_backups(db_path: &Path, keep_count: usize) -> Result<(), std:
 }
 
 /// Public schema version constant for external checks.
-pub const CURRENT_SCHEMA_VERSION: i64 = 5;
+pub const CURRENT_SCHEMA_VERSION: i64 = 6;
 
 /// Result of checking schema compatibility.
 #[derive(Debug, Clone)]
@@ -195,7 +244,7 @@ fn check_schema_compatibility(path: &Path) -> std::result::Result<SchemaCheck, r
     }
 }
 
-const SCHEMA_VERSION: i64 = 5;
+const SCHEMA_VERSION: i64 = 6;
 
 const MIGRATION_V1: &str = r"
 PRAGMA foreign_keys = ON;
@@ -392,17 +441,123 @@ CREATE INDEX IF NOT EXISTS idx_conversations_source_id ON conversations(source_i
 PRAGMA foreign_keys = ON;
 ";
 
+const MIGRATION_V6: &str = r"
+-- Content hash for incremental dedup when connectors renumber or reorder messages.
+-- Existing rows are backfilled in Rust by backfill_message_hashes().
+ALTER TABLE messages ADD COLUMN content_hash TEXT;
+CREATE INDEX IF NOT EXISTS idx_messages_conv_hash ON messages(conversation_id, content_hash);
+";
+
 pub struct SqliteStorage {
     conn: Connection,
 }
 
+/// Durability/throughput pragmas for a writable connection.
+///
+/// The defaults (WAL, `synchronous = NORMAL`, 64 MB page cache) are what every
+/// writer uses. The indexer reads overrides from `CASS_SQLITE_JOURNAL_MODE`,
+/// `CASS_SQLITE_SYNCHRONOUS` and `CASS_SQLITE_CACHE_MB` (see
+/// [`SqlitePragmas::from_env`]) so a rebuild can trade crash safety for speed;
+/// other connections keep the defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlitePragmas {
+    pub journal_mode: String,
+    pub synchronous: String,
+    pub cache_size_mb: u32,
+}
+
+const JOURNAL_MODES: &[&str] = &["WAL", "DELETE", "TRUNCATE", "PERSIST", "MEMORY", "OFF"];
+const SYNCHRONOUS_MODES: &[&str] = &["OFF", "NORMAL", "FULL", "EXTRA"];
+
+impl Default for SqlitePragmas {
+    fn default() -> Self {
+        Self {
+            journal_mode: "WAL".to_string(),
+            synchronous: "NORMAL".to_string(),
+            cache_size_mb: 64,
+        }
+    }
+}
+
+impl SqlitePragmas {
+    /// Defaults overridden by the `CASS_SQLITE_*` environment variables.
+    pub fn from_env() -> Self {
+        Self::from_vars(
+            dotenvy::var("CASS_SQLITE_JOURNAL_MODE").ok().as_deref(),
+            dotenvy::var("CASS_SQLITE_SYNCHRONOUS").ok().as_deref(),
+            dotenvy::var("CASS_SQLITE_CACHE_MB").ok().as_deref(),
+        )
+    }
+
+    /// Defaults overridden by the given values. Unrecognized values are
+    /// logged and ignored rather than failing the index run.
+    pub fn from_vars(
+        journal_mode: Option<&str>,
+        synchronous: Option<&str>,
+        cache_size_mb: Option<&str>,
+    ) -> Self {
+        let mut pragmas = Self::default();
+        if let Some(mode) = journal_mode {
+            match pragma_keyword(JOURNAL_MODES, mode) {
+                Some(m) => pragmas.journal_mode = m.to_string(),
+                None => tracing::warn!(value = mode, "ignoring invalid CASS_SQLITE_JOURNAL_MODE"),
+            }
+        }
+        if let Some(sync) = synchronous {
+            match pragma_keyword(SYNCHRONOUS_MODES, sync) {
+                Some(m) => pragmas.synchronous = m.to_string(),
+                None => tracing::warn!(value = sync, "ignoring invalid CASS_SQLITE_SYNCHRONOUS"),
+            }
+        }
+        if let Some(mb) = cache_size_mb {
+            match mb.trim().parse::<u32>() {
+                Ok(mb) if mb > 0 => pragmas.cache_size_mb = mb,
+                _ => tracing::warn!(value = mb, "ignoring invalid CASS_SQLITE_CACHE_MB"),
+            }
+        }
+        pragmas
+    }
+}
+
+/// Canonical spelling of `value` if it is one of `allowed` (case-insensitive).
+fn pragma_keyword(allowed: &[&'static str], value: &str) -> Option<&'static str> {
+    allowed
+        .iter()
+        .copied()
+        .find(|m| m.eq_ignore_ascii_case(value.trim()))
+}
+
 pub struct InsertOutcome {
     pub conversation_id: i64,
+    /// Indices the new or changed messages were stored under.
     pub inserted_indices: Vec<i64>,
+    /// The connector's index for each entry of `inserted_indices`. They differ
+    /// when a renumbered session puts a new message at an index already in use.
+    pub source_indices: Vec<i64>,
+    /// Entries of `inserted_indices` that rewrote an existing row whose content
+    /// changed (e.g. a growing final reply), so older search documents are stale.
+    pub replaced_indices: Vec<i64>,
+}
+
+impl InsertOutcome {
+    fn all_inserted(conversation_id: i64, conv: &Conversation) -> Self {
+        let indices: Vec<i64> = conv.messages.iter().map(|m| m.idx).collect();
+        Self {
+            conversation_id,
+            inserted_indices: indices.clone(),
+            source_indices: indices,
+            replaced_indices: Vec::new(),
+        }
+    }
 }
 
 impl SqliteStorage {
     pub fn open(path: &Path) -> Result<Self> {
+        Self::open_with_pragmas(path, &SqlitePragmas::default())
+    }
+
+    /// Like [`open`](Self::open), with explicit durability/throughput pragmas.
+    pub fn open_with_pragmas(path: &Path, pragmas: &SqlitePragmas) -> Result<Self> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("creating db directory {}", parent.display()))?;
@@ -411,7 +566,18 @@ impl SqliteStorage {
         let mut conn = Connection::open(path)
             .with_context(|| format!("opening sqlite db at {}", path.display()))?;
 
-        apply_pragmas(&mut conn)?;
+        apply_pragmas(&mut conn, pragmas)?;
+        init_meta(&mut conn)?;
+        migrate(&mut conn)?;
+
+        Ok(Self { conn })
+    }
+
+    /// A fresh, fully migrated database in memory (`:memory:`), discarded on drop.
+    pub fn open_in_memory() -> Result<Self> {
+        let mut conn = Connection::open_in_memory().context("opening in-memory sqlite db")?;
+
+        apply_common_pragmas(&conn, SqlitePragmas::default().cache_size_mb)?;
         init_meta(&mut conn)?;
         migrate(&mut conn)?;
 
@@ -419,13 +585,9 @@ impl SqliteStorage {
     }
 
     pub fn open_readonly(path: &Path) -> Result<Self> {
-        let conn = Connection::open_with_flags(
-            path,
-            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
-        )
-        .with_context(|| format!("opening sqlite db readonly at {}", path.display()))?;
+        let conn = open_readonly_connection(path)?;
 
-        apply_common_pragmas(&conn)?;
+        apply_common_pragmas(&conn, SqlitePragmas::default().cache_size_mb)?;
 
         Ok(Self { conn })
     }
@@ -486,7 +648,8 @@ impl SqliteStorage {
 
         // Now open and migrate normally
         let mut conn = Connection::open(path)?;
-        apply_pragmas(&mut conn).map_err(|e| MigrationError::Other(e.to_string()))?;
+        apply_pragmas(&mut conn, &SqlitePragmas::default())
+            .map_err(|e| MigrationError::Other(e.to_string()))?;
         init_meta(&mut conn).map_err(|e| MigrationError::Other(e.to_string()))?;
         migrate(&mut conn).map_err(|e| MigrationError::Other(e.to_string()))?;
 
@@ -578,10 +741,7 @@ impl SqliteStorage {
             insert_fts_message(&tx, msg_id, msg, conv)?;
         }
         tx.commit()?;
-        Ok(InsertOutcome {
-            conversation_id: conv_id,
-            inserted_indices: conv.messages.iter().map(|m| m.idx).collect(),
-        })
+        Ok(InsertOutcome::all_inserted(conv_id, conv))
     }
 
     fn append_messages(
@@ -590,39 +750,9 @@ impl SqliteStorage {
         conv: &Conversation,
     ) -> Result<InsertOutcome> {
         let tx = self.conn.transaction()?;
-
-        let max_idx: Option<i64> = tx.query_row(
-            "SELECT MAX(idx) FROM messages WHERE conversation_id = ?",
-            params![conversation_id],
-            |row| row.get::<_, Option<i64>>(0),
-        )?;
-        let cutoff = max_idx.unwrap_or(-1);
-
-        let mut inserted_indices = Vec::new();
-        for msg in &conv.messages {
-            if msg.idx <= cutoff {
-                continue;
-            }
-            let msg_id = insert_message(&tx, conversation_id, msg)?;
-            insert_snippets(&tx, msg_id, &msg.snippets)?;
-            insert_fts_message(&tx, msg_id, msg, conv)?;
-            inserted_indices.push(msg.idx);
-        }
-
-        if let Some(last_ts) = conv.messages.iter().filter_map(|m| m.created_at).max() {
-            // Use IFNULL to handle NULL ended_at values correctly.
-            // SQLite's scalar MAX(NULL, x) returns NULL, so we need to coalesce first.
-            tx.execute(
-                "UPDATE conversations SET ended_at = MAX(IFNULL(ended_at, 0), ?) WHERE id = ?",
-                params![last_ts, conversation_id],
-            )?;
-        }
-
+        let outcome = append_new_messages(&tx, conversation_id, conv)?;
         tx.commit()?;
-        Ok(InsertOutcome {
-            conversation_id,
-            inserted_indices,
-        })
+        Ok(outcome)
     }
 
     /// Insert multiple conversations in a single transaction for better performance.
@@ -672,11 +802,20 @@ impl SqliteStorage {
         Ok(out)
     }
 
-    pub fn list_workspaces(&self) -> Result<Vec<crate::model::types::Workspace>> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT id, path, display_name FROM workspaces ORDER BY path")?;
-        let rows = stmt.query_map([], |row| {
+    /// Workspaces whose path starts with `prefix` (empty matches all), sorted by
+    /// path and capped at `limit`.
+    pub fn list_workspaces(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::model::types::Workspace>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, path, display_name FROM workspaces
+             WHERE substr(path, 1, length(?1)) = ?1
+             ORDER BY path LIMIT ?2",
+        )?;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let rows = stmt.query_map(params![prefix, limit], |row| {
             Ok(crate::model::types::Workspace {
                 id: Some(row.get(0)?),
                 path: Path::new(&row.get::<_, String>(1)?).to_path_buf(),
@@ -702,30 +841,52 @@ impl SqliteStorage {
                 LIMIT ? OFFSET ?",
         )?;
 
-        let rows = stmt.query_map(params![limit, offset], |row| {
-            Ok(Conversation {
-                id: Some(row.get(0)?),
-                agent_slug: row.get(1)?,
-                workspace: row
-                    .get::<_, Option<String>>(2)?
-                    .map(|p| Path::new(&p).to_path_buf()),
-                external_id: row.get(3)?,
-                title: row.get(4)?,
-                source_path: Path::new(&row.get::<_, String>(5)?).to_path_buf(),
-                started_at: row.get(6)?,
-                ended_at: row.get(7)?,
-                approx_tokens: row.get(8)?,
-                metadata_json: row
-                    .get::<_, Option<String>>(9)?
-                    .and_then(|s| serde_json::from_str(&s).ok())
-                    .unwrap_or_default(),
-                messages: Vec::new(),
-                source_id: row
-                    .get::<_, String>(10)
-                    .unwrap_or_else(|_| "local".to_string()),
-                origin_host: row.get(11)?,
-            })
-        })?;
+        let rows = stmt.query_map(params![limit, offset], conversation_from_row)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// The `limit` most recently modified conversations across all agents,
+    /// newest first. A conversation's modification time is its `ended_at`,
+    /// falling back to `started_at`.
+    pub fn recent_conversations(&self, limit: i64) -> Result<Vec<Conversation>> {
+        let mut stmt = self.conn.prepare(
+            r"SELECT c.id, a.slug, w.path, c.external_id, c.title, c.source_path,
+                       c.started_at, c.ended_at, c.approx_tokens, c.metadata_json,
+                       c.source_id, c.origin_host
+                FROM conversations c
+                JOIN agents a ON c.agent_id = a.id
+                LEFT JOIN workspaces w ON c.workspace_id = w.id
+                ORDER BY COALESCE(c.ended_at, c.started_at) IS NULL,
+                         COALESCE(c.ended_at, c.started_at) DESC, c.id DESC
+                LIMIT ?",
+        )?;
+
+        let rows = stmt.query_map(params![limit], conversation_from_row)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Conversations added with `cass import`, oldest first.
+    pub fn imported_conversations(&self) -> Result<Vec<Conversation>> {
+        let mut stmt = self.conn.prepare(&format!(
+            r"SELECT c.id, a.slug, w.path, c.external_id, c.title, c.source_path,
+                       c.started_at, c.ended_at, c.approx_tokens, c.metadata_json,
+                       c.source_id, c.origin_host
+                FROM conversations c
+                JOIN agents a ON c.agent_id = a.id
+                LEFT JOIN workspaces w ON c.workspace_id = w.id
+                WHERE {IMPORTED_CONVERSATION_SQL}
+                ORDER BY c.id"
+        ))?;
+
+        let rows = stmt.query_map([], conversation_from_row)?;
         let mut out = Vec::new();
         for r in rows {
             out.push(r?);
@@ -733,6 +894,88 @@ impl SqliteStorage {
         Ok(out)
     }
 
+    /// Conversations whose source path or external id equals `target`.
+    pub fn find_conversations(&self, target: &str) -> Result<Vec<Conversation>> {
+        let mut stmt = self.conn.prepare(
+            r"SELECT c.id, a.slug, w.path, c.external_id, c.title, c.source_path,
+                       c.started_at, c.ended_at, c.approx_tokens, c.metadata_json,
+                       c.source_id, c.origin_host
+                FROM conversations c
+                JOIN agents a ON c.agent_id = a.id
+                LEFT JOIN workspaces w ON c.workspace_id = w.id
+                WHERE c.source_path = ?1 OR c.external_id = ?1
+                ORDER BY c.id",
+        )?;
+
+        let rows = stmt.query_map(params![target], conversation_from_row)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// The conversation indexed from `source_path` (the most recently started
+    /// one if several share the path), with its messages in order.
+    pub fn get_conversation_by_source_path(
+        &self,
+        source_path: &str,
+    ) -> Result<Option<Conversation>> {
+        let mut stmt = self.conn.prepare(
+            r"SELECT c.id, a.slug, w.path, c.external_id, c.title, c.source_path,
+                       c.started_at, c.ended_at, c.approx_tokens, c.metadata_json,
+                       c.source_id, c.origin_host
+                FROM conversations c
+                JOIN agents a ON c.agent_id = a.id
+                LEFT JOIN workspaces w ON c.workspace_id = w.id
+                WHERE c.source_path = ?1
+                ORDER BY c.started_at DESC LIMIT 1",
+        )?;
+        let Some(mut convo) = stmt
+            .query_row(params![source_path], conversation_from_row)
+            .optional()?
+        else {
+            return Ok(None);
+        };
+        if let Some(id) = convo.id {
+            convo.messages = self.fetch_messages(id)?;
+        }
+        Ok(Some(convo))
+    }
+
+    /// Delete a conversation together with its messages, snippets, tags and
+    /// `fts_messages` rows in one transaction. Returns the deleted message ids.
+    pub fn delete_conversation(&mut self, conversation_id: i64) -> Result<Vec<i64>> {
+        let tx = self.conn.transaction()?;
+        let message_ids = {
+            let mut stmt = tx.prepare("SELECT id FROM messages WHERE conversation_id = ?")?;
+            stmt.query_map(params![conversation_id], |row| row.get::<_, i64>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?
+        };
+        tx.execute(
+            "DELETE FROM fts_messages WHERE message_id IN (SELECT id FROM messages WHERE conversation_id = ?)",
+            params![conversation_id],
+        )?;
+        tx.execute(
+            "DELETE FROM snippets WHERE message_id IN (SELECT id FROM messages WHERE conversation_id = ?)",
+            params![conversation_id],
+        )?;
+        tx.execute(
+            "DELETE FROM messages WHERE conversation_id = ?",
+            params![conversation_id],
+        )?;
+        tx.execute(
+            "DELETE FROM conversation_tags WHERE conversation_id = ?",
+            params![conversation_id],
+        )?;
+        tx.execute(
+            "DELETE FROM conversations WHERE id = ?",
+            params![conversation_id],
+        )?;
+        tx.commit()?;
+        Ok(message_ids)
+    }
+
     pub fn fetch_messages(&self, conversation_id: i64) -> Result<Vec<Message>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, idx, role, author, created_at, content, extra_json FROM messages WHERE conversation_id = ? ORDER BY idx",
@@ -806,6 +1049,36 @@ impl SqliteStorage {
         Ok(())
     }
 
+    /// Counter bumped after every committed change to the search index, so
+    /// clients can cheaply tell that cached results are stale. 0 before the
+    /// first index run.
+    pub fn get_index_generation(&self) -> Result<u64> {
+        let generation: Option<u64> = self
+            .conn
+            .query_row(
+                "SELECT value FROM meta WHERE key = 'index_generation'",
+                [],
+                |row| {
+                    let s: String = row.get(0)?;
+                    Ok(s.parse().ok())
+                },
+            )
+            .optional()?
+            .flatten();
+        Ok(generation.unwrap_or(0))
+    }
+
+    /// Advance the index generation by one and return the new value. Never
+    /// reset, so it only grows (including across `index --full`).
+    pub fn bump_index_generation(&mut self) -> Result<u64> {
+        self.conn.execute(
+            "INSERT INTO meta(key, value) VALUES('index_generation', '1')
+             ON CONFLICT(key) DO UPDATE SET value = CAST(CAST(value AS INTEGER) + 1 AS TEXT)",
+            [],
+        )?;
+        self.get_index_generation()
+    }
+
     /// Get current time as milliseconds since epoch.
     pub fn now_millis() -> i64 {
         SystemTime::now()
@@ -939,25 +1212,53 @@ impl SqliteStorage {
     }
 }
 
-fn apply_pragmas(conn: &mut Connection) -> Result<()> {
-    conn.execute_batch(
-        r"
-        PRAGMA journal_mode = WAL;
-        PRAGMA synchronous = NORMAL;
-        ",
-    )?;
-    apply_common_pragmas(conn)
+fn conversation_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Conversation> {
+    Ok(Conversation {
+        id: Some(row.get(0)?),
+        agent_slug: row.get(1)?,
+        workspace: row
+            .get::<_, Option<String>>(2)?
+            .map(|p| Path::new(&p).to_path_buf()),
+        external_id: row.get(3)?,
+        title: row.get(4)?,
+        source_path: Path::new(&row.get::<_, String>(5)?).to_path_buf(),
+        started_at: row.get(6)?,
+        ended_at: row.get(7)?,
+        approx_tokens: row.get(8)?,
+        metadata_json: row
+            .get::<_, Option<String>>(9)?
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+        messages: Vec::new(),
+        source_id: row
+            .get::<_, String>(10)
+            .unwrap_or_else(|_| "local".to_string()),
+        origin_host: row.get(11)?,
+    })
 }
 
-fn apply_common_pragmas(conn: &Connection) -> Result<()> {
+fn apply_pragmas(conn: &mut Connection, pragmas: &SqlitePragmas) -> Result<()> {
+    // Values are validated against JOURNAL_MODES/SYNCHRONOUS_MODES in `from_vars`.
+    conn.execute_batch(&format!(
+        "PRAGMA journal_mode = {};\nPRAGMA synchronous = {};",
+        pragmas.journal_mode, pragmas.synchronous
+    ))?;
+    apply_common_pragmas(conn, pragmas.cache_size_mb)
+}
+
+fn apply_common_pragmas(conn: &Connection, cache_size_mb: u32) -> Result<()> {
     conn.execute_batch(
         r"
         PRAGMA temp_store = MEMORY;
-        PRAGMA cache_size = -65536; -- 64MB
         PRAGMA mmap_size = 268435456; -- 256MB
         PRAGMA foreign_keys = ON;
         ",
     )?;
+    // Negative cache_size is in KiB rather than pages.
+    conn.execute_batch(&format!(
+        "PRAGMA cache_size = -{};",
+        u64::from(cache_size_mb) * 1024
+    ))?;
     Ok(())
 }
 
@@ -999,6 +1300,14 @@ fn migrate(conn: &mut Connection) -> Result<()> {
     if current == SCHEMA_VERSION {
         return Ok(());
     }
+    if current > SCHEMA_VERSION {
+        // Never touch a newer database: migrating "down" would corrupt it.
+        return Err(MigrationError::IncompatibleVersion {
+            found: current,
+            supported: SCHEMA_VERSION,
+        }
+        .into());
+    }
 
     let tx = conn.transaction()?;
 
@@ -1009,28 +1318,38 @@ fn migrate(conn: &mut Connection) -> Result<()> {
             tx.execute_batch(MIGRATION_V3)?;
             tx.execute_batch(MIGRATION_V4)?;
             tx.execute_batch(MIGRATION_V5)?;
+            tx.execute_batch(MIGRATION_V6)?;
         }
         1 => {
             tx.execute_batch(MIGRATION_V2)?;
             tx.execute_batch(MIGRATION_V3)?;
             tx.execute_batch(MIGRATION_V4)?;
             tx.execute_batch(MIGRATION_V5)?;
+            tx.execute_batch(MIGRATION_V6)?;
         }
         2 => {
             tx.execute_batch(MIGRATION_V3)?;
             tx.execute_batch(MIGRATION_V4)?;
             tx.execute_batch(MIGRATION_V5)?;
+            tx.execute_batch(MIGRATION_V6)?;
         }
         3 => {
             tx.execute_batch(MIGRATION_V4)?;
             tx.execute_batch(MIGRATION_V5)?;
+            tx.execute_batch(MIGRATION_V6)?;
         }
         4 => {
             tx.execute_batch(MIGRATION_V5)?;
+            tx.execute_batch(MIGRATION_V6)?;
+        }
+        5 => {
+            tx.execute_batch(MIGRATION_V6)?;
         }
         v => return Err(anyhow!("unsupported schema version {v}")),
     }
 
+    backfill_message_hashes(&tx)?;
+
     tx.execute(
         "UPDATE meta SET value = ? WHERE key = 'schema_version'",
         params![SCHEMA_VERSION.to_string()],
@@ -1040,6 +1359,194 @@ fn migrate(conn: &mut Connection) -> Result<()> {
     Ok(())
 }
 
+/// Stable FNV-1a hash of a message's role, timestamp and content.
+///
+/// Used to recognise messages that a connector re-emits under a different index.
+fn message_content_hash(role: &str, created_at: Option<i64>, content: &str) -> String {
+    const FNV_OFFSET: u64 = 14695981039346656037;
+    const FNV_PRIME: u64 = 1099511628211;
+    let ts = created_at.map(|t| t.to_string()).unwrap_or_default();
+    let mut hash = FNV_OFFSET;
+    for part in [role, ts.as_str(), content] {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+/// Rows hashed per batch by [`backfill_message_hashes`].
+const HASH_BACKFILL_BATCH: i64 = 5_000;
+
+/// Fill `content_hash` for rows written before schema v6.
+///
+/// Works through the table in id order, a bounded batch at a time, so large
+/// histories are never held in memory at once.
+fn backfill_message_hashes(tx: &Transaction<'_>) -> Result<()> {
+    let mut select = tx.prepare(
+        "SELECT id, role, created_at, content FROM messages
+         WHERE content_hash IS NULL AND id > ? ORDER BY id LIMIT ?",
+    )?;
+    let mut update = tx.prepare("UPDATE messages SET content_hash = ? WHERE id = ?")?;
+    let mut last_id = i64::MIN;
+    loop {
+        let batch: Vec<(i64, String, Option<i64>, String)> = select
+            .query_map(params![last_id, HASH_BACKFILL_BATCH], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        let Some(&(id, ..)) = batch.last() else {
+            return Ok(());
+        };
+        last_id = id;
+        for (id, role, created_at, content) in batch {
+            update.execute(params![
+                message_content_hash(&role, created_at, &content),
+                id
+            ])?;
+        }
+    }
+}
+
+/// Append only messages whose content isn't already stored for `conversation_id`.
+///
+/// Content hashes are matched first (as a multiset, so genuinely repeated
+/// messages are kept), which tolerates connectors that renumber or reorder on
+/// rescan. A new message whose index holds content that no longer appears in the
+/// conversation (an edited or still-growing message) rewrites that row in place;
+/// if the index holds content that merely moved, the message is stored after the
+/// current maximum index instead.
+fn append_new_messages(
+    tx: &Transaction<'_>,
+    conversation_id: i64,
+    conv: &Conversation,
+) -> Result<InsertOutcome> {
+    let mut by_idx: HashMap<i64, (i64, Option<String>)> = HashMap::new();
+    let mut unmatched: HashMap<String, usize> = HashMap::new();
+    {
+        let mut stmt =
+            tx.prepare("SELECT idx, id, content_hash FROM messages WHERE conversation_id = ?")?;
+        let rows = stmt.query_map(params![conversation_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (idx, id, hash) = row?;
+            if let Some(hash) = &hash {
+                *unmatched.entry(hash.clone()).or_default() += 1;
+            }
+            by_idx.insert(idx, (id, hash));
+        }
+    }
+    let mut next_idx = by_idx.keys().max().map_or(0, |m| m + 1);
+
+    // Drop messages already stored (under any index); whatever stays in
+    // `unmatched` is stored content the connector no longer reports.
+    let mut fresh = Vec::new();
+    for msg in &conv.messages {
+        let hash = message_content_hash(&role_str(&msg.role), msg.created_at, &msg.content);
+        if let Some(count) = unmatched.get_mut(&hash)
+            && *count > 0
+        {
+            *count -= 1;
+            continue;
+        }
+        fresh.push(msg);
+    }
+
+    let mut outcome = InsertOutcome {
+        conversation_id,
+        inserted_indices: Vec::new(),
+        source_indices: Vec::new(),
+        replaced_indices: Vec::new(),
+    };
+    let mut written: HashSet<i64> = HashSet::new();
+    for msg in fresh {
+        let stale_row = by_idx
+            .get(&msg.idx)
+            .filter(|_| !written.contains(&msg.idx))
+            .and_then(|(id, hash)| {
+                let stale = hash
+                    .as_ref()
+                    .is_none_or(|hash| match unmatched.get_mut(hash) {
+                        Some(count) if *count > 0 => {
+                            *count -= 1;
+                            true
+                        }
+                        _ => false,
+                    });
+                stale.then_some(*id)
+            });
+        let taken = by_idx.contains_key(&msg.idx) || written.contains(&msg.idx);
+        let idx = if taken && stale_row.is_none() {
+            // The row's content moved to another index; keep it and append.
+            next_idx
+        } else {
+            msg.idx
+        };
+
+        let msg_id = match stale_row {
+            Some(msg_id) => {
+                replace_message(tx, msg_id, msg)?;
+                outcome.replaced_indices.push(idx);
+                msg_id
+            }
+            None => insert_message_at(tx, conversation_id, msg, idx)?,
+        };
+        insert_snippets(tx, msg_id, &msg.snippets)?;
+        insert_fts_message(tx, msg_id, msg, conv)?;
+        written.insert(idx);
+        next_idx = next_idx.max(idx + 1);
+        outcome.inserted_indices.push(idx);
+        outcome.source_indices.push(msg.idx);
+    }
+
+    if let Some(last_ts) = conv.messages.iter().filter_map(|m| m.created_at).max() {
+        // Use IFNULL to handle NULL ended_at values correctly.
+        // SQLite's scalar MAX(NULL, x) returns NULL, so we need to coalesce first.
+        tx.execute(
+            "UPDATE conversations SET ended_at = MAX(IFNULL(ended_at, 0), ?) WHERE id = ?",
+            params![last_ts, conversation_id],
+        )?;
+    }
+
+    Ok(outcome)
+}
+
+/// Overwrite message `message_id` with `msg`, clearing its snippets and FTS row
+/// so the caller can re-insert them.
+fn replace_message(tx: &Transaction<'_>, message_id: i64, msg: &Message) -> Result<()> {
+    let role = role_str(&msg.role);
+    let content_hash = message_content_hash(&role, msg.created_at, &msg.content);
+    tx.execute(
+        "UPDATE messages
+         SET role = ?, author = ?, created_at = ?, content = ?, extra_json = ?, content_hash = ?
+         WHERE id = ?",
+        params![
+            role,
+            msg.author,
+            msg.created_at,
+            msg.content,
+            serde_json::to_string(&msg.extra_json)?,
+            content_hash,
+            message_id
+        ],
+    )?;
+    tx.execute(
+        "DELETE FROM snippets WHERE message_id = ?",
+        params![message_id],
+    )?;
+    tx.execute(
+        "DELETE FROM fts_messages WHERE message_id = ?",
+        params![message_id],
+    )?;
+    Ok(())
+}
+
 fn insert_conversation(
     tx: &Transaction<'_>,
     agent_id: i64,
@@ -1069,17 +1576,29 @@ fn insert_conversation(
 }
 
 fn insert_message(tx: &Transaction<'_>, conversation_id: i64, msg: &Message) -> Result<i64> {
+    insert_message_at(tx, conversation_id, msg, msg.idx)
+}
+
+fn insert_message_at(
+    tx: &Transaction<'_>,
+    conversation_id: i64,
+    msg: &Message,
+    idx: i64,
+) -> Result<i64> {
+    let role = role_str(&msg.role);
+    let content_hash = message_content_hash(&role, msg.created_at, &msg.content);
     tx.execute(
-        "INSERT INTO messages(conversation_id, idx, role, author, created_at, content, extra_json)
-         VALUES(?,?,?,?,?,?,?)",
+        "INSERT INTO messages(conversation_id, idx, role, author, created_at, content, extra_json, content_hash)
+         VALUES(?,?,?,?,?,?,?,?)",
         params![
             conversation_id,
-            msg.idx,
-            role_str(&msg.role),
+            idx,
+            role,
             msg.author,
             msg.created_at,
             msg.content,
-            serde_json::to_string(&msg.extra_json)?
+            serde_json::to_string(&msg.extra_json)?,
+            content_hash
         ],
     )?;
     Ok(tx.last_insert_rowid())
@@ -1157,35 +1676,7 @@ fn insert_conversation_in_tx(
 
         if let Some(conversation_id) = existing {
             // Append messages to existing conversation
-            let max_idx: Option<i64> = tx.query_row(
-                "SELECT MAX(idx) FROM messages WHERE conversation_id = ?",
-                params![conversation_id],
-                |row| row.get::<_, Option<i64>>(0),
-            )?;
-            let cutoff = max_idx.unwrap_or(-1);
-
-            let mut inserted_indices = Vec::new();
-            for msg in &conv.messages {
-                if msg.idx <= cutoff {
-                    continue;
-                }
-                let msg_id = insert_message(tx, conversation_id, msg)?;
-                insert_snippets(tx, msg_id, &msg.snippets)?;
-                insert_fts_message(tx, msg_id, msg, conv)?;
-                inserted_indices.push(msg.idx);
-            }
-
-            if let Some(last_ts) = conv.messages.iter().filter_map(|m| m.created_at).max() {
-                tx.execute(
-                    "UPDATE conversations SET ended_at = MAX(IFNULL(ended_at, 0), ?) WHERE id = ?",
-                    params![last_ts, conversation_id],
-                )?;
-            }
-
-            return Ok(InsertOutcome {
-                conversation_id,
-                inserted_indices,
-            });
+            return append_new_messages(tx, conversation_id, conv);
         }
     }
 
@@ -1197,10 +1688,7 @@ fn insert_conversation_in_tx(
         insert_fts_message(tx, msg_id, msg, conv)?;
     }
 
-    Ok(InsertOutcome {
-        conversation_id: conv_id,
-        inserted_indices: conv.messages.iter().map(|m| m.idx).collect(),
-    })
+    Ok(InsertOutcome::all_inserted(conv_id, conv))
 }
 
 fn path_to_string<P: AsRef<Path>>(p: P) -> String {
@@ -1352,6 +1840,49 @@ mod tests {
         drop(storage);
     }
 
+    #[test]
+    fn open_in_memory_is_fully_migrated() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+        assert!(storage.list_conversations(10, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn open_with_pragmas_applies_journal_mode_and_sync() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("pragmas.db");
+        let pragmas = SqlitePragmas::from_vars(Some("delete"), Some("off"), Some("16"));
+        let storage = SqliteStorage::open_with_pragmas(&db_path, &pragmas).unwrap();
+        let conn = storage.raw();
+        let mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(mode, "delete");
+        let sync: i64 = conn
+            .query_row("PRAGMA synchronous", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(sync, 0);
+        let cache: i64 = conn
+            .query_row("PRAGMA cache_size", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(cache, -16 * 1024);
+        drop(storage);
+
+        // The default open switches the file back to WAL.
+        let storage = SqliteStorage::open(&db_path).unwrap();
+        let mode: String = storage
+            .raw()
+            .query_row("PRAGMA journal_mode", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(mode, "wal");
+    }
+
+    #[test]
+    fn sqlite_pragmas_ignore_invalid_values() {
+        let pragmas = SqlitePragmas::from_vars(Some("bogus; DROP"), Some("fast"), Some("0"));
+        assert_eq!(pragmas, SqlitePragmas::default());
+    }
+
     #[test]
     fn open_readonly_fails_for_nonexistent() {
         let dir = TempDir::new().unwrap();
@@ -1485,7 +2016,7 @@ mod tests {
             .ensure_workspace(Path::new("/test/workspace"), Some("Test WS"))
             .unwrap();
 
-        let workspaces = storage.list_workspaces().unwrap();
+        let workspaces = storage.list_workspaces("", usize::MAX).unwrap();
         assert!(
             workspaces
                 .iter()
@@ -1493,6 +2024,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn list_workspaces_filters_by_prefix_and_limit() {
+        let dir = TempDir::new().unwrap();
+        let storage = SqliteStorage::open(&dir.path().join("test.db")).unwrap();
+        for path in ["/home/a/api", "/home/a/web", "/srv/b", "/home/a%/odd"] {
+            storage.ensure_workspace(Path::new(path), None).unwrap();
+        }
+
+        let paths = |prefix: &str, limit: usize| -> Vec<String> {
+            storage
+                .list_workspaces(prefix, limit)
+                .unwrap()
+                .into_iter()
+                .map(|w| w.path.to_string_lossy().into_owned())
+                .collect()
+        };
+        assert_eq!(paths("/home/a/", 10), vec!["/home/a/api", "/home/a/web"]);
+        assert_eq!(paths("/home/a%", 10), vec!["/home/a%/odd"]);
+        assert_eq!(paths("", 2), vec!["/home/a%/odd", "/home/a/api"]);
+    }
+
     // =========================================================================
     // Source storage tests (bead yln.4)
     // =========================================================================
@@ -1651,6 +2203,18 @@ mod tests {
         assert_eq!(actual_ts, Some(expected_ts));
     }
 
+    #[test]
+    fn index_generation_starts_at_zero_and_increments() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("test.db");
+        let mut storage = SqliteStorage::open(&db_path).unwrap();
+
+        assert_eq!(storage.get_index_generation().unwrap(), 0);
+        assert_eq!(storage.bump_index_generation().unwrap(), 1);
+        assert_eq!(storage.bump_index_generation().unwrap(), 2);
+        assert_eq!(storage.get_index_generation().unwrap(), 2);
+    }
+
     // =========================================================================
     // now_millis utility test (bead yln.4)
     // =========================================================================
@@ -26,6 +26,7 @@ fn ranking_label(r: tui::RankingMode) -> &'static str {
         tui::RankingMode::MatchQualityHeavy => "Quality",
         tui::RankingMode::DateNewest => "Newest",
         tui::RankingMode::DateOldest => "Oldest",
+        tui::RankingMode::Blend { .. } => "Blend",
     }
 }
 
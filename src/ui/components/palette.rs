@@ -22,12 +22,18 @@ pub enum PaletteAction {
     FilterWorkspace,
     FilterToday,
     FilterWeek,
+    FilterThisMonth,
+    FilterLastMonth,
+    FilterThisYear,
     FilterCustomDate,
     OpenSavedViews,
     SaveViewSlot(u8),
     LoadViewSlot(u8),
     OpenBulkActions,
     ReloadIndex,
+    RecentSessions,
+    ExportHtml,
+    ExportHtmlEncrypted,
 }
 
 /// Render-ready descriptor for an action.
@@ -198,6 +204,21 @@ pub fn default_actions() -> Vec<PaletteItem> {
             "Filter: last 7 days",
             "Restrict to week",
         ),
+        item(
+            PaletteAction::FilterThisMonth,
+            "Filter: this month",
+            "Since the 1st of this month",
+        ),
+        item(
+            PaletteAction::FilterLastMonth,
+            "Filter: last month",
+            "Previous calendar month",
+        ),
+        item(
+            PaletteAction::FilterThisYear,
+            "Filter: this year",
+            "Since January 1st",
+        ),
         item(
             PaletteAction::FilterCustomDate,
             "Filter: date range",
@@ -213,11 +234,26 @@ pub fn default_actions() -> Vec<PaletteItem> {
             "Reload index/view",
             "Refresh reader",
         ),
+        item(
+            PaletteAction::RecentSessions,
+            "Recent sessions",
+            "Most recently modified conversations",
+        ),
         item(
             PaletteAction::OpenSavedViews,
             "Saved views",
             "List saved slots",
         ),
+        item(
+            PaletteAction::ExportHtml,
+            "Export conversation to HTML",
+            "Save to Downloads",
+        ),
+        item(
+            PaletteAction::ExportHtmlEncrypted,
+            "Export conversation (encrypted)",
+            "Passphrase-protected HTML",
+        ),
     ];
     // Slots 1-9
     for slot in 1..=9 {
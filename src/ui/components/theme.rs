@@ -433,6 +433,7 @@ impl ThemePalette {
             "chatgpt" => (colors::AGENT_CHATGPT_BG, Color::Rgb(16, 163, 127)), // ChatGPT green
             "opencode" => (colors::AGENT_OPENCODE_BG, colors::ROLE_USER), // Neutral/sage
             "pi_agent" => (colors::AGENT_CODEX_BG, Color::Rgb(255, 140, 0)), // Orange for pi
+            "copilot" => (colors::AGENT_GEMINI_BG, Color::Rgb(137, 87, 229)), // Copilot violet
             _ => (colors::BG_DEEP, colors::ACCENT_PRIMARY),
         };
 
@@ -457,6 +458,7 @@ impl ThemePalette {
             "chatgpt" => "💬",
             "opencode" => "📦",
             "pi_agent" => "🥧",
+            "copilot" => "🐙",
             _ => "✨",
         }
     }
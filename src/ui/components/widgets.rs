@@ -73,6 +73,13 @@ pub fn search_bar(
                 .add_modifier(Modifier::BOLD),
             palette.border_focus_style(),
         ),
+        InputMode::ExportPassphrase => (
+            " Export Passphrase ".to_string(),
+            Style::default()
+                .fg(palette.accent_alt)
+                .add_modifier(Modifier::BOLD),
+            palette.border_focus_style(),
+        ),
     };
     let title = Span::styled(title_text, title_style);
 
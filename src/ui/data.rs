@@ -2,6 +2,7 @@ use crate::model::types::{Conversation, Message, MessageRole, Workspace};
 use crate::storage::sqlite::SqliteStorage;
 use crate::ui::components::theme::ThemePalette;
 use anyhow::Result;
+use rusqlite::OptionalExtension;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum InputMode {
@@ -13,6 +14,8 @@ pub enum InputMode {
     PaneFilter,
     /// Inline find within the detail pane (local, non-indexed)
     DetailFind,
+    /// Passphrase for an encrypted HTML export of the open conversation
+    ExportPassphrase,
 }
 
 #[derive(Clone, Debug)]
@@ -26,53 +29,32 @@ pub fn load_conversation(
     storage: &SqliteStorage,
     source_path: &str,
 ) -> Result<Option<ConversationView>> {
-    let mut stmt = storage.raw().prepare(
-        "SELECT c.id, a.slug, w.id, w.path, w.display_name, c.external_id, c.title, c.source_path,
-                c.started_at, c.ended_at, c.approx_tokens, c.metadata_json, c.source_id, c.origin_host
-         FROM conversations c
-         JOIN agents a ON c.agent_id = a.id
-         LEFT JOIN workspaces w ON c.workspace_id = w.id
-         WHERE c.source_path = ?1
-         ORDER BY c.started_at DESC LIMIT 1",
-    )?;
-    let mut rows = stmt.query([source_path])?;
-    if let Some(row) = rows.next()? {
-        let convo_id: i64 = row.get(0)?;
-        let convo = Conversation {
-            id: Some(convo_id),
-            agent_slug: row.get(1)?,
-            workspace: row
-                .get::<_, Option<String>>(3)?
-                .map(std::path::PathBuf::from),
-            external_id: row.get(5)?,
-            title: row.get(6)?,
-            source_path: std::path::PathBuf::from(row.get::<_, String>(7)?),
-            started_at: row.get(8)?,
-            ended_at: row.get(9)?,
-            approx_tokens: row.get(10)?,
-            metadata_json: row
-                .get::<_, Option<String>>(11)?
-                .and_then(|s| serde_json::from_str(&s).ok())
-                .unwrap_or_default(),
-            messages: Vec::new(),
-            source_id: row
-                .get::<_, String>(12)
-                .unwrap_or_else(|_| "local".to_string()),
-            origin_host: row.get(13)?,
-        };
-        let workspace = row.get::<_, Option<i64>>(2)?.map(|id| Workspace {
-            id: Some(id),
-            path: convo.workspace.clone().unwrap_or_default(),
-            display_name: row.get(4).ok().flatten(),
-        });
-        let messages = storage.fetch_messages(convo_id)?;
-        return Ok(Some(ConversationView {
-            convo,
-            messages,
-            workspace,
-        }));
-    }
-    Ok(None)
+    let Some(mut convo) = storage.get_conversation_by_source_path(source_path)? else {
+        return Ok(None);
+    };
+    let messages = std::mem::take(&mut convo.messages);
+    let workspace = match &convo.workspace {
+        Some(path) => storage
+            .raw()
+            .query_row(
+                "SELECT id, display_name FROM workspaces WHERE path = ?1",
+                [path.to_string_lossy()],
+                |row| {
+                    Ok(Workspace {
+                        id: Some(row.get(0)?),
+                        path: path.clone(),
+                        display_name: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?,
+        None => None,
+    };
+    Ok(Some(ConversationView {
+        convo,
+        messages,
+        workspace,
+    }))
 }
 
 pub fn role_style(role: &MessageRole, palette: ThemePalette) -> ratatui::style::Style {
@@ -4,6 +4,7 @@ use chrono::{Duration, Local, LocalResult, NaiveDate, TimeZone, Utc};
 ///
 /// Supported formats:
 /// - Relative: "-7d", "-24h", "-30m", "-1w"
+/// - Natural: "3 days ago", "2 weeks ago", "last week", "last hour"
 /// - Keywords: "now", "today", "yesterday"
 /// - ISO dates: "2024-11-25", "2024-11-25T14:30:00Z"
 /// - Date formats: "YYYY-MM-DD", "YYYY/MM/DD", "MM/DD/YYYY", "MM-DD-YYYY"
@@ -22,17 +23,24 @@ pub fn parse_time_input(input: &str) -> Option<i64> {
         let val_str: String = stripped.chars().take_while(|c| c.is_numeric()).collect();
         if let Ok(val) = val_str.parse::<i64>() {
             let unit = stripped.trim_start_matches(&val_str).trim();
-            let duration = match unit {
-                "d" | "day" | "days" => Duration::days(val),
-                "h" | "hr" | "hrs" | "hour" | "hours" => Duration::hours(val),
-                "m" | "min" | "mins" | "minute" | "minutes" => Duration::minutes(val),
-                "w" | "wk" | "wks" | "week" | "weeks" => Duration::weeks(val),
-                _ => return None,
-            };
+            let duration = relative_duration(val, unit)?;
             return Some((now_utc - duration).timestamp_millis());
         }
     }
 
+    // Natural relative: "3 days ago", "last week"
+    if let Some(amount) = input.strip_suffix(" ago") {
+        let mut parts = amount.split_whitespace();
+        if let (Some(val), Some(unit), None) = (parts.next(), parts.next(), parts.next())
+            && let Ok(val) = val.parse::<i64>()
+        {
+            return Some((now_utc - relative_duration(val, unit)?).timestamp_millis());
+        }
+    }
+    if let Some(unit) = input.strip_prefix("last ") {
+        return Some((now_utc - relative_duration(1, unit.trim())?).timestamp_millis());
+    }
+
     // Keywords
     match input.as_str() {
         "now" => return Some(now_ms),
@@ -77,6 +85,16 @@ pub fn parse_time_input(input: &str) -> Option<i64> {
     None
 }
 
+fn relative_duration(val: i64, unit: &str) -> Option<Duration> {
+    match unit {
+        "d" | "day" | "days" => Some(Duration::days(val)),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(Duration::hours(val)),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(Duration::minutes(val)),
+        "w" | "wk" | "wks" | "week" | "weeks" => Some(Duration::weeks(val)),
+        _ => None,
+    }
+}
+
 fn local_midnight_to_utc(date: NaiveDate) -> Option<i64> {
     let dt = date.and_hms_opt(0, 0, 0)?;
     let local = match Local.from_local_datetime(&dt) {
@@ -110,6 +128,24 @@ mod tests {
         assert!((diff - 86400 * 1000).abs() < tolerance);
     }
 
+    #[test]
+    fn test_natural_relative_time() {
+        let now = Utc::now().timestamp_millis();
+        let tolerance = 60 * 1000;
+
+        let t1 = parse_time_input("3 days ago").unwrap();
+        assert!((now - t1 - 3 * 86400 * 1000).abs() < tolerance);
+
+        let t2 = parse_time_input("2 Weeks Ago").unwrap();
+        assert!((now - t2 - 14 * 86400 * 1000).abs() < tolerance);
+
+        let t3 = parse_time_input("last week").unwrap();
+        assert!((now - t3 - 7 * 86400 * 1000).abs() < tolerance);
+
+        assert!(parse_time_input("several days ago").is_none());
+        assert!(parse_time_input("last fortnight").is_none());
+    }
+
     #[test]
     fn test_keywords() {
         assert!(parse_time_input("now").is_some());
@@ -17,7 +17,7 @@ use ratatui::widgets::{
     Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io;
 use std::path::Path;
 use std::process::Command as StdCommand;
@@ -83,6 +83,45 @@ fn split_editor_command(editor: &str) -> (String, Vec<String>) {
     }
 }
 
+/// Pick a pager: `CASS_PAGER`, then `PAGER`, then whichever of `less`/`more`
+/// is on PATH. Returns the program and its leading arguments.
+fn resolve_pager() -> Option<(String, Vec<String>)> {
+    for var in ["CASS_PAGER", "PAGER"] {
+        if let Ok(pager) = dotenvy::var(var)
+            && !pager.trim().is_empty()
+        {
+            return Some(split_editor_command(&pager));
+        }
+    }
+    ["less", "more"]
+        .into_iter()
+        .find(|candidate| {
+            StdCommand::new("which")
+                .arg(candidate)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        })
+        .map(|candidate| (candidate.to_string(), Vec::new()))
+}
+
+/// Arguments that position `pager_bin` at `line` in `path`. Only pagers known
+/// to accept `+<line>` get it; anything else just receives the path.
+fn pager_args(pager_bin: &str, path: &str, line: Option<usize>) -> Vec<String> {
+    let name = std::path::Path::new(pager_bin)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(pager_bin);
+    let mut args = Vec::new();
+    if let Some(ln) = line
+        && matches!(name, "less" | "more" | "most")
+    {
+        args.push(format!("+{ln}"));
+    }
+    args.push(path.to_string());
+    args
+}
+
 /// Format time filter range as readable chip text.
 fn format_time_chip(from: Option<i64>, to: Option<i64>) -> String {
     match (from, to) {
@@ -103,7 +142,7 @@ pub enum MatchMode {
     Prefix,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum RankingMode {
     RecentHeavy,
     Balanced,
@@ -111,6 +150,11 @@ pub enum RankingMode {
     MatchQualityHeavy, // Prioritizes exact matches over wildcard/fuzzy
     DateNewest,        // Pure newest-first (ignores relevance score)
     DateOldest,        // Pure oldest-first (ignores relevance score)
+    /// Continuous relevance/recency tradeoff (0.0 = pure relevance, 1.0 = pure recency);
+    /// see `search::query::blend_recency`
+    Blend {
+        recency_weight: f32,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -253,7 +297,7 @@ struct AgentPane {
 
 /// Returns style modifiers based on score magnitude.
 /// High scores (>8) get bold, medium scores (>5) normal, low scores dimmed.
-fn score_style(score: f32) -> Modifier {
+pub(crate) fn score_style(score: f32) -> Modifier {
     if score >= 8.0 {
         Modifier::BOLD
     } else if score >= 5.0 {
@@ -822,6 +866,55 @@ fn format_relative_time(timestamp_ms: i64) -> String {
     }
 }
 
+/// How many conversations the "Recent sessions" palette action lists.
+const RECENT_SESSIONS_LIMIT: i64 = 50;
+
+/// Synthetic hits for the most recently modified conversations, newest first.
+/// The snippet carries the relative modification time since there is no query
+/// text to highlight.
+fn recent_session_hits(db: &crate::storage::sqlite::SqliteStorage, limit: i64) -> Vec<SearchHit> {
+    db.recent_conversations(limit)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|conv| {
+            let modified = conv.ended_at.or(conv.started_at);
+            let origin_kind = if conv.source_id == "local" {
+                "local"
+            } else {
+                "ssh"
+            };
+            SearchHit {
+                title: conv.title.unwrap_or_default(),
+                snippet: modified
+                    .map(|ts| format!("modified {}", format_relative_time(ts)))
+                    .unwrap_or_default(),
+                content: String::new(),
+                score: 0.0,
+                source_path: conv.source_path.to_string_lossy().into_owned(),
+                agent: conv.agent_slug,
+                workspace: conv
+                    .workspace
+                    .map(|ws| ws.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                workspace_original: None,
+                created_at: modified,
+                line_number: None,
+                match_type: Default::default(),
+                source_id: conv.source_id,
+                origin_kind: origin_kind.to_string(),
+                origin_host: conv.origin_host,
+                explanation: None,
+                rerank_score: None,
+                duplicate_count: None,
+                message_matches: None,
+                relative_path: None,
+                message_id: None,
+                conversation_external_id: None,
+            }
+        })
+        .collect()
+}
+
 /// Formats a timestamp as an absolute string with date and time in UTC.
 fn format_absolute_time(timestamp_ms: i64) -> String {
     DateTime::<Utc>::from_timestamp_millis(timestamp_ms).map_or_else(
@@ -891,7 +984,7 @@ pub fn help_lines(palette: ThemePalette) -> Vec<Line<'static>> {
             "  tui_state.json - UI preferences | watch_state.json - Watch timestamps".to_string(),
             "  remotes/ - Synced session data from remote sources".to_string(),
             "Config: ~/.config/cass/sources.toml (remote sources)".to_string(),
-            "Agents: Claude, Codex, Gemini, Cline, OpenCode, Amp, Cursor, ChatGPT, Aider, Pi-Agent, Factory"
+            "Agents: Claude, Codex, Gemini, Cline, OpenCode, Amp, Cursor, ChatGPT, Aider, Pi-Agent, Factory, Copilot"
                 .to_string(),
         ],
     ));
@@ -1009,7 +1102,7 @@ pub fn help_lines(palette: ThemePalette) -> Vec<Line<'static>> {
         "Actions",
         &[
             format!(
-                "{} opens detail modal (o=open, c=copy, p=path, s=snip, n=nano, Esc=close)",
+                "{} opens detail modal (o=open, c=copy, M=markdown, T=roles, p=path, s=snip, n=nano, v=pager, e/E=export HTML, Esc=close)",
                 shortcuts::DETAIL_OPEN
             ),
             format!(
@@ -1134,8 +1227,23 @@ fn render_parsed_content(
     detail: &ConversationView,
     query: &str,
     palette: ThemePalette,
+    role_filter: RoleFilter,
 ) -> Vec<Line<'static>> {
+    render_parsed_content_focused(detail, query, palette, role_filter, None).0
+}
+
+/// [`render_parsed_content`] with the message whose `idx` is `focus` marked by
+/// an accent gutter. Also returns the line that message's header starts on, or
+/// `None` when it is absent or hidden by `role_filter`.
+fn render_parsed_content_focused(
+    detail: &ConversationView,
+    query: &str,
+    palette: ThemePalette,
+    role_filter: RoleFilter,
+    focus: Option<i64>,
+) -> (Vec<Line<'static>>, Option<usize>) {
     let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut focus_line = None;
 
     // Header with conversation info
     if let Some(title) = &detail.convo.title {
@@ -1184,13 +1292,18 @@ fn render_parsed_content(
     lines.push(Line::from(""));
 
     // Render messages with beautiful formatting
-    for msg in &detail.messages {
-        let (role_icon, role_label, role_color) = match &msg.role {
-            MessageRole::User => ("👤", "You", palette.user),
-            MessageRole::Agent => ("🤖", "Assistant", palette.agent),
-            MessageRole::Tool => ("🔧", "Tool", palette.tool),
-            MessageRole::System => ("⚙️", "System", palette.system),
-            MessageRole::Other(r) => ("📝", r.as_str(), palette.hint),
+    for msg in detail
+        .messages
+        .iter()
+        .filter(|m| role_filter.shows(&m.role))
+    {
+        let (role_icon, role_label) = role_icon_label(&msg.role);
+        let role_color = match &msg.role {
+            MessageRole::User => palette.user,
+            MessageRole::Agent => palette.agent,
+            MessageRole::Tool => palette.tool,
+            MessageRole::System => palette.system,
+            MessageRole::Other(_) => palette.hint,
         };
 
         // Role header with timestamp
@@ -1198,6 +1311,11 @@ fn render_parsed_content(
             .created_at
             .map(|t| format!(" · {}", format_absolute_time(t)))
             .unwrap_or_default();
+        let is_focus = focus == Some(msg.idx);
+        let start = lines.len();
+        if is_focus {
+            focus_line = Some(start);
+        }
         lines.push(Line::from(vec![
             Span::styled(format!("{role_icon} "), Style::default()),
             Span::styled(
@@ -1212,6 +1330,12 @@ fn render_parsed_content(
         let content = &msg.content;
         let parsed_lines = parse_message_content(content, query, palette);
         lines.extend(parsed_lines);
+        if is_focus {
+            for line in &mut lines[start..] {
+                line.spans
+                    .insert(0, Span::styled("▌ ", Style::default().fg(palette.accent)));
+            }
+        }
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "─".repeat(60),
@@ -1222,7 +1346,136 @@ fn render_parsed_content(
         lines.push(Line::from(""));
     }
 
-    lines
+    (lines, focus_line)
+}
+
+/// Message index a hit points at (`line_number` is the 1-indexed message position).
+fn hit_message_idx(hit: &SearchHit) -> Option<i64> {
+    hit.line_number.map(|n| n.saturating_sub(1) as i64)
+}
+
+/// Initial detail-modal scroll that centers `hit`'s message on a terminal
+/// `height` rows tall; 0 (the top) when the message is not shown.
+fn modal_scroll_for_hit(
+    detail: &ConversationView,
+    hit: &SearchHit,
+    role_filter: RoleFilter,
+    height: u16,
+) -> u16 {
+    // Layout does not depend on colors or highlighting, so any palette and query will do.
+    let (_, focus_line) = render_parsed_content_focused(
+        detail,
+        "",
+        ThemePalette::dark(),
+        role_filter,
+        hit_message_idx(hit),
+    );
+    // Matches the 90% popup from `render_detail_modal`, minus its borders.
+    let visible = (usize::from(height) * 90 / 100).saturating_sub(2);
+    focus_line.map_or(0, |line| {
+        u16::try_from(line.saturating_sub(visible / 2)).unwrap_or(u16::MAX)
+    })
+}
+
+/// Icon and display label for a message role (shared by the detail modal and
+/// markdown copy).
+fn role_icon_label(role: &MessageRole) -> (&'static str, &str) {
+    match role {
+        MessageRole::User => ("👤", "You"),
+        MessageRole::Agent => ("🤖", "Assistant"),
+        MessageRole::Tool => ("🔧", "Tool"),
+        MessageRole::System => ("⚙️", "System"),
+        MessageRole::Other(r) => ("📝", r.as_str()),
+    }
+}
+
+/// Serialize a conversation as a markdown transcript suitable for pasting
+/// into a PR description or issue.
+fn conversation_markdown(detail: &ConversationView) -> String {
+    let mut out = String::new();
+    if let Some(title) = &detail.convo.title {
+        out.push_str(&format!("# {title}\n\n"));
+    }
+    if let Some(ws) = &detail.workspace {
+        out.push_str(&format!("- **Workspace:** `{}`\n", ws.path.display()));
+    }
+    if let Some(ts) = detail.convo.started_at {
+        out.push_str(&format!("- **Started:** {}\n", format_absolute_time(ts)));
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+
+    for msg in &detail.messages {
+        let (role_icon, role_label) = role_icon_label(&msg.role);
+        let ts_text = msg
+            .created_at
+            .map(|t| format!(" · {}", format_absolute_time(t)))
+            .unwrap_or_default();
+        out.push_str(&format!("### {role_icon} {role_label}{ts_text}\n\n"));
+        let content = msg.content.trim_end();
+        if matches!(msg.role, MessageRole::Tool) && !content.contains("```") {
+            // Tool output is raw text; fence it so it stays verbatim.
+            out.push_str(&format!("```\n{content}\n```\n\n"));
+        } else {
+            out.push_str(content);
+            out.push_str("\n\n");
+        }
+    }
+    out
+}
+
+const CLIPBOARD_TOOLS: [&str; 4] = [
+    "pbcopy",
+    "wl-copy",
+    "xclip -selection clipboard",
+    "xsel --clipboard --input",
+];
+
+/// Copy `text` to the system clipboard via pbcopy/wl-copy/xclip/xsel, falling
+/// back to an OSC 52 escape (handled by the terminal itself) when no display
+/// or clipboard tool is available, e.g. over SSH.
+fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let has_display = cfg!(target_os = "macos")
+        || std::env::var_os("DISPLAY").is_some()
+        || std::env::var_os("WAYLAND_DISPLAY").is_some();
+    let tool = CLIPBOARD_TOOLS.into_iter().find(|cmd| {
+        let bin = cmd.split_whitespace().next().unwrap_or(cmd);
+        StdCommand::new("which")
+            .arg(bin)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    });
+    if has_display && let Some(cmd) = tool {
+        let copied = StdCommand::new("sh")
+            .arg("-c")
+            .arg(cmd)
+            .stdin(std::process::Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(stdin) = child.stdin.as_mut() {
+                    stdin.write_all(text.as_bytes())?;
+                }
+                child.wait()
+            })
+            .map(|s| s.success())
+            .unwrap_or(false);
+        if copied {
+            return Ok(());
+        }
+    }
+
+    let mut stdout = io::stdout();
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()
+}
+
+fn osc52_sequence(text: &str) -> String {
+    use base64::prelude::*;
+    format!("\x1b]52;c;{}\x07", BASE64_STANDARD.encode(text))
 }
 
 /// Parse message content and render with beautiful formatting.
@@ -1388,20 +1641,25 @@ fn render_detail_modal(
     query: &str,
     palette: ThemePalette,
     scroll: u16,
+    role_filter: RoleFilter,
 ) {
     let area = frame.area();
     // Use near-full-screen for maximum readability
     let popup_area = centered_rect(90, 90, area);
 
-    let lines = render_parsed_content(detail, query, palette);
+    let (lines, _) =
+        render_parsed_content_focused(detail, query, palette, role_filter, hit_message_idx(hit));
     let total_lines = lines.len();
     // Clamp scroll for display (actual scroll handled by Paragraph)
     let display_line = (scroll as usize).min(total_lines.saturating_sub(1)) + 1;
 
     // Build title with scroll position and hints
     let title_text = format!(
-        " {} · line {}/{} · Esc · o open · c copy · p path · s snip · n nano ",
-        hit.title, display_line, total_lines
+        " {} · line {}/{} · {} · Esc · o open · c copy · M md · T roles · p path · s snip · n nano · v pager ",
+        hit.title,
+        display_line,
+        total_lines,
+        role_filter.label()
     );
 
     let block = Block::default()
@@ -1546,13 +1804,51 @@ fn rebuild_panes_with_filter(
     panes
 }
 
+/// After a search rebuild, fall back to the most recently remembered hit when
+/// `prev_path` is no longer selected. Returns the active path if the selection
+/// was neither kept nor restored (so the next search does not remember it).
+fn restore_remembered_selection(
+    panes: &mut [AgentPane],
+    active_pane: &mut usize,
+    pane_scroll_offset: &mut usize,
+    prev_path: Option<&str>,
+    memory: &HashMap<String, usize>,
+    max_visible_panes: usize,
+) -> Option<String> {
+    let current = active_hit(panes, *active_pane).map(|h| h.source_path.clone());
+    if prev_path.is_some() && current.as_deref() == prev_path {
+        return None;
+    }
+    let remembered = panes
+        .iter()
+        .enumerate()
+        .flat_map(|(pi, pane)| {
+            pane.hits
+                .iter()
+                .enumerate()
+                .filter_map(move |(hi, h)| memory.get(&h.source_path).map(|stamp| (*stamp, pi, hi)))
+        })
+        .max();
+    let Some((_, pi, hi)) = remembered else {
+        return current;
+    };
+    *active_pane = pi;
+    panes[pi].selected = hi;
+    if pi < *pane_scroll_offset {
+        *pane_scroll_offset = pi;
+    } else if pi >= *pane_scroll_offset + max_visible_panes {
+        *pane_scroll_offset = pi.saturating_sub(max_visible_panes - 1);
+    }
+    None
+}
+
 fn active_hit(panes: &[AgentPane], active_idx: usize) -> Option<&SearchHit> {
     panes
         .get(active_idx)
         .and_then(|pane| pane.hits.get(pane.selected))
 }
 
-/// Known agent slugs for autocomplete suggestions
+/// Fallback agent slugs for autocomplete when the db has none indexed yet
 const KNOWN_AGENTS: &[&str] = &[
     "claude_code",
     "codex",
@@ -1563,16 +1859,69 @@ const KNOWN_AGENTS: &[&str] = &[
     "opencode",
 ];
 
+/// Most workspace paths loaded for autocomplete at startup.
+const WORKSPACE_SUGGESTION_LIMIT: usize = 500;
+
+/// Agent slugs offered for autocomplete: everything indexed in the db, or
+/// `KNOWN_AGENTS` when the db is missing or empty.
+fn autocomplete_agents(db: Option<&crate::storage::sqlite::SqliteStorage>) -> Vec<String> {
+    let indexed: Vec<String> = db
+        .and_then(|db| db.list_agents().ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|agent| agent.slug)
+        .collect();
+    if indexed.is_empty() {
+        KNOWN_AGENTS.iter().map(|s| s.to_string()).collect()
+    } else {
+        indexed
+    }
+}
+
+/// Indexed workspace paths offered for autocomplete.
+fn autocomplete_workspaces(db: Option<&crate::storage::sqlite::SqliteStorage>) -> Vec<String> {
+    db.and_then(|db| db.list_workspaces("", WORKSPACE_SUGGESTION_LIMIT).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|ws| ws.path.to_string_lossy().into_owned())
+        .collect()
+}
+
 /// Returns agent suggestions matching the given prefix (case-insensitive)
-fn agent_suggestions(prefix: &str) -> Vec<&'static str> {
+fn agent_suggestions<'a>(agents: &'a [String], prefix: &str) -> Vec<&'a str> {
     let prefix_lower = prefix.to_lowercase();
-    KNOWN_AGENTS
+    agents
         .iter()
         .filter(|agent| agent.to_lowercase().starts_with(&prefix_lower))
-        .copied()
+        .map(String::as_str)
         .collect()
 }
 
+/// Returns workspace paths starting with the given prefix
+fn workspace_suggestions<'a>(workspaces: &'a [String], prefix: &str) -> Vec<&'a str> {
+    workspaces
+        .iter()
+        .filter(|ws| ws.starts_with(prefix))
+        .map(String::as_str)
+        .collect()
+}
+
+/// Status line listing the first few workspace completions for `prefix`.
+fn workspace_hint(workspaces: &[String], prefix: &str) -> Option<String> {
+    if prefix.is_empty() {
+        return None;
+    }
+    let suggestions = workspace_suggestions(workspaces, prefix);
+    if suggestions.is_empty() {
+        return None;
+    }
+    let shown: Vec<&str> = suggestions.into_iter().take(5).collect();
+    Some(format!(
+        "Suggestions: {} (Tab to complete)",
+        shown.join(", ")
+    ))
+}
+
 /// Suggests a correction for a query based on history.
 /// Uses Levenshtein distance to find close matches (max edit distance 2).
 /// Only suggests if the history item is different from the query.
@@ -1627,6 +1976,7 @@ fn agent_color(agent: &str) -> Color {
         "chatgpt" => Color::Rgb(16, 163, 127), // ChatGPT Green
         "aider" => Color::Rgb(255, 165, 0), // Orange
         "pi_agent" => Color::Rgb(255, 140, 0), // Dark Orange (Pi)
+        "copilot" => Color::Rgb(137, 87, 229), // Violet (GitHub Copilot)
         _ => Color::Rgb(169, 169, 169),     // Dark Gray (fallback)
     }
 }
@@ -1910,7 +2260,25 @@ fn ranking_from_str(s: &str) -> RankingMode {
         "quality" => RankingMode::MatchQualityHeavy,
         "newest" => RankingMode::DateNewest,
         "oldest" => RankingMode::DateOldest,
-        _ => RankingMode::Balanced,
+        other => other
+            .strip_prefix("blend:")
+            .and_then(|w| w.parse::<f32>().ok())
+            .map_or(RankingMode::Balanced, |w| RankingMode::Blend {
+                recency_weight: w.clamp(0.0, 1.0),
+            }),
+    }
+}
+
+/// Inverse of [`ranking_from_str`], used when persisting state and saved views.
+fn ranking_to_str(mode: RankingMode) -> String {
+    match mode {
+        RankingMode::RecentHeavy => "recent".into(),
+        RankingMode::RelevanceHeavy => "relevance".into(),
+        RankingMode::MatchQualityHeavy => "quality".into(),
+        RankingMode::DateNewest => "newest".into(),
+        RankingMode::DateOldest => "oldest".into(),
+        RankingMode::Balanced => "balanced".into(),
+        RankingMode::Blend { recency_weight } => format!("blend:{recency_weight}"),
     }
 }
 
@@ -2079,6 +2447,7 @@ fn contextual_shortcuts(
             ("j/k".into(), "Scroll".into()),
             ("Home/End".into(), "Top/Bottom".into()),
             ("c".into(), "Copy".into()),
+            ("e/E".into(), "Export HTML".into()),
         ];
     }
     match input_mode {
@@ -2107,6 +2476,11 @@ fn contextual_shortcuts(
             (shortcuts::DETAIL_OPEN.into(), "Apply".into()),
             (shortcuts::DETAIL_CLOSE.into(), "Cancel".into()),
         ],
+        InputMode::ExportPassphrase => vec![
+            ("type".into(), "Passphrase".into()),
+            (shortcuts::DETAIL_OPEN.into(), "Export".into()),
+            (shortcuts::DETAIL_CLOSE.into(), "Cancel".into()),
+        ],
         InputMode::Query => match focus_region {
             FocusRegion::Results => vec![
                 ("Ctrl+P".into(), "Palette".into()),
@@ -2139,6 +2513,72 @@ fn contextual_shortcuts(
     }
 }
 
+/// Write the open conversation as HTML (or a passphrase-encrypted envelope)
+/// to the downloads dir under a unique name, returning the path.
+fn export_conversation_html(
+    detail: &ConversationView,
+    passphrase: Option<&str>,
+) -> Result<std::path::PathBuf> {
+    let messages: Vec<serde_json::Value> = detail
+        .messages
+        .iter()
+        .map(|m| {
+            let role = match &m.role {
+                MessageRole::User => "user",
+                MessageRole::Agent => "assistant",
+                MessageRole::Tool => "tool",
+                MessageRole::System => "system",
+                MessageRole::Other(r) => r.as_str(),
+            };
+            serde_json::json!({
+                "role": role,
+                "content": m.content,
+                "timestamp": m.created_at,
+            })
+        })
+        .collect();
+    let html = crate::format_as_html(
+        &messages,
+        &detail.convo.title,
+        detail.convo.started_at,
+        false,
+        true,
+        crate::export::ExportTheme::Auto,
+    );
+
+    let dir = dirs::download_dir().unwrap_or_else(std::env::temp_dir);
+    std::fs::create_dir_all(&dir)?;
+    let stem = format!(
+        "cass-{}-{}",
+        detail.convo.agent_slug,
+        Utc::now().format("%Y%m%d-%H%M%S")
+    );
+    let ext = if passphrase.is_some() {
+        "html.enc"
+    } else {
+        "html"
+    };
+    let mut path = dir.join(format!("{stem}.{ext}"));
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("{stem}-{n}.{ext}"));
+        n += 1;
+    }
+    let bytes = match passphrase {
+        Some(p) => crate::export::encrypt_export(html.as_bytes(), p)?,
+        None => html.into_bytes(),
+    };
+    std::fs::write(&path, bytes)?;
+    Ok(path)
+}
+
+fn export_status(result: Result<std::path::PathBuf>) -> String {
+    match result {
+        Ok(path) => format!("✓ Exported to {}", path.display()),
+        Err(e) => format!("✗ Export failed: {e}"),
+    }
+}
+
 fn save_view_slot(
     slot: u8,
     filters: &SearchFilters,
@@ -2399,6 +2839,46 @@ fn quick_date_range_week() -> Option<(i64, i64)> {
     Some((week_ago.timestamp_millis(), now.timestamp_millis()))
 }
 
+fn quick_date_range_this_month() -> Option<(i64, i64)> {
+    use chrono::{Datelike, Local};
+    let now = Local::now();
+    let start = local_month_start(now.year(), now.month())?;
+    Some((start, now.timestamp_millis()))
+}
+
+fn quick_date_range_last_month() -> Option<(i64, i64)> {
+    last_month_range(chrono::Local::now())
+}
+
+/// Whole previous calendar month relative to `now` (inclusive bounds), rolling
+/// January back to December of the prior year.
+fn last_month_range(now: chrono::DateTime<chrono::Local>) -> Option<(i64, i64)> {
+    use chrono::Datelike;
+    let (year, month) = if now.month() == 1 {
+        (now.year() - 1, 12)
+    } else {
+        (now.year(), now.month() - 1)
+    };
+    let start = local_month_start(year, month)?;
+    let end = local_month_start(now.year(), now.month())? - 1;
+    Some((start, end))
+}
+
+fn quick_date_range_this_year() -> Option<(i64, i64)> {
+    use chrono::{Datelike, Local};
+    let now = Local::now();
+    let start = local_month_start(now.year(), 1)?;
+    Some((start, now.timestamp_millis()))
+}
+
+fn local_month_start(year: i32, month: u32) -> Option<i64> {
+    use chrono::{Local, TimeZone};
+    Local
+        .with_ymd_and_hms(year, month, 1, 0, 0, 0)
+        .single()
+        .map(|d| d.timestamp_millis())
+}
+
 fn quick_date_range_hours(hours: i64) -> Option<(i64, i64)> {
     use chrono::{Duration, Local};
     let now = Local::now();
@@ -2412,6 +2892,41 @@ pub enum FocusRegion {
     Detail,
 }
 
+/// Which message roles the conversation detail renders; cycled with `T`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoleFilter {
+    #[default]
+    All,
+    HideTool,
+    HideToolSystem,
+}
+
+impl RoleFilter {
+    pub fn next(self) -> Self {
+        match self {
+            RoleFilter::All => RoleFilter::HideTool,
+            RoleFilter::HideTool => RoleFilter::HideToolSystem,
+            RoleFilter::HideToolSystem => RoleFilter::All,
+        }
+    }
+
+    pub fn shows(self, role: &MessageRole) -> bool {
+        match self {
+            RoleFilter::All => true,
+            RoleFilter::HideTool => !matches!(role, MessageRole::Tool),
+            RoleFilter::HideToolSystem => matches!(role, MessageRole::User | MessageRole::Agent),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RoleFilter::All => "roles: all",
+            RoleFilter::HideTool => "roles: user+agent+system",
+            RoleFilter::HideToolSystem => "roles: user+agent",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct DetailFindState {
     pub query: String,
@@ -2480,12 +2995,31 @@ pub fn footer_legend(show_help: bool) -> &'static str {
     }
 }
 
+/// Query and filters the TUI starts with (`cass tui --query/--agent/--workspace`).
+#[derive(Clone, Debug, Default)]
+pub struct InitialSearch {
+    pub query: Option<String>,
+    pub agents: Vec<String>,
+    pub workspaces: Vec<String>,
+}
+
+impl InitialSearch {
+    fn filters(&self) -> SearchFilters {
+        let mut filters = SearchFilters::default();
+        filters.agents.extend(self.agents.iter().cloned());
+        filters.workspaces.extend(self.workspaces.iter().cloned());
+        filters
+    }
+}
+
 pub fn run_tui(
     data_dir_override: Option<std::path::PathBuf>,
     once: bool,
     reset_state: bool,
     progress: Option<std::sync::Arc<crate::indexer::IndexingProgress>>,
     reindex_tx: Option<crossbeam_channel::Sender<crate::indexer::IndexerEvent>>,
+    update_check: bool,
+    initial: InitialSearch,
 ) -> Result<()> {
     // Resolve data dir early so we can honor reset-state in headless mode too.
     let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
@@ -2501,7 +3035,7 @@ pub fn run_tui(
             .map(|v| v == "1")
             .unwrap_or(false)
     {
-        return run_tui_headless(Some(data_dir));
+        return run_tui_headless(Some(data_dir), &initial);
     }
 
     let mut stdout = io::stdout();
@@ -2531,6 +3065,8 @@ pub fn run_tui(
     // Open a read-only connection for the UI to fetch details efficiently.
     // If DB doesn't exist yet (first run), this will be None, which is fine as we can't view details anyway.
     let db_reader = crate::storage::sqlite::SqliteStorage::open_readonly(&db_path).ok();
+    let known_agents = autocomplete_agents(db_reader.as_ref());
+    let known_workspaces = autocomplete_workspaces(db_reader.as_ref());
 
     let index_ready = search_client.is_some();
     let mut status = if index_ready {
@@ -2551,8 +3087,8 @@ pub fn run_tui(
         status = format!("State reset (tui_state.json cleared). {status}");
     }
 
-    let mut query = String::new();
-    let mut filters = SearchFilters::default();
+    let mut query = initial.query.clone().unwrap_or_default();
+    let mut filters = initial.filters();
     let mut input_mode = InputMode::Query;
     let mut input_buffer = String::new();
     let page_size: usize = 120;
@@ -2633,7 +3169,12 @@ pub fn run_tui(
 
     // Update check state (bead 018)
     // Spawn background thread to check for updates on startup
-    let update_check_rx = spawn_update_check(env!("CARGO_PKG_VERSION").to_string());
+    // `--no-update-check`: a receiver whose sender is already gone never yields.
+    let update_check_rx = if update_check {
+        spawn_update_check(env!("CARGO_PKG_VERSION").to_string())
+    } else {
+        std::sync::mpsc::channel().1
+    };
     let mut update_info: Option<UpdateInfo> = None;
     let mut update_dismissed = false; // Session-only dismissal (not persisted)
 
@@ -2645,6 +3186,14 @@ pub fn run_tui(
     // Full-screen modal for viewing parsed content
     let mut show_detail_modal = false;
     let mut modal_scroll: u16 = 0;
+    // In-memory place per conversation (keyed by source_path), cleared by Ctrl+Del:
+    // detail-modal scroll, and a recency stamp for hits the user selected so a
+    // later search can return to them.
+    let mut modal_scroll_memory: HashMap<String, u16> = HashMap::new();
+    let mut selection_memory: HashMap<String, usize> = HashMap::new();
+    let mut selection_stamp: usize = 0;
+    // Hit picked by a search fallback rather than the user; not remembered.
+    let mut auto_selected_path: Option<String> = None;
     // Bulk action modal state
     let mut show_bulk_modal = false;
     let mut bulk_action_idx: usize = 0;
@@ -2659,6 +3208,7 @@ pub fn run_tui(
         .with_position(crate::ui::components::toast::ToastPosition::TopRight);
     let mut cached_detail: Option<(String, ConversationView)> = None;
     let mut detail_find: Option<DetailFindState> = None;
+    let mut role_filter = RoleFilter::default();
     let mut last_query = String::new();
     let mut needs_draw = true;
     // Load query history from persisted state, or start fresh
@@ -2989,6 +3539,9 @@ pub fn run_tui(
                     InputMode::CreatedTo => format!("[to] {input_buffer}"),
                     InputMode::PaneFilter => format!("[pane] {input_buffer}"),
                     InputMode::DetailFind => format!("[detail find] {input_buffer}"),
+                    InputMode::ExportPassphrase => {
+                        format!("[passphrase] {}", "•".repeat(input_buffer.chars().count()))
+                    }
                 };
                 let mode_label = match match_mode {
                     MatchMode::Standard => "standard",
@@ -3269,8 +3822,7 @@ pub fn run_tui(
                 } else {
                     // Cap visible panes at MAX_VISIBLE_PANES
                     // Safety: clamp scroll offset to valid range to prevent slice panic
-                    let safe_scroll_offset =
-                        pane_scroll_offset.min(panes.len().saturating_sub(1).max(0));
+                    let safe_scroll_offset = pane_scroll_offset.min(panes.len().saturating_sub(1));
                     let visible_end = (safe_scroll_offset + MAX_VISIBLE_PANES).min(panes.len());
                     let visible_panes: Vec<&AgentPane> =
                         panes[safe_scroll_offset..visible_end].iter().collect();
@@ -3744,7 +4296,12 @@ pub fn run_tui(
                     let content_lines: Vec<Line> = match detail_tab {
                         DetailTab::Messages => {
                             if let Some(full) = detail {
-                                let lines = render_parsed_content(&full, highlight_term, palette);
+                                let lines = render_parsed_content(
+                                    &full,
+                                    highlight_term,
+                                    palette,
+                                    role_filter,
+                                );
                                 detail_match_lines = match_line_indices(&lines, highlight_term);
                                 if lines.is_empty() {
                                     vec![Line::from(Span::styled(
@@ -3968,11 +4525,10 @@ pub fn run_tui(
                             if elapsed_ms >= THROUGHPUT_SAMPLE_INTERVAL_MS {
                                 // Calculate items/second
                                 let items_delta = current.saturating_sub(last_count);
-                                let items_per_sec = if elapsed_ms > 0 {
-                                    (items_delta as u64 * 1000 / elapsed_ms) as u16
-                                } else {
-                                    0
-                                };
+                                let items_per_sec = (items_delta as u64 * 1000)
+                                    .checked_div(elapsed_ms)
+                                    .unwrap_or(0)
+                                    as u16;
                                 if throughput_history.len() == throughput_history.capacity() {
                                     throughput_history.pop_front();
                                 }
@@ -4035,6 +4591,9 @@ pub fn run_tui(
                     RankingMode::MatchQualityHeavy => footer_parts.push("rank:quality".to_string()),
                     RankingMode::DateNewest => footer_parts.push("rank:newest".to_string()),
                     RankingMode::DateOldest => footer_parts.push("rank:oldest".to_string()),
+                    RankingMode::Blend { recency_weight } => {
+                        footer_parts.push(format!("rank:blend({recency_weight:.2})"));
+                    }
                     RankingMode::Balanced => {}
                 }
                 if wildcard_fallback {
@@ -4169,7 +4728,7 @@ pub fn run_tui(
 
                 // Render autocomplete dropdown for Agent Filter
                 if input_mode == InputMode::Agent {
-                    let suggestions = agent_suggestions(&input_buffer);
+                    let suggestions = agent_suggestions(&known_agents, &input_buffer);
                     if !suggestions.is_empty() {
                         let area = Rect::new(
                             chunks[0].x + 14, // Align with " Filter: Agent " prompt
@@ -4263,7 +4822,15 @@ pub fn run_tui(
                     } else {
                         last_query.as_str()
                     };
-                    render_detail_modal(f, detail, hit, modal_highlight, palette, modal_scroll);
+                    render_detail_modal(
+                        f,
+                        detail,
+                        hit,
+                        modal_highlight,
+                        palette,
+                        modal_scroll,
+                        role_filter,
+                    );
                 }
 
                 // Bulk action modal
@@ -4469,6 +5036,10 @@ pub fn run_tui(
                 render_toasts(f, &toast_manager, &palette);
             })?;
             needs_draw = false;
+            // --once: stop after the first frame that shows the initial search's results
+            if once && (dirty_since.is_none() || search_client.is_none()) {
+                break;
+            }
         }
 
         let timeout = if needs_draw {
@@ -4775,6 +5346,27 @@ pub fn run_tui(
                                         dirty_since = Some(Instant::now());
                                     }
                                 }
+                                PaletteAction::FilterThisMonth => {
+                                    if let Some((start, _)) = quick_date_range_this_month() {
+                                        filters.created_from = Some(start);
+                                        filters.created_to = None;
+                                        dirty_since = Some(Instant::now());
+                                    }
+                                }
+                                PaletteAction::FilterLastMonth => {
+                                    if let Some((start, end)) = quick_date_range_last_month() {
+                                        filters.created_from = Some(start);
+                                        filters.created_to = Some(end);
+                                        dirty_since = Some(Instant::now());
+                                    }
+                                }
+                                PaletteAction::FilterThisYear => {
+                                    if let Some((start, _)) = quick_date_range_this_year() {
+                                        filters.created_from = Some(start);
+                                        filters.created_to = None;
+                                        dirty_since = Some(Instant::now());
+                                    }
+                                }
                                 PaletteAction::FilterCustomDate => {
                                     input_mode = InputMode::CreatedFrom;
                                     input_buffer.clear();
@@ -4786,6 +5378,53 @@ pub fn run_tui(
                                 PaletteAction::ReloadIndex => {
                                     dirty_since = Some(Instant::now());
                                 }
+                                PaletteAction::RecentSessions => {
+                                    if let Some(db) = &db_reader {
+                                        query.clear();
+                                        page = 0;
+                                        results = recent_session_hits(db, RECENT_SESSIONS_LIMIT);
+                                        panes = rebuild_panes_with_filter(
+                                            &results,
+                                            None,
+                                            per_pane_limit,
+                                            &mut active_pane,
+                                            &mut pane_scroll_offset,
+                                            None,
+                                            None,
+                                            MAX_VISIBLE_PANES,
+                                        );
+                                        selected.clear();
+                                        cached_detail = None;
+                                        detail_scroll = 0;
+                                        dirty_since = None;
+                                        focus_region = FocusRegion::Results;
+                                        status = format!(
+                                            "Recent sessions: {} most recently modified",
+                                            results.len()
+                                        );
+                                        needs_draw = true;
+                                    } else {
+                                        status =
+                                            "No database yet; run `cass index --full`".to_string();
+                                    }
+                                }
+                                PaletteAction::ExportHtml => {
+                                    status = match &cached_detail {
+                                        Some((_, detail)) => {
+                                            export_status(export_conversation_html(detail, None))
+                                        }
+                                        None => "Open a conversation to export".to_string(),
+                                    };
+                                }
+                                PaletteAction::ExportHtmlEncrypted => {
+                                    if cached_detail.is_some() {
+                                        input_mode = InputMode::ExportPassphrase;
+                                        input_buffer.clear();
+                                        status = "Enter export passphrase".to_string();
+                                    } else {
+                                        status = "Open a conversation to export".to_string();
+                                    }
+                                }
                                 PaletteAction::OpenSavedViews => {
                                     status =
                                         "Saved views: Ctrl+<n> save, Shift+<n> load".to_string();
@@ -5198,6 +5837,9 @@ pub fn run_tui(
 
             // While detail modal is open, handle its keyboard shortcuts
             if show_detail_modal {
+                if let Some((path, _)) = &cached_detail {
+                    modal_scroll_memory.insert(path.clone(), modal_scroll);
+                }
                 match key.code {
                     KeyCode::Esc => {
                         show_detail_modal = false;
@@ -5280,6 +5922,39 @@ pub fn run_tui(
                             };
                         }
                     }
+                    KeyCode::Char('e') => {
+                        if let Some((_, ref detail)) = cached_detail {
+                            status = export_status(export_conversation_html(detail, None));
+                        }
+                    }
+                    KeyCode::Char('E') => {
+                        // Passphrase prompt lives in the search bar; close the modal so it gets keys
+                        if cached_detail.is_some() {
+                            show_detail_modal = false;
+                            modal_scroll = 0;
+                            input_mode = InputMode::ExportPassphrase;
+                            input_buffer.clear();
+                            status = "Enter export passphrase".to_string();
+                        }
+                    }
+                    KeyCode::Char('T') => {
+                        role_filter = role_filter.next();
+                        // Line offsets change with the filter; start from the top
+                        modal_scroll = 0;
+                        status = format!("Detail {}", role_filter.label());
+                    }
+                    KeyCode::Char('M') => {
+                        if let Some((_, ref detail)) = cached_detail {
+                            let markdown = conversation_markdown(detail);
+                            status = match copy_to_clipboard(&markdown) {
+                                Ok(()) => format!(
+                                    "✓ Copied {} lines as markdown",
+                                    markdown.lines().count()
+                                ),
+                                Err(e) => format!("✗ Clipboard copy failed: {e}"),
+                            };
+                        }
+                    }
                     KeyCode::Char('n') => {
                         // Open content in nano via temp file
                         if let Some((_, ref detail)) = cached_detail {
@@ -5404,6 +6079,35 @@ pub fn run_tui(
                             modal_scroll = 0;
                         }
                     }
+                    KeyCode::Char('v') => {
+                        // View source file in a pager at the matched line
+                        if let Some(pane) = panes.get(active_pane)
+                            && let Some(hit) = pane.hits.get(pane.selected)
+                        {
+                            if let Some((pager_bin, pager_leading)) = resolve_pager() {
+                                disable_raw_mode().ok();
+                                execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)
+                                    .ok();
+                                let result = StdCommand::new(&pager_bin)
+                                    .args(&pager_leading)
+                                    .args(pager_args(&pager_bin, &hit.source_path, hit.line_number))
+                                    .status();
+                                // Restore the terminal whatever the pager's exit status was
+                                execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)
+                                    .ok();
+                                enable_raw_mode().ok();
+                                status = match result {
+                                    Ok(s) if s.success() => format!("Returned from {pager_bin}"),
+                                    Ok(s) => format!("✗ {pager_bin} exited with {s}"),
+                                    Err(e) => format!("✗ Failed to launch {pager_bin}: {e}"),
+                                };
+                                show_detail_modal = false;
+                                modal_scroll = 0;
+                            } else {
+                                status = "✗ No pager found (set CASS_PAGER or PAGER)".to_string();
+                            }
+                        }
+                    }
                     KeyCode::Char('p') => {
                         // Copy source path to clipboard
                         if let Some(pane) = panes.get(active_pane)
@@ -5988,7 +6692,7 @@ pub fn run_tui(
                             input_buffer.clear();
                             status = format!(
                                 "Agents: {} (type to filter, Tab=complete, Enter=apply)",
-                                KNOWN_AGENTS.join(", ")
+                                known_agents.join(", ")
                             );
                         }
                         KeyCode::F(4) if key.modifiers.contains(KeyModifiers::SHIFT) => {
@@ -6124,7 +6828,9 @@ pub fn run_tui(
                                 RankingMode::RelevanceHeavy => RankingMode::MatchQualityHeavy,
                                 RankingMode::MatchQualityHeavy => RankingMode::DateNewest,
                                 RankingMode::DateNewest => RankingMode::DateOldest,
-                                RankingMode::DateOldest => RankingMode::RecentHeavy,
+                                RankingMode::DateOldest | RankingMode::Blend { .. } => {
+                                    RankingMode::RecentHeavy
+                                }
                             };
                             status = format!(
                                 "Ranking: {}",
@@ -6135,6 +6841,7 @@ pub fn run_tui(
                                     RankingMode::MatchQualityHeavy => "match-quality",
                                     RankingMode::DateNewest => "date (newest first)",
                                     RankingMode::DateOldest => "date (oldest first)",
+                                    RankingMode::Blend { .. } => "blend",
                                 }
                             );
                             dirty_since = Some(Instant::now());
@@ -6195,6 +6902,9 @@ pub fn run_tui(
                             focus_region = FocusRegion::Results;
                             cached_detail = None;
                             detail_scroll = 0;
+                            modal_scroll_memory.clear();
+                            selection_memory.clear();
+                            auto_selected_path = None;
                         }
                         KeyCode::F(8) => {
                             if let Some(hit) = active_hit(&panes, active_pane) {
@@ -6618,7 +7328,17 @@ pub fn run_tui(
                                 save_query_to_history(&query, &mut query_history, history_cap);
                                 // Open full-screen detail modal for parsed viewing
                                 show_detail_modal = true;
-                                modal_scroll = 0;
+                                let height = terminal.size().map(|r| r.height).unwrap_or(24);
+                                // First open lands on the matched message rather than the top.
+                                modal_scroll = cached_detail
+                                    .as_ref()
+                                    .and_then(|(path, _)| modal_scroll_memory.get(path).copied())
+                                    .or_else(|| {
+                                        let (_, detail) = cached_detail.as_ref()?;
+                                        let hit = active_hit(&panes, active_pane)?;
+                                        Some(modal_scroll_for_hit(detail, hit, role_filter, height))
+                                    })
+                                    .unwrap_or(0);
                                 status = "Detail view · Esc close · c copy · n nano".to_string();
                             } else if active_hit(&panes, active_pane).is_some() {
                                 // User committed to viewing a result - save query to history
@@ -6637,7 +7357,7 @@ pub fn run_tui(
                     }
                     KeyCode::Tab => {
                         // Tab completes to first matching suggestion
-                        let suggestions = agent_suggestions(&input_buffer);
+                        let suggestions = agent_suggestions(&known_agents, &input_buffer);
                         if let Some(first) = suggestions.first() {
                             input_buffer = first.to_string();
                             status = format!("Completed to '{first}'. Press Enter to apply.");
@@ -6680,7 +7400,7 @@ pub fn run_tui(
                     KeyCode::Backspace => {
                         input_buffer.pop();
                         // Update suggestions in status
-                        let suggestions = agent_suggestions(&input_buffer);
+                        let suggestions = agent_suggestions(&known_agents, &input_buffer);
                         if !suggestions.is_empty() && !input_buffer.is_empty() {
                             status = format!(
                                 "Suggestions: {} (Tab to complete)",
@@ -6689,17 +7409,17 @@ pub fn run_tui(
                         } else if input_buffer.is_empty() {
                             status = format!(
                                 "Agents: {} (type to filter, Tab to complete)",
-                                KNOWN_AGENTS.join(", ")
+                                known_agents.join(", ")
                             );
                         }
                     }
                     KeyCode::Char(c) => {
                         input_buffer.push(c);
                         // Update suggestions in status
-                        let suggestions = agent_suggestions(&input_buffer);
+                        let suggestions = agent_suggestions(&known_agents, &input_buffer);
                         if suggestions.is_empty() {
                             status =
-                                format!("No matching agents. Known: {}", KNOWN_AGENTS.join(", "));
+                                format!("No matching agents. Known: {}", known_agents.join(", "));
                         } else if suggestions.len() == 1 {
                             status = format!(
                                 "Match: {} (Tab to complete, Enter to apply)",
@@ -6720,6 +7440,13 @@ pub fn run_tui(
                         input_buffer.clear();
                         status = "Workspace filter cancelled".to_string();
                     }
+                    KeyCode::Tab => {
+                        let suggestions = workspace_suggestions(&known_workspaces, &input_buffer);
+                        if let Some(first) = suggestions.first() {
+                            input_buffer = first.to_string();
+                            status = format!("Completed to '{first}'. Press Enter to apply.");
+                        }
+                    }
                     KeyCode::Enter => {
                         filters.workspaces.clear();
                         if !input_buffer.trim().is_empty() {
@@ -6745,8 +7472,16 @@ pub fn run_tui(
                     }
                     KeyCode::Backspace => {
                         input_buffer.pop();
+                        if let Some(hint) = workspace_hint(&known_workspaces, &input_buffer) {
+                            status = hint;
+                        }
+                    }
+                    KeyCode::Char(c) => {
+                        input_buffer.push(c);
+                        if let Some(hint) = workspace_hint(&known_workspaces, &input_buffer) {
+                            status = hint;
+                        }
                     }
-                    KeyCode::Char(c) => input_buffer.push(c),
                     _ => {}
                 },
                 InputMode::CreatedFrom => match key.code {
@@ -6965,6 +7700,33 @@ pub fn run_tui(
                     }
                     _ => {}
                 },
+                InputMode::ExportPassphrase => match key.code {
+                    KeyCode::Esc => {
+                        input_buffer.clear();
+                        input_mode = InputMode::Query;
+                        status = "Export cancelled".to_string();
+                        needs_draw = true;
+                    }
+                    KeyCode::Enter => {
+                        status = if input_buffer.is_empty() {
+                            "✗ Passphrase must not be empty".to_string()
+                        } else if let Some((_, ref detail)) = cached_detail {
+                            export_status(export_conversation_html(detail, Some(&input_buffer)))
+                        } else {
+                            "Open a conversation to export".to_string()
+                        };
+                        input_buffer.clear();
+                        input_mode = InputMode::Query;
+                        needs_draw = true;
+                    }
+                    KeyCode::Backspace => {
+                        input_buffer.pop();
+                    }
+                    KeyCode::Char(c) => {
+                        input_buffer.push(c);
+                    }
+                    _ => {}
+                },
             }
         }
 
@@ -6985,9 +7747,21 @@ pub fn run_tui(
                     let prev_agent = active_hit(&panes, active_pane)
                         .map(|h| h.agent.clone())
                         .or_else(|| panes.get(active_pane).map(|p| p.agent.clone()));
-                    let prev_path = active_hit(&panes, active_pane).map(|h| h.source_path.clone());
-                    let lexical_query = apply_match_mode(&query, match_mode);
-                    let semantic_query = query.clone();
+                    let current_path =
+                        active_hit(&panes, active_pane).map(|h| h.source_path.clone());
+                    if let Some(path) = &current_path
+                        && auto_selected_path.as_ref() != Some(path)
+                    {
+                        selection_stamp += 1;
+                        selection_memory.insert(path.clone(), selection_stamp);
+                    }
+                    let prev_path = current_path.filter(|p| auto_selected_path.as_ref() != Some(p));
+                    // Leading `agent:`/`ws:`/`role:`/`source:` terms narrow this search only.
+                    let mut search_filters = filters.clone();
+                    let free_text =
+                        crate::search::query::apply_field_scopes(&query, &mut search_filters);
+                    let lexical_query = apply_match_mode(&free_text, match_mode);
+                    let semantic_query = free_text;
                     // Use search_with_fallback for implicit wildcard expansion on sparse results
                     const SPARSE_THRESHOLD: usize = 3;
                     let search_started = Instant::now();
@@ -7007,7 +7781,7 @@ pub fn run_tui(
                             match client.search_hybrid(
                                 &lexical_query,
                                 &semantic_query,
-                                filters.clone(),
+                                search_filters.clone(),
                                 page_size,
                                 page * page_size,
                                 SPARSE_THRESHOLD,
@@ -7027,7 +7801,7 @@ pub fn run_tui(
                                     ));
                                     client.search_with_fallback(
                                         &lexical_query,
-                                        filters.clone(),
+                                        search_filters.clone(),
                                         page_size,
                                         page * page_size,
                                         SPARSE_THRESHOLD,
@@ -7038,7 +7812,7 @@ pub fn run_tui(
                         SearchMode::Semantic if use_semantic => {
                             match client.search_semantic(
                                 &semantic_query,
-                                filters.clone(),
+                                search_filters.clone(),
                                 page_size,
                                 page * page_size,
                             ) {
@@ -7063,7 +7837,7 @@ pub fn run_tui(
                                     ));
                                     client.search_with_fallback(
                                         &lexical_query,
-                                        filters.clone(),
+                                        search_filters.clone(),
                                         page_size,
                                         page * page_size,
                                         SPARSE_THRESHOLD,
@@ -7073,7 +7847,7 @@ pub fn run_tui(
                         }
                         _ => client.search_with_fallback(
                             &lexical_query,
-                            filters.clone(),
+                            search_filters.clone(),
                             page_size,
                             page * page_size,
                             SPARSE_THRESHOLD,
@@ -7144,6 +7918,14 @@ pub fn run_tui(
                                     prev_path.clone(),
                                     MAX_VISIBLE_PANES,
                                 );
+                                auto_selected_path = restore_remembered_selection(
+                                    &mut panes,
+                                    &mut active_pane,
+                                    &mut pane_scroll_offset,
+                                    prev_path.as_deref(),
+                                    &selection_memory,
+                                    MAX_VISIBLE_PANES,
+                                );
                                 selected.clear();
                                 open_confirm_armed = false;
                                 // Start staggered reveal animation for fallback results (bead 013)
@@ -7199,6 +7981,12 @@ pub fn run_tui(
                                             ts_a.cmp(&ts_b) // Ascending (oldest first)
                                         }
                                     });
+                                } else if let RankingMode::Blend { recency_weight } = ranking_mode {
+                                    crate::search::query::blend_recency(
+                                        &mut results,
+                                        recency_weight,
+                                        crate::search::query::DEFAULT_RECENCY_HALF_LIFE_DAYS,
+                                    );
                                 } else {
                                     // RankingMode support for all search modes (bead vq8v)
                                     // Recency helper (shared across all modes)
@@ -7218,7 +8006,8 @@ pub fn run_tui(
                                                 RankingMode::RelevanceHeavy => 0.1,
                                                 RankingMode::MatchQualityHeavy => 0.2,
                                                 RankingMode::DateNewest
-                                                | RankingMode::DateOldest => unreachable!(),
+                                                | RankingMode::DateOldest
+                                                | RankingMode::Blend { .. } => unreachable!(),
                                             };
                                             // Per-hit quality factor based on match_type
                                             //   Exact: 1.0, Prefix: 0.9, Suffix: 0.8,
@@ -7246,7 +8035,8 @@ pub fn run_tui(
                                                 RankingMode::RelevanceHeavy => (0.8, 0.2),
                                                 RankingMode::MatchQualityHeavy => (0.85, 0.15),
                                                 RankingMode::DateNewest
-                                                | RankingMode::DateOldest => unreachable!(),
+                                                | RankingMode::DateOldest
+                                                | RankingMode::Blend { .. } => unreachable!(),
                                             };
                                             let norm_score = |h: &SearchHit| (h.score + 1.0) / 2.0;
                                             results.sort_by(|a, b| {
@@ -7272,7 +8062,8 @@ pub fn run_tui(
                                                 RankingMode::RelevanceHeavy => (0.8, 0.2),
                                                 RankingMode::MatchQualityHeavy => (0.85, 0.15),
                                                 RankingMode::DateNewest
-                                                | RankingMode::DateOldest => unreachable!(),
+                                                | RankingMode::DateOldest
+                                                | RankingMode::Blend { .. } => unreachable!(),
                                             };
                                             let norm_score = |h: &SearchHit| {
                                                 if max_rrf > 0.0 {
@@ -7300,7 +8091,15 @@ pub fn run_tui(
                                     &mut active_pane,
                                     &mut pane_scroll_offset,
                                     prev_agent,
-                                    prev_path,
+                                    prev_path.clone(),
+                                    MAX_VISIBLE_PANES,
+                                );
+                                auto_selected_path = restore_remembered_selection(
+                                    &mut panes,
+                                    &mut active_pane,
+                                    &mut pane_scroll_offset,
+                                    prev_path.as_deref(),
+                                    &selection_memory,
                                     MAX_VISIBLE_PANES,
                                 );
                                 // Clear multi-selection when results change
@@ -7519,14 +8318,7 @@ pub fn run_tui(
                     workspaces: v.workspaces.iter().cloned().collect(),
                     created_from: v.created_from,
                     created_to: v.created_to,
-                    ranking: Some(match v.ranking {
-                        RankingMode::RecentHeavy => "recent".into(),
-                        RankingMode::RelevanceHeavy => "relevance".into(),
-                        RankingMode::MatchQualityHeavy => "quality".into(),
-                        RankingMode::DateNewest => "newest".into(),
-                        RankingMode::DateOldest => "oldest".into(),
-                        RankingMode::Balanced => "balanced".into(),
-                    }),
+                    ranking: Some(ranking_to_str(v.ranking)),
                     source_filter: if v.source_filter.is_all() {
                         None
                     } else {
@@ -7537,14 +8329,7 @@ pub fn run_tui(
         ),
         // Persist pane count & ranking mode (bead 46t.1)
         per_pane_limit: Some(per_pane_limit),
-        ranking_mode: Some(match ranking_mode {
-            RankingMode::RecentHeavy => "recent".into(),
-            RankingMode::RelevanceHeavy => "relevance".into(),
-            RankingMode::MatchQualityHeavy => "quality".into(),
-            RankingMode::DateNewest => "newest".into(),
-            RankingMode::DateOldest => "oldest".into(),
-            RankingMode::Balanced => "balanced".into(),
-        }),
+        ranking_mode: Some(ranking_to_str(ranking_mode)),
     };
     save_state(&state_path, &persisted_out);
 
@@ -7555,13 +8340,19 @@ fn default_db_path_for(data_dir: &std::path::Path) -> std::path::PathBuf {
     data_dir.join("agent_search.db")
 }
 
-fn run_tui_headless(data_dir_override: Option<std::path::PathBuf>) -> Result<()> {
+fn run_tui_headless(
+    data_dir_override: Option<std::path::PathBuf>,
+    initial: &InitialSearch,
+) -> Result<()> {
     let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
     let index_path = index_dir(&data_dir)?;
     let db_path = default_db_path_for(&data_dir);
     let client = SearchClient::open(&index_path, Some(&db_path))?
         .ok_or_else(|| anyhow::anyhow!("index/db not found"))?;
-    let _ = client.search("", SearchFilters::default(), 5, 0)?;
+    let mut filters = initial.filters();
+    let query = initial.query.as_deref().unwrap_or_default();
+    let free_text = crate::search::query::apply_field_scopes(query, &mut filters);
+    let _ = client.search(&free_text, filters, 5, 0)?;
     Ok(())
 }
 
@@ -7740,7 +8531,7 @@ mod tests {
             workspace: None,
         };
 
-        let lines = render_parsed_content(&detail, "", palette);
+        let lines = render_parsed_content(&detail, "", palette, RoleFilter::All);
         let joined = lines
             .iter()
             .map(line_to_string)
@@ -7755,6 +8546,171 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detail_modal_opens_centered_on_hit_message() {
+        let convo = Conversation {
+            id: Some(1),
+            agent_slug: "codex".into(),
+            workspace: None,
+            external_id: None,
+            title: Some("Long session".into()),
+            source_path: PathBuf::from("/tmp/long"),
+            started_at: None,
+            ended_at: None,
+            approx_tokens: None,
+            metadata_json: json!({}),
+            messages: Vec::new(),
+            source_id: "local".to_string(),
+            origin_host: None,
+        };
+        let messages = (0..40)
+            .map(|idx| Message {
+                id: Some(idx + 1),
+                idx,
+                role: MessageRole::User,
+                author: None,
+                created_at: None,
+                content: format!("message {idx}"),
+                extra_json: json!({}),
+                snippets: vec![],
+            })
+            .collect();
+        let detail = ConversationView {
+            convo,
+            messages,
+            workspace: None,
+        };
+
+        let (lines, focus_line) = render_parsed_content_focused(
+            &detail,
+            "",
+            ThemePalette::dark(),
+            RoleFilter::All,
+            Some(30),
+        );
+        let focus_line = focus_line.expect("message 30 rendered");
+        assert!(line_to_string(&lines[focus_line]).starts_with("▌ "));
+        assert!(
+            lines[focus_line..]
+                .iter()
+                .map(line_to_string)
+                .any(|l| l.starts_with("▌ ") && l.contains("message 30"))
+        );
+
+        let mut hit = make_hit("codex", "/tmp/long", 1.0, "message 30");
+        hit.line_number = Some(31);
+        let scroll = modal_scroll_for_hit(&detail, &hit, RoleFilter::All, 40);
+        // 40 rows -> 34 visible lines in the modal, so the header sits 17 lines down.
+        assert_eq!(usize::from(scroll), focus_line - 17);
+
+        hit.line_number = None;
+        assert_eq!(modal_scroll_for_hit(&detail, &hit, RoleFilter::All, 40), 0);
+    }
+
+    #[test]
+    fn conversation_markdown_has_role_headers_and_fenced_tool_output() {
+        let convo = Conversation {
+            id: Some(1),
+            agent_slug: "codex".into(),
+            workspace: None,
+            external_id: None,
+            title: Some("Fix the build".into()),
+            source_path: PathBuf::from("/tmp/test"),
+            started_at: None,
+            ended_at: None,
+            approx_tokens: None,
+            metadata_json: json!({}),
+            messages: Vec::new(),
+            source_id: "local".to_string(),
+            origin_host: None,
+        };
+        let message = |idx: i64, role: MessageRole, content: &str| Message {
+            id: None,
+            idx,
+            role,
+            author: None,
+            created_at: None,
+            content: content.into(),
+            extra_json: json!({}),
+            snippets: vec![],
+        };
+        let detail = ConversationView {
+            convo,
+            messages: vec![
+                message(0, MessageRole::User, "why does it fail?"),
+                message(1, MessageRole::Tool, "error[E0425]: cannot find value"),
+            ],
+            workspace: None,
+        };
+
+        let md = conversation_markdown(&detail);
+        assert!(md.starts_with("# Fix the build\n"));
+        assert!(md.contains("### 👤 You\n\nwhy does it fail?"));
+        assert!(md.contains("### 🔧 Tool\n\n```\nerror[E0425]: cannot find value\n```"));
+    }
+
+    #[test]
+    fn last_month_range_rolls_january_back_to_december() {
+        use chrono::TimeZone;
+        let now = chrono::Local
+            .with_ymd_and_hms(2025, 1, 15, 12, 0, 0)
+            .single()
+            .unwrap();
+        let (start, end) = last_month_range(now).unwrap();
+        assert_eq!(Some(start), local_month_start(2024, 12));
+        assert_eq!(Some(end + 1), local_month_start(2025, 1));
+
+        let march = chrono::Local
+            .with_ymd_and_hms(2024, 3, 1, 0, 0, 0)
+            .single()
+            .unwrap();
+        let (start, end) = last_month_range(march).unwrap();
+        assert_eq!(Some(start), local_month_start(2024, 2));
+        assert_eq!(end + 1, march.timestamp_millis());
+    }
+
+    #[test]
+    fn role_filter_cycles_and_hides_roles() {
+        let f = RoleFilter::default();
+        assert_eq!(f, RoleFilter::All);
+        assert_eq!(f.next().next().next(), RoleFilter::All);
+
+        let hide_tool = f.next();
+        assert!(!hide_tool.shows(&MessageRole::Tool));
+        assert!(hide_tool.shows(&MessageRole::System));
+
+        let hide_both = hide_tool.next();
+        assert!(!hide_both.shows(&MessageRole::System));
+        assert!(hide_both.shows(&MessageRole::User));
+        assert!(hide_both.shows(&MessageRole::Agent));
+        assert_eq!(hide_both.label(), "roles: user+agent");
+    }
+
+    #[test]
+    fn osc52_sequence_base64_encodes_payload() {
+        assert_eq!(osc52_sequence("hi"), "\x1b]52;c;aGk=\x07");
+    }
+
+    #[test]
+    fn pager_args_jump_to_line_only_for_known_pagers() {
+        assert_eq!(
+            pager_args("less", "/tmp/a.jsonl", Some(42)),
+            vec!["+42", "/tmp/a.jsonl"]
+        );
+        assert_eq!(
+            pager_args("/usr/bin/more", "/tmp/a.jsonl", Some(7)),
+            vec!["+7", "/tmp/a.jsonl"]
+        );
+        assert_eq!(
+            pager_args("less", "/tmp/a.jsonl", None),
+            vec!["/tmp/a.jsonl"]
+        );
+        assert_eq!(
+            pager_args("bat", "/tmp/a.jsonl", Some(3)),
+            vec!["/tmp/a.jsonl"]
+        );
+    }
+
     // ==========================================================================
     // Navigation State Tests (tst.ui.nav)
     // Tests for TUI navigation state machine behavior
@@ -7841,6 +8797,13 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
         }
     }
 
@@ -8008,7 +8971,8 @@ mod tests {
 
     #[test]
     fn agent_suggestions_returns_matching_agents() {
-        let suggestions = agent_suggestions("cl");
+        let agents = autocomplete_agents(None);
+        let suggestions = agent_suggestions(&agents, "cl");
         assert!(suggestions.contains(&"claude_code"));
         assert!(suggestions.contains(&"cline"));
         assert!(!suggestions.contains(&"codex"));
@@ -8016,17 +8980,59 @@ mod tests {
 
     #[test]
     fn agent_suggestions_case_insensitive() {
-        let suggestions = agent_suggestions("CL");
+        let agents = autocomplete_agents(None);
+        let suggestions = agent_suggestions(&agents, "CL");
         assert!(suggestions.contains(&"claude_code"));
         assert!(suggestions.contains(&"cline"));
     }
 
     #[test]
     fn agent_suggestions_empty_prefix_returns_all() {
-        let suggestions = agent_suggestions("");
+        let agents = autocomplete_agents(None);
+        let suggestions = agent_suggestions(&agents, "");
         assert_eq!(suggestions.len(), KNOWN_AGENTS.len());
     }
 
+    #[test]
+    fn autocomplete_uses_indexed_agents_and_workspaces() {
+        use crate::model::types::{Agent, AgentKind};
+        use crate::storage::sqlite::SqliteStorage;
+
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("agent_search.db");
+        {
+            let storage = SqliteStorage::open(&db_path).unwrap();
+            assert_eq!(
+                autocomplete_agents(Some(&storage)).len(),
+                KNOWN_AGENTS.len()
+            );
+            storage
+                .ensure_agent(&Agent {
+                    id: None,
+                    slug: "remote_tool".into(),
+                    name: "Remote Tool".into(),
+                    version: None,
+                    kind: AgentKind::Cli,
+                })
+                .unwrap();
+            storage
+                .ensure_workspace(Path::new("/home/me/project"), None)
+                .unwrap();
+        }
+        let db = SqliteStorage::open_readonly(&db_path).ok();
+
+        let agents = autocomplete_agents(db.as_ref());
+        assert_eq!(agents, vec!["remote_tool".to_string()]);
+        assert_eq!(agent_suggestions(&agents, "rem"), vec!["remote_tool"]);
+
+        let workspaces = autocomplete_workspaces(db.as_ref());
+        assert_eq!(
+            workspace_suggestions(&workspaces, "/home/me"),
+            vec!["/home/me/project"]
+        );
+        assert!(workspace_suggestions(&workspaces, "/srv").is_empty());
+    }
+
     // ==========================================================================
     // UI State Persistence Tests (tst.ui.pers)
     // Tests for saving and restoring UI state across sessions
@@ -8110,6 +9116,65 @@ mod tests {
         assert_eq!(view5.created_to, Some(5000));
     }
 
+    #[test]
+    fn ranking_from_str_parses_blend_weight() {
+        assert_eq!(
+            ranking_from_str("blend:0.25"),
+            RankingMode::Blend {
+                recency_weight: 0.25
+            }
+        );
+        assert_eq!(
+            ranking_from_str("blend:3"),
+            RankingMode::Blend {
+                recency_weight: 1.0
+            }
+        );
+        assert_eq!(ranking_from_str("blend:abc"), RankingMode::Balanced);
+    }
+
+    #[test]
+    fn ranking_mode_round_trips_through_state_file() {
+        let dir = TempDir::new().unwrap();
+        let path = state_path_for(dir.path());
+
+        for mode in [
+            RankingMode::RecentHeavy,
+            RankingMode::Balanced,
+            RankingMode::RelevanceHeavy,
+            RankingMode::MatchQualityHeavy,
+            RankingMode::DateNewest,
+            RankingMode::DateOldest,
+            RankingMode::Blend {
+                recency_weight: 0.75,
+            },
+        ] {
+            let state = TuiStatePersisted {
+                ranking_mode: Some(ranking_to_str(mode)),
+                ..Default::default()
+            };
+            save_state(&path, &state);
+
+            let loaded = load_state(&path);
+            let restored = loaded
+                .ranking_mode
+                .as_deref()
+                .map_or(RankingMode::Balanced, ranking_from_str);
+            assert_eq!(restored, mode);
+        }
+    }
+
+    #[test]
+    fn state_without_ranking_mode_loads_as_none() {
+        let dir = TempDir::new().unwrap();
+        let path = state_path_for(dir.path());
+        std::fs::write(&path, r#"{"match_mode":"prefix","density_mode":"cozy"}"#).unwrap();
+
+        let loaded = load_state(&path);
+        assert_eq!(loaded.match_mode.as_deref(), Some("prefix"));
+        assert!(loaded.ranking_mode.is_none());
+    }
+
     #[test]
     fn state_persistence_per_pane_limit_and_ranking_mode() {
         let dir = TempDir::new().unwrap();
@@ -9145,11 +10210,9 @@ mod tests {
         let items_delta = 100usize;
         let elapsed_ms = 500u64;
 
-        let items_per_sec = if elapsed_ms > 0 {
-            (items_delta as u64 * 1000 / elapsed_ms) as u16
-        } else {
-            0
-        };
+        let items_per_sec = (items_delta as u64 * 1000)
+            .checked_div(elapsed_ms)
+            .unwrap_or(0) as u16;
 
         // 100 items in 500ms = 200 items/sec
         assert_eq!(items_per_sec, 200);
@@ -9161,13 +10224,58 @@ mod tests {
         let items_delta = 100usize;
         let elapsed_ms = 0u64;
 
-        let items_per_sec = if elapsed_ms > 0 {
-            (items_delta as u64 * 1000 / elapsed_ms) as u16
-        } else {
-            0
-        };
+        let items_per_sec = (items_delta as u64 * 1000)
+            .checked_div(elapsed_ms)
+            .unwrap_or(0) as u16;
 
         assert_eq!(items_per_sec, 0);
         let _ = items_delta; // suppress unused warning
     }
+
+    #[test]
+    fn restore_remembered_selection_prefers_most_recent_remembered_hit() {
+        let hits = vec![
+            make_hit("codex", "/a", 9.0, "a"),
+            make_hit("claude", "/b", 8.0, "b"),
+            make_hit("claude", "/c", 7.0, "c"),
+        ];
+        let mut active_pane = 0;
+        let mut scroll_offset = 0;
+        let mut panes = rebuild_panes_with_filter(
+            &hits,
+            None,
+            10,
+            &mut active_pane,
+            &mut scroll_offset,
+            None,
+            Some("/gone".into()),
+            5,
+        );
+        let memory = HashMap::from([("/b".to_string(), 1), ("/c".to_string(), 2)]);
+
+        let auto = restore_remembered_selection(
+            &mut panes,
+            &mut active_pane,
+            &mut scroll_offset,
+            Some("/gone"),
+            &memory,
+            5,
+        );
+        assert!(auto.is_none());
+        assert_eq!(
+            active_hit(&panes, active_pane).map(|h| h.source_path.as_str()),
+            Some("/c")
+        );
+
+        // Nothing remembered: selection stays put and is reported as automatic.
+        let auto = restore_remembered_selection(
+            &mut panes,
+            &mut active_pane,
+            &mut scroll_offset,
+            Some("/gone"),
+            &HashMap::new(),
+            5,
+        );
+        assert_eq!(auto.as_deref(), Some("/c"));
+    }
 }
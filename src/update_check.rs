@@ -4,19 +4,24 @@
 //! - GitHub releases API integration
 //! - Persistent state (last check time, skipped versions)
 //! - Offline-friendly behavior (silent failure)
-//! - Hourly check cadence (configurable)
+//! - Hourly check cadence (configurable via `CASS_UPDATE_CHECK_INTERVAL_SECS`)
+//! - Cached latest release tag, reused between checks and when offline
 
 use anyhow::{Context, Result};
 use reqwest::Client;
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tracing::{debug, warn};
 
 /// How often to check for updates (1 hour default)
 const CHECK_INTERVAL_SECS: u64 = 3600;
 
+/// Env var overriding [`CHECK_INTERVAL_SECS`].
+const CHECK_INTERVAL_ENV: &str = "CASS_UPDATE_CHECK_INTERVAL_SECS";
+
 /// Timeout for HTTP requests (short to avoid blocking startup)
 const HTTP_TIMEOUT_SECS: u64 = 5;
 
@@ -30,13 +35,20 @@ pub struct UpdateState {
     pub last_check_ts: i64,
     /// Version string that user chose to skip (e.g., "0.2.0")
     pub skipped_version: Option<String>,
+    /// Latest release tag seen by the most recent successful check (e.g., "v0.2.0")
+    #[serde(default)]
+    pub latest_tag: Option<String>,
 }
 
 impl UpdateState {
     /// Load state from disk (synchronous)
     pub fn load() -> Self {
-        let path = state_path();
-        match std::fs::read_to_string(&path) {
+        Self::load_from(&state_path())
+    }
+
+    /// Load state from `path`; missing or unreadable files yield the default state.
+    pub fn load_from(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
             Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
             Err(_) => Self::default(),
         }
@@ -53,13 +65,17 @@ impl UpdateState {
 
     /// Save state to disk (synchronous)
     pub fn save(&self) -> Result<()> {
-        let path = state_path();
+        self.save_to(&state_path())
+    }
+
+    /// Save state to `path`, creating its directory if needed.
+    pub fn save_to(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)
                 .with_context(|| format!("creating update state directory {}", parent.display()))?;
         }
         let json = serde_json::to_string_pretty(self)?;
-        std::fs::write(&path, json).with_context(|| format!("writing {}", path.display()))?;
+        std::fs::write(path, json).with_context(|| format!("writing {}", path.display()))?;
         Ok(())
     }
 
@@ -80,8 +96,12 @@ impl UpdateState {
 
     /// Check if enough time has passed since last check
     pub fn should_check(&self) -> bool {
-        let now = now_unix();
-        (now - self.last_check_ts) >= CHECK_INTERVAL_SECS as i64
+        self.is_due(check_interval())
+    }
+
+    /// Whether at least `interval` has passed since the last check
+    pub fn is_due(&self, interval: Duration) -> bool {
+        (now_unix() - self.last_check_ts) >= interval.as_secs() as i64
     }
 
     /// Mark that we just checked
@@ -165,6 +185,11 @@ pub async fn check_for_updates(current_version: &str) -> Option<UpdateInfo> {
         }
     };
 
+    state.latest_tag = Some(release.tag_name.clone());
+    if let Err(e) = state.save_async().await {
+        warn!("update check: failed to save state: {e}");
+    }
+
     // Parse versions
     let latest_str = release.tag_name.trim_start_matches('v');
     let latest = match Version::parse(latest_str) {
@@ -312,8 +337,44 @@ async fn fetch_latest_release() -> Result<GitHubRelease> {
         .context("parsing release JSON")
 }
 
+/// Latest release tag, cached in the update state file at `path`.
+///
+/// Within `interval` of the previous check the cached tag is returned and `fetch`
+/// is never called. Otherwise `fetch` runs; if it fails (e.g. offline) the cached
+/// tag is returned instead. The check time is recorded either way, so an offline
+/// machine does not retry on every launch.
+pub async fn cached_latest_tag<F, Fut>(path: &Path, interval: Duration, fetch: F) -> Option<String>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Option<String>>,
+{
+    let mut state = UpdateState::load_from(path);
+    if !state.is_due(interval) {
+        debug!("update check: using cached release tag");
+        return state.latest_tag;
+    }
+    state.mark_checked();
+    match fetch().await {
+        Some(tag) => state.latest_tag = Some(tag),
+        None => debug!("update check: fetch failed (offline?); using cached release tag"),
+    }
+    if let Err(e) = state.save_to(path) {
+        warn!("update check: failed to save state: {e}");
+    }
+    state.latest_tag
+}
+
+/// Update check interval: `CASS_UPDATE_CHECK_INTERVAL_SECS` or one hour.
+pub fn check_interval() -> Duration {
+    let secs = dotenvy::var(CHECK_INTERVAL_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(CHECK_INTERVAL_SECS);
+    Duration::from_secs(secs)
+}
+
 /// Get path to update state file
-fn state_path() -> PathBuf {
+pub(crate) fn state_path() -> PathBuf {
     directories::ProjectDirs::from("com", "coding-agent-search", "coding-agent-search").map_or_else(
         || PathBuf::from("update_state.json"),
         |dirs| dirs.data_dir().join("update_state.json"),
@@ -358,6 +419,11 @@ pub fn check_for_updates_sync(current_version: &str) -> Option<UpdateInfo> {
         }
     };
 
+    state.latest_tag = Some(release.tag_name.clone());
+    if let Err(e) = state.save() {
+        warn!("update check: failed to save state: {e}");
+    }
+
     // Parse versions
     let latest_str = release.tag_name.trim_start_matches('v');
     let latest = match Version::parse(latest_str) {
@@ -456,6 +522,40 @@ mod tests {
         assert!(!state.is_skipped("1.0.0"));
     }
 
+    #[test]
+    fn test_cached_latest_tag_skips_network_within_interval() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("update_state.json");
+        let fetches = AtomicUsize::new(0);
+        let fetch = |tag: Option<&'static str>| {
+            let fetches = &fetches;
+            move || async move {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                tag.map(str::to_string)
+            }
+        };
+        let hour = Duration::from_secs(3600);
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let first = rt.block_on(cached_latest_tag(&path, hour, fetch(Some("v9.9.9"))));
+        assert_eq!(first.as_deref(), Some("v9.9.9"));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        // Within the interval: served from update_state.json, no fetch.
+        let second = rt.block_on(cached_latest_tag(&path, hour, fetch(Some("v10.0.0"))));
+        assert_eq!(second.as_deref(), Some("v9.9.9"));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        // Due but offline: falls back to the cached tag.
+        let offline = rt.block_on(cached_latest_tag(&path, Duration::ZERO, fetch(None)));
+        assert_eq!(offline.as_deref(), Some("v9.9.9"));
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+
     #[test]
     fn test_update_info_should_show() {
         let info = UpdateInfo {
@@ -523,6 +623,7 @@ mod tests {
         let mut state = UpdateState {
             last_check_ts: 1234567890,
             skipped_version: Some("0.1.50".to_string()),
+            latest_tag: None,
         };
 
         // Write to temp location
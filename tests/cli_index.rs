@@ -90,6 +90,33 @@ fn index_watch_once_triggers() {
     cmd.assert().success();
 }
 
+#[test]
+fn index_watch_rejects_max_wait_below_debounce() {
+    let tmp = TempDir::new().unwrap();
+    let data_dir = tmp.path().join("data");
+    fs::create_dir_all(&data_dir).unwrap();
+
+    let dummy_path = data_dir.join("dummy.txt");
+    fs::write(&dummy_path, "dummy content").unwrap();
+
+    let mut cmd = base_cmd(tmp.path());
+    cmd.args([
+        "index",
+        "--watch-once",
+        dummy_path.to_str().unwrap(),
+        "--watch-debounce",
+        "3000",
+        "--watch-max-wait",
+        "1000",
+        "--data-dir",
+        data_dir.to_str().unwrap(),
+    ]);
+
+    cmd.assert()
+        .failure()
+        .stderr(contains("must be >= debounce"));
+}
+
 #[test]
 fn index_force_rebuild_flag() {
     let tmp = TempDir::new().unwrap();
@@ -281,3 +308,264 @@ fn incremental_index_only_processes_new_sessions() {
         "Hit should be from codex connector"
     );
 }
+
+#[test]
+fn index_unknown_connector_is_usage_error() {
+    let tmp = TempDir::new().unwrap();
+    let data_dir = tmp.path().join("data");
+    fs::create_dir_all(&data_dir).unwrap();
+
+    let mut cmd = base_cmd(tmp.path());
+    cmd.args([
+        "index",
+        "--connector",
+        "nope",
+        "--data-dir",
+        data_dir.to_str().unwrap(),
+        "--json",
+    ]);
+    cmd.assert()
+        .code(2)
+        .stderr(contains("Unknown connector 'nope'"))
+        .stderr(contains("claude"))
+        .stderr(contains("codex"));
+}
+
+#[test]
+fn index_reports_lock_busy_when_another_indexer_holds_the_lock() {
+    let tmp = TempDir::new().unwrap();
+    let data_dir = tmp.path().join("data");
+    fs::create_dir_all(&data_dir).unwrap();
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    fs::write(
+        data_dir.join("index.lock"),
+        format!(r#"{{"pid":4242,"acquired_at":{now_ms},"heartbeat_at":{now_ms}}}"#),
+    )
+    .unwrap();
+
+    let mut cmd = base_cmd(tmp.path());
+    cmd.args(["index", "--data-dir", data_dir.to_str().unwrap(), "--json"]);
+    cmd.assert()
+        .code(7)
+        .stderr(contains("lock-busy"))
+        .stderr(contains("4242"));
+    assert!(
+        data_dir.join("index.lock").exists(),
+        "a busy lock must not be removed by the loser"
+    );
+}
+
+fn search_hit_count(home: &std::path::Path, data_dir: &std::path::Path, term: &str) -> usize {
+    let mut search = base_cmd(home);
+    search.env("CODEX_HOME", home.join(".codex"));
+    search.args([
+        "search",
+        term,
+        "--robot",
+        "--data-dir",
+        data_dir.to_str().unwrap(),
+    ]);
+    let output = search.output().expect("search command");
+    assert!(output.status.success(), "search should succeed for {term}");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("valid json output");
+    json["hits"].as_array().map(Vec::len).unwrap_or(0)
+}
+
+#[test]
+fn index_connector_filters_limit_scanned_connectors() {
+    let tmp = TempDir::new().unwrap();
+    let home = tmp.path();
+    let codex_home = home.join(".codex");
+    let data_dir = home.join("cass_data");
+    fs::create_dir_all(&data_dir).unwrap();
+    make_codex_session(
+        &codex_home,
+        "2025/11/20",
+        "rollout-1.jsonl",
+        "filtered_content",
+    );
+
+    // Skipping codex leaves its sessions out of the index
+    let mut skip = base_cmd(home);
+    skip.env("CODEX_HOME", &codex_home);
+    skip.args([
+        "index",
+        "--skip-connector",
+        "codex",
+        "--data-dir",
+        data_dir.to_str().unwrap(),
+        "--json",
+    ]);
+    skip.assert().success();
+    assert_eq!(search_hit_count(home, &data_dir, "filtered_content"), 0);
+
+    // A later run restricted to codex still sees the session (filtered runs keep last_scan_ts)
+    let mut only = base_cmd(home);
+    only.env("CODEX_HOME", &codex_home);
+    only.args([
+        "index",
+        "--connector",
+        "codex",
+        "--data-dir",
+        data_dir.to_str().unwrap(),
+        "--json",
+    ]);
+    only.assert().success();
+    assert!(search_hit_count(home, &data_dir, "filtered_content") > 0);
+}
+
+fn make_claude_session(root: &std::path::Path, project: &str, content: &str) {
+    let project_dir = root.join(format!("projects/{project}"));
+    fs::create_dir_all(&project_dir).unwrap();
+    let sample = format!(
+        r#"{{"type": "user", "timestamp": "2024-12-01T10:00:00Z", "message": {{"role": "user", "content": "{content}"}}}}
+{{"type": "assistant", "timestamp": "2024-12-01T10:01:00Z", "message": {{"role": "assistant", "content": "{content}_response"}}}}"#
+    );
+    fs::write(project_dir.join("session.jsonl"), sample).unwrap();
+}
+
+#[test]
+fn index_full_with_connector_filter_keeps_other_connectors() {
+    let tmp = TempDir::new().unwrap();
+    let home = tmp.path();
+    let codex_home = home.join(".codex");
+    let data_dir = home.join("cass_data");
+    fs::create_dir_all(&data_dir).unwrap();
+    make_codex_session(&codex_home, "2025/11/20", "rollout-1.jsonl", "codex_kept");
+    make_claude_session(&home.join(".claude"), "myapp", "claude_kept");
+
+    let mut index = base_cmd(home);
+    index.args(["index", "--data-dir", data_dir.to_str().unwrap(), "--json"]);
+    index.assert().success();
+    assert!(search_hit_count(home, &data_dir, "claude_kept") > 0);
+
+    // A full reset would drop claude's conversations too, so a filtered one is refused
+    for flags in [["--full"], ["--force-rebuild"]] {
+        let mut full = base_cmd(home);
+        full.args(["index", "--connector", "codex"]);
+        full.args(flags);
+        full.args(["--data-dir", data_dir.to_str().unwrap(), "--json"]);
+        full.assert()
+            .code(2)
+            .stderr(contains("filtered run does not rescan"));
+    }
+
+    let mut include = base_cmd(home);
+    include.args([
+        "index",
+        "--full",
+        "--include",
+        "**/sessions/**",
+        "--data-dir",
+        data_dir.to_str().unwrap(),
+        "--json",
+    ]);
+    include.assert().code(2);
+
+    assert!(search_hit_count(home, &data_dir, "claude_kept") > 0);
+    assert!(search_hit_count(home, &data_dir, "codex_kept") > 0);
+}
+
+#[test]
+fn index_dry_run_counts_candidates_without_writing() {
+    let tmp = TempDir::new().unwrap();
+    let home = tmp.path();
+    let codex_home = home.join(".codex");
+    let data_dir = home.join("cass_data");
+    make_codex_session(
+        &codex_home,
+        "2025/11/20",
+        "rollout-1.jsonl",
+        "dry_run_content",
+    );
+
+    let mut cmd = base_cmd(home);
+    cmd.env("CODEX_HOME", &codex_home);
+    cmd.args([
+        "index",
+        "--dry-run",
+        "--connector",
+        "codex",
+        "--data-dir",
+        data_dir.to_str().unwrap(),
+        "--json",
+    ]);
+    let output = cmd.output().expect("dry run");
+    assert!(output.status.success(), "dry run should succeed");
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("valid json output");
+    assert_eq!(json["dry_run"], true);
+    let connectors = json["connectors"].as_array().expect("connectors array");
+    assert_eq!(connectors.len(), 1);
+    assert_eq!(connectors[0]["name"], "codex");
+    assert_eq!(connectors[0]["detected"], true);
+    assert_eq!(connectors[0]["candidate_files"], 1);
+    assert_eq!(json["total_candidates"], 1);
+
+    assert!(!data_dir.join("agent_search.db").exists());
+    assert!(!data_dir.join("index").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn read_only_data_dir_rejects_index_but_still_searches() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = TempDir::new().unwrap();
+    let home = tmp.path();
+    let codex_home = home.join(".codex");
+    let data_dir = home.join("cass_data");
+    fs::create_dir_all(&data_dir).unwrap();
+    make_codex_session(
+        &codex_home,
+        "2025/11/20",
+        "rollout-1.jsonl",
+        "readonly_content",
+    );
+    let mut index = base_cmd(home);
+    index.env("CODEX_HOME", &codex_home);
+    index.args(["index", "--data-dir", data_dir.to_str().unwrap(), "--json"]);
+    index.assert().success();
+
+    fs::set_permissions(&data_dir, fs::Permissions::from_mode(0o555)).unwrap();
+    let restore = || fs::set_permissions(&data_dir, fs::Permissions::from_mode(0o755)).unwrap();
+    if fs::write(data_dir.join("probe"), "").is_ok() {
+        // Running as root: permission bits don't apply, so there is nothing to simulate.
+        restore();
+        return;
+    }
+
+    let mut cmd = base_cmd(home);
+    cmd.env("CODEX_HOME", &codex_home);
+    cmd.args(["index", "--data-dir", data_dir.to_str().unwrap(), "--json"]);
+    let index_out = cmd.output().expect("index command");
+    let mut search = base_cmd(home);
+    search.args([
+        "search",
+        "readonly_content",
+        "--robot",
+        "--data-dir",
+        data_dir.to_str().unwrap(),
+    ]);
+    let search_out = search.output().expect("search command");
+    restore();
+
+    assert_eq!(index_out.status.code(), Some(7));
+    let stderr = String::from_utf8_lossy(&index_out.stderr);
+    assert!(stderr.contains("read-only-data-dir"), "{stderr}");
+    assert!(
+        stderr.contains("--data-dir"),
+        "hint names --data-dir: {stderr}"
+    );
+
+    assert!(
+        search_out.status.success(),
+        "search works on a read-only data dir"
+    );
+    let json: serde_json::Value = serde_json::from_slice(&search_out.stdout).expect("valid json");
+    assert!(!json["hits"].as_array().expect("hits").is_empty());
+}
@@ -886,6 +886,520 @@ fn search_robot_meta_includes_fallback_and_cache_stats() {
     );
 }
 
+#[test]
+fn compact_flag_prints_single_line_json() {
+    let data_dir = "tests/fixtures/search_demo_data";
+    for args in [
+        vec!["search", "hello", "--json", "--compact", "--limit", "2"],
+        vec!["stats", "--json", "--compact"],
+    ] {
+        let out = base_cmd()
+            .args(&args)
+            .args(["--data-dir", data_dir])
+            .assert()
+            .success()
+            .get_output()
+            .clone();
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        assert_eq!(stdout.trim_end().lines().count(), 1, "{args:?}: {stdout}");
+        let _: Value = serde_json::from_str(stdout.trim()).expect("valid JSON");
+    }
+
+    // Without --compact the same output is pretty-printed.
+    let out = base_cmd()
+        .args(["stats", "--json", "--data-dir", data_dir])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert!(String::from_utf8_lossy(&out.stdout).lines().count() > 1);
+}
+
+#[test]
+fn stats_porcelain_prints_stable_key_value_lines() {
+    let data_dir = "tests/fixtures/search_demo_data";
+    let out = base_cmd()
+        .args(["stats", "--porcelain", "--data-dir", data_dir])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let keys: Vec<&str> = stdout
+        .lines()
+        .map(|line| line.rsplit_once('=').expect("key=value").0)
+        .collect();
+    assert_eq!(&keys[..2], ["conversations", "messages"]);
+    assert!(keys.iter().any(|k| k.starts_with("agent.")), "{stdout}");
+    assert_eq!(&keys[keys.len() - 3..], ["oldest", "newest", "last_scan"]);
+
+    let json: Value = {
+        let out = base_cmd()
+            .args(["stats", "--json", "--data-dir", data_dir])
+            .output()
+            .expect("run stats");
+        serde_json::from_slice(&out.stdout).expect("valid json")
+    };
+    assert!(stdout.contains(&format!("conversations={}\n", json["conversations"])));
+}
+
+#[test]
+fn search_limit_above_ceiling_is_usage_error() {
+    let data_dir = "tests/fixtures/search_demo_data";
+    let out = base_cmd()
+        .args(["search", "hello", "--json", "--limit", "100000000"])
+        .args(["--data-dir", data_dir])
+        .output()
+        .expect("run search");
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("10000"));
+
+    let out = base_cmd()
+        .args(["search", "hello", "--json", "--limit", "6"])
+        .args(["--data-dir", data_dir])
+        .env("CASS_MAX_LIMIT", "5")
+        .output()
+        .expect("run search");
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn human_search_warns_when_page_is_full() {
+    let out = base_cmd()
+        .args(["search", "hello", "--limit", "1"])
+        .args(["--data-dir", "tests/fixtures/search_demo_data"])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("showing first 1 of possibly more"),
+        "stderr: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+#[test]
+fn human_search_tags_hits_with_match_type() {
+    // Wildcard queries need a Tantivy index; the shared fixture only has SQLite.
+    let tmp = TempDir::new().unwrap();
+    let sessions = tmp.path().join(".codex/sessions/2025/11/20");
+    fs::create_dir_all(&sessions).unwrap();
+    fs::write(
+        sessions.join("rollout-1.jsonl"),
+        r#"{"type": "event_msg", "timestamp": 1732118400000, "payload": {"type": "user_message", "message": "hello world"}}"#,
+    )
+    .unwrap();
+    let data_dir = tmp.path().join("data");
+    let with_env = |mut cmd: Command| {
+        cmd.env("HOME", tmp.path())
+            .env("XDG_DATA_HOME", tmp.path().join(".local/share"))
+            .env("XDG_CONFIG_HOME", tmp.path().join(".config"))
+            .env("CODEX_HOME", tmp.path().join(".codex"))
+            .arg("--data-dir")
+            .arg(&data_dir);
+        cmd
+    };
+    let mut index = base_cmd();
+    index.args(["index", "--full"]);
+    with_env(index).assert().success();
+
+    let search = |query: &str| {
+        let mut cmd = base_cmd();
+        cmd.args(["--color=never", "search", query, "--limit", "3"]);
+        let out = with_env(cmd)
+            .assert()
+            .success()
+            .get_output()
+            .clone();
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+    let exact = search("hello");
+    assert!(exact.contains("[exact] | Agent:"), "{exact}");
+    let prefix = search("hel*");
+    assert!(prefix.contains("[prefix] | Agent:"), "{prefix}");
+}
+
+#[test]
+fn config_path_honors_data_dir_and_db_overrides() {
+    let tmp = TempDir::new().unwrap();
+    let path = |what: &str, env_db: Option<&Path>| {
+        let mut cmd = base_cmd();
+        cmd.args(["config", "path", what])
+            .arg("--data-dir")
+            .arg(tmp.path())
+            .env_remove("CASS_DB_PATH");
+        if let Some(db) = env_db {
+            cmd.env("CASS_DB_PATH", db);
+        }
+        let out = cmd.assert().success().get_output().clone();
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    };
+    assert_eq!(path("data", None), tmp.path().display().to_string());
+    assert_eq!(
+        path("db", None),
+        tmp.path().join("agent_search.db").display().to_string()
+    );
+    let custom = tmp.path().join("custom.db");
+    assert_eq!(path("db", Some(&custom)), custom.display().to_string());
+
+    let out = base_cmd()
+        .args(["config", "show", "--json", "--data-dir"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let json: Value = serde_json::from_slice(&out.stdout).expect("valid json");
+    for key in ["data", "db", "index", "sources", "log"] {
+        assert!(json.get(key).is_some(), "missing {key}");
+    }
+}
+
+#[test]
+fn dedupe_collapses_hits_from_the_same_file() {
+    let search = |extra: &[&str]| {
+        let out = base_cmd()
+            .args(["search", "hello", "--json", "--limit", "20"])
+            .args(extra)
+            .args(["--data-dir", "tests/fixtures/search_demo_data"])
+            .output()
+            .expect("run search");
+        assert!(out.status.success());
+        let json: Value = serde_json::from_slice(&out.stdout).expect("valid json");
+        json["hits"].as_array().cloned().unwrap_or_default()
+    };
+    let all = search(&[]);
+    let deduped = search(&["--dedupe"]);
+
+    let mut paths: Vec<&str> = deduped
+        .iter()
+        .map(|h| h["source_path"].as_str().unwrap())
+        .collect();
+    let unique = paths.len();
+    paths.sort_unstable();
+    paths.dedup();
+    assert_eq!(paths.len(), unique, "one hit per source_path");
+    let represented: u64 = deduped
+        .iter()
+        .map(|h| 1 + h["duplicate_count"].as_u64().unwrap_or(0))
+        .sum();
+    assert_eq!(represented as usize, all.len());
+    assert!(all.iter().all(|h| h.get("duplicate_count").is_none()));
+}
+
+#[test]
+fn by_conversation_returns_one_hit_per_conversation() {
+    let search = |extra: &[&str]| {
+        let out = base_cmd()
+            .args(["search", "hello", "--json"])
+            .args(extra)
+            .args(["--data-dir", "tests/fixtures/search_demo_data"])
+            .output()
+            .expect("run search");
+        assert!(out.status.success());
+        let json: Value = serde_json::from_slice(&out.stdout).expect("valid json");
+        json["hits"].as_array().cloned().unwrap_or_default()
+    };
+    let all = search(&["--limit", "50"]);
+    let grouped = search(&["--limit", "50", "--by-conversation"]);
+
+    let conversations: HashSet<(&str, Option<&str>)> = grouped
+        .iter()
+        .map(|h| {
+            (
+                h["source_path"].as_str().unwrap(),
+                h["conversation_external_id"].as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        conversations.len(),
+        grouped.len(),
+        "one hit per conversation"
+    );
+    let matches: u64 = grouped
+        .iter()
+        .map(|h| h["message_matches"].as_u64().expect("message_matches"))
+        .sum();
+    assert_eq!(matches as usize, all.len());
+
+    // --limit counts conversations, not messages.
+    let first = search(&["--limit", "1", "--by-conversation"]);
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0]["source_path"], grouped[0]["source_path"]);
+}
+
+#[test]
+fn relative_paths_adds_workspace_relative_path_to_json_hits() {
+    let search = |extra: &[&str]| {
+        let out = base_cmd()
+            .args(["search", "hello", "--json", "--limit", "20"])
+            .args(extra)
+            .args(["--data-dir", "tests/fixtures/search_demo_data"])
+            .output()
+            .expect("run search");
+        assert!(out.status.success());
+        let json: Value = serde_json::from_slice(&out.stdout).expect("valid json");
+        json["hits"].as_array().cloned().unwrap_or_default()
+    };
+    let plain = search(&[]);
+    let relative = search(&["--relative-paths"]);
+
+    assert!(plain.iter().all(|h| h.get("relative_path").is_none()));
+    assert_eq!(plain.len(), relative.len());
+    for hit in &relative {
+        let source_path = hit["source_path"].as_str().unwrap();
+        if let Some(rel) = hit.get("relative_path").and_then(Value::as_str) {
+            let roots = [&hit["workspace"], &hit["workspace_original"]];
+            assert!(
+                roots.iter().filter_map(|ws| ws.as_str()).any(|ws| {
+                    std::path::Path::new(ws).join(rel) == std::path::Path::new(source_path)
+                }),
+                "{rel} is {source_path} relative to its workspace"
+            );
+        }
+    }
+}
+
+#[test]
+fn profile_reports_search_phase_timings() {
+    let out = base_cmd()
+        .args(["search", "hello", "--json", "--profile"])
+        .args(["--data-dir", "tests/fixtures/search_demo_data"])
+        .output()
+        .expect("run search");
+    assert!(out.status.success());
+    let json: Value = serde_json::from_slice(&out.stdout).expect("valid json");
+    let timings = json["timings_ms"].as_object().expect("timings_ms object");
+    for phase in ["open", "parse", "search", "highlight", "total"] {
+        assert!(timings[phase].as_f64().is_some(), "{phase} is a number");
+    }
+    assert!(timings["total"].as_f64() >= timings["search"].as_f64());
+
+    let out = base_cmd()
+        .args(["search", "hello", "--robot", "--limit", "1"])
+        .args(["--data-dir", "tests/fixtures/search_demo_data"])
+        .output()
+        .expect("run search");
+    let json: Value = serde_json::from_slice(&out.stdout).expect("valid json");
+    assert!(json.get("timings_ms").is_none(), "only with --profile");
+
+    let out = base_cmd()
+        .args(["search", "hello", "--profile"])
+        .args(["--data-dir", "tests/fixtures/search_demo_data"])
+        .output()
+        .expect("run search");
+    assert!(out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("timings: open "), "stderr: {stderr}");
+}
+
+#[test]
+fn no_snippet_returns_hits_without_text() {
+    let out = base_cmd()
+        .args(["search", "hello", "--json", "--no-snippet"])
+        .args(["--data-dir", "tests/fixtures/search_demo_data"])
+        .output()
+        .expect("run search");
+    assert!(out.status.success());
+    let json: Value = serde_json::from_slice(&out.stdout).expect("valid json");
+    let hits = json["hits"].as_array().expect("hits array");
+    assert!(!hits.is_empty());
+    for hit in hits {
+        assert_eq!(hit["snippet"], "");
+        assert_eq!(hit["content"], "");
+        assert!(hit["source_path"].as_str().is_some());
+        assert!(hit["line_number"].as_u64().is_some(), "line_number kept");
+    }
+}
+
+#[test]
+fn min_score_excludes_hits_below_threshold() {
+    let scores = |extra: &[&str]| -> Vec<f64> {
+        let out = base_cmd()
+            .args(["search", "hello", "--json", "--limit", "50"])
+            .args(extra)
+            .args(["--data-dir", "tests/fixtures/search_demo_data"])
+            .output()
+            .expect("run search");
+        assert!(out.status.success());
+        let json: Value = serde_json::from_slice(&out.stdout).expect("valid json");
+        json["hits"]
+            .as_array()
+            .expect("hits array")
+            .iter()
+            .map(|h| h["score"].as_f64().expect("score"))
+            .collect()
+    };
+    let all = scores(&[]);
+    assert!(!all.is_empty());
+    let best = all.iter().copied().fold(f64::MIN, f64::max);
+
+    let top = scores(&["--min-score-pct", "100"]);
+    assert!(!top.is_empty());
+    assert!(top.iter().all(|&s| s >= best - 1e-4));
+
+    // `=` keeps a negative threshold (SQLite fallback scores) from parsing as a flag.
+    let above_best = format!("--min-score={}", best + 1.0);
+    assert!(scores(&[&above_best]).is_empty());
+
+    base_cmd()
+        .args(["search", "hello", "--json", "--min-score-pct", "150"])
+        .args(["--data-dir", "tests/fixtures/search_demo_data"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn search_json_echoes_effective_filters() {
+    let out = base_cmd()
+        .args([
+            "search",
+            "role:user hello",
+            "--json",
+            "--workspace",
+            "/ws/a",
+        ])
+        .args(["--data-dir", "tests/fixtures/search_demo_data"])
+        .output()
+        .expect("run search");
+    assert!(out.status.success());
+    let json: Value = serde_json::from_slice(&out.stdout).expect("valid json");
+    let filters = &json["filters"];
+    assert_eq!(filters["workspaces"], serde_json::json!(["/ws/a"]));
+    assert_eq!(filters["roles"], serde_json::json!(["user"]));
+    assert_eq!(filters["agents"], serde_json::json!([]));
+    assert!(filters["created_from"].is_null());
+    assert!(
+        filters["data_dir"]
+            .as_str()
+            .expect("data_dir")
+            .ends_with("search_demo_data")
+    );
+    assert!(
+        filters["db_path"]
+            .as_str()
+            .expect("db_path")
+            .ends_with("agent_search.db")
+    );
+}
+
+#[test]
+fn display_table_fits_columns_to_wrap_width() {
+    let table = |global: &[&str]| -> String {
+        let out = base_cmd()
+            .args(["--color=never"])
+            .args(global)
+            .args(["search", "hello", "--format", "table", "--mark", "none"])
+            .args(["--data-dir", "tests/fixtures/search_demo_data"])
+            .output()
+            .expect("run search");
+        assert!(out.status.success());
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    let narrow = table(&["--wrap=70"]);
+    let header = narrow.lines().next().expect("header row");
+    for column in ["SCORE", "AGENT", "TIME", "PATH", "SNIPPET"] {
+        assert!(header.contains(column), "missing {column} column: {header}");
+    }
+    assert!(
+        narrow.lines().all(|line| line.chars().count() <= 70),
+        "rows should fit --wrap=70:\n{narrow}"
+    );
+
+    let wide = table(&["--wrap=70", "--nowrap"]);
+    assert!(
+        wide.lines().any(|line| line.chars().count() > 70),
+        "--nowrap should let rows overflow:\n{wide}"
+    );
+}
+
+#[test]
+fn mark_wraps_matched_terms_when_color_is_off() {
+    let search = |extra: &[&str]| -> String {
+        let out = base_cmd()
+            .args(["--color=never", "search", "hello", "--limit", "5"])
+            .args(extra)
+            .args(["--data-dir", "tests/fixtures/search_demo_data"])
+            .output()
+            .expect("run search");
+        assert!(out.status.success());
+        String::from_utf8_lossy(&out.stdout).to_lowercase()
+    };
+    assert!(search(&[]).contains("**hello**"));
+    assert!(search(&["--mark", "brackets"]).contains("[hello]"));
+    assert!(!search(&["--mark", "none"]).contains("**hello**"));
+    assert!(!search(&["--json", "--mark", "brackets"]).contains("[hello]"));
+}
+
+#[test]
+fn group_by_returns_groups_in_place_of_hits() {
+    let out = base_cmd()
+        .args([
+            "search",
+            "hello",
+            "--json",
+            "--group-by",
+            "agent",
+            "--limit",
+            "5",
+            "--data-dir",
+            "tests/fixtures/search_demo_data",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let json: Value = serde_json::from_slice(&out.stdout).expect("valid JSON");
+    assert!(json.get("hits").is_none(), "groups replace hits");
+    let groups = json["groups"].as_array().expect("groups array");
+    assert!(!groups.is_empty());
+
+    let mut total = 0;
+    for group in groups {
+        let key = group["key"].as_str().expect("group key");
+        let hits = group["hits"].as_array().expect("group hits");
+        assert_eq!(group["count"], hits.len());
+        assert!(hits.iter().all(|h| h["agent"] == key), "{group}");
+        total += hits.len();
+    }
+    assert_eq!(json["count"], total);
+}
+
+#[test]
+fn doctor_json_reports_connector_detection_evidence() {
+    let tmp = TempDir::new().unwrap();
+    let codex_home = tmp.path().join("codex");
+    fs::create_dir_all(codex_home.join("sessions")).unwrap();
+
+    let out = base_cmd()
+        .args(["doctor", "--json", "--data-dir"])
+        .arg(tmp.path().join("data"))
+        .env("HOME", tmp.path())
+        .env("CODEX_HOME", &codex_home)
+        .output()
+        .expect("run doctor");
+    let json: Value = serde_json::from_slice(&out.stdout).expect("valid JSON");
+    let connectors = json["connectors"].as_array().expect("connectors array");
+    let find = |name: &str| {
+        connectors
+            .iter()
+            .find(|c| c["name"] == name)
+            .unwrap_or_else(|| panic!("missing connector {name}"))
+    };
+
+    let codex = find("codex");
+    assert_eq!(codex["detected"], true);
+    assert!(codex["evidence"][0].as_str().unwrap().starts_with("found "));
+
+    let claude = find("claude");
+    assert_eq!(claude["detected"], false);
+    let evidence = claude["evidence"][0].as_str().unwrap();
+    assert!(evidence.starts_with("no ") && evidence.ends_with("projects"));
+}
+
 #[test]
 fn stats_json_reports_counts() {
     let mut cmd = base_cmd();
@@ -912,6 +1426,14 @@ fn stats_json_reports_counts() {
         json["by_agent"].is_array(),
         "stats should include per-agent breakdown"
     );
+    assert!(
+        json["last_scan"].is_object(),
+        "stats should include last_scan freshness"
+    );
+    assert!(
+        json["watch_state"].is_null() || json["watch_state"].is_object(),
+        "watch_state should be an object or null"
+    );
 }
 
 #[test]
@@ -1012,6 +1534,28 @@ fn view_json_highlights_requested_line() {
     );
 }
 
+#[test]
+fn view_reads_stdin_when_path_is_dash() {
+    let file = "tests/fixtures/amp/thread-001.json";
+    let out = base_cmd()
+        .args(["view", "-", "--json", "-n", "5", "-C", "0"])
+        .write_stdin(fs::read(file).unwrap())
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let json: Value = serde_json::from_slice(&out.stdout).expect("valid JSON");
+    assert_eq!(json["path"], "<stdin>");
+
+    let expected = base_cmd()
+        .args(["view", file, "--json", "-n", "5", "-C", "0"])
+        .output()
+        .expect("run view");
+    let expected: Value = serde_json::from_slice(&expected.stdout).expect("valid JSON");
+    assert_eq!(json["lines"], expected["lines"]);
+    assert_eq!(json["total_lines"], expected["total_lines"]);
+}
+
 #[test]
 fn introspect_json_lists_commands() {
     let mut cmd = base_cmd();
@@ -1058,7 +1602,14 @@ fn find_arg<'a>(cmd: &'a Value, name: &str) -> &'a Value {
 fn introspect_commands_match_clap_subcommands() {
     let json = fetch_introspect_json();
 
-    let clap_cmd = Cli::command();
+    // Building the full command tree needs more than the 2 MiB default test
+    // thread stack in debug builds (the binary's main thread has 8 MiB).
+    let clap_cmd = std::thread::Builder::new()
+        .stack_size(8 * 1024 * 1024)
+        .spawn(Cli::command)
+        .expect("spawn")
+        .join()
+        .expect("build clap command");
     let clap_commands: HashSet<String> = clap_cmd
         .get_subcommands()
         .map(|c: &clap::Command| c.get_name().to_string())
@@ -1269,6 +1820,26 @@ fn search_agent_filter_limits_hits() {
     }
 }
 
+#[test]
+fn search_unknown_agent_suggests_closest_indexed_slug() {
+    let out = base_cmd()
+        .args(["search", "hello", "--json", "--agent", "gemni"])
+        .args(["--data-dir", "tests/fixtures/search_demo_data"])
+        .output()
+        .expect("run search");
+    assert_eq!(out.status.code(), Some(2));
+    let err: Value = serde_json::from_slice(&out.stderr).expect("structured error");
+    assert_eq!(err["error"]["kind"], "unknown-agent");
+    assert_eq!(err["error"]["suggestion"], "gemini");
+
+    base_cmd()
+        .args(["search", "hello", "--agent", "gemni"])
+        .args(["--data-dir", "tests/fixtures/search_demo_data"])
+        .assert()
+        .code(2)
+        .stderr(contains("did you mean 'gemini'?"));
+}
+
 #[test]
 fn search_offset_skips_results() {
     // Offset should skip earlier hits while preserving order
@@ -1903,6 +2474,58 @@ fn aggregate_includes_total_matches() {
     );
 }
 
+#[test]
+fn search_with_total_reports_paging_fields() {
+    let search = |extra: &[&str]| -> Value {
+        let mut cmd = base_cmd();
+        cmd.args([
+            "search",
+            "hello",
+            "--json",
+            "--limit",
+            "1",
+            "--data-dir",
+            "tests/fixtures/search_demo_data",
+        ]);
+        cmd.args(extra);
+        let output = cmd.assert().success().get_output().clone();
+        serde_json::from_slice(&output.stdout).expect("valid JSON")
+    };
+
+    let plain = search(&[]);
+    assert!(
+        plain["total"].is_null(),
+        "total is null without --with-total"
+    );
+    assert!(plain["has_more"].is_null());
+
+    let json = search(&["--with-total"]);
+    let count = json["count"].as_u64().expect("count");
+    let total = json["total"].as_u64().expect("total with --with-total");
+    assert!(
+        total >= count,
+        "total ({total}) should cover the page ({count})"
+    );
+    assert_eq!(json["has_more"].as_bool(), Some(count < total));
+}
+
+#[test]
+fn search_follow_requires_terminal_plain_output() {
+    // JSON output and a piped stdout are both rejected up front rather than looping forever.
+    for extra in [&["--json"][..], &[][..]] {
+        let mut cmd = base_cmd();
+        cmd.args([
+            "search",
+            "hello",
+            "--follow",
+            "--data-dir",
+            "tests/fixtures/search_demo_data",
+        ]);
+        cmd.args(extra);
+        cmd.assert().code(2).stderr(contains("--follow"));
+    }
+}
+
 #[test]
 fn aggregate_with_limit_returns_both_hits_and_aggs() {
     // rob.flow.agg: --aggregate with --limit returns both aggregations and hits
@@ -2660,6 +3283,51 @@ fn robot_docs_wrap_explains_wrapping() {
     );
 }
 
+/// robot-docs --json wraps each topic in a versioned envelope
+#[test]
+fn robot_docs_json_emits_structured_topics() {
+    let docs = |topic: &str| -> Value {
+        let out = base_cmd()
+            .args(["robot-docs", topic, "--json"])
+            .output()
+            .expect("run robot-docs");
+        assert!(out.status.success());
+        let json: Value = serde_json::from_slice(&out.stdout).expect("valid json");
+        assert_eq!(json["contract_version"], "1");
+        assert_eq!(json["topic"], topic);
+        json["data"].clone()
+    };
+
+    let commands = docs("commands");
+    let search = commands["commands"]
+        .as_array()
+        .expect("commands array")
+        .iter()
+        .find(|c| c["name"] == "search")
+        .expect("search command");
+    assert!(
+        search["arguments"]
+            .as_array()
+            .expect("arguments")
+            .iter()
+            .any(|a| a["name"] == "limit" && a["description"].is_string())
+    );
+
+    let exit_codes = docs("exit-codes");
+    assert_eq!(exit_codes["0"], "ok");
+    assert_eq!(exit_codes["3"], "missing index/db");
+
+    let paths = docs("paths");
+    for key in ["data", "db", "index", "log"] {
+        let path = paths[key].as_str().expect("resolved path");
+        assert!(std::path::Path::new(path).is_absolute(), "{key}: {path}");
+    }
+
+    assert!(docs("env")["CASS_DATA_DIR"].is_string());
+    assert!(docs("schemas")["search"].is_object());
+    assert!(!docs("guide")["lines"].as_array().expect("lines").is_empty());
+}
+
 // =============================================================================
 // ege.10: Golden Contract Tests
 // =============================================================================
@@ -3574,3 +4242,40 @@ fn introspect_all_integer_options_documented() {
         "health --stale-threshold should be integer type"
     );
 }
+
+#[test]
+fn completions_output_writes_conventional_files() {
+    let tmp = TempDir::new().unwrap();
+    let dir = tmp.path().join("completions");
+
+    let mut cmd = base_cmd();
+    cmd.args(["completions", "zsh", "--output"]).arg(&dir);
+    cmd.assert().success().stdout(contains("_cass"));
+    assert!(dir.join("_cass").is_file());
+
+    let mut cmd = base_cmd();
+    cmd.args(["completions", "--all", "--output"]).arg(&dir);
+    cmd.assert().success();
+    for name in ["cass.bash", "cass.fish", "_cass", "_cass.ps1", "cass.elv"] {
+        assert!(dir.join(name).is_file(), "missing {name}");
+    }
+
+    let mut cmd = base_cmd();
+    cmd.args(["completions", "--all"]);
+    cmd.assert().failure();
+}
+
+#[test]
+fn no_update_check_is_a_documented_global_flag() {
+    let mut cmd = base_cmd();
+    cmd.args(["robot-docs", "env", "--no-update-check"]);
+    cmd.assert().success().stdout(contains("--no-update-check"));
+
+    let json = fetch_introspect_json();
+    let globals = json["global_flags"].as_array().expect("global_flags array");
+    assert!(
+        globals
+            .iter()
+            .any(|f| f["name"] == "no-update-check" && f["arg_type"] == "flag")
+    );
+}
@@ -30,6 +30,12 @@ fn aider_parses_chat_history() {
         data_dir: fixture_root,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -75,6 +81,12 @@ fn aider_sets_agent_slug() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -93,6 +105,12 @@ fn aider_sets_source_path() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -111,6 +129,12 @@ fn aider_sets_external_id_from_filename() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -132,6 +156,12 @@ fn aider_title_includes_path() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -156,6 +186,12 @@ fn aider_sets_workspace_to_parent() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -179,6 +215,12 @@ fn aider_timestamps_from_mtime() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -202,6 +244,12 @@ fn aider_since_ts_filters_old_files() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: Some(future_ts),
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -220,6 +268,12 @@ fn aider_no_since_ts_includes_all() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -245,6 +299,12 @@ fn aider_message_indices_sequential() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -272,6 +332,12 @@ fn aider_author_matches_role() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -296,6 +362,12 @@ fn aider_user_messages_from_prefix() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -325,6 +397,12 @@ fn aider_multiline_user_input() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -354,6 +432,12 @@ fn aider_assistant_after_user() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -381,6 +465,12 @@ fn aider_multiple_turns() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -414,6 +504,12 @@ fn aider_empty_file() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -432,6 +528,12 @@ fn aider_whitespace_only_file() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -454,6 +556,12 @@ fn aider_only_user_messages() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -479,6 +587,12 @@ fn aider_no_user_prefix_content() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -520,6 +634,12 @@ fn aider_scans_subdirectories() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -542,6 +662,12 @@ fn aider_only_scans_chat_history_files() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -569,6 +695,12 @@ fn aider_multiple_projects() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -602,6 +734,12 @@ fn aider_preserves_commands() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -627,6 +765,12 @@ fn aider_code_blocks_in_response() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -654,6 +798,12 @@ fn aider_markdown_formatting() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -683,6 +833,12 @@ fn aider_gt_in_code_not_user_input() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -784,6 +940,12 @@ fn aider_metadata_is_empty() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -802,6 +964,12 @@ fn aider_message_extra_is_empty() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -822,6 +990,12 @@ fn aider_message_created_at_is_none() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -842,6 +1016,12 @@ fn aider_message_snippets_empty() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -865,6 +1045,12 @@ fn aider_nonexistent_directory() {
         // Provide explicit scan_roots to disable default detection fallback to CWD/home
         scan_roots: vec![ScanRoot::local(nonexistent)],
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -881,6 +1067,12 @@ fn aider_empty_directory() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -903,6 +1095,12 @@ fn aider_long_user_input() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -927,6 +1125,12 @@ fn aider_special_characters() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -952,6 +1156,12 @@ fn aider_blank_lines_between_messages() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -976,6 +1186,12 @@ fn aider_consecutive_user_lines_combined() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
@@ -1005,6 +1221,12 @@ fn aider_trailing_whitespace() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
 
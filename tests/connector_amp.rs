@@ -24,6 +24,12 @@ fn amp_parses_minimal_cache() {
         data_dir: fixture_root.clone(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty(), "expected at least one conversation");
@@ -44,6 +50,12 @@ fn amp_includes_all_messages_when_file_modified() {
         data_dir: fixture_root.clone(),
         scan_roots: Vec::new(),
         since_ts: Some(1_700_000_000_000),
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert_eq!(convs.len(), 1);
@@ -91,6 +103,12 @@ fn amp_skips_malformed_json() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     // Should not panic, should return only the valid session
@@ -136,6 +154,12 @@ fn amp_parses_alternate_fields() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -183,6 +207,12 @@ fn amp_handles_timestamp_formats() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -230,6 +260,12 @@ fn amp_extracts_workspace() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 2);
@@ -279,6 +315,12 @@ fn amp_handles_nested_structure() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -318,6 +360,12 @@ fn amp_extracts_title() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
 
@@ -356,6 +404,12 @@ fn amp_detects_valid_files() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
 
@@ -387,6 +441,12 @@ fn amp_normalizes_roles() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -429,6 +489,12 @@ fn amp_extracts_external_id() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
 
@@ -479,6 +545,12 @@ fn amp_filters_empty_content() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -514,6 +586,12 @@ fn amp_extracts_author_field() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -540,6 +618,12 @@ fn amp_handles_empty_directory() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert!(convs.is_empty());
@@ -565,6 +649,12 @@ fn amp_sets_correct_agent_slug() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -588,6 +678,12 @@ fn amp_sets_source_path() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -618,6 +714,12 @@ fn amp_computes_started_ended_at() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -652,6 +754,12 @@ fn amp_assigns_sequential_indices() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -728,6 +836,12 @@ fn amp_workspace_from_alternate_keys() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 5);
@@ -799,6 +913,12 @@ fn amp_skips_json_without_messages() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
 
@@ -834,6 +954,12 @@ fn amp_handles_camel_case_timestamps() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -873,6 +999,12 @@ fn amp_scans_nested_directories() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
 
@@ -913,6 +1045,12 @@ fn amp_filters_whitespace_content() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -958,6 +1096,12 @@ fn amp_skips_empty_content_conversations() {
         data_dir: amp_dir,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
 
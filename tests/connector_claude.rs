@@ -21,6 +21,12 @@ fn claude_parses_project_fixture() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert_eq!(convs.len(), 1);
@@ -63,6 +69,12 @@ fn claude_connector_parses_jsonl_format() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -103,6 +115,12 @@ fn claude_connector_filters_summary_entries() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -133,6 +151,12 @@ fn claude_connector_extracts_model_as_author() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -159,6 +183,12 @@ fn claude_connector_flattens_tool_use() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -187,6 +217,12 @@ fn claude_connector_extracts_title_from_user() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -211,6 +247,12 @@ fn claude_connector_title_fallback_to_workspace() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -238,6 +280,12 @@ also not json
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -263,6 +311,12 @@ fn claude_connector_filters_empty_content() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -290,6 +344,12 @@ fn claude_connector_assigns_sequential_indices() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -322,6 +382,12 @@ fn claude_connector_handles_multiple_files() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 3);
@@ -349,6 +415,12 @@ fn claude_connector_parses_json_format() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -379,6 +451,12 @@ fn claude_connector_parses_claude_extension() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -397,6 +475,12 @@ fn claude_connector_handles_empty_directory() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert!(convs.is_empty());
@@ -419,6 +503,12 @@ fn claude_connector_sets_external_id() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -445,6 +535,12 @@ fn claude_connector_sets_source_path() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -469,6 +565,12 @@ fn claude_connector_parses_timestamps() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -500,6 +602,12 @@ fn claude_connector_truncates_long_title() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -536,6 +644,12 @@ fn claude_connector_ignores_other_extensions() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -558,6 +672,12 @@ fn claude_connector_handles_nested_projects() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -582,6 +702,12 @@ fn claude_connector_uses_entry_type_as_role() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -613,6 +739,12 @@ fn connector_handles_various_timezone_formats() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -652,6 +784,12 @@ fn connector_handles_epoch_and_iso_timestamps() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -689,6 +827,12 @@ fn connector_symlinked_directories_not_followed_by_default() {
         data_dir: mock_claude,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     // Current behavior: symlinks are not followed, so directory symlinks result in empty scan
@@ -725,6 +869,12 @@ fn connector_follows_symlinked_files() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     // File symlinks are typically followed when reading, but walkdir may not report them as files
@@ -768,6 +918,12 @@ fn connector_handles_unreadable_files() {
                 data_dir: dir.path().join("mock-claude"),
                 scan_roots: Vec::new(),
                 since_ts: None,
+                parse_cache: None,
+                include_globs: Vec::new(),
+                exclude_globs: Vec::new(),
+                max_file_bytes: None,
+                oversized_files: Default::default(),
+                strict: false,
             };
             // Should not panic, just skip the unreadable file
             let result = conn.scan(&ctx);
@@ -810,6 +966,12 @@ fn connector_handles_long_file_paths() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -834,6 +996,12 @@ fn connector_handles_special_chars_in_paths() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -856,6 +1024,12 @@ fn connector_handles_unicode_in_paths() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -875,6 +1049,12 @@ fn connector_handles_empty_project_dirs() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert!(convs.is_empty());
@@ -907,6 +1087,12 @@ fn connector_respects_since_ts_filter() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -916,6 +1102,12 @@ fn connector_respects_since_ts_filter() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: Some(mtime_millis + 3_600_000), // 1 hour in the future
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert!(convs.is_empty(), "Future since_ts should skip the file");
@@ -925,6 +1117,12 @@ fn connector_respects_since_ts_filter() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: Some(mtime_millis - 3_600_000), // 1 hour in the past
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1, "Past since_ts should include the file");
@@ -16,6 +16,12 @@ fn cline_parses_fixture_task() {
         data_dir: fixture_root.clone(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert_eq!(convs.len(), 1);
@@ -66,6 +72,12 @@ fn cline_respects_since_ts_and_resequences_indices() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: Some(1_500),
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let convs = connector.scan(&ctx).unwrap();
@@ -120,6 +132,12 @@ fn cline_prefers_ui_messages() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -147,6 +165,12 @@ fn cline_fallback_to_api_history() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -171,6 +195,12 @@ fn cline_handles_multiple_tasks() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 3);
@@ -196,6 +226,12 @@ fn cline_skips_task_history_json() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -219,6 +255,12 @@ fn cline_extracts_title_from_metadata() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -242,6 +284,12 @@ fn cline_title_fallback_to_first_message() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -265,6 +313,12 @@ fn cline_extracts_workspace_from_rootpath() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -291,6 +345,12 @@ fn cline_extracts_workspace_from_cwd() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -318,6 +378,12 @@ fn cline_filters_empty_content() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -344,6 +410,12 @@ fn cline_sorts_messages_by_timestamp() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -374,6 +446,12 @@ fn cline_sets_external_id_from_directory() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -394,6 +472,12 @@ fn cline_sets_source_path_to_task_dir() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -410,6 +494,12 @@ fn cline_handles_empty_directory() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert!(convs.is_empty());
@@ -427,6 +517,12 @@ fn cline_skips_task_without_messages() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert!(convs.is_empty());
@@ -449,6 +545,12 @@ fn cline_sets_started_and_ended_at() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -470,6 +572,12 @@ fn cline_sets_agent_slug() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -493,6 +601,12 @@ fn cline_parses_alternate_content_fields() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -522,6 +636,12 @@ fn cline_parses_alternate_timestamp_fields() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -545,6 +665,12 @@ fn cline_uses_type_as_role_fallback() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -568,6 +694,12 @@ fn cline_truncates_long_title() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -589,6 +721,12 @@ fn cline_sets_metadata_source() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -617,6 +755,12 @@ fn cline_ignores_files_in_root() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -638,6 +782,12 @@ fn cline_parses_iso_timestamps() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
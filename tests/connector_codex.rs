@@ -30,6 +30,12 @@ fn codex_connector_reads_modern_envelope_jsonl() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -69,6 +75,12 @@ fn codex_connector_includes_agent_reasoning() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -117,6 +129,12 @@ fn codex_connector_filters_token_count() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -165,6 +183,12 @@ fn codex_connector_respects_since_ts_at_file_level_only() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: Some(1_700_000_000_000),
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -222,6 +246,12 @@ fn codex_connector_reads_legacy_json_format() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -301,6 +331,12 @@ fn codex_connector_handles_user_message_event() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -339,6 +375,12 @@ also not valid
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -375,6 +417,12 @@ fn codex_connector_handles_multiple_sessions() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 3);
@@ -409,6 +457,12 @@ fn codex_connector_filters_empty_content() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -444,6 +498,12 @@ fn codex_connector_extracts_title() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -478,6 +538,12 @@ fn codex_connector_assigns_sequential_indices() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -512,6 +578,12 @@ fn codex_connector_sets_external_id_from_filename() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -542,6 +614,12 @@ fn codex_connector_handles_empty_sessions() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert!(convs.is_empty());
@@ -572,6 +650,12 @@ fn codex_connector_parses_millis_timestamp() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -609,6 +693,12 @@ fn codex_connector_flattens_tool_use_blocks() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -647,6 +737,12 @@ fn codex_connector_handles_missing_cwd() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -690,6 +786,12 @@ fn codex_connector_ignores_non_rollout_files() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     // Only the rollout- prefixed file should be processed
@@ -730,6 +832,12 @@ fn codex_connector_handles_legacy_json_missing_session() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -764,6 +872,12 @@ fn codex_connector_title_fallback_to_first_message() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -796,6 +910,12 @@ fn codex_connector_handles_nested_directories() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -827,6 +947,12 @@ fn codex_connector_filters_turn_aborted() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -865,6 +991,12 @@ fn codex_connector_truncates_long_title() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -897,6 +1029,12 @@ fn codex_connector_sets_source_path() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -934,6 +1072,12 @@ fn codex_connector_metadata_indicates_format() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 2);
@@ -951,3 +1095,185 @@ fn codex_connector_metadata_indicates_format() {
         Some("rollout_json")
     );
 }
+
+#[test]
+#[serial]
+fn codex_connector_reuses_parse_cache_for_unchanged_files() {
+    use coding_agent_search::connectors::parse_cache::ParseCache;
+    use std::sync::Arc;
+
+    let dir = TempDir::new().unwrap();
+    let cache_dir = TempDir::new().unwrap();
+    let sessions = dir.path().join("sessions/2025/11/23");
+    fs::create_dir_all(&sessions).unwrap();
+    let sample = r#"{"timestamp":"2025-09-30T15:42:34.559Z","type":"session_meta","payload":{"id":"test-id","cwd":"/test"}}
+{"timestamp":"2025-09-30T15:42:36.190Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"cache me"}]}}
+"#;
+    fs::write(sessions.join("rollout-a.jsonl"), sample).unwrap();
+    fs::write(sessions.join("rollout-b.jsonl"), sample).unwrap();
+
+    unsafe {
+        std::env::set_var("CODEX_HOME", dir.path());
+    }
+
+    let connector = CodexConnector::new();
+    let scan = |cache: &Arc<ParseCache>| {
+        let ctx = ScanContext::local_default(dir.path().to_path_buf(), None)
+            .with_parse_cache(Some(cache.clone()));
+        connector.scan(&ctx).unwrap()
+    };
+
+    let first = Arc::new(ParseCache::new(cache_dir.path(), true));
+    let fresh = scan(&first);
+    assert_eq!(fresh.len(), 2);
+    assert_eq!((first.hits(), first.misses()), (0, 2));
+
+    let second = Arc::new(ParseCache::new(cache_dir.path(), true));
+    let cached = scan(&second);
+    assert_eq!((second.hits(), second.misses()), (2, 0));
+    assert_eq!(cached.len(), 2);
+    assert_eq!(cached[0].title, fresh[0].title);
+    assert_eq!(cached[0].messages.len(), fresh[0].messages.len());
+
+    let ignored = Arc::new(ParseCache::new(cache_dir.path(), false));
+    assert_eq!(scan(&ignored).len(), 2);
+    assert_eq!((ignored.hits(), ignored.misses()), (0, 2));
+}
+
+/// `exclude_globs` drops matching files before they are parsed; `include_globs` keeps only matches.
+#[test]
+#[serial]
+fn codex_connector_honors_include_and_exclude_globs() {
+    let dir = TempDir::new().unwrap();
+    let sample = r#"{"timestamp":"2025-09-30T15:42:34.559Z","type":"session_meta","payload":{"id":"test-id","cwd":"/test"}}
+{"timestamp":"2025-09-30T15:42:36.190Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"filter me"}]}}
+"#;
+    for day in ["2025/11/23", "2025/11/24"] {
+        let sessions = dir.path().join("sessions").join(day);
+        fs::create_dir_all(&sessions).unwrap();
+        fs::write(sessions.join("rollout-1.jsonl"), sample).unwrap();
+    }
+
+    unsafe {
+        std::env::set_var("CODEX_HOME", dir.path());
+    }
+
+    let connector = CodexConnector::new();
+    let globs = |patterns: &[&str]| -> Vec<glob::Pattern> {
+        patterns
+            .iter()
+            .map(|p| glob::Pattern::new(p).unwrap())
+            .collect()
+    };
+    let scan = |include: &[&str], exclude: &[&str]| {
+        let ctx = ScanContext::local_default(dir.path().to_path_buf(), None)
+            .with_path_filters(globs(include), globs(exclude));
+        connector.scan(&ctx).unwrap()
+    };
+
+    assert_eq!(scan(&[], &[]).len(), 2);
+
+    let excluded = scan(&[], &["**/11/23/*"]);
+    assert_eq!(excluded.len(), 1);
+    assert!(excluded[0].source_path.to_string_lossy().contains("11/24"));
+
+    let included = scan(&["**/11/23/*"], &[]);
+    assert_eq!(included.len(), 1);
+    assert!(included[0].source_path.to_string_lossy().contains("11/23"));
+
+    assert!(scan(&["**/11/23/*"], &["**/rollout-1.jsonl"]).is_empty());
+}
+
+/// Files over `max_file_bytes` are skipped and counted; smaller files still parse.
+#[test]
+#[serial]
+fn codex_connector_skips_files_over_max_file_bytes() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let dir = TempDir::new().unwrap();
+    let sessions = dir.path().join("sessions/2025/11/23");
+    fs::create_dir_all(&sessions).unwrap();
+    let sample = r#"{"timestamp":"2025-09-30T15:42:34.559Z","type":"session_meta","payload":{"id":"test-id","cwd":"/test"}}
+{"timestamp":"2025-09-30T15:42:36.190Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"small"}]}}
+"#;
+    fs::write(sessions.join("rollout-small.jsonl"), sample).unwrap();
+    let big = sample.replace("small", &"x".repeat(4096));
+    fs::write(sessions.join("rollout-big.jsonl"), big).unwrap();
+
+    unsafe {
+        std::env::set_var("CODEX_HOME", dir.path());
+    }
+
+    let connector = CodexConnector::new();
+    let skipped = Arc::new(AtomicUsize::new(0));
+    let ctx = ScanContext::local_default(dir.path().to_path_buf(), None)
+        .with_max_file_bytes(Some(1024), skipped.clone());
+    let convs = connector.scan(&ctx).unwrap();
+
+    assert_eq!(convs.len(), 1);
+    assert!(convs[0].source_path.ends_with("rollout-small.jsonl"));
+    assert_eq!(skipped.load(Ordering::Relaxed), 1);
+
+    let unlimited = ScanContext::local_default(dir.path().to_path_buf(), None);
+    assert_eq!(connector.scan(&unlimited).unwrap().len(), 2);
+}
+
+/// `history.jsonl` is opt-in; its prompts become one conversation per day, minus
+/// prompts already indexed from session files.
+#[test]
+#[serial]
+fn codex_connector_indexes_history_by_day_when_enabled() {
+    let fixture = PathBuf::from("tests/fixtures/codex_history");
+    unsafe {
+        std::env::set_var("CODEX_HOME", &fixture);
+        std::env::remove_var("CASS_CODEX_HISTORY");
+    }
+    let connector = CodexConnector::new();
+    let ctx = ScanContext::local_default(fixture.clone(), None);
+
+    let convs = connector.scan(&ctx).unwrap();
+    assert_eq!(convs.len(), 1, "history is off by default");
+
+    unsafe {
+        std::env::set_var("CASS_CODEX_HISTORY", "1");
+    }
+    let convs = connector.scan(&ctx).unwrap();
+    unsafe {
+        std::env::remove_var("CASS_CODEX_HISTORY");
+    }
+
+    let history: Vec<_> = convs
+        .iter()
+        .filter(|c| c.metadata["source"] == "history")
+        .collect();
+    let ids: Vec<_> = history
+        .iter()
+        .map(|c| c.external_id.as_deref().unwrap())
+        .collect();
+    assert_eq!(ids, ["history/2025-03-01", "history/2025-03-02"]);
+    assert!(history.iter().all(|c| c.agent_slug == "codex"));
+    assert!(
+        history
+            .iter()
+            .all(|c| c.source_path.ends_with("history.jsonl"))
+    );
+
+    // The session's own prompt is skipped, both by session id and by text.
+    let first: Vec<_> = history[0]
+        .messages
+        .iter()
+        .map(|m| m.content.as_str())
+        .collect();
+    assert_eq!(
+        first,
+        [
+            "explain the caching layer in api/cache.rs",
+            "now write tests for the cache eviction"
+        ]
+    );
+    assert_eq!(history[0].messages[1].idx, 1);
+    assert_eq!(history[0].started_at, Some(1_740_823_200_000));
+    assert_eq!(history[1].messages.len(), 1);
+    assert_eq!(history[1].messages[0].role, "user");
+}
@@ -0,0 +1,77 @@
+use coding_agent_search::connectors::copilot::CopilotConnector;
+use coding_agent_search::connectors::{Connector, ScanContext};
+use std::path::PathBuf;
+
+// ============================================================================
+// Fixture-based tests
+// ============================================================================
+
+fn scan_fixture() -> Vec<coding_agent_search::connectors::NormalizedConversation> {
+    let ctx = ScanContext::local_default(PathBuf::from("tests/fixtures/copilot/User"), None);
+    let mut convs = CopilotConnector::new().scan(&ctx).expect("scan");
+    convs.sort_by(|a, b| a.source_path.cmp(&b.source_path));
+    convs
+}
+
+#[test]
+fn copilot_parses_workspace_and_empty_window_sessions() {
+    let convs = scan_fixture();
+    assert_eq!(convs.len(), 2);
+    assert!(convs.iter().all(|c| c.agent_slug == "copilot"));
+
+    let global = &convs[0];
+    assert!(global.source_path.ends_with(
+        "globalStorage/emptyWindowChatSessions/3e2d1c0b-aaaa-4bbb-8ccc-1234567890ab.json"
+    ));
+    assert_eq!(global.workspace, None);
+    assert_eq!(
+        global.title.as_deref(),
+        Some("What does the borrow checker enforce?")
+    );
+    assert_eq!(global.messages.len(), 2);
+    assert!(global.messages[1].content.contains("never outlive"));
+
+    let workspace = &convs[1];
+    assert_eq!(
+        workspace.workspace,
+        Some(PathBuf::from("/home/dev/my service"))
+    );
+    assert_eq!(
+        workspace.external_id.as_deref(),
+        Some("0b6f3c2e-5d41-4a8e-9f10-2c7a1e8d9b34")
+    );
+    assert_eq!(
+        workspace.title.as_deref(),
+        Some("Retry policy for the HTTP client")
+    );
+    assert_eq!(workspace.started_at, Some(1_718_000_000_000));
+    assert_eq!(workspace.ended_at, Some(1_718_000_065_000));
+}
+
+#[test]
+fn copilot_joins_streamed_response_parts() {
+    let convs = scan_fixture();
+    let msgs = &convs[1].messages;
+    let roles: Vec<_> = msgs.iter().map(|m| m.role.as_str()).collect();
+    assert_eq!(roles, ["user", "assistant", "user", "assistant"]);
+    assert_eq!(
+        msgs[1].content,
+        "Wrap each request in a retry loop that doubles the delay after every failed attempt, capped at 30 seconds."
+    );
+    assert_eq!(
+        msgs[3].content,
+        "Yes: add random jitter so clients do not retry in lockstep."
+    );
+    assert_eq!(msgs[1].created_at, Some(1_718_000_014_000));
+    assert!(msgs.iter().enumerate().all(|(i, m)| m.idx == i as i64));
+}
+
+#[test]
+fn copilot_respects_since_ts() {
+    let ctx = ScanContext::local_default(
+        PathBuf::from("tests/fixtures/copilot/User"),
+        Some(i64::MAX / 2),
+    );
+    let convs = CopilotConnector::new().scan(&ctx).expect("scan");
+    assert!(convs.is_empty());
+}
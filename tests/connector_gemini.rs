@@ -12,6 +12,12 @@ fn gemini_parses_jsonl_fixture() {
         data_dir: fixture_root.clone(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(
@@ -32,6 +38,12 @@ fn gemini_maps_model_role_to_assistant() {
         data_dir: fixture_root,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -51,6 +63,12 @@ fn gemini_extracts_metadata() {
         data_dir: fixture_root,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -72,6 +90,12 @@ fn gemini_parses_timestamps() {
         data_dir: fixture_root,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -147,6 +171,12 @@ fn gemini_includes_all_messages_when_file_modified() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: Some(since_ts),
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -194,6 +224,12 @@ fn gemini_extracts_workspace_from_agents_md_content() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -235,6 +271,12 @@ fn gemini_extracts_workspace_from_working_directory() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -283,6 +325,12 @@ fn gemini_filters_empty_messages() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -329,6 +377,12 @@ fn gemini_skips_malformed_json() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     // Should not panic, should return only the valid session
@@ -361,6 +415,12 @@ fn gemini_skips_sessions_without_messages() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let convs = conn.scan(&ctx).expect("scan");
@@ -402,6 +462,12 @@ fn gemini_extracts_title_from_first_user_message() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -453,6 +519,12 @@ fn gemini_assigns_sequential_message_indices() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -473,6 +545,12 @@ fn gemini_sets_agent_slug() {
         data_dir: fixture_root,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -491,6 +569,12 @@ fn gemini_sets_source_path() {
         data_dir: fixture_root.clone(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -533,6 +617,12 @@ fn gemini_handles_multiple_sessions() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert_eq!(convs.len(), 3);
@@ -569,6 +659,12 @@ fn gemini_falls_back_to_hash_directory_for_workspace() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert!(!convs.is_empty());
@@ -584,3 +680,67 @@ fn gemini_falls_back_to_hash_directory_for_workspace() {
             .contains("myhash")
     );
 }
+
+/// A corrupt session file is skipped while the good one next to it still indexes
+#[test]
+fn gemini_skips_corrupt_session_file() {
+    let conn = GeminiConnector::new();
+    let ctx = ScanContext::local_default(PathBuf::from("tests/fixtures/gemini_corrupt"), None);
+    let convs = conn.scan(&ctx).expect("scan");
+    assert_eq!(convs.len(), 1);
+    assert_eq!(convs[0].external_id.as_deref(), Some("good-session"));
+    assert_eq!(convs[0].messages.len(), 2);
+}
+
+/// Strict mode fails the scan and names the corrupt file
+#[test]
+fn gemini_strict_scan_fails_on_corrupt_session_file() {
+    let conn = GeminiConnector::new();
+    let ctx = ScanContext::local_default(PathBuf::from("tests/fixtures/gemini_corrupt"), None)
+        .with_strict(true);
+    let err = conn.scan(&ctx).expect_err("strict scan should fail");
+    assert!(format!("{err:#}").contains("session-corrupt.json"));
+}
+
+/// Legacy sessions and checkpoint files are both parsed, and projects.json maps
+/// the project hash to the real workspace directory
+#[test]
+fn gemini_parses_checkpoints_and_resolves_workspace_from_project_map() {
+    let conn = GeminiConnector::new();
+    let ctx = ScanContext::local_default(PathBuf::from("tests/fixtures/gemini_checkpoint"), None);
+    let convs = conn.scan(&ctx).expect("scan");
+    assert_eq!(convs.len(), 3);
+    let by_id = |id: &str| {
+        convs
+            .iter()
+            .find(|c| c.external_id.as_deref() == Some(id))
+            .unwrap_or_else(|| panic!("missing conversation {id}"))
+    };
+
+    let legacy = by_id("legacy-session-1");
+    assert_eq!(
+        legacy.workspace,
+        Some(PathBuf::from("/home/dev/projects/weather-app"))
+    );
+    assert_eq!(legacy.metadata["format"], "session");
+
+    let checkpoint = by_id("checkpoint-refactor");
+    assert_eq!(checkpoint.workspace, legacy.workspace);
+    assert_eq!(checkpoint.metadata["project_hash"], "abc123");
+    assert_eq!(checkpoint.metadata["format"], "checkpoint");
+    let roles: Vec<_> = checkpoint
+        .messages
+        .iter()
+        .map(|m| m.role.as_str())
+        .collect();
+    assert_eq!(roles, ["user", "assistant"]);
+    assert_eq!(
+        checkpoint.messages[1].content,
+        "I'll wrap the request in a retry loop with backoff.\nDone: fetch_forecast now retries three times."
+    );
+
+    // No mapping for this hash: fall back to the hash directory
+    let unmapped = by_id("checkpoint-notes");
+    assert_eq!(unmapped.messages.len(), 2);
+    assert!(unmapped.workspace.as_ref().unwrap().ends_with("def456"));
+}
@@ -111,6 +111,12 @@ fn opencode_parses_json_fixture() {
         data_dir: fixture_root.clone(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).expect("scan");
     assert_eq!(convs.len(), 1);
@@ -165,6 +171,12 @@ fn opencode_parses_created_storage() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -231,6 +243,12 @@ fn opencode_handles_multiple_sessions() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 2);
@@ -279,6 +297,12 @@ fn opencode_handles_tool_parts() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -328,6 +352,12 @@ fn opencode_handles_reasoning_parts() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -370,6 +400,12 @@ fn opencode_sets_correct_agent_slug() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -388,6 +424,12 @@ fn opencode_handles_empty_storage() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert!(convs.is_empty());
@@ -403,6 +445,12 @@ fn opencode_handles_missing_storage() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert!(convs.is_empty());
@@ -464,6 +512,12 @@ fn opencode_orders_messages_by_timestamp() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -530,6 +584,12 @@ fn opencode_assigns_sequential_indices() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -572,6 +632,12 @@ fn opencode_title_fallback_to_first_message() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -628,6 +694,12 @@ fn opencode_computes_started_ended_at() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -666,6 +738,12 @@ fn opencode_skips_sessions_without_messages() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
 
@@ -705,6 +783,12 @@ fn opencode_metadata_contains_session_id() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -752,6 +836,12 @@ fn opencode_external_id_is_session_id() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -785,6 +875,12 @@ fn opencode_handles_corrupted_session_json() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     // Should not panic, just skip the corrupted file
     let convs = connector.scan(&ctx).unwrap();
@@ -846,6 +942,12 @@ fn opencode_handles_partial_session_data() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -904,6 +1006,12 @@ fn opencode_handles_unicode_content() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -978,6 +1086,12 @@ fn opencode_handles_very_long_session() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let start = std::time::Instant::now();
@@ -1041,6 +1155,12 @@ fn opencode_handles_empty_message_parts() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -1094,6 +1214,12 @@ fn opencode_handles_null_text_parts() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -1160,6 +1286,12 @@ fn opencode_handles_deeply_nested_project_dirs() {
         data_dir: dir.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = connector.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
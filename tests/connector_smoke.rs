@@ -0,0 +1,54 @@
+//! Every connector registered with the indexer must construct and report a
+//! well-formed `DetectionResult` on a machine with no agent history.
+
+use std::collections::HashSet;
+
+use coding_agent_search::indexer::{connector_slugs, get_connector_factories};
+use serial_test::serial;
+use tempfile::TempDir;
+
+#[test]
+#[serial]
+fn every_connector_detects_cleanly_on_empty_home() {
+    let home = TempDir::new().unwrap();
+
+    // Safe in test scope: we control process env and run serially.
+    unsafe {
+        std::env::set_var("HOME", home.path());
+        std::env::set_var("XDG_DATA_HOME", home.path().join(".local/share"));
+        std::env::set_var("XDG_CONFIG_HOME", home.path().join(".config"));
+        std::env::set_var("CODEX_HOME", home.path().join(".codex"));
+        std::env::set_var("GEMINI_HOME", home.path().join(".gemini/tmp"));
+        std::env::set_var("PI_CODING_AGENT_DIR", home.path().join(".pi/agent"));
+        std::env::set_var("OPENCODE_STORAGE_ROOT", home.path().join("opencode"));
+        std::env::remove_var("CASS_AIDER_DATA_ROOT");
+    }
+
+    let factories = get_connector_factories();
+    let slugs: HashSet<_> = factories.iter().map(|(name, _)| *name).collect();
+    assert_eq!(
+        slugs.len(),
+        factories.len(),
+        "connector slugs must be unique"
+    );
+    assert_eq!(
+        connector_slugs(),
+        factories.iter().map(|(name, _)| *name).collect::<Vec<_>>()
+    );
+
+    for (name, factory) in factories {
+        let detection = factory().detect();
+        if detection.detected {
+            assert!(
+                !detection.evidence.is_empty(),
+                "{name}: detection without evidence"
+            );
+        } else {
+            assert!(
+                detection.root_paths.is_empty(),
+                "{name}: undetected connector reported roots {:?}",
+                detection.root_paths
+            );
+        }
+    }
+}
@@ -130,6 +130,440 @@ fn search_basic_returns_valid_json() {
     );
 }
 
+#[test]
+fn search_export_html_writes_pages_and_index() {
+    let (tmp, data_dir) = setup_indexed_env();
+    let out_dir = tmp.path().join("export");
+
+    let output = base_cmd()
+        .args(["search", "authentication", "--robot", "--data-dir"])
+        .arg(&data_dir)
+        .arg("--export-html")
+        .arg(&out_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "export should succeed");
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["exported"], 1);
+
+    let index = fs::read_to_string(out_dir.join("index.html")).expect("index.html");
+    let pages: Vec<_> = fs::read_dir(&out_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|name| name != "index.html")
+        .collect();
+    assert_eq!(pages.len(), 1);
+    assert!(pages[0].starts_with("001-codex-"));
+    assert!(index.contains(&pages[0]), "index should link the page");
+}
+
+#[test]
+fn search_export_html_uncreatable_dir_is_usage_error() {
+    let (tmp, data_dir) = setup_indexed_env();
+    let blocker = tmp.path().join("not_a_dir");
+    fs::write(&blocker, "file").unwrap();
+
+    let output = base_cmd()
+        .args(["search", "authentication", "--robot", "--data-dir"])
+        .arg(&data_dir)
+        .arg("--export-html")
+        .arg(blocker.join("out"))
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn search_falls_back_to_sqlite_fts_without_tantivy_index() {
+    let (tmp, data_dir) = setup_indexed_env();
+    fs::remove_dir_all(data_dir.join("index")).unwrap();
+
+    let output = base_cmd()
+        .args(["search", "authentication", "--robot", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "fallback search should succeed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("SQLite FTS fallback"), "stderr: {stderr}");
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let hits = json["hits"].as_array().expect("hits array");
+    assert!(!hits.is_empty(), "fallback should find the codex session");
+}
+
+#[test]
+fn forget_removes_conversation_from_db_and_index() {
+    use coding_agent_search::search::tantivy::{TantivyIndex, index_dir_path};
+    use coding_agent_search::storage::sqlite::SqliteStorage;
+
+    let (tmp, data_dir) = setup_indexed_env();
+    let db_path = data_dir.join("agent_search.db");
+    let counts = || {
+        let storage = SqliteStorage::open_readonly(&db_path).unwrap();
+        let convs = storage.list_conversations(100, 0).unwrap();
+        let messages: usize = convs
+            .iter()
+            .map(|c| storage.fetch_messages(c.id.unwrap()).unwrap().len())
+            .sum();
+        let index = TantivyIndex::open_or_create(&index_dir_path(&data_dir)).unwrap();
+        let docs = index.reader().unwrap().searcher().num_docs();
+        (convs, messages, docs)
+    };
+
+    let (convs, messages_before, docs_before) = counts();
+    assert_eq!(convs.len(), 2, "codex and claude sessions indexed");
+    let codex = convs.iter().find(|c| c.agent_slug == "codex").unwrap();
+    let codex_messages = {
+        let storage = SqliteStorage::open_readonly(&db_path).unwrap();
+        storage.fetch_messages(codex.id.unwrap()).unwrap().len()
+    };
+    let target = codex.source_path.to_string_lossy().into_owned();
+
+    let output = base_cmd()
+        .args(["forget", &target, "--yes", "--json", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "forget should succeed");
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["conversations"], 1);
+
+    let (convs, messages_after, docs_after) = counts();
+    assert_eq!(convs.len(), 1);
+    assert_eq!(convs[0].agent_slug, "claude_code");
+    assert_eq!(messages_after, messages_before - codex_messages);
+    assert_eq!(docs_after, docs_before - codex_messages as u64);
+}
+
+#[test]
+fn forget_requires_yes_when_stdin_is_not_a_tty() {
+    use coding_agent_search::storage::sqlite::SqliteStorage;
+
+    let (tmp, data_dir) = setup_indexed_env();
+    let db_path = data_dir.join("agent_search.db");
+    let target = {
+        let storage = SqliteStorage::open_readonly(&db_path).unwrap();
+        let convs = storage.list_conversations(100, 0).unwrap();
+        convs[0].source_path.to_string_lossy().into_owned()
+    };
+
+    let output = base_cmd()
+        .args(["forget", &target, "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+
+    let unknown = base_cmd()
+        .args(["forget", "no-such-conversation", "--yes", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    assert_eq!(unknown.status.code(), Some(13));
+
+    let storage = SqliteStorage::open_readonly(&db_path).unwrap();
+    assert_eq!(storage.list_conversations(100, 0).unwrap().len(), 2);
+}
+
+#[test]
+fn reindex_one_ingests_conversations_stored_inside_a_cursor_db() {
+    let tmp = TempDir::new().unwrap();
+    let data_dir = tmp.path().join("cass_data");
+    let ws_dir = tmp.path().join(".config/Cursor/User/workspaceStorage/ws1");
+    fs::create_dir_all(&ws_dir).unwrap();
+    let db_path = ws_dir.join("state.vscdb");
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    conn.execute_batch("CREATE TABLE ItemTable (key TEXT PRIMARY KEY, value TEXT);")
+        .unwrap();
+    conn.execute(
+        "INSERT INTO ItemTable (key, value) VALUES ('aiService.prompts', ?1)",
+        [r#"[{"text": "explain the quokka parser", "commandType": 4}]"#],
+    )
+    .unwrap();
+    drop(conn);
+
+    // The conversation's source path is `state.vscdb/<id>`, under the file passed in.
+    let output = base_cmd()
+        .args(["reindex-one", "--json", "--data-dir"])
+        .arg(&data_dir)
+        .arg(&db_path)
+        .env("HOME", tmp.path())
+        .env("XDG_CONFIG_HOME", tmp.path().join(".config"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "reindex-one failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["connector"], "cursor");
+    assert_eq!(json["conversations"], 1);
+    assert_eq!(json["messages_added"], 1);
+}
+
+#[test]
+fn import_indexes_jsonl_transcript_and_rejects_malformed_input() {
+    let tmp = TempDir::new().unwrap();
+    let data_dir = tmp.path().join("cass_data");
+    let transcript = tmp.path().join("transcript.jsonl");
+    fs::write(
+        &transcript,
+        r#"{"role": "user", "content": "how do I rotate the zanzibar keys", "created_at": "2024-05-01T10:00:00Z"}
+{"role": "assistant", "content": "Run the rotation job, then restart the gateway.", "created_at": 1714557660000}
+"#,
+    )
+    .unwrap();
+
+    let import = || {
+        base_cmd()
+            .args(["import", "--agent", "my_tool", "--json", "--data-dir"])
+            .arg(&data_dir)
+            .arg(&transcript)
+            .env("HOME", tmp.path())
+            .output()
+            .unwrap()
+    };
+    let output = import();
+    assert!(output.status.success(), "import should succeed");
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["messages"], 2);
+    assert_eq!(json["messages_added"], 2);
+
+    // Importing the same file again adds nothing.
+    let json: Value = serde_json::from_slice(&import().stdout).expect("valid JSON");
+    assert_eq!(json["messages_added"], 0);
+
+    // The first user message becomes the title, which every message's doc carries,
+    // so search for a term that only the assistant reply contains.
+    let search = base_cmd()
+        .args(["search", "gateway", "--json", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    let json: Value = serde_json::from_slice(&search.stdout).expect("valid JSON");
+    let hits = json["hits"].as_array().expect("hits array");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["agent"], "my_tool");
+
+    let malformed = tmp.path().join("broken.jsonl");
+    fs::write(
+        &malformed,
+        "{\"role\": \"user\", \"content\": \"ok\"}\n{\"role\": 3}\n",
+    )
+    .unwrap();
+    let output = base_cmd()
+        .args(["import", "--agent", "my_tool", "--json", "--data-dir"])
+        .arg(&data_dir)
+        .arg(&malformed)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("line 2"),
+        "error should name the line: {stderr}"
+    );
+}
+
+#[test]
+fn imported_transcripts_survive_full_index_and_rebuild() {
+    let tmp = TempDir::new().unwrap();
+    let data_dir = tmp.path().join("cass_data");
+    let transcript = tmp.path().join("transcript.jsonl");
+    fs::write(
+        &transcript,
+        r#"{"role": "user", "content": "where do the wombat configs live"}
+{"role": "assistant", "content": "Under the marsupial directory."}
+"#,
+    )
+    .unwrap();
+    let run = |args: &[&str]| {
+        let output = base_cmd()
+            .args(args)
+            .arg("--data-dir")
+            .arg(&data_dir)
+            .env("HOME", tmp.path())
+            .env("XDG_DATA_HOME", tmp.path().join(".local/share"))
+            .env("XDG_CONFIG_HOME", tmp.path().join(".config"))
+            .env("CODEX_HOME", tmp.path().join(".codex"))
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    };
+    let hits = || {
+        let output = run(&["search", "marsupial", "--json"]);
+        let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+        json["hits"].as_array().map(Vec::len).unwrap_or(0)
+    };
+
+    run(&[
+        "import",
+        transcript.to_str().unwrap(),
+        "--agent",
+        "my_tool",
+        "--json",
+    ]);
+    assert_eq!(hits(), 1);
+
+    // No connector can rescan an import, so a full reset and a rebuild keep it.
+    run(&["index", "--full", "--json"]);
+    assert_eq!(hits(), 1);
+    run(&["index", "--force-rebuild", "--json"]);
+    assert_eq!(hits(), 1);
+}
+
+#[test]
+fn show_prints_normalized_messages_from_db() {
+    use coding_agent_search::storage::sqlite::SqliteStorage;
+
+    let (tmp, data_dir) = setup_indexed_env();
+    let target = {
+        let storage = SqliteStorage::open_readonly(&data_dir.join("agent_search.db")).unwrap();
+        let convs = storage.list_conversations(100, 0).unwrap();
+        let codex = convs.iter().find(|c| c.agent_slug == "codex").unwrap();
+        codex.source_path.to_string_lossy().into_owned()
+    };
+
+    let output = base_cmd()
+        .args(["show", &target, "--json", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "show should succeed");
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["agent"], "codex");
+    assert_eq!(json["source_path"], target.as_str());
+    let messages = json["messages"].as_array().expect("messages array");
+    assert_eq!(json["message_count"], messages.len());
+    assert_eq!(messages[0]["role"], "user");
+    assert_eq!(messages[0]["content"], "authentication error in login");
+    assert_eq!(messages[1]["role"], "assistant");
+
+    let human = base_cmd()
+        .args(["show", &target, "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&human.stdout);
+    assert!(stdout.contains("[0] user"), "{stdout}");
+    assert!(
+        stdout.contains("authentication error in login_response"),
+        "{stdout}"
+    );
+
+    let missing = base_cmd()
+        .args(["show", "/no/such/session.jsonl", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    assert_eq!(missing.status.code(), Some(13));
+}
+
+#[test]
+fn show_message_opens_a_search_hit_by_message_id() {
+    let (tmp, data_dir) = setup_indexed_env();
+    let search = base_cmd()
+        .args(["search", "authentication", "--json", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    assert!(search.status.success());
+    let json: Value = serde_json::from_slice(&search.stdout).expect("valid JSON");
+    let hit = &json["hits"][0];
+    let message_id = hit["message_id"].as_i64().expect("hits carry message_id");
+
+    let output = base_cmd()
+        .args(["show", "--message", &message_id.to_string(), "-C", "0"])
+        .args(["--json", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "show --message should succeed");
+    let shown: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(shown["source_path"], hit["source_path"]);
+    assert_eq!(shown["message_id"], message_id);
+    let messages = shown["messages"].as_array().expect("messages array");
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0]["id"], message_id);
+    assert_eq!(
+        messages[0]["idx"].as_u64().map(|i| i + 1),
+        hit["line_number"].as_u64()
+    );
+
+    let missing = base_cmd()
+        .args(["show", "--message", "999999", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    assert_eq!(missing.status.code(), Some(13));
+}
+
+#[test]
+fn index_generation_advances_on_every_index_run() {
+    let (tmp, data_dir) = setup_indexed_env();
+    let generation = || {
+        let out = base_cmd()
+            .args(["index", "--print-generation", "--data-dir"])
+            .arg(&data_dir)
+            .env("HOME", tmp.path())
+            .output()
+            .unwrap();
+        assert!(out.status.success());
+        String::from_utf8_lossy(&out.stdout)
+            .trim()
+            .parse::<u64>()
+            .expect("bare number")
+    };
+    let first = generation();
+    assert!(first >= 1);
+
+    for args in [vec!["index"], vec!["index", "--full"]] {
+        base_cmd()
+            .args(&args)
+            .arg("--data-dir")
+            .arg(&data_dir)
+            .env("CODEX_HOME", tmp.path().join(".codex"))
+            .env("HOME", tmp.path())
+            .assert()
+            .success();
+    }
+    let after = generation();
+    assert_eq!(after, first + 2);
+
+    let stats = base_cmd()
+        .args(["stats", "--json", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .output()
+        .unwrap();
+    let json: Value = serde_json::from_slice(&stats.stdout).expect("valid JSON");
+    assert_eq!(json["index_generation"], after);
+}
+
 #[test]
 fn search_returns_hits_with_expected_fields() {
     let (tmp, data_dir) = setup_indexed_env();
@@ -171,6 +605,86 @@ fn search_returns_hits_with_expected_fields() {
     }
 }
 
+#[test]
+fn search_human_output_shows_host_only_for_remote_hits() {
+    use coding_agent_search::connectors::{NormalizedConversation, NormalizedMessage};
+    use coding_agent_search::search::tantivy::{TantivyIndex, index_dir_path};
+
+    let (tmp, data_dir) = setup_indexed_env();
+    {
+        let mut index = TantivyIndex::open_or_create(&index_dir_path(&data_dir)).unwrap();
+        let conv = NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: Some("remote-1".into()),
+            title: Some("remote session".into()),
+            workspace: None,
+            source_path: "/remote/sessions/rollout-remote.jsonl".into(),
+            started_at: Some(1733011200000),
+            ended_at: Some(1733011200000),
+            metadata: serde_json::json!({
+                "cass": {"origin": {"source_id": "laptop", "kind": "ssh", "host": "user@laptop.local"}}
+            }),
+            messages: vec![NormalizedMessage {
+                idx: 0,
+                role: "user".into(),
+                author: None,
+                created_at: Some(1733011200000),
+                content: "remoteonlyterm in the laptop session".into(),
+                extra: serde_json::json!({}),
+                snippets: vec![],
+            }],
+        };
+        index.add_conversation(&conv).unwrap();
+        index.commit().unwrap();
+    }
+
+    let search = |query: &str| {
+        let output = base_cmd()
+            .args(["search", query, "--data-dir"])
+            .arg(&data_dir)
+            .env("HOME", tmp.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let remote = search("remoteonlyterm");
+    assert!(
+        remote.contains("Host: user@laptop.local"),
+        "remote hit should name its host: {remote}"
+    );
+
+    let local = search("authentication");
+    assert!(
+        local.contains("Path: "),
+        "local search should print hits: {local}"
+    );
+    assert!(
+        !local.contains("Host:"),
+        "local hits should not print a host: {local}"
+    );
+}
+
+#[test]
+fn search_human_output_has_no_hyperlinks_when_piped() {
+    let (tmp, data_dir) = setup_indexed_env();
+    let output = base_cmd()
+        .args(["--color=always", "search", "authentication", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", tmp.path())
+        .env("CASS_HYPERLINKS", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Path: "), "should print hits: {stdout}");
+    assert!(
+        !stdout.contains("\x1b]8;"),
+        "OSC-8 links need a terminal on stdout: {stdout}"
+    );
+}
+
 // =============================================================================
 // Detail Find Tests (view/expand)
 // =============================================================================
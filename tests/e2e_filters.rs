@@ -11,7 +11,7 @@ use std::fs;
 use std::path::Path;
 
 mod util;
-use util::EnvGuard;
+use util::{CwdGuard, EnvGuard};
 
 /// Creates a Codex session with specific date and content.
 /// Timestamp should be in milliseconds.
@@ -574,6 +574,66 @@ fn filter_by_workspace() {
     }
 }
 
+/// Test: --here scopes results to the current directory's workspace
+#[test]
+fn filter_by_here_uses_current_directory() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let home = tmp.path();
+    let claude_home = home.join(".claude");
+    let data_dir = home.join("cass_data");
+    fs::create_dir_all(&data_dir).unwrap();
+    let project_dir = home.join("work/project-here");
+    fs::create_dir_all(&project_dir).unwrap();
+    let here = fs::canonicalize(&project_dir).unwrap();
+    let here = here.to_string_lossy();
+    let elsewhere = "/projects/somewhere-else";
+
+    let _guard_home = EnvGuard::set("HOME", home.to_string_lossy());
+
+    for (name, ws) in [("project-a", here.as_ref()), ("project-b", elsewhere)] {
+        let project = claude_home.join(format!("projects/{name}"));
+        fs::create_dir_all(&project).unwrap();
+        let sample = format!(
+            r#"{{"type": "user", "timestamp": "2024-11-20T10:00:00Z", "cwd": "{ws}", "message": {{"role": "user", "content": "heretest from {name}"}}}}"#
+        );
+        fs::write(project.join(format!("session-{name}.jsonl")), sample).unwrap();
+    }
+
+    cargo_bin_cmd!("cass")
+        .args(["index", "--full", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", home)
+        .assert()
+        .success();
+
+    let _cwd_guard = CwdGuard::change_to(&project_dir).unwrap();
+    let output = cargo_bin_cmd!("cass")
+        .args(["search", "heretest", "--here", "--robot", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", home)
+        .output()
+        .expect("search command");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    let hits = json["hits"].as_array().expect("hits array");
+    assert!(!hits.is_empty(), "--here should find the current project");
+    for hit in hits {
+        assert_eq!(hit["workspace"].as_str(), Some(here.as_ref()));
+    }
+
+    // --here is added to explicit --workspace values rather than replacing them
+    let output = cargo_bin_cmd!("cass")
+        .args(["search", "heretest", "--here", "--workspace", elsewhere])
+        .args(["--robot", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", home)
+        .output()
+        .expect("search command");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    assert_eq!(json["hits"].as_array().expect("hits array").len(), 2);
+}
+
 /// Test: Days filter (--days N)
 #[test]
 fn filter_by_days() {
@@ -783,6 +843,100 @@ fn filter_by_source_specific_name() {
     );
 }
 
+/// Test: --local-only keeps conversations from this machine and drops synced remotes
+#[test]
+fn local_only_excludes_remote_sources() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let home = tmp.path();
+    let codex_home = home.join(".codex");
+    let data_dir = home.join("cass_data");
+    let config_dir = home.join("config");
+    fs::create_dir_all(&data_dir).unwrap();
+    fs::create_dir_all(config_dir.join("cass")).unwrap();
+    fs::write(
+        config_dir.join("cass/sources.toml"),
+        r#"
+[[sources]]
+name = "laptop"
+type = "ssh"
+host = "user@laptop.local"
+paths = ["~/.codex"]
+"#,
+    )
+    .unwrap();
+
+    let _guard_home = EnvGuard::set("HOME", home.to_string_lossy());
+    let _guard_codex = EnvGuard::set("CODEX_HOME", codex_home.to_string_lossy());
+
+    make_codex_session_at(
+        &codex_home,
+        "2024/11/20",
+        "rollout-1.jsonl",
+        "localmachine mixtest",
+        1732118400000,
+    );
+    // A synced copy of the laptop's ~/.codex, as `cass sources sync` leaves it.
+    make_codex_session_at(
+        &data_dir.join("remotes/laptop/mirror/.codex"),
+        "2024/11/21",
+        "rollout-2.jsonl",
+        "laptopmachine mixtest",
+        1732204800000,
+    );
+
+    let cass = |args: &[&str]| {
+        let mut cmd = cargo_bin_cmd!("cass");
+        cmd.args(args)
+            .arg("--data-dir")
+            .arg(&data_dir)
+            .env("HOME", home)
+            .env("CODEX_HOME", &codex_home)
+            .env("XDG_CONFIG_HOME", &config_dir);
+        cmd
+    };
+    cass(&["index", "--full"]).assert().success();
+
+    let source_ids = |extra: &[&str]| -> Vec<String> {
+        let output = cass(&[&["search", "mixtest", "--robot"][..], extra].concat())
+            .output()
+            .expect("search command");
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+        let mut ids: Vec<String> = json["hits"]
+            .as_array()
+            .expect("hits array")
+            .iter()
+            .map(|h| h["source_id"].as_str().unwrap_or("local").to_string())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    };
+    assert_eq!(source_ids(&[]), ["laptop", "local"]);
+    assert_eq!(source_ids(&["--local-only"]), ["local"]);
+
+    cass(&["search", "mixtest", "--local-only", "--source", "laptop"])
+        .assert()
+        .code(2);
+    cass(&["search", "mixtest", "--local-only", "--host", "laptop"])
+        .assert()
+        .code(2);
+
+    let conversations = |extra: &[&str]| {
+        let output = cass(&[&["stats", "--json"][..], extra].concat())
+            .output()
+            .expect("stats command");
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+        json["conversations"].as_i64().expect("conversations count")
+    };
+    assert_eq!(conversations(&[]), 2);
+    assert_eq!(conversations(&["--local-only"]), 1);
+    cass(&["stats", "--local-only", "--source", "remote"])
+        .assert()
+        .code(2);
+}
+
 /// Test: search --source with nonexistent source returns empty results
 #[test]
 fn filter_by_source_nonexistent() {
@@ -1480,3 +1634,169 @@ fn stats_by_source_with_filter() {
         }
     }
 }
+
+/// Test: --since accepts natural-language relative times
+#[test]
+fn filter_by_time_since_natural_language() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let home = tmp.path();
+    let codex_home = home.join(".codex");
+    let data_dir = home.join("cass_data");
+    fs::create_dir_all(&data_dir).unwrap();
+
+    let _guard_home = EnvGuard::set("HOME", home.to_string_lossy());
+    let _guard_codex = EnvGuard::set("CODEX_HOME", codex_home.to_string_lossy());
+
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    let day_ms = 86_400_000;
+    make_codex_session_at(
+        &codex_home,
+        "2024/11/15",
+        "rollout-old.jsonl",
+        "oldsession naturaltest",
+        1731682800000,
+    );
+    make_codex_session_at(
+        &codex_home,
+        "2099/01/01",
+        "rollout-recent.jsonl",
+        "recentsession naturaltest",
+        now_ms - 3 * day_ms,
+    );
+
+    cargo_bin_cmd!("cass")
+        .args(["index", "--full", "--data-dir"])
+        .arg(&data_dir)
+        .env("CODEX_HOME", &codex_home)
+        .env("HOME", home)
+        .assert()
+        .success();
+
+    for since in ["2 weeks ago", "last week"] {
+        let output = cargo_bin_cmd!("cass")
+            .args([
+                "search",
+                "naturaltest",
+                "--since",
+                since,
+                "--robot",
+                "--data-dir",
+            ])
+            .arg(&data_dir)
+            .env("HOME", home)
+            .env("CODEX_HOME", &codex_home)
+            .output()
+            .expect("search command");
+
+        assert!(output.status.success(), "--since '{since}' should parse");
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+        let hits = json
+            .get("hits")
+            .and_then(|h| h.as_array())
+            .expect("hits array");
+        assert!(
+            !hits.is_empty(),
+            "--since '{since}' should find the recent session"
+        );
+        for hit in hits {
+            let content = hit["content"].as_str().unwrap_or("");
+            assert!(
+                content.contains("recentsession"),
+                "--since '{since}' should exclude the 2024 session, got: {content}"
+            );
+        }
+    }
+}
+
+/// Test: an unparseable --since is a usage error rather than an ignored filter
+#[test]
+fn filter_by_time_rejects_unparseable_since() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let home = tmp.path();
+    let data_dir = home.join("cass_data");
+
+    let output = cargo_bin_cmd!("cass")
+        .args([
+            "search",
+            "anything",
+            "--since",
+            "the other day",
+            "--robot",
+            "--data-dir",
+        ])
+        .arg(&data_dir)
+        .env("HOME", home)
+        .output()
+        .expect("search command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--since"),
+        "error should name the flag, got: {stderr}"
+    );
+}
+
+/// Test: --in restricts hits to the conversation stored at one source path
+#[test]
+fn filter_by_in_session_path() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let home = tmp.path();
+    let codex_home = home.join(".codex");
+    let data_dir = home.join("cass_data");
+    fs::create_dir_all(&data_dir).unwrap();
+
+    let _guard_home = EnvGuard::set("HOME", home.to_string_lossy());
+    let _guard_codex = EnvGuard::set("CODEX_HOME", codex_home.to_string_lossy());
+
+    make_codex_session_at(
+        &codex_home,
+        "2024/11/20",
+        "rollout-a.jsonl",
+        "scopedtest alpha",
+        1732118400000,
+    );
+    make_codex_session_at(
+        &codex_home,
+        "2024/11/21",
+        "rollout-b.jsonl",
+        "scopedtest beta",
+        1732204800000,
+    );
+    let target = codex_home.join("sessions/2024/11/21/rollout-b.jsonl");
+
+    cargo_bin_cmd!("cass")
+        .args(["index", "--full", "--data-dir"])
+        .arg(&data_dir)
+        .env("CODEX_HOME", &codex_home)
+        .env("HOME", home)
+        .assert()
+        .success();
+
+    let output = cargo_bin_cmd!("cass")
+        .args(["search", "scopedtest", "--in"])
+        .arg(&target)
+        .args(["--robot", "--data-dir"])
+        .arg(&data_dir)
+        .env("HOME", home)
+        .output()
+        .expect("search command");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    let hits = json
+        .get("hits")
+        .and_then(|h| h.as_array())
+        .expect("hits array");
+    assert!(!hits.is_empty(), "Should find hits in the scoped session");
+    for hit in hits {
+        assert_eq!(hit["source_path"], target.to_string_lossy().as_ref());
+        assert!(
+            hit["line_number"].is_u64(),
+            "hits should carry line numbers"
+        );
+    }
+}
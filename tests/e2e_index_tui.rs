@@ -48,7 +48,16 @@ fn index_then_tui_once_headless() {
         .assert()
         .success();
 
+    // Same, pre-seeded with a query and filters.
+    cargo_bin_cmd!("cass")
+        .args(["tui", "--once", "--query", "hello", "--agent", "codex"])
+        .arg("--data-dir")
+        .arg(&data_dir)
+        .env("TUI_HEADLESS", "1")
+        .assert()
+        .success();
+
     // Ensure index artifacts exist.
     assert!(data_dir.join("agent_search.db").exists());
-    assert!(data_dir.join("index/v6").exists());
+    assert!(data_dir.join("index/v7").exists());
 }
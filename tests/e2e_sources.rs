@@ -5,6 +5,7 @@
 //! - sources list
 //! - sources remove
 //! - sources doctor (limited without actual SSH)
+//! - sources probe (error paths only)
 //! - sources sync (dry-run only)
 //!
 //! Note: Tests that require actual SSH connectivity are marked #[ignore].
@@ -170,6 +171,99 @@ paths = ["~/.claude/projects"]
     assert_eq!(sources[0]["name"], "laptop");
 }
 
+#[test]
+fn sources_list_honors_explicit_config_path() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let config_path = tmp.path().join("team-sources.toml");
+    fs::write(
+        &config_path,
+        r#"
+[[sources]]
+name = "buildbox"
+type = "ssh"
+host = "ci@buildbox"
+paths = ["~/.codex/sessions"]
+"#,
+    )
+    .unwrap();
+    // Default location is empty, so only the explicit path can supply sources.
+    let empty_config_home = tmp.path().join("config");
+    fs::create_dir_all(&empty_config_home).unwrap();
+
+    let output = cargo_bin_cmd!("cass")
+        .arg("--sources")
+        .arg(&config_path)
+        .args(["sources", "list", "--json"])
+        .env("XDG_CONFIG_HOME", &empty_config_home)
+        .env_remove("CASS_SOURCES_CONFIG")
+        .output()
+        .expect("sources list with --sources");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["sources"][0]["name"], "buildbox");
+
+    let output = cargo_bin_cmd!("cass")
+        .args(["sources", "list", "--json"])
+        .env("XDG_CONFIG_HOME", &empty_config_home)
+        .env("CASS_SOURCES_CONFIG", &config_path)
+        .output()
+        .expect("sources list with CASS_SOURCES_CONFIG");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["sources"][0]["name"], "buildbox");
+}
+
+/// Test: sources list --json reports last sync from sync_status.json.
+#[test]
+fn sources_list_json_includes_last_sync() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let config_dir = tmp.path().join("config");
+    let data_dir = tmp.path().join("data");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    create_sources_config(
+        &config_dir,
+        r#"
+[[sources]]
+name = "laptop"
+type = "ssh"
+host = "user@laptop.local"
+paths = ["~/.claude/projects"]
+
+[[sources]]
+name = "workstation"
+type = "ssh"
+host = "dev@work.local"
+paths = ["~/.codex/sessions"]
+"#,
+    );
+    fs::create_dir_all(&data_dir).unwrap();
+    fs::write(
+        data_dir.join("sync_status.json"),
+        r#"{"sources":{"laptop":{"last_sync":1700000000000,"last_result":"success","files_synced":3,"bytes_transferred":10,"duration_ms":5}}}"#,
+    )
+    .unwrap();
+
+    let output = cargo_bin_cmd!("cass")
+        .args(["sources", "list", "--json"])
+        .env("XDG_CONFIG_HOME", &config_dir)
+        .env("CASS_DATA_DIR", &data_dir)
+        .output()
+        .expect("sources list --json command");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["sources"][0]["name"], "laptop");
+    assert!(
+        json["sources"][0]["last_sync"]
+            .as_str()
+            .is_some_and(|s| s.starts_with("2023-11-14")),
+        "unexpected last_sync: {}",
+        json["sources"][0]
+    );
+    assert!(json["sources"][1]["last_sync"].is_null());
+}
+
 // =============================================================================
 // sources add tests
 // =============================================================================
@@ -658,6 +752,57 @@ paths = ["~/.claude/projects"]
     }
 }
 
+// =============================================================================
+// sources probe tests
+// =============================================================================
+
+/// Test: sources probe with no remote sources is not an error.
+#[test]
+fn sources_probe_no_sources_json() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let config_dir = tmp.path().join("config");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    let output = cargo_bin_cmd!("cass")
+        .args(["sources", "probe", "--json"])
+        .env("XDG_CONFIG_HOME", &config_dir)
+        .output()
+        .expect("sources probe command");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["total"], 0);
+}
+
+/// Test: sources probe NAME fails for an unknown source.
+#[test]
+fn sources_probe_unknown_name() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let config_dir = tmp.path().join("config");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    create_sources_config(
+        &config_dir,
+        r#"
+[[sources]]
+name = "laptop"
+type = "ssh"
+host = "user@laptop.local"
+paths = ["~/.claude/projects"]
+"#,
+    );
+
+    let output = cargo_bin_cmd!("cass")
+        .args(["sources", "probe", "desktop", "--json"])
+        .env("XDG_CONFIG_HOME", &config_dir)
+        .output()
+        .expect("sources probe command");
+
+    assert_eq!(output.status.code(), Some(13));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("desktop"), "got: {stderr}");
+}
+
 // =============================================================================
 // sources sync tests
 // =============================================================================
@@ -779,6 +924,45 @@ paths = ["~/.claude/projects"]
     // The source filter should work even if sync fails due to SSH
 }
 
+/// Test: sources sync NAME is shorthand for --source NAME.
+#[test]
+fn sources_sync_positional_name() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let config_dir = tmp.path().join("config");
+    let data_dir = tmp.path().join("data");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::create_dir_all(&data_dir).unwrap();
+
+    create_sources_config(
+        &config_dir,
+        r#"
+[[sources]]
+name = "laptop"
+type = "ssh"
+host = "user@laptop.local"
+paths = ["~/.claude/projects"]
+
+[[sources]]
+name = "workstation"
+type = "ssh"
+host = "dev@work.local"
+paths = ["~/.codex/sessions"]
+"#,
+    );
+
+    let output = cargo_bin_cmd!("cass")
+        .args(["sources", "sync", "workstation", "--dry-run"])
+        .env("XDG_CONFIG_HOME", &config_dir)
+        .env("XDG_DATA_HOME", &data_dir)
+        .output()
+        .expect("sources sync NAME command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("workstation"), "got: {stdout}");
+    assert!(!stdout.contains("laptop"), "got: {stdout}");
+}
+
 /// Test: sources sync --json outputs valid JSON.
 #[test]
 fn sources_sync_json() {
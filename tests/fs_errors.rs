@@ -31,6 +31,12 @@ fn scan_nonexistent_directory_handles_gracefully() {
         data_dir: nonexistent,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     // Should not panic - returns empty or error (connector may search ~/.claude anyway)
@@ -65,6 +71,12 @@ fn file_deleted_mid_scan_handles_gracefully() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     // Should handle missing file gracefully
@@ -88,6 +100,12 @@ fn empty_directory_returns_no_conversations() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let result = conn.scan(&ctx);
@@ -108,6 +126,12 @@ fn missing_session_file_in_project() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let result = conn.scan(&ctx);
@@ -145,6 +169,12 @@ fn symlink_to_valid_file_is_followed() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     // Test that symlink doesn't cause a panic - actual behavior depends on
@@ -172,6 +202,12 @@ fn broken_symlink_is_handled_gracefully() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     // Should handle broken symlink gracefully
@@ -207,6 +243,12 @@ fn symlink_to_directory_is_followed() {
         data_dir: mock_claude,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     // Test that symlinked directory doesn't cause a panic - actual behavior
@@ -237,6 +279,12 @@ fn directory_named_like_session_file() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     // Should not crash when encountering directory with file-like name
@@ -262,6 +310,12 @@ fn zero_byte_file_handles_gracefully() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let result = conn.scan(&ctx);
@@ -284,6 +338,12 @@ fn newlines_only_file_handles_gracefully() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let result = conn.scan(&ctx);
@@ -314,6 +374,12 @@ fn path_with_spaces_is_handled() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let result = conn.scan(&ctx);
@@ -340,6 +406,12 @@ fn path_with_unicode_is_handled() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let result = conn.scan(&ctx);
@@ -371,6 +443,12 @@ fn deeply_nested_directory_is_handled() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let result = conn.scan(&ctx);
@@ -398,6 +476,12 @@ fn gemini_handles_missing_chats_dir() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     // Gemini connector should not panic even with incomplete directory structure
@@ -423,6 +507,12 @@ fn codex_handles_missing_sessions_dir() {
         data_dir: codex_home,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let result = conn.scan(&ctx);
@@ -450,6 +540,12 @@ fn error_contains_path_context() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let result = conn.scan(&ctx);
@@ -494,6 +590,12 @@ fn multiple_bad_files_dont_prevent_good_file_processing() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     let result = conn.scan(&ctx);
@@ -539,6 +641,12 @@ fn file_readable_with_other_handle() {
         data_dir: tmp.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
 
     // Should still be able to read the file
@@ -63,6 +63,12 @@ fn amp_connector_emits_scan_span() {
         data_dir: fixture_root,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert!(!convs.is_empty());
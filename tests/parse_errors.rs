@@ -44,6 +44,12 @@ fn claude_skips_invalid_json_syntax() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -70,6 +76,12 @@ fn claude_skips_missing_type_field() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -97,6 +109,12 @@ fn claude_handles_wrong_field_types() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -122,6 +140,12 @@ fn claude_handles_truncated_json() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert_eq!(convs.len(), 1);
@@ -153,6 +177,12 @@ fn claude_handles_binary_in_content() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     // Should not panic - gracefully handle the file
     let result = conn.scan(&ctx);
@@ -180,6 +210,12 @@ fn claude_returns_error_on_invalid_utf8() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     // The scanner uses BufRead::lines() which returns Err on invalid UTF-8,
     // but the implementation silently skips such lines for resilience.
@@ -212,6 +248,12 @@ fn claude_handles_empty_file() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     // Empty file produces no conversations
@@ -233,6 +275,12 @@ fn claude_handles_whitespace_only_file() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     // Whitespace-only file produces no conversations
@@ -258,6 +306,12 @@ fn gemini_skips_invalid_json() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     // Invalid file should be skipped, no conversations
@@ -287,6 +341,12 @@ fn gemini_handles_missing_messages() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     // File without messages should produce empty or skipped conversation
@@ -316,6 +376,12 @@ fn gemini_handles_wrong_messages_type() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     // Should not panic
     let result = conn.scan(&ctx);
@@ -345,6 +411,12 @@ fn codex_skips_invalid_json() {
         data_dir: codex_home,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     // Invalid JSON causes read error, which propagates
     let result = conn.scan(&ctx);
@@ -380,6 +452,12 @@ fn codex_handles_missing_events() {
         data_dir: codex_home,
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     // Should not panic - gracefully handle missing fields
     let result = conn.scan(&ctx);
@@ -405,6 +483,12 @@ fn cline_skips_invalid_json() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
     assert!(convs.is_empty());
@@ -432,6 +516,12 @@ fn cline_handles_missing_task_history() {
         data_dir: tmp.path().to_path_buf(),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     // Should not panic
     let result = conn.scan(&ctx);
@@ -467,6 +557,12 @@ fn claude_processes_valid_files_despite_bad_ones() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     let convs = conn.scan(&ctx).unwrap();
 
@@ -501,6 +597,12 @@ fn claude_handles_extremely_long_content() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     // Should not panic or hang
     let result = conn.scan(&ctx);
@@ -530,6 +632,12 @@ fn claude_handles_deeply_nested_json() {
         data_dir: dir.path().join("mock-claude"),
         scan_roots: Vec::new(),
         since_ts: None,
+        parse_cache: None,
+        include_globs: Vec::new(),
+        exclude_globs: Vec::new(),
+        max_file_bytes: None,
+        oversized_files: Default::default(),
+        strict: false,
     };
     // Should not panic
     let result = conn.scan(&ctx);
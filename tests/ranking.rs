@@ -30,6 +30,13 @@ fn exact_hits_rank_above_wildcards_at_equal_recency_and_score() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        explanation: None,
+        rerank_score: None,
+        duplicate_count: None,
+        message_matches: None,
+        relative_path: None,
+        message_id: None,
+        conversation_external_id: None,
     };
 
     let prefix = SearchHit {
@@ -82,6 +89,13 @@ fn recency_boost_can_outweigh_quality_when_far_newer() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        explanation: None,
+        rerank_score: None,
+        duplicate_count: None,
+        message_matches: None,
+        relative_path: None,
+        message_id: None,
+        conversation_external_id: None,
     };
 
     let newer_suffix = SearchHit {
@@ -99,6 +113,13 @@ fn recency_boost_can_outweigh_quality_when_far_newer() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        explanation: None,
+        rerank_score: None,
+        duplicate_count: None,
+        message_matches: None,
+        relative_path: None,
+        message_id: None,
+        conversation_external_id: None,
     };
 
     let max_created = newer_suffix.created_at.unwrap();
@@ -132,6 +153,13 @@ fn relevance_heavy_mode_prefers_quality_over_recency() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        explanation: None,
+        rerank_score: None,
+        duplicate_count: None,
+        message_matches: None,
+        relative_path: None,
+        message_id: None,
+        conversation_external_id: None,
     };
 
     let newer_substring = SearchHit {
@@ -149,6 +177,13 @@ fn relevance_heavy_mode_prefers_quality_over_recency() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        explanation: None,
+        rerank_score: None,
+        duplicate_count: None,
+        message_matches: None,
+        relative_path: None,
+        message_id: None,
+        conversation_external_id: None,
     };
 
     let older_score = blended_score(&older_exact, max_created, alpha);
@@ -183,6 +218,13 @@ fn match_quality_heavy_mode_balances_quality_and_recency() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        explanation: None,
+        rerank_score: None,
+        duplicate_count: None,
+        message_matches: None,
+        relative_path: None,
+        message_id: None,
+        conversation_external_id: None,
     };
 
     let implicit = SearchHit {
@@ -229,6 +271,13 @@ fn ranking_handles_missing_created_at() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        explanation: None,
+        rerank_score: None,
+        duplicate_count: None,
+        message_matches: None,
+        relative_path: None,
+        message_id: None,
+        conversation_external_id: None,
     };
 
     let hit_without_date = SearchHit {
@@ -246,6 +295,13 @@ fn ranking_handles_missing_created_at() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        explanation: None,
+        rerank_score: None,
+        duplicate_count: None,
+        message_matches: None,
+        relative_path: None,
+        message_id: None,
+        conversation_external_id: None,
     };
 
     let with_date_score = blended_score(&hit_with_date, max_created, alpha);
@@ -281,6 +337,13 @@ fn ranking_handles_zero_max_created() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        explanation: None,
+        rerank_score: None,
+        duplicate_count: None,
+        message_matches: None,
+        relative_path: None,
+        message_id: None,
+        conversation_external_id: None,
     };
 
     let score = blended_score(&hit, max_created, alpha);
@@ -314,6 +377,13 @@ fn all_ranking_modes_maintain_quality_ordering_at_equal_inputs() {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            explanation: None,
+            rerank_score: None,
+            duplicate_count: None,
+            message_matches: None,
+            relative_path: None,
+            message_id: None,
+            conversation_external_id: None,
         };
 
         let exact_score = blended_score(&base, max_created, alpha);
@@ -1,5 +1,7 @@
 use coding_agent_search::search::query::{SearchClient, SearchFilters};
+use coding_agent_search::search::result_cache::ResultCache;
 use coding_agent_search::search::tantivy::TantivyIndex;
+use std::time::Duration;
 use tempfile::TempDir;
 
 mod util;
@@ -87,3 +89,65 @@ fn search_client_prefix_cache_works() {
         "Should hit cache for 'appl' using 'app' entry (stats: {stats:?})"
     );
 }
+
+#[test]
+fn result_cache_is_shared_across_clients_and_invalidated_on_commit() {
+    let dir = TempDir::new().unwrap();
+    let data_dir = TempDir::new().unwrap();
+    let mut index = TantivyIndex::open_or_create(dir.path()).unwrap();
+
+    let conv = util::ConversationFixtureBuilder::new("tester")
+        .title("disk cache test")
+        .source_path(dir.path().join("log.jsonl"))
+        .base_ts(1000)
+        .messages(1)
+        .with_content(0, "persisted_term_for_disk_cache")
+        .build_normalized();
+    index.add_conversation(&conv).unwrap();
+    index.commit().unwrap();
+
+    let open_client = || {
+        SearchClient::open(dir.path(), None)
+            .unwrap()
+            .expect("client")
+            .with_result_cache(ResultCache::new(
+                data_dir.path(),
+                dir.path(),
+                Duration::from_secs(60),
+            ))
+    };
+    let filters = SearchFilters::default();
+
+    // First process: cold, writes the entry.
+    let first = open_client();
+    let hits = first
+        .search("persisted_term", filters.clone(), 5, 0)
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(first.cache_stats().disk_miss, 1);
+
+    // Second process: served from disk.
+    let second = open_client();
+    let cached = second
+        .search("persisted_term", filters.clone(), 5, 0)
+        .unwrap();
+    assert_eq!(cached.len(), 1);
+    assert_eq!(second.cache_stats().disk_hits, 1);
+
+    // A commit changes the index generation, so the old entry must not be reused.
+    let conv2 = util::ConversationFixtureBuilder::new("tester")
+        .title("second")
+        .source_path(dir.path().join("log2.jsonl"))
+        .base_ts(2000)
+        .messages(1)
+        .with_content(0, "persisted_term_again")
+        .build_normalized();
+    index.add_conversation(&conv2).unwrap();
+    index.commit().unwrap();
+
+    let third = open_client();
+    let fresh = third.search("persisted_term", filters, 5, 0).unwrap();
+    assert_eq!(third.cache_stats().disk_miss, 1);
+    assert_eq!(third.cache_stats().disk_hits, 0);
+    assert!(!fresh.is_empty());
+}
@@ -427,6 +427,128 @@ fn test_mode_flag_hybrid() {
     }
 }
 
+/// Test: --semantic degrades to lexical search with a warning when no model is installed
+#[test]
+fn test_semantic_flag_falls_back_to_lexical() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let home = tmp.path();
+    let codex_home = home.join(".codex");
+    let data_dir = home.join("cass_data");
+    fs::create_dir_all(&data_dir).unwrap();
+
+    let _guard_home = EnvGuard::set("HOME", home.to_string_lossy());
+    let _guard_codex = EnvGuard::set("CODEX_HOME", codex_home.to_string_lossy());
+
+    make_codex_session(
+        &codex_home,
+        "2024/11/20",
+        "rollout-semantic-flag.jsonl",
+        "semantic_flag_test_content",
+        1732118400000,
+    );
+
+    cargo_bin_cmd!("cass")
+        .args(["index", "--full", "--data-dir"])
+        .arg(&data_dir)
+        .env("CODEX_HOME", &codex_home)
+        .env("HOME", home)
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .assert()
+        .success();
+
+    let output = cargo_bin_cmd!("cass")
+        .args([
+            "search",
+            "semantic_flag_test_content",
+            "--semantic",
+            "--robot",
+            "--data-dir",
+        ])
+        .arg(&data_dir)
+        .env("HOME", home)
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .output()
+        .expect("search --semantic");
+
+    // No model is installed in the temp data dir, so this must fall back (exit 0).
+    assert!(
+        output.status.success(),
+        "--semantic should fall back to lexical. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("semantic search unavailable"),
+        "expected fallback warning, got: {stderr}"
+    );
+
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let hits = json["hits"].as_array().expect("hits array");
+    assert!(!hits.is_empty(), "lexical fallback should find the session");
+    assert!(hits.iter().all(|h| h["match_type"] != "semantic"));
+}
+
+/// Test: --rerank keeps lexical order with a warning when the reranker model is missing
+#[test]
+fn test_rerank_flag_falls_back_without_model() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let home = tmp.path();
+    let codex_home = home.join(".codex");
+    let data_dir = home.join("cass_data");
+    fs::create_dir_all(&data_dir).unwrap();
+
+    let _guard_home = EnvGuard::set("HOME", home.to_string_lossy());
+    let _guard_codex = EnvGuard::set("CODEX_HOME", codex_home.to_string_lossy());
+
+    make_codex_session(
+        &codex_home,
+        "2024/11/20",
+        "rollout-rerank.jsonl",
+        "rerank_flag_test_content",
+        1732118400000,
+    );
+
+    cargo_bin_cmd!("cass")
+        .args(["index", "--full", "--data-dir"])
+        .arg(&data_dir)
+        .env("CODEX_HOME", &codex_home)
+        .env("HOME", home)
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .assert()
+        .success();
+
+    let output = cargo_bin_cmd!("cass")
+        .args([
+            "search",
+            "rerank_flag_test_content",
+            "--rerank",
+            "--rerank-top",
+            "10",
+            "--robot",
+            "--data-dir",
+        ])
+        .arg(&data_dir)
+        .env("HOME", home)
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .output()
+        .expect("search --rerank");
+
+    assert!(
+        output.status.success(),
+        "--rerank should fall back to lexical order. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("reranker unavailable"),
+        "expected fallback warning, got: {stderr}"
+    );
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let hits = json["hits"].as_array().expect("hits array");
+    assert!(!hits.is_empty());
+    assert!(hits.iter().all(|h| h.get("rerank_score").is_none()));
+}
+
 // =============================================================================
 // Determinism Tests
 // =============================================================================
@@ -51,7 +51,7 @@ fn schema_version_created_on_open() {
     let db_path = tmp.path().join("store.db");
     let storage = SqliteStorage::open(&db_path).expect("open");
 
-    assert_eq!(storage.schema_version().unwrap(), 5);
+    assert_eq!(storage.schema_version().unwrap(), 6);
 
     // If meta row is removed, the getter surfaces an error.
     storage.raw().execute("DELETE FROM meta", []).unwrap();
@@ -252,6 +252,67 @@ fn last_scan_ts_overwrite() {
     assert_eq!(storage.get_last_scan_ts().unwrap(), Some(20));
 }
 
+#[test]
+fn recent_conversations_orders_by_last_activity() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("recent.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+
+    // (external_id, started_at, ended_at): "long" started first but ended last.
+    for (ext, started, ended) in [
+        ("old", Some(100), Some(200)),
+        ("long", Some(50), Some(900)),
+        ("open", Some(500), None),
+        ("undated", None, None),
+    ] {
+        let mut conv = sample_conv(Some(ext), vec![msg(0, 100)]);
+        conv.started_at = started;
+        conv.ended_at = ended;
+        storage
+            .insert_conversation_tree(agent_id, None, &conv)
+            .unwrap();
+    }
+
+    let ids = |limit| -> Vec<String> {
+        storage
+            .recent_conversations(limit)
+            .unwrap()
+            .into_iter()
+            .filter_map(|c| c.external_id)
+            .collect()
+    };
+    assert_eq!(ids(10), vec!["long", "open", "old", "undated"]);
+    assert_eq!(ids(2), vec!["long", "open"]);
+}
+
+#[test]
+fn get_conversation_by_source_path_loads_messages_in_order() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("by_path.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+    let conv = sample_conv(Some("by-path"), vec![msg(1, 200), msg(0, 100)]);
+    storage
+        .insert_conversation_tree(agent_id, None, &conv)
+        .unwrap();
+
+    let found = storage
+        .get_conversation_by_source_path("/logs/demo.jsonl")
+        .unwrap()
+        .expect("conversation by source path");
+    assert_eq!(found.external_id.as_deref(), Some("by-path"));
+    let contents: Vec<_> = found.messages.iter().map(|m| m.content.as_str()).collect();
+    assert_eq!(contents, vec!["msg-0", "msg-1"]);
+
+    assert!(
+        storage
+            .get_conversation_by_source_path("/logs/missing.jsonl")
+            .unwrap()
+            .is_none()
+    );
+}
+
 #[test]
 fn unsupported_schema_version_errors() {
     let tmp = tempfile::TempDir::new().unwrap();
@@ -415,6 +476,147 @@ fn conversations_table_has_correct_columns() {
     assert!(columns.contains(&"metadata_json".to_string()));
 }
 
+#[test]
+fn append_skips_renumbered_messages_by_content_hash() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("dedup.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+
+    let first = sample_conv(Some("ext-r"), vec![msg(0, 100), msg(1, 200)]);
+    storage
+        .insert_conversation_tree(agent_id, None, &first)
+        .unwrap();
+
+    // Rescan renumbers the existing messages and inserts a new one in the middle
+    let mut moved_a = msg(0, 100);
+    moved_a.idx = 0;
+    let mut new_msg = msg(7, 150);
+    new_msg.idx = 1;
+    let mut moved_b = msg(1, 200);
+    moved_b.idx = 2;
+    let second = sample_conv(Some("ext-r"), vec![moved_a, new_msg, moved_b]);
+    let outcome = storage
+        .insert_conversation_tree(agent_id, None, &second)
+        .unwrap();
+    // Index 1 still holds msg-1 (now reported at 2), so msg-7 is stored after it
+    assert_eq!(outcome.inserted_indices, vec![2]);
+    assert_eq!(outcome.source_indices, vec![1]);
+    assert!(outcome.replaced_indices.is_empty());
+
+    let contents: Vec<String> = storage
+        .raw()
+        .prepare("SELECT content FROM messages ORDER BY idx")
+        .unwrap()
+        .query_map([], |r| r.get(0))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(contents, vec!["msg-0", "msg-1", "msg-7"]);
+}
+
+#[test]
+fn append_rewrites_changed_message_in_place() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("edit.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+
+    let first = sample_conv(Some("ext-e"), vec![msg(0, 100), msg(1, 200)]);
+    storage
+        .insert_conversation_tree(agent_id, None, &first)
+        .unwrap();
+
+    let mut edited = msg(1, 200);
+    edited.content = "msg-1, edited".into();
+    let second = sample_conv(Some("ext-e"), vec![msg(0, 100), edited]);
+    let outcome = storage
+        .insert_conversation_tree(agent_id, None, &second)
+        .unwrap();
+    assert_eq!(outcome.inserted_indices, vec![1]);
+    assert_eq!(outcome.replaced_indices, vec![1]);
+
+    let contents: Vec<String> = storage
+        .raw()
+        .prepare("SELECT content FROM messages ORDER BY idx")
+        .unwrap()
+        .query_map([], |r| r.get(0))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(contents, vec!["msg-0", "msg-1, edited"]);
+}
+
+#[test]
+fn append_keeps_repeated_identical_messages() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("repeat.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+
+    let mut yes = msg(0, 100);
+    yes.content = "yes".into();
+    let first = sample_conv(Some("ext-y"), vec![yes.clone()]);
+    storage
+        .insert_conversation_tree(agent_id, None, &first)
+        .unwrap();
+
+    let mut again = yes.clone();
+    again.idx = 1;
+    let second = sample_conv(Some("ext-y"), vec![yes, again]);
+    let outcome = storage
+        .insert_conversation_tree(agent_id, None, &second)
+        .unwrap();
+    assert_eq!(outcome.inserted_indices, vec![1]);
+}
+
+#[test]
+fn migration_from_v5_backfills_content_hashes() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("migrate_v5.db");
+    {
+        let mut storage = SqliteStorage::open(&db_path).expect("open");
+        let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+        let conv = sample_conv(Some("ext-v5"), vec![msg(0, 100), msg(1, 200)]);
+        storage
+            .insert_conversation_tree(agent_id, None, &conv)
+            .unwrap();
+        // Downgrade to the v5 layout
+        storage
+            .raw()
+            .execute_batch(
+                "DROP INDEX idx_messages_conv_hash;
+                 ALTER TABLE messages DROP COLUMN content_hash;
+                 UPDATE meta SET value = '5' WHERE key = 'schema_version';",
+            )
+            .unwrap();
+    }
+
+    let mut storage = SqliteStorage::open(&db_path).expect("reopen v5 db");
+    assert_eq!(storage.schema_version().unwrap(), 6);
+    let missing: i64 = storage
+        .raw()
+        .query_row(
+            "SELECT COUNT(*) FROM messages WHERE content_hash IS NULL",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert_eq!(missing, 0);
+
+    // Backfilled hashes match freshly computed ones, so a renumbered rescan adds nothing
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+    let mut a = msg(1, 200);
+    a.idx = 0;
+    let mut b = msg(0, 100);
+    b.idx = 1;
+    let rescan = sample_conv(Some("ext-v5"), vec![a, b]);
+    let outcome = storage
+        .insert_conversation_tree(agent_id, None, &rescan)
+        .unwrap();
+    assert!(outcome.inserted_indices.is_empty());
+}
+
 #[test]
 fn messages_table_has_correct_columns() {
     let tmp = tempfile::TempDir::new().unwrap();
@@ -438,6 +640,7 @@ fn messages_table_has_correct_columns() {
     assert!(columns.contains(&"created_at".to_string()));
     assert!(columns.contains(&"content".to_string()));
     assert!(columns.contains(&"extra_json".to_string()));
+    assert!(columns.contains(&"content_hash".to_string()));
 }
 
 #[test]
@@ -562,7 +765,7 @@ fn migration_from_v1_applies_v2_and_v3() {
     let storage = SqliteStorage::open(&db_path).expect("open v1 db");
 
     // Verify migration completed
-    assert_eq!(storage.schema_version().unwrap(), 5, "should migrate to v5");
+    assert_eq!(storage.schema_version().unwrap(), 6, "should migrate to v6");
 
     // Verify FTS5 table was created
     let tables: Vec<String> = storage
@@ -678,7 +881,7 @@ fn migration_from_v2_applies_v3() {
     let storage = SqliteStorage::open(&db_path).expect("open v2 db");
 
     // Verify migration completed
-    assert_eq!(storage.schema_version().unwrap(), 5, "should migrate to v5");
+    assert_eq!(storage.schema_version().unwrap(), 6, "should migrate to v6");
 }
 
 #[test]
@@ -1039,7 +1242,7 @@ fn migration_from_v3_creates_sources_table() {
     let storage = SqliteStorage::open(&db_path).expect("open v3 db");
 
     // Verify migration completed
-    assert_eq!(storage.schema_version().unwrap(), 5, "should migrate to v5");
+    assert_eq!(storage.schema_version().unwrap(), 6, "should migrate to v6");
 
     // Verify sources table was created with local source
     let sources = storage.list_sources().expect("list_sources");
@@ -1328,6 +1531,67 @@ fn open_or_rebuild_triggers_rebuild_for_future_version() {
     assert!(!db_path.exists(), "original db should be deleted");
 }
 
+#[test]
+fn open_rejects_newer_schema_version_without_modifying_db() {
+    use rusqlite::Connection;
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("newer.db");
+    {
+        let conn = Connection::open(&db_path).expect("create db");
+        conn.execute_batch(
+            r"
+            CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+            INSERT INTO meta(key, value) VALUES('schema_version', '999');
+            ",
+        )
+        .expect("seed newer version");
+    }
+
+    let err = SqliteStorage::open(&db_path)
+        .err()
+        .expect("newer schema should be rejected");
+    match err.downcast_ref::<MigrationError>() {
+        Some(MigrationError::IncompatibleVersion { found, supported }) => {
+            assert_eq!(*found, 999);
+            assert_eq!(*supported, CURRENT_SCHEMA_VERSION);
+        }
+        other => panic!("expected IncompatibleVersion, got {other:?}"),
+    }
+
+    // The database is left as-is for the newer binary.
+    let conn = Connection::open(&db_path).unwrap();
+    let version: String = conn
+        .query_row(
+            "SELECT value FROM meta WHERE key = 'schema_version'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(version, "999");
+}
+
+#[test]
+fn open_migrates_older_schema_version() {
+    use rusqlite::Connection;
+
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("older.db");
+    {
+        let conn = Connection::open(&db_path).expect("create db");
+        conn.execute_batch(
+            r"
+            CREATE TABLE meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+            INSERT INTO meta(key, value) VALUES('schema_version', '0');
+            ",
+        )
+        .expect("seed older version");
+    }
+
+    let storage = SqliteStorage::open(&db_path).expect("older schema migrates");
+    assert_eq!(storage.schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+}
+
 #[test]
 fn open_or_rebuild_handles_corrupted_db() {
     let tmp = tempfile::TempDir::new().unwrap();
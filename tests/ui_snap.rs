@@ -815,6 +815,7 @@ fn agent_pane_text_meets_wcag_aa() {
         "chatgpt",
         "opencode",
         "pi_agent",
+        "copilot",
         "unknown",
     ];
 
@@ -845,6 +846,7 @@ fn agent_pane_accent_meets_wcag_aa_large() {
         "chatgpt",
         "opencode",
         "pi_agent",
+        "copilot",
     ];
 
     for agent in agents {